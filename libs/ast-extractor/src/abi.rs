@@ -0,0 +1,108 @@
+/**
+ * abi.rs
+ * Renders types as they appear in ABI signatures
+*/
+use crate::storage_layout::{array_length, Definitions};
+use syn_solidity::Type;
+
+/// Canonical name of a type in the ABI, as used in function selectors:
+/// `uint` is `uint256`, enums are `uint8`, contracts are `address` and structs are tuples
+pub fn abi_type(definitions: &Definitions, ty: &Type) -> String {
+    abi_name(definitions, ty, &mut Vec::new())
+}
+
+fn abi_name(definitions: &Definitions, ty: &Type, structs: &mut Vec<String>) -> String {
+    match ty {
+        Type::Address(..) => "address".to_string(),
+        Type::Int(_, size) => format!("int{}", size.map_or(256, |size| size.get())),
+        Type::Uint(_, size) => format!("uint{}", size.map_or(256, |size| size.get())),
+        Type::Array(array) => {
+            let length = match (array.size.is_some(), array_length(array)) {
+                (false, _) => String::new(),
+                (true, Some(length)) => length.to_string(),
+                // Lengths given by constants are kept as written
+                (true, None) => return ty.to_string().split_whitespace().collect(),
+            };
+            format!("{}[{}]", abi_name(definitions, &array.ty, structs), length)
+        }
+        Type::Tuple(tuple) => {
+            let types: Vec<String> = tuple
+                .types
+                .iter()
+                .map(|ty| abi_name(definitions, ty, structs))
+                .collect();
+            format!("({})", types.join(","))
+        }
+        Type::Custom(path) => {
+            let name = path.last().to_string();
+            if definitions.is_enum(&name) {
+                "uint8".to_string()
+            } else if let Some(ty) = definitions.udt(&name) {
+                abi_name(definitions, ty, structs)
+            } else if let Some(strukt) = definitions.struct_definition(&name) {
+                if structs.contains(&name) {
+                    return name;
+                }
+                structs.push(name);
+                let fields: Vec<String> = strukt
+                    .fields
+                    .iter()
+                    .map(|field| abi_name(definitions, &field.ty, structs))
+                    .collect();
+                structs.pop();
+                format!("({})", fields.join(","))
+            } else {
+                "address".to_string()
+            }
+        }
+        _ => ty.to_string().split_whitespace().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extract::extract_ast_from_content;
+    use std::fs;
+    use std::path::PathBuf;
+    use syn_solidity::Item;
+
+    #[test]
+    fn test_abi_type() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests");
+        path.push("files");
+        path.push("abi");
+        path.push("types.sol");
+        let source = fs::read_to_string(path).unwrap();
+        let file = extract_ast_from_content(&source).unwrap();
+        let definitions = Definitions::new([&file]);
+        let Some(Item::Contract(contract)) = file.items.last() else {
+            panic!("Expected a contract");
+        };
+        let types: Vec<String> = contract
+            .body
+            .iter()
+            .filter_map(|item| match item {
+                Item::Variable(variable) => Some(abi_type(&definitions, &variable.ty)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                "bool",
+                "address",
+                "uint256",
+                "address",
+                "(bool,uint256)",
+                "uint8",
+                "uint128",
+                "mapping(address=>uint256)",
+                "uint256[]",
+                "int8[3]",
+                "string",
+            ]
+        );
+    }
+}
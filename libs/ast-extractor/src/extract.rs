@@ -4,20 +4,101 @@
  * author: 0xMemoryGrinder
  */
 use crate::errors::ExtractError;
-use proc_macro2::TokenStream;
+use crate::scanner::{scan, LexemeKind};
+use proc_macro2::{Group, LineColumn, Literal, TokenStream, TokenTree};
+use std::collections::HashMap;
 use std::str::FromStr;
 
 pub fn extract_ast_from_content(content: &str) -> Result<syn_solidity::File, ExtractError> {
-    let tokens = TokenStream::from_str(content)?;
+    let tokens = tokenize(content)?;
     let ast = syn_solidity::parse2(tokens)?;
     Ok(ast)
 }
 
+/// Tokenizes solidity source code, whose single-quoted string literals the tokenizer does not accept.
+///
+/// They are first rewritten with double quotes, their inner double quotes becoming single quotes,
+/// so that every token keeps its position. Each of those literals is then replaced by a double-quoted
+/// literal escaping the original characters, spanning the original source.
+pub fn tokenize(content: &str) -> Result<TokenStream, ExtractError> {
+    let mut normalized = String::with_capacity(content.len());
+    let mut originals = HashMap::new();
+    let mut last = 0;
+    for lexeme in scan(content) {
+        if lexeme.kind != LexemeKind::String('\'') {
+            continue;
+        }
+        let inner = lexeme.inner_text(content);
+        normalized.push_str(&content[last..lexeme.start]);
+        normalized.push('"');
+        normalized.push_str(&inner.replace('"', "'"));
+        if lexeme.text(content).len() > inner.len() + 1 {
+            normalized.push('"');
+        }
+        last = lexeme.end;
+        originals.insert(lexeme.start_position, double_quoted(inner));
+    }
+    normalized.push_str(&content[last..]);
+
+    let tokens = TokenStream::from_str(&normalized)?;
+    Ok(restore_literals(tokens, &originals))
+}
+
+/// A double-quoted literal with the characters of the inner text of a single-quoted one
+fn double_quoted(inner: &str) -> String {
+    let mut res = String::with_capacity(inner.len() + 2);
+    res.push('"');
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                res.push(c);
+                res.extend(chars.next());
+            }
+            '"' => res.push_str("\\\""),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+fn restore_literals(tokens: TokenStream, originals: &HashMap<LineColumn, String>) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Group(group) => {
+                let mut restored = Group::new(
+                    group.delimiter(),
+                    restore_literals(group.stream(), originals),
+                );
+                restored.set_span(group.span());
+                TokenTree::Group(restored)
+            }
+            TokenTree::Literal(literal) => {
+                let span = literal.span();
+                match originals
+                    .get(&span.start())
+                    .and_then(|original| Literal::from_str(original).ok())
+                {
+                    Some(mut original) => {
+                        original.set_span(span);
+                        TokenTree::Literal(original)
+                    }
+                    None => TokenTree::Literal(literal),
+                }
+            }
+            token => token,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
     use std::path::PathBuf;
+    use syn_solidity::{Expr, Item, Lit, Spanned};
 
     #[test]
     fn test_extract_ast_from_content_good() {
@@ -48,4 +129,62 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().to_string(), "Parsing error");
     }
+
+    #[test]
+    fn test_extract_ast_from_content_single_quotes() {
+        let source = String::from(
+            "import './a.sol'; contract test { string s = 'say \\'hi\\' or \"hi\"'; }",
+        );
+        let result = extract_ast_from_content(&source);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_single_quoted_literals_keep_their_characters_and_positions() {
+        let source = "contract test { string s = 'say \\'hi\\' or \"hi\"'; string t = \"it's\"; }";
+        let file = extract_ast_from_content(source).unwrap();
+        let Item::Contract(contract) = &file.items[0] else {
+            panic!("expected a contract");
+        };
+        let values: Vec<(String, LineColumn, LineColumn)> = contract
+            .body
+            .iter()
+            .filter_map(|item| match item {
+                Item::Variable(variable) => match &variable.initializer {
+                    Some((_, Expr::Lit(Lit::Str(lit)))) => {
+                        Some((lit.value(), lit.span().start(), lit.span().end()))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                (
+                    "say 'hi' or \"hi\"".to_string(),
+                    LineColumn {
+                        line: 1,
+                        column: 27
+                    },
+                    LineColumn {
+                        line: 1,
+                        column: 47
+                    }
+                ),
+                (
+                    "it's".to_string(),
+                    LineColumn {
+                        line: 1,
+                        column: 60
+                    },
+                    LineColumn {
+                        line: 1,
+                        column: 66
+                    }
+                ),
+            ]
+        );
+    }
 }
@@ -1,6 +1,9 @@
+pub mod abi;
 pub mod errors;
 pub mod extract;
 pub mod retriever;
+pub mod scanner;
+pub mod storage_layout;
 
 // Expose syn_solidity crate
 pub use syn_solidity::*;
@@ -42,3 +42,6 @@ pub use stmts::*;
 
 mod block;
 pub use block::*;
+
+mod yul_path;
+pub use yul_path::*;
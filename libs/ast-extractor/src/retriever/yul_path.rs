@@ -0,0 +1,147 @@
+/**
+ * yul_path.rs
+ * Function to retrieve the identifiers referenced by the paths of a Yul block
+*/
+use syn::parse::{ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::Token;
+use syn_solidity::{Spanned, YulBlock, YulExpr, YulFnCall, YulFor, YulIdent, YulPath, YulStmt};
+
+struct YulPathVisitor {
+    roots: Vec<YulIdent>,
+}
+
+impl YulPathVisitor {
+    fn visit_block(&mut self, block: &YulBlock) {
+        for stmt in &block.stmts {
+            self.visit_stmt(stmt);
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &YulStmt) {
+        match stmt {
+            YulStmt::Block(block) => self.visit_block(block),
+            YulStmt::Decl(decl) => {
+                if let Some((_, value)) = &decl.init_value {
+                    self.visit_expr(value);
+                }
+            }
+            YulStmt::Assign(assign) => {
+                for path in &assign.vars {
+                    self.visit_path(path);
+                }
+                self.visit_expr(&assign.assigned_value);
+            }
+            YulStmt::Call(call) => self.visit_call(call),
+            YulStmt::If(stmt) => {
+                self.visit_expr(&stmt.cond);
+                self.visit_block(&stmt.then_branch);
+            }
+            YulStmt::For(stmt) => self.visit_for(stmt),
+            YulStmt::Switch(switch) => {
+                self.visit_expr(&switch.selector);
+                for branch in &switch.branches {
+                    self.visit_block(&branch.body);
+                }
+                if let Some(default) = &switch.default_case {
+                    self.visit_block(&default.body);
+                }
+            }
+            YulStmt::FunctionDef(function) => self.visit_block(&function.body),
+            YulStmt::Leave(_) | YulStmt::Break(_) | YulStmt::Continue(_) => {}
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &YulExpr) {
+        match expr {
+            YulExpr::Path(path) => self.visit_path(path),
+            YulExpr::Call(call) => self.visit_call(call),
+            YulExpr::Literal(_) => {}
+        }
+    }
+
+    fn visit_call(&mut self, call: &YulFnCall) {
+        for argument in &call.arguments {
+            self.visit_expr(argument);
+        }
+    }
+
+    // The identifiers of paths and the parts of for loops are private, they are parsed again from the source
+    fn visit_path(&mut self, path: &YulPath) {
+        let Some(source) = path.span().source_text() else {
+            return;
+        };
+        let parser = Punctuated::<YulIdent, Token![.]>::parse_separated_nonempty;
+        if let Ok(idents) = parser.parse_str(&source) {
+            self.roots.extend(idents.into_iter().next());
+        }
+    }
+
+    fn visit_for(&mut self, stmt: &YulFor) {
+        let Some(source) = stmt.span().source_text() else {
+            return;
+        };
+        let parser = |input: ParseStream<'_>| {
+            input.parse::<Token![for]>()?;
+            let initialization: YulBlock = input.parse()?;
+            let condition: YulExpr = input.parse()?;
+            let post_iteration: YulBlock = input.parse()?;
+            let body: YulBlock = input.parse()?;
+            Ok((initialization, condition, post_iteration, body))
+        };
+        if let Ok((initialization, condition, post_iteration, body)) = parser.parse_str(&source) {
+            self.visit_block(&initialization);
+            self.visit_expr(&condition);
+            self.visit_block(&post_iteration);
+            self.visit_block(&body);
+        }
+    }
+}
+
+/// Retrieves the first identifier of each path of a Yul block, such as `x` in `x.slot`.
+/// The block must have been parsed on the current thread, from which its source is read.
+pub fn retrieve_yul_path_roots(block: &YulBlock) -> Vec<YulIdent> {
+    let mut visitor = YulPathVisitor { roots: Vec::new() };
+    visitor.visit_block(block);
+    visitor.roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extract::extract_ast_from_content;
+    use syn_solidity::{Item, Stmt};
+
+    fn assembly_block(source: &str) -> YulBlock {
+        let ast = extract_ast_from_content(source).unwrap();
+        let Some(Item::Function(function)) = ast.items.first() else {
+            panic!("expected a function");
+        };
+        let Some(Stmt::Assembly(assembly)) = function.body().and_then(|body| body.first()) else {
+            panic!("expected an assembly statement");
+        };
+        assembly.block.clone()
+    }
+
+    #[test]
+    fn test_retrieve_yul_path_roots() {
+        let block = assembly_block(
+            "function f() {
+                assembly {
+                    let stmts := add(a, b.slot)
+                    c := mload(0x40)
+                    for { let i := 0 } lt(i, d) { i := add(i, 1) } { sstore(e.slot, 'x') }
+                    function g(x) -> y { y := f.selector }
+                }
+            }",
+        );
+        let roots: Vec<String> = retrieve_yul_path_roots(&block)
+            .iter()
+            .map(|ident| ident.to_string())
+            .collect();
+        assert_eq!(
+            roots,
+            vec!["a", "b", "c", "i", "d", "i", "i", "e", "y", "f"]
+        );
+    }
+}
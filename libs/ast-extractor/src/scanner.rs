@@ -0,0 +1,173 @@
+/**
+ * scanner.rs
+ * Finds the comments and string literals of solidity source code
+*/
+use proc_macro2::LineColumn;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LexemeKind {
+    LineComment,
+    BlockComment,
+    /// A string literal, along with its delimiting quote
+    String(char),
+}
+
+/// A comment or a string literal, located by byte offsets and by positions
+#[derive(Debug, Clone)]
+pub struct Lexeme {
+    pub kind: LexemeKind,
+    pub start: usize,
+    pub end: usize,
+    pub start_position: LineColumn,
+    pub end_position: LineColumn,
+}
+
+impl Lexeme {
+    /// The source text of the lexeme, including its delimiters
+    pub fn text<'c>(&self, content: &'c str) -> &'c str {
+        &content[self.start..self.end]
+    }
+
+    /// The source text between the quotes of a string literal, escape sequences included
+    pub fn inner_text<'c>(&self, content: &'c str) -> &'c str {
+        let text = self.text(content);
+        let quote = match self.kind {
+            LexemeKind::String(quote) => quote,
+            _ => return text,
+        };
+        let text = &text[1..];
+        text.strip_suffix(quote).unwrap_or(text)
+    }
+}
+
+/// Walks a source file while tracking the position of the next character
+struct Cursor<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    offset: usize,
+    position: LineColumn,
+}
+
+impl Cursor<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let (offset, c) = self.chars.next()?;
+        self.offset = offset + c.len_utf8();
+        if c == '\n' {
+            self.position.line += 1;
+            self.position.column = 0;
+        } else {
+            self.position.column += 1;
+        }
+        Some(c)
+    }
+}
+
+/// Lists the comments and the string literals of a source file, in order.
+/// An unterminated string literal ends with its line.
+pub fn scan(content: &str) -> Vec<Lexeme> {
+    let mut lexemes = Vec::new();
+    let mut cursor = Cursor {
+        chars: content.char_indices().peekable(),
+        offset: 0,
+        position: LineColumn { line: 1, column: 0 },
+    };
+
+    while let Some(c) = cursor.peek() {
+        let start = cursor.offset;
+        let start_position = cursor.position;
+        cursor.next();
+        let kind = match c {
+            '/' if cursor.peek() == Some('/') => {
+                while cursor.peek().is_some_and(|c| c != '\n') {
+                    cursor.next();
+                }
+                LexemeKind::LineComment
+            }
+            '/' if cursor.peek() == Some('*') => {
+                cursor.next();
+                let mut previous = ' ';
+                while let Some(c) = cursor.next() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+                LexemeKind::BlockComment
+            }
+            '"' | '\'' => {
+                while let Some(current) = cursor.peek() {
+                    if current == '\n' {
+                        break;
+                    }
+                    cursor.next();
+                    if current == c {
+                        break;
+                    }
+                    if current == '\\' && cursor.peek().is_some_and(|c| c != '\n') {
+                        cursor.next();
+                    }
+                }
+                LexemeKind::String(c)
+            }
+            _ => continue,
+        };
+        lexemes.push(Lexeme {
+            kind,
+            start,
+            end: cursor.offset,
+            start_position,
+            end_position: cursor.position,
+        });
+    }
+    lexemes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan() {
+        let source = "// it's\nstring s = 'a \"b\"'; /* \"c\" */ string t = \"\\\"d\";";
+        let lexemes = scan(source);
+        let kinds: Vec<LexemeKind> = lexemes.iter().map(|lexeme| lexeme.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                LexemeKind::LineComment,
+                LexemeKind::String('\''),
+                LexemeKind::BlockComment,
+                LexemeKind::String('"'),
+            ]
+        );
+        assert_eq!(lexemes[0].text(source), "// it's");
+        assert_eq!(lexemes[1].inner_text(source), "a \"b\"");
+        assert_eq!(
+            lexemes[1].start_position,
+            LineColumn {
+                line: 2,
+                column: 11
+            }
+        );
+        assert_eq!(
+            lexemes[1].end_position,
+            LineColumn {
+                line: 2,
+                column: 18
+            }
+        );
+        assert_eq!(lexemes[2].text(source), "/* \"c\" */");
+        assert_eq!(lexemes[3].inner_text(source), "\\\"d");
+    }
+
+    #[test]
+    fn test_scan_unterminated_string() {
+        let source = "string s = 'a\nuint b;";
+        let lexemes = scan(source);
+        assert_eq!(lexemes.len(), 1);
+        assert_eq!(lexemes[0].text(source), "'a");
+    }
+}
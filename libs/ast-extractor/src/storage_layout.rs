@@ -0,0 +1,529 @@
+/**
+ * storage_layout.rs
+ * Computes the storage slots of state variables and struct members
+*/
+use std::collections::HashMap;
+use syn_solidity::{
+    Expr, File, FunctionAttribute, FunctionAttributes, FunctionBody, FunctionKind, Item,
+    ItemContract, ItemFunction, ItemStruct, Lit, LitNumber, Type, TypeArray, TypeFunction,
+};
+
+pub const SLOT_SIZE: usize = 32;
+
+/// Storage taken by a value: a number of bytes packed with its neighbours, or whole slots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageSize {
+    Bytes(usize),
+    Slots(usize),
+}
+
+/// Position of a value in storage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StoragePosition {
+    pub slot: usize,
+    pub offset: usize,
+}
+
+/// A state variable or struct member, and where it is stored
+#[derive(Debug, Clone)]
+pub struct StorageEntry {
+    /// Contract or struct declaring the entry
+    pub parent: String,
+    pub name: String,
+    pub ty: Type,
+    pub size: StorageSize,
+    pub position: StoragePosition,
+}
+
+/// Assigns a position to values stored one after the other from `start`,
+/// and returns the positions with the first position free after them
+pub fn assign_positions(
+    sizes: &[StorageSize],
+    start: StoragePosition,
+) -> (Vec<StoragePosition>, StoragePosition) {
+    let mut current = start;
+    let mut positions = Vec::new();
+    for size in sizes {
+        match size {
+            StorageSize::Bytes(bytes) => {
+                if current.offset + bytes > SLOT_SIZE {
+                    current = StoragePosition {
+                        slot: current.slot + 1,
+                        offset: 0,
+                    };
+                }
+                positions.push(current);
+                current.offset += bytes;
+            }
+            // Structs and arrays start a new slot, and the values following them too
+            StorageSize::Slots(slots) => {
+                if current.offset > 0 {
+                    current = StoragePosition {
+                        slot: current.slot + 1,
+                        offset: 0,
+                    };
+                }
+                positions.push(current);
+                current.slot += slots;
+            }
+        }
+    }
+    (positions, current)
+}
+
+/// Number of slots started by values stored one after the other from `start`
+pub fn slot_count(sizes: &[StorageSize], start: StoragePosition) -> usize {
+    let (_, end) = assign_positions(sizes, start);
+    let end = end.slot + usize::from(end.offset > 0);
+    let start = start.slot + usize::from(start.offset > 0);
+    end.saturating_sub(start)
+}
+
+/// An order of values packing them in few slots from `start`: the largest values are placed first
+/// in the first slot with enough room, and the values taking whole slots come last
+pub fn packed_order(sizes: &[StorageSize], start: StoragePosition) -> Vec<usize> {
+    let mut packed: Vec<usize> = (0..sizes.len())
+        .filter(|index| matches!(sizes[*index], StorageSize::Bytes(_)))
+        .collect();
+    let bytes = |index: &usize| match sizes[*index] {
+        StorageSize::Bytes(bytes) => bytes,
+        StorageSize::Slots(_) => SLOT_SIZE,
+    };
+    // Stable, so that values of the same size keep their order
+    packed.sort_by_key(|index| std::cmp::Reverse(bytes(index)));
+
+    // The slot started before `start` is filled first
+    let mut slots: Vec<(usize, Vec<usize>)> = vec![(start.offset, Vec::new())];
+    for index in packed {
+        let size = bytes(&index);
+        match slots.iter_mut().find(|(used, _)| used + size <= SLOT_SIZE) {
+            Some((used, indexes)) => {
+                *used += size;
+                indexes.push(index);
+            }
+            None => slots.push((size, vec![index])),
+        }
+    }
+    let mut order: Vec<usize> = slots.into_iter().flat_map(|(_, indexes)| indexes).collect();
+    order.extend((0..sizes.len()).filter(|index| matches!(sizes[*index], StorageSize::Slots(_))));
+    order
+}
+
+/// Length of a fixed size array, when it is given by a literal
+pub(crate) fn array_length(array: &TypeArray) -> Option<usize> {
+    match array.size.as_deref() {
+        Some(Expr::Lit(Lit::Number(LitNumber::Int(length)))) => length.base10_parse().ok(),
+        _ => None,
+    }
+}
+
+/// A state variable of function type, such as `function() internal hook;`, which the parser reads
+/// as a function without name nor body whose last attribute is a modifier named after the variable
+fn function_type_variable(function: &ItemFunction) -> Option<(String, Type)> {
+    let FunctionKind::Function(function_token) = function.kind else {
+        return None;
+    };
+    if function.name.is_some() || !matches!(function.body, FunctionBody::Empty(_)) {
+        return None;
+    }
+    let (FunctionAttribute::Modifier(modifier), attributes) = function.attributes.split_last()?
+    else {
+        return None;
+    };
+    if modifier.paren_token.is_some() || modifier.name.len() != 1 {
+        return None;
+    }
+    let ty = Type::Function(TypeFunction {
+        function_token,
+        paren_token: function.paren_token?,
+        arguments: function.arguments.clone(),
+        attributes: FunctionAttributes(attributes.to_vec()),
+        returns: function.returns.clone(),
+    });
+    Some((modifier.name.last().to_string(), ty))
+}
+
+/// Types and contracts declared in a set of files
+pub struct Definitions<'a> {
+    contracts: HashMap<String, &'a ItemContract>,
+    structs: HashMap<String, &'a ItemStruct>,
+    udts: HashMap<String, &'a Type>,
+    enums: Vec<String>,
+}
+
+impl<'a> Definitions<'a> {
+    pub fn new(files: impl IntoIterator<Item = &'a File>) -> Self {
+        let mut definitions = Definitions {
+            contracts: HashMap::new(),
+            structs: HashMap::new(),
+            udts: HashMap::new(),
+            enums: Vec::new(),
+        };
+        for file in files {
+            for item in &file.items {
+                definitions.add(item);
+                if let Item::Contract(contract) = item {
+                    for item in &contract.body {
+                        definitions.add(item);
+                    }
+                }
+            }
+        }
+        definitions
+    }
+
+    fn add(&mut self, item: &'a Item) {
+        match item {
+            Item::Contract(contract) => {
+                self.contracts.insert(contract.name.to_string(), contract);
+            }
+            Item::Struct(strukt) => {
+                self.structs.insert(strukt.name.to_string(), strukt);
+            }
+            Item::Udt(udt) => {
+                self.udts.insert(udt.name.to_string(), &udt.ty);
+            }
+            Item::Enum(enumm) => self.enums.push(enumm.name.to_string()),
+            _ => {}
+        }
+    }
+
+    pub fn contract(&self, name: &str) -> Option<&'a ItemContract> {
+        self.contracts.get(name).copied()
+    }
+
+    pub fn struct_definition(&self, name: &str) -> Option<&'a ItemStruct> {
+        self.structs.get(name).copied()
+    }
+
+    /// Underlying type of a user defined value type
+    pub fn udt(&self, name: &str) -> Option<&'a Type> {
+        self.udts.get(name).copied()
+    }
+
+    pub fn is_enum(&self, name: &str) -> bool {
+        self.enums.iter().any(|enumm| enumm == name)
+    }
+
+    /// Storage taken by a value of a type
+    pub fn type_size(&self, ty: &Type) -> StorageSize {
+        self.sized(ty, &mut Vec::new())
+    }
+
+    fn sized(&self, ty: &Type, structs: &mut Vec<String>) -> StorageSize {
+        match ty {
+            Type::Bool(_) => StorageSize::Bytes(1),
+            Type::Address(..) => StorageSize::Bytes(20),
+            // Address and selector of external functions, code position of internal ones
+            Type::Function(function) if function.attributes.has_external() => {
+                StorageSize::Bytes(24)
+            }
+            Type::Function(_) => StorageSize::Bytes(8),
+            Type::FixedBytes(_, size) => StorageSize::Bytes(size.get() as usize),
+            Type::Int(_, size) | Type::Uint(_, size) => {
+                StorageSize::Bytes(size.map_or(SLOT_SIZE, |size| size.get() as usize / 8))
+            }
+            Type::Array(array) => {
+                let length = array_length(array);
+                // Dynamic arrays store their length in their slot
+                let Some(length) = length else {
+                    return StorageSize::Slots(1);
+                };
+                match self.sized(&array.ty, structs) {
+                    StorageSize::Bytes(bytes) => {
+                        StorageSize::Slots(length.div_ceil(SLOT_SIZE / bytes))
+                    }
+                    StorageSize::Slots(slots) => StorageSize::Slots(length * slots),
+                }
+            }
+            Type::Custom(path) => {
+                let name = path.last().to_string();
+                if self.enums.contains(&name) {
+                    StorageSize::Bytes(1)
+                } else if let Some(ty) = self.udts.get(&name) {
+                    self.sized(ty, structs)
+                } else if let Some(strukt) = self.structs.get(&name) {
+                    // A struct can only contain itself through a mapping or a dynamic array
+                    if structs.contains(&name) {
+                        return StorageSize::Slots(1);
+                    }
+                    structs.push(name);
+                    let sizes: Vec<StorageSize> = strukt
+                        .fields
+                        .iter()
+                        .map(|field| self.sized(&field.ty, structs))
+                        .collect();
+                    structs.pop();
+                    StorageSize::Slots(slot_count(&sizes, StoragePosition::default()).max(1))
+                } else {
+                    // Contracts and interfaces are stored as addresses
+                    StorageSize::Bytes(20)
+                }
+            }
+            Type::String(_) | Type::Bytes(_) | Type::Mapping(_) | Type::Tuple(_) => {
+                StorageSize::Slots(1)
+            }
+        }
+    }
+
+    /// C3 linearization of a contract, from the most derived contract to the most base one.
+    /// Returns None if the inheritance graph cannot be linearized.
+    /// The bases which are not declared in the files are kept without their own bases.
+    pub fn linearize(&self, contract: &ItemContract) -> Option<Vec<String>> {
+        self.linearize_name(&contract.name.to_string(), &mut Vec::new())
+    }
+
+    fn linearize_name(&self, name: &str, visiting: &mut Vec<String>) -> Option<Vec<String>> {
+        if visiting.iter().any(|visited| visited == name) {
+            return None;
+        }
+        let Some(contract) = self.contract(name) else {
+            return Some(vec![name.to_string()]);
+        };
+        // The last base listed is the most derived one
+        let bases: Vec<String> = contract
+            .inheritance
+            .iter()
+            .flat_map(|inheritance| inheritance.inheritance.iter())
+            .map(|base| base.name.last().to_string())
+            .rev()
+            .collect();
+
+        visiting.push(name.to_string());
+        let mut sequences = Vec::new();
+        for base in &bases {
+            sequences.push(self.linearize_name(base, visiting)?);
+        }
+        visiting.pop();
+        sequences.push(bases);
+
+        let mut linearization = vec![name.to_string()];
+        loop {
+            sequences.retain(|sequence| !sequence.is_empty());
+            if sequences.is_empty() {
+                return Some(linearization);
+            }
+            // The first head which does not appear in the tail of another sequence
+            let head = sequences.iter().map(|sequence| &sequence[0]).find(|head| {
+                sequences
+                    .iter()
+                    .all(|sequence| !sequence[1..].contains(head))
+            })?;
+            let head = head.clone();
+            for sequence in sequences.iter_mut() {
+                if sequence[0] == head {
+                    sequence.remove(0);
+                }
+            }
+            linearization.push(head);
+        }
+    }
+
+    /// Storage layout of a contract, including the state variables inherited from its bases,
+    /// from the most base contract to the contract itself.
+    /// Returns None if the inheritance graph cannot be linearized.
+    pub fn contract_layout(&self, contract: &ItemContract) -> Option<Vec<StorageEntry>> {
+        let mut entries = Vec::new();
+        for name in self.linearize(contract)?.iter().rev() {
+            let Some(contract) = self.contract(name) else {
+                continue;
+            };
+            for item in &contract.body {
+                let (variable_name, ty) = match item {
+                    // Constants and immutables are stored in the code
+                    Item::Variable(variable)
+                        if !variable.attributes.has_constant()
+                            && !variable.attributes.has_immutable() =>
+                    {
+                        (variable.name.to_string(), variable.ty.clone())
+                    }
+                    Item::Function(function) => match function_type_variable(function) {
+                        Some(variable) => variable,
+                        None => continue,
+                    },
+                    _ => continue,
+                };
+                entries.push(StorageEntry {
+                    parent: name.clone(),
+                    name: variable_name,
+                    size: self.type_size(&ty),
+                    ty,
+                    position: StoragePosition::default(),
+                });
+            }
+        }
+        Some(self.positioned(entries))
+    }
+
+    /// Storage layout of the members of a struct, relative to the first slot of the struct
+    pub fn struct_layout(&self, strukt: &ItemStruct) -> Vec<StorageEntry> {
+        let entries = strukt
+            .fields
+            .iter()
+            .map(|field| StorageEntry {
+                parent: strukt.name.to_string(),
+                name: field
+                    .name
+                    .as_ref()
+                    .map(|name| name.to_string())
+                    .unwrap_or_default(),
+                ty: field.ty.clone(),
+                size: self.type_size(&field.ty),
+                position: StoragePosition::default(),
+            })
+            .collect();
+        self.positioned(entries)
+    }
+
+    fn positioned(&self, mut entries: Vec<StorageEntry>) -> Vec<StorageEntry> {
+        let sizes: Vec<StorageSize> = entries.iter().map(|entry| entry.size).collect();
+        let (positions, _) = assign_positions(&sizes, StoragePosition::default());
+        for (entry, position) in entries.iter_mut().zip(positions) {
+            entry.position = position;
+        }
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extract::extract_ast_from_content;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn parse(name: &str) -> File {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests");
+        path.push("files");
+        path.push("storage_layout");
+        path.push(name);
+        let source = fs::read_to_string(path).unwrap();
+        extract_ast_from_content(&source).unwrap()
+    }
+
+    fn contract<'a>(file: &'a File, name: &str) -> &'a ItemContract {
+        file.items
+            .iter()
+            .find_map(|item| match item {
+                Item::Contract(contract) if contract.name == name => Some(contract),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn positions(entries: &[StorageEntry]) -> Vec<(String, usize, usize)> {
+        entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.name.clone(),
+                    entry.position.slot,
+                    entry.position.offset,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_linearize_diamond() {
+        let file = parse("inheritance.sol");
+        let definitions = Definitions::new([&file]);
+        let res = definitions.linearize(contract(&file, "D")).unwrap();
+        assert_eq!(res, vec!["D", "C", "B", "A"]);
+    }
+
+    #[test]
+    fn test_contract_layout_inherited() {
+        let file = parse("inheritance.sol");
+        let definitions = Definitions::new([&file]);
+        let res = definitions.contract_layout(contract(&file, "D")).unwrap();
+        assert_eq!(
+            positions(&res),
+            vec![
+                ("a".to_string(), 0, 0),
+                ("b".to_string(), 0, 16),
+                ("c".to_string(), 1, 0),
+                ("d".to_string(), 1, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_contract_layout_packing() {
+        let file = parse("packing.sol");
+        let definitions = Definitions::new([&file]);
+        let res = definitions
+            .contract_layout(contract(&file, "Packing"))
+            .unwrap();
+        assert_eq!(
+            positions(&res),
+            vec![
+                ("flag".to_string(), 0, 0),
+                ("owner".to_string(), 0, 1),
+                ("amount".to_string(), 1, 0),
+                ("small".to_string(), 2, 0),
+                ("position".to_string(), 3, 0),
+                ("next".to_string(), 5, 0),
+                ("balances".to_string(), 6, 0),
+                ("values".to_string(), 7, 0),
+                ("fixed".to_string(), 8, 0),
+                ("status".to_string(), 10, 0),
+                ("name".to_string(), 11, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_contract_layout_function_types() {
+        let file = parse("function_types.sol");
+        let definitions = Definitions::new([&file]);
+        let res = definitions
+            .contract_layout(contract(&file, "FunctionTypes"))
+            .unwrap();
+        assert_eq!(
+            positions(&res),
+            vec![
+                ("callback".to_string(), 0, 0),
+                ("hook".to_string(), 0, 8),
+                ("counter".to_string(), 0, 16),
+                ("notify".to_string(), 1, 0),
+                ("nonce".to_string(), 1, 24),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_struct_layout() {
+        let file = parse("packing.sol");
+        let definitions = Definitions::new([&file]);
+        let strukt = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Item::Struct(strukt) => Some(strukt),
+                _ => None,
+            })
+            .unwrap();
+        let res = definitions.struct_layout(strukt);
+        assert_eq!(
+            positions(&res),
+            vec![("open".to_string(), 0, 0), ("amount".to_string(), 1, 0),]
+        );
+    }
+
+    #[test]
+    fn test_packed_order() {
+        let sizes = [
+            StorageSize::Bytes(16),
+            StorageSize::Bytes(32),
+            StorageSize::Slots(1),
+            StorageSize::Bytes(16),
+        ];
+        let order = packed_order(&sizes, StoragePosition::default());
+        assert_eq!(order, vec![1, 0, 3, 2]);
+        let ordered: Vec<StorageSize> = order.iter().map(|index| sizes[*index]).collect();
+        assert_eq!(slot_count(&sizes, StoragePosition::default()), 4);
+        assert_eq!(slot_count(&ordered, StoragePosition::default()), 3);
+    }
+}
@@ -0,0 +1,25 @@
+struct Position {
+    bool open;
+    uint256 amount;
+}
+
+type Price is uint128;
+
+contract Types {
+    enum Status {
+        Active,
+        Closed
+    }
+
+    bool flag;
+    address owner;
+    uint amount;
+    IERC20 token;
+    Position position;
+    Status status;
+    Price price;
+    mapping(address => uint256) balances;
+    uint256[] values;
+    int8[3] fixed;
+    string name;
+}
@@ -0,0 +1,9 @@
+contract FunctionTypes {
+    function(uint256) internal pure callback;
+    function() internal hook;
+    uint64 counter;
+    function() external notify;
+    uint64 nonce;
+
+    function run() external {}
+}
@@ -0,0 +1,17 @@
+contract A {
+    uint128 a;
+}
+
+contract B is A {
+    uint128 b;
+}
+
+contract C is A {
+    bool c;
+    uint256 public constant MAX = 1;
+}
+
+contract D is B, C {
+    bool d;
+    address immutable owner;
+}
@@ -0,0 +1,23 @@
+struct Position {
+    bool open;
+    uint256 amount;
+}
+
+contract Packing {
+    enum Status {
+        Active,
+        Closed
+    }
+
+    bool flag;
+    address owner;
+    uint256 amount;
+    uint8 small;
+    Position position;
+    uint8 next;
+    mapping(address => uint256) balances;
+    uint256[] values;
+    uint128[3] fixed;
+    Status status;
+    string name;
+}
@@ -0,0 +1,851 @@
+use crate::handler::Handler;
+use crate::helpers::log;
+use crate::jsonrpc::{Error, Result};
+use async_trait::async_trait;
+use lsp_types::request::{
+    GotoDeclarationParams, GotoDeclarationResponse, GotoImplementationParams,
+    GotoImplementationResponse, GotoTypeDefinitionParams, GotoTypeDefinitionResponse,
+};
+use lsp_types::*;
+use serde_json::Value;
+
+/// Asynchronous variant of [`Handler`].
+///
+/// Handlers which need the response of the client, such as the result of
+/// [`Connection::configuration`](crate::Connection::configuration), or which run long tasks,
+/// implement this trait so that they do not block the event loop of the extension host.
+/// Handlers of both kinds can be registered on the same [`Dispatcher`](crate::Dispatcher).
+#[async_trait(?Send)]
+pub trait AsyncHandler {
+    /// See [`Handler::initialize`].
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult>;
+
+    /// See [`Handler::initialized`].
+    async fn initialized(&self, params: InitializedParams) {
+        let _ = params;
+    }
+
+    /// See [`Handler::shutdown`].
+    async fn shutdown(&self) -> Result<()>;
+
+    // Document Synchronization
+
+    /// See [`Handler::did_open`].
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let _ = params;
+        log("Got a textDocument/didOpen notification, but it is not implemented");
+    }
+
+    /// See [`Handler::did_change`].
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        let _ = params;
+        log("Got a textDocument/didChange notification, but it is not implemented");
+    }
+
+    /// See [`Handler::will_save`].
+    async fn will_save(&self, params: WillSaveTextDocumentParams) {
+        let _ = params;
+        log("Got a textDocument/willSave notification, but it is not implemented");
+    }
+
+    /// See [`Handler::will_save_wait_until`].
+    async fn will_save_wait_until(
+        &self,
+        params: WillSaveTextDocumentParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        let _ = params;
+        log("Got a textDocument/willSaveWaitUntil request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::did_save`].
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        let _ = params;
+        log("Got a textDocument/didSave notification, but it is not implemented");
+    }
+
+    /// See [`Handler::did_close`].
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let _ = params;
+        log("Got a textDocument/didClose notification, but it is not implemented");
+    }
+
+    // Language Features
+
+    /// See [`Handler::goto_declaration`].
+    async fn goto_declaration(
+        &self,
+        params: GotoDeclarationParams,
+    ) -> Result<Option<GotoDeclarationResponse>> {
+        let _ = params;
+        log("Got a textDocument/declaration request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::goto_definition`].
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let _ = params;
+        log("Got a textDocument/definition request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::goto_type_definition`].
+    async fn goto_type_definition(
+        &self,
+        params: GotoTypeDefinitionParams,
+    ) -> Result<Option<GotoTypeDefinitionResponse>> {
+        let _ = params;
+        log("Got a textDocument/typeDefinition request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::goto_implementation`].
+    async fn goto_implementation(
+        &self,
+        params: GotoImplementationParams,
+    ) -> Result<Option<GotoImplementationResponse>> {
+        let _ = params;
+        log("Got a textDocument/implementation request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::references`].
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let _ = params;
+        log("Got a textDocument/references request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::prepare_call_hierarchy`].
+    async fn prepare_call_hierarchy(
+        &self,
+        params: CallHierarchyPrepareParams,
+    ) -> Result<Option<Vec<CallHierarchyItem>>> {
+        let _ = params;
+        log("Got a textDocument/prepareCallHierarchy request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::incoming_calls`].
+    async fn incoming_calls(
+        &self,
+        params: CallHierarchyIncomingCallsParams,
+    ) -> Result<Option<Vec<CallHierarchyIncomingCall>>> {
+        let _ = params;
+        log("Got a callHierarchy/incomingCalls request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::outgoing_calls`].
+    async fn outgoing_calls(
+        &self,
+        params: CallHierarchyOutgoingCallsParams,
+    ) -> Result<Option<Vec<CallHierarchyOutgoingCall>>> {
+        let _ = params;
+        log("Got a callHierarchy/outgoingCalls request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::prepare_type_hierarchy`].
+    async fn prepare_type_hierarchy(
+        &self,
+        params: TypeHierarchyPrepareParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        let _ = params;
+        log("Got a textDocument/prepareTypeHierarchy request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::supertypes`].
+    async fn supertypes(
+        &self,
+        params: TypeHierarchySupertypesParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        let _ = params;
+        log("Got a typeHierarchy/supertypes request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::subtypes`].
+    async fn subtypes(
+        &self,
+        params: TypeHierarchySubtypesParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        let _ = params;
+        log("Got a typeHierarchy/subtypes request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::document_highlight`].
+    async fn document_highlight(
+        &self,
+        params: DocumentHighlightParams,
+    ) -> Result<Option<Vec<DocumentHighlight>>> {
+        let _ = params;
+        log("Got a textDocument/documentHighlight request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::document_link`].
+    async fn document_link(&self, params: DocumentLinkParams) -> Result<Option<Vec<DocumentLink>>> {
+        let _ = params;
+        log("Got a textDocument/documentLink request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::document_link_resolve`].
+    async fn document_link_resolve(&self, params: DocumentLink) -> Result<DocumentLink> {
+        let _ = params;
+        log("Got a documentLink/resolve request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::hover`].
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let _ = params;
+        log("Got a textDocument/hover request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::code_lens`].
+    async fn code_lens(&self, params: CodeLensParams) -> Result<Option<Vec<CodeLens>>> {
+        let _ = params;
+        log("Got a textDocument/codeLens request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::code_lens_resolve`].
+    async fn code_lens_resolve(&self, params: CodeLens) -> Result<CodeLens> {
+        let _ = params;
+        log("Got a codeLens/resolve request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::folding_range`].
+    async fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
+        let _ = params;
+        log("Got a textDocument/foldingRange request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::selection_range`].
+    async fn selection_range(
+        &self,
+        params: SelectionRangeParams,
+    ) -> Result<Option<Vec<SelectionRange>>> {
+        let _ = params;
+        log("Got a textDocument/selectionRange request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::document_symbol`].
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        let _ = params;
+        log("Got a textDocument/documentSymbol request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::semantic_tokens_full`].
+    async fn semantic_tokens_full(
+        &self,
+        params: SemanticTokensParams,
+    ) -> Result<Option<SemanticTokensResult>> {
+        let _ = params;
+        log("Got a textDocument/semanticTokens/full request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::semantic_tokens_full_delta`].
+    async fn semantic_tokens_full_delta(
+        &self,
+        params: SemanticTokensDeltaParams,
+    ) -> Result<Option<SemanticTokensFullDeltaResult>> {
+        let _ = params;
+        log("Got a textDocument/semanticTokens/full/delta request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::semantic_tokens_range`].
+    async fn semantic_tokens_range(
+        &self,
+        params: SemanticTokensRangeParams,
+    ) -> Result<Option<SemanticTokensRangeResult>> {
+        let _ = params;
+        log("Got a textDocument/semanticTokens/range request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::inline_value`].
+    async fn inline_value(&self, params: InlineValueParams) -> Result<Option<Vec<InlineValue>>> {
+        let _ = params;
+        log("Got a textDocument/inlineValue request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::inlay_hint`].
+    async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
+        let _ = params;
+        log("Got a textDocument/inlayHint request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::inlay_hint_resolve`].
+    async fn inlay_hint_resolve(&self, params: InlayHint) -> Result<InlayHint> {
+        let _ = params;
+        log("Got a inlayHint/resolve request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::moniker`].
+    async fn moniker(&self, params: MonikerParams) -> Result<Option<Vec<Moniker>>> {
+        let _ = params;
+        log("Got a textDocument/moniker request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::completion`].
+    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        let _ = params;
+        log("Got a textDocument/completion request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::completion_resolve`].
+    async fn completion_resolve(&self, params: CompletionItem) -> Result<CompletionItem> {
+        let _ = params;
+        log("Got a completionItem/resolve request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::diagnostic`].
+    async fn diagnostic(
+        &self,
+        params: DocumentDiagnosticParams,
+    ) -> Result<DocumentDiagnosticReportResult> {
+        let _ = params;
+        log("Got a textDocument/diagnostic request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::workspace_diagnostic`].
+    async fn workspace_diagnostic(
+        &self,
+        params: WorkspaceDiagnosticParams,
+    ) -> Result<WorkspaceDiagnosticReportResult> {
+        let _ = params;
+        log("Got a workspace/diagnostic request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::signature_help`].
+    async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        let _ = params;
+        log("Got a textDocument/signatureHelp request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::code_action`].
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let _ = params;
+        log("Got a textDocument/codeAction request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::code_action_resolve`].
+    async fn code_action_resolve(&self, params: CodeAction) -> Result<CodeAction> {
+        let _ = params;
+        log("Got a codeAction/resolve request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::document_color`].
+    async fn document_color(&self, params: DocumentColorParams) -> Result<Vec<ColorInformation>> {
+        let _ = params;
+        log("Got a textDocument/documentColor request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::color_presentation`].
+    async fn color_presentation(
+        &self,
+        params: ColorPresentationParams,
+    ) -> Result<Vec<ColorPresentation>> {
+        let _ = params;
+        log("Got a textDocument/colorPresentation request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::formatting`].
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        let _ = params;
+        log("Got a textDocument/formatting request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::range_formatting`].
+    async fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        let _ = params;
+        log("Got a textDocument/rangeFormatting request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::on_type_formatting`].
+    async fn on_type_formatting(
+        &self,
+        params: DocumentOnTypeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        let _ = params;
+        log("Got a textDocument/onTypeFormatting request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::rename`].
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let _ = params;
+        log("Got a textDocument/rename request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::prepare_rename`].
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        let _ = params;
+        log("Got a textDocument/prepareRename request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::linked_editing_range`].
+    async fn linked_editing_range(
+        &self,
+        params: LinkedEditingRangeParams,
+    ) -> Result<Option<LinkedEditingRanges>> {
+        let _ = params;
+        log("Got a textDocument/linkedEditingRange request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    // Workspace Features
+
+    /// See [`Handler::symbol`].
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<Vec<SymbolInformation>>> {
+        let _ = params;
+        log("Got a workspace/symbol request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::symbol_resolve`].
+    async fn symbol_resolve(&self, params: WorkspaceSymbol) -> Result<WorkspaceSymbol> {
+        let _ = params;
+        log("Got a workspaceSymbol/resolve request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::did_change_configuration`].
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        let _ = params;
+        log("Got a workspace/didChangeConfiguration notification, but it is not implemented");
+    }
+
+    /// See [`Handler::did_change_workspace_folders`].
+    async fn did_change_workspace_folders(&self, params: DidChangeWorkspaceFoldersParams) {
+        let _ = params;
+        log("Got a workspace/didChangeWorkspaceFolders notification, but it is not implemented");
+    }
+
+    /// See [`Handler::will_create_files`].
+    async fn will_create_files(&self, params: CreateFilesParams) -> Result<Option<WorkspaceEdit>> {
+        let _ = params;
+        log("Got a workspace/willCreateFiles request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::did_create_files`].
+    async fn did_create_files(&self, params: CreateFilesParams) {
+        let _ = params;
+        log("Got a workspace/didCreateFiles notification, but it is not implemented");
+    }
+
+    /// See [`Handler::will_rename_files`].
+    async fn will_rename_files(&self, params: RenameFilesParams) -> Result<Option<WorkspaceEdit>> {
+        let _ = params;
+        log("Got a workspace/willRenameFiles request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::did_rename_files`].
+    async fn did_rename_files(&self, params: RenameFilesParams) {
+        let _ = params;
+        log("Got a workspace/didRenameFiles notification, but it is not implemented");
+    }
+
+    /// See [`Handler::will_delete_files`].
+    async fn will_delete_files(&self, params: DeleteFilesParams) -> Result<Option<WorkspaceEdit>> {
+        let _ = params;
+        log("Got a workspace/willDeleteFiles request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+
+    /// See [`Handler::did_delete_files`].
+    async fn did_delete_files(&self, params: DeleteFilesParams) {
+        let _ = params;
+        log("Got a workspace/didDeleteFiles notification, but it is not implemented");
+    }
+
+    /// See [`Handler::did_change_watched_files`].
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        let _ = params;
+        log("Got a workspace/didChangeWatchedFiles notification, but it is not implemented");
+    }
+
+    /// See [`Handler::execute_command`].
+    async fn execute_command(&self, params: ExecuteCommandParams) -> Result<Option<Value>> {
+        let _ = params;
+        log("Got a workspace/executeCommand request, but it is not implemented");
+        Err(Error::method_not_found())
+    }
+}
+
+/// Synchronous handlers are run to completion when awaited
+#[async_trait(?Send)]
+impl AsyncHandler for Box<dyn Handler> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        Handler::initialize(self.as_ref(), params)
+    }
+
+    async fn initialized(&self, params: InitializedParams) {
+        Handler::initialized(self.as_ref(), params)
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        Handler::shutdown(self.as_ref())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        Handler::did_open(self.as_ref(), params)
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        Handler::did_change(self.as_ref(), params)
+    }
+
+    async fn will_save(&self, params: WillSaveTextDocumentParams) {
+        Handler::will_save(self.as_ref(), params)
+    }
+
+    async fn will_save_wait_until(
+        &self,
+        params: WillSaveTextDocumentParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        Handler::will_save_wait_until(self.as_ref(), params)
+    }
+
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        Handler::did_save(self.as_ref(), params)
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        Handler::did_close(self.as_ref(), params)
+    }
+
+    async fn goto_declaration(
+        &self,
+        params: GotoDeclarationParams,
+    ) -> Result<Option<GotoDeclarationResponse>> {
+        Handler::goto_declaration(self.as_ref(), params)
+    }
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        Handler::goto_definition(self.as_ref(), params)
+    }
+
+    async fn goto_type_definition(
+        &self,
+        params: GotoTypeDefinitionParams,
+    ) -> Result<Option<GotoTypeDefinitionResponse>> {
+        Handler::goto_type_definition(self.as_ref(), params)
+    }
+
+    async fn goto_implementation(
+        &self,
+        params: GotoImplementationParams,
+    ) -> Result<Option<GotoImplementationResponse>> {
+        Handler::goto_implementation(self.as_ref(), params)
+    }
+
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        Handler::references(self.as_ref(), params)
+    }
+
+    async fn prepare_call_hierarchy(
+        &self,
+        params: CallHierarchyPrepareParams,
+    ) -> Result<Option<Vec<CallHierarchyItem>>> {
+        Handler::prepare_call_hierarchy(self.as_ref(), params)
+    }
+
+    async fn incoming_calls(
+        &self,
+        params: CallHierarchyIncomingCallsParams,
+    ) -> Result<Option<Vec<CallHierarchyIncomingCall>>> {
+        Handler::incoming_calls(self.as_ref(), params)
+    }
+
+    async fn outgoing_calls(
+        &self,
+        params: CallHierarchyOutgoingCallsParams,
+    ) -> Result<Option<Vec<CallHierarchyOutgoingCall>>> {
+        Handler::outgoing_calls(self.as_ref(), params)
+    }
+
+    async fn prepare_type_hierarchy(
+        &self,
+        params: TypeHierarchyPrepareParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        Handler::prepare_type_hierarchy(self.as_ref(), params)
+    }
+
+    async fn supertypes(
+        &self,
+        params: TypeHierarchySupertypesParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        Handler::supertypes(self.as_ref(), params)
+    }
+
+    async fn subtypes(
+        &self,
+        params: TypeHierarchySubtypesParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        Handler::subtypes(self.as_ref(), params)
+    }
+
+    async fn document_highlight(
+        &self,
+        params: DocumentHighlightParams,
+    ) -> Result<Option<Vec<DocumentHighlight>>> {
+        Handler::document_highlight(self.as_ref(), params)
+    }
+
+    async fn document_link(&self, params: DocumentLinkParams) -> Result<Option<Vec<DocumentLink>>> {
+        Handler::document_link(self.as_ref(), params)
+    }
+
+    async fn document_link_resolve(&self, params: DocumentLink) -> Result<DocumentLink> {
+        Handler::document_link_resolve(self.as_ref(), params)
+    }
+
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        Handler::hover(self.as_ref(), params)
+    }
+
+    async fn code_lens(&self, params: CodeLensParams) -> Result<Option<Vec<CodeLens>>> {
+        Handler::code_lens(self.as_ref(), params)
+    }
+
+    async fn code_lens_resolve(&self, params: CodeLens) -> Result<CodeLens> {
+        Handler::code_lens_resolve(self.as_ref(), params)
+    }
+
+    async fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
+        Handler::folding_range(self.as_ref(), params)
+    }
+
+    async fn selection_range(
+        &self,
+        params: SelectionRangeParams,
+    ) -> Result<Option<Vec<SelectionRange>>> {
+        Handler::selection_range(self.as_ref(), params)
+    }
+
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        Handler::document_symbol(self.as_ref(), params)
+    }
+
+    async fn semantic_tokens_full(
+        &self,
+        params: SemanticTokensParams,
+    ) -> Result<Option<SemanticTokensResult>> {
+        Handler::semantic_tokens_full(self.as_ref(), params)
+    }
+
+    async fn semantic_tokens_full_delta(
+        &self,
+        params: SemanticTokensDeltaParams,
+    ) -> Result<Option<SemanticTokensFullDeltaResult>> {
+        Handler::semantic_tokens_full_delta(self.as_ref(), params)
+    }
+
+    async fn semantic_tokens_range(
+        &self,
+        params: SemanticTokensRangeParams,
+    ) -> Result<Option<SemanticTokensRangeResult>> {
+        Handler::semantic_tokens_range(self.as_ref(), params)
+    }
+
+    async fn inline_value(&self, params: InlineValueParams) -> Result<Option<Vec<InlineValue>>> {
+        Handler::inline_value(self.as_ref(), params)
+    }
+
+    async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
+        Handler::inlay_hint(self.as_ref(), params)
+    }
+
+    async fn inlay_hint_resolve(&self, params: InlayHint) -> Result<InlayHint> {
+        Handler::inlay_hint_resolve(self.as_ref(), params)
+    }
+
+    async fn moniker(&self, params: MonikerParams) -> Result<Option<Vec<Moniker>>> {
+        Handler::moniker(self.as_ref(), params)
+    }
+
+    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        Handler::completion(self.as_ref(), params)
+    }
+
+    async fn completion_resolve(&self, params: CompletionItem) -> Result<CompletionItem> {
+        Handler::completion_resolve(self.as_ref(), params)
+    }
+
+    async fn diagnostic(
+        &self,
+        params: DocumentDiagnosticParams,
+    ) -> Result<DocumentDiagnosticReportResult> {
+        Handler::diagnostic(self.as_ref(), params)
+    }
+
+    async fn workspace_diagnostic(
+        &self,
+        params: WorkspaceDiagnosticParams,
+    ) -> Result<WorkspaceDiagnosticReportResult> {
+        Handler::workspace_diagnostic(self.as_ref(), params)
+    }
+
+    async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        Handler::signature_help(self.as_ref(), params)
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        Handler::code_action(self.as_ref(), params)
+    }
+
+    async fn code_action_resolve(&self, params: CodeAction) -> Result<CodeAction> {
+        Handler::code_action_resolve(self.as_ref(), params)
+    }
+
+    async fn document_color(&self, params: DocumentColorParams) -> Result<Vec<ColorInformation>> {
+        Handler::document_color(self.as_ref(), params)
+    }
+
+    async fn color_presentation(
+        &self,
+        params: ColorPresentationParams,
+    ) -> Result<Vec<ColorPresentation>> {
+        Handler::color_presentation(self.as_ref(), params)
+    }
+
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        Handler::formatting(self.as_ref(), params)
+    }
+
+    async fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        Handler::range_formatting(self.as_ref(), params)
+    }
+
+    async fn on_type_formatting(
+        &self,
+        params: DocumentOnTypeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        Handler::on_type_formatting(self.as_ref(), params)
+    }
+
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        Handler::rename(self.as_ref(), params)
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        Handler::prepare_rename(self.as_ref(), params)
+    }
+
+    async fn linked_editing_range(
+        &self,
+        params: LinkedEditingRangeParams,
+    ) -> Result<Option<LinkedEditingRanges>> {
+        Handler::linked_editing_range(self.as_ref(), params)
+    }
+
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<Vec<SymbolInformation>>> {
+        Handler::symbol(self.as_ref(), params)
+    }
+
+    async fn symbol_resolve(&self, params: WorkspaceSymbol) -> Result<WorkspaceSymbol> {
+        Handler::symbol_resolve(self.as_ref(), params)
+    }
+
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        Handler::did_change_configuration(self.as_ref(), params)
+    }
+
+    async fn did_change_workspace_folders(&self, params: DidChangeWorkspaceFoldersParams) {
+        Handler::did_change_workspace_folders(self.as_ref(), params)
+    }
+
+    async fn will_create_files(&self, params: CreateFilesParams) -> Result<Option<WorkspaceEdit>> {
+        Handler::will_create_files(self.as_ref(), params)
+    }
+
+    async fn did_create_files(&self, params: CreateFilesParams) {
+        Handler::did_create_files(self.as_ref(), params)
+    }
+
+    async fn will_rename_files(&self, params: RenameFilesParams) -> Result<Option<WorkspaceEdit>> {
+        Handler::will_rename_files(self.as_ref(), params)
+    }
+
+    async fn did_rename_files(&self, params: RenameFilesParams) {
+        Handler::did_rename_files(self.as_ref(), params)
+    }
+
+    async fn will_delete_files(&self, params: DeleteFilesParams) -> Result<Option<WorkspaceEdit>> {
+        Handler::will_delete_files(self.as_ref(), params)
+    }
+
+    async fn did_delete_files(&self, params: DeleteFilesParams) {
+        Handler::did_delete_files(self.as_ref(), params)
+    }
+
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        Handler::did_change_watched_files(self.as_ref(), params)
+    }
+
+    async fn execute_command(&self, params: ExecuteCommandParams) -> Result<Option<Value>> {
+        Handler::execute_command(self.as_ref(), params)
+    }
+}
@@ -6,14 +6,14 @@ use serde::Serialize;
 use serde_json::Value;
 use std::fmt::Display;
 
-use serde_wasm_bindgen::{from_value, to_value};
+use std::rc::Rc;
 use tracing::error;
-use wasm_bindgen::JsValue;
+
+use crate::transport::Transport;
 
 #[derive(Clone)]
 struct ConnectionInner {
-    send_request_callback: js_sys::Function,
-    send_notification_callback: js_sys::Function,
+    transport: Rc<dyn Transport>,
 }
 
 /// Handle for communicating with the language client.
@@ -29,14 +29,11 @@ pub struct Connection {
 }
 
 impl Connection {
-    pub fn new(
-        send_request_callback: js_sys::Function,
-        send_notification_callback: js_sys::Function,
-    ) -> Self {
+    /// Creates a connection sending messages to the client through the given transport
+    pub fn with_transport<T: Transport + 'static>(transport: T) -> Self {
         Self {
             inner: ConnectionInner {
-                send_request_callback,
-                send_notification_callback,
+                transport: Rc::new(transport),
             },
         }
     }
@@ -57,8 +54,12 @@ impl Connection {
     /// immediately return `Err` with JSON-RPC error code `-32002` ([read more]).
     ///
     /// [read more]: https://microsoft.github.io/language-server-protocol/specification#initialize
-    pub fn register_capability(&self, registrations: Vec<Registration>) -> jsonrpc::Result<()> {
+    pub async fn register_capability(
+        &self,
+        registrations: Vec<Registration>,
+    ) -> jsonrpc::Result<()> {
         self.send_request::<RegisterCapability>(RegistrationParams { registrations })
+            .await
     }
 
     /// Unregisters a capability with the client.
@@ -73,11 +74,12 @@ impl Connection {
     /// immediately return `Err` with JSON-RPC error code `-32002` ([read more]).
     ///
     /// [read more]: https://microsoft.github.io/language-server-protocol/specification#initialize
-    pub fn unregister_capability(
+    pub async fn unregister_capability(
         &self,
         unregisterations: Vec<Unregistration>,
     ) -> jsonrpc::Result<()> {
         self.send_request::<UnregisterCapability>(UnregistrationParams { unregisterations })
+            .await
     }
 
     // Window Features
@@ -102,18 +104,19 @@ impl Connection {
     /// This corresponds to the [`window/showMessageRequest`] request.
     ///
     /// [`window/showMessageRequest`]: https://microsoft.github.io/language-server-protocol/specification#window_showMessageRequest
-    /*pub fn show_message_request<M: Display>(
+    pub async fn show_message_request<M: Display>(
         &self,
         typ: MessageType,
         message: M,
         actions: Option<Vec<MessageActionItem>>,
     ) -> jsonrpc::Result<Option<MessageActionItem>> {
-        self.send_request_unchecked::<ShowMessageRequest>(ShowMessageRequestParams {
+        self.send_request::<ShowMessageRequest>(ShowMessageRequestParams {
             typ,
             message: message.to_string(),
             actions,
         })
-    }*/
+        .await
+    }
 
     /// Notifies the client to log a particular message.
     ///
@@ -145,8 +148,8 @@ impl Connection {
     /// # Compatibility
     ///
     /// This request was introduced in specification version 3.16.0.
-    pub fn show_document(&self, params: ShowDocumentParams) -> jsonrpc::Result<bool> {
-        let response = self.send_request::<ShowDocument>(params)?;
+    pub async fn show_document(&self, params: ShowDocumentParams) -> jsonrpc::Result<bool> {
+        let response = self.send_request::<ShowDocument>(params).await?;
         Ok(response.success)
     }
 
@@ -193,8 +196,8 @@ impl Connection {
     /// # Compatibility
     ///
     /// This request was introduced in specification version 3.16.0.
-    pub fn code_lens_refresh(&self) -> jsonrpc::Result<()> {
-        self.send_request::<CodeLensRefresh>(())
+    pub async fn code_lens_refresh(&self) -> jsonrpc::Result<()> {
+        self.send_request::<CodeLensRefresh>(()).await
     }
 
     /// Asks the client to refresh the editors for which this server provides semantic tokens. As a
@@ -219,8 +222,8 @@ impl Connection {
     /// # Compatibility
     ///
     /// This request was introduced in specification version 3.16.0.
-    pub fn semantic_tokens_refresh(&self) -> jsonrpc::Result<()> {
-        self.send_request::<SemanticTokensRefresh>(())
+    pub async fn semantic_tokens_refresh(&self) -> jsonrpc::Result<()> {
+        self.send_request::<SemanticTokensRefresh>(()).await
     }
 
     /// Asks the client to refresh the inline values currently shown in editors. As a result, the
@@ -244,8 +247,8 @@ impl Connection {
     /// # Compatibility
     ///
     /// This request was introduced in specification version 3.17.0.
-    pub fn inline_value_refresh(&self) -> jsonrpc::Result<()> {
-        self.send_request::<InlineValueRefreshRequest>(())
+    pub async fn inline_value_refresh(&self) -> jsonrpc::Result<()> {
+        self.send_request::<InlineValueRefreshRequest>(()).await
     }
 
     /// Asks the client to refresh the inlay hints currently shown in editors. As a result, the
@@ -269,8 +272,8 @@ impl Connection {
     /// # Compatibility
     ///
     /// This request was introduced in specification version 3.17.0.
-    pub fn inlay_hint_refresh(&self) -> jsonrpc::Result<()> {
-        self.send_request::<InlayHintRefreshRequest>(())
+    pub async fn inlay_hint_refresh(&self) -> jsonrpc::Result<()> {
+        self.send_request::<InlayHintRefreshRequest>(()).await
     }
 
     /// Asks the client to refresh all needed document and workspace diagnostics.
@@ -292,8 +295,8 @@ impl Connection {
     /// # Compatibility
     ///
     /// This request was introduced in specification version 3.17.0.
-    pub fn workspace_diagnostic_refresh(&self) -> jsonrpc::Result<()> {
-        self.send_request::<WorkspaceDiagnosticRefresh>(())
+    pub async fn workspace_diagnostic_refresh(&self) -> jsonrpc::Result<()> {
+        self.send_request::<WorkspaceDiagnosticRefresh>(()).await
     }
 
     /// Submits validation diagnostics for an open file with the given URI.
@@ -334,8 +337,12 @@ impl Connection {
     /// # Compatibility
     ///
     /// This request was introduced in specification version 3.6.0.
-    pub fn configuration(&self, items: Vec<ConfigurationItem>) -> jsonrpc::Result<Vec<Value>> {
+    pub async fn configuration(
+        &self,
+        items: Vec<ConfigurationItem>,
+    ) -> jsonrpc::Result<Vec<Value>> {
         self.send_request::<WorkspaceConfiguration>(ConfigurationParams { items })
+            .await
     }
 
     /// Fetches the current open list of workspace folders.
@@ -357,8 +364,8 @@ impl Connection {
     /// # Compatibility
     ///
     /// This request was introduced in specification version 3.6.0.
-    pub fn workspace_folders(&self) -> jsonrpc::Result<Option<Vec<WorkspaceFolder>>> {
-        self.send_request::<WorkspaceFoldersRequest>(())
+    pub async fn workspace_folders(&self) -> jsonrpc::Result<Option<Vec<WorkspaceFolder>>> {
+        self.send_request::<WorkspaceFoldersRequest>(()).await
     }
 
     /// Requests a workspace resource be edited on the client side and returns whether the edit was
@@ -374,8 +381,12 @@ impl Connection {
     /// immediately return `Err` with JSON-RPC error code `-32002` ([read more]).
     ///
     /// [read more]: https://microsoft.github.io/language-server-protocol/specification#initialize
-    pub fn apply_edit(&self, edit: WorkspaceEdit) -> jsonrpc::Result<ApplyWorkspaceEditResponse> {
+    pub async fn apply_edit(
+        &self,
+        edit: WorkspaceEdit,
+    ) -> jsonrpc::Result<ApplyWorkspaceEditResponse> {
         self.send_request::<ApplyWorkspaceEdit>(ApplyWorkspaceEditParams { edit, label: None })
+            .await
     }
 
     /// Sends a custom notification to the client.
@@ -387,12 +398,10 @@ impl Connection {
     where
         N: lsp_types::notification::Notification,
     {
-        let inner = self.inner.clone();
-        let notification = to_value(&N::METHOD.to_string()).unwrap();
-        let param = to_value(&params).unwrap();
-        let _ = inner
-            .send_notification_callback
-            .call2(&JsValue::NULL, &notification, &param);
+        match serde_json::to_value(params) {
+            Ok(params) => self.inner.transport.send_notification(N::METHOD, params),
+            Err(e) => error!("invalid JSON in `{}` notification: {}", N::METHOD, e),
+        }
     }
 
     /// Sends a custom request to the client.
@@ -403,24 +412,12 @@ impl Connection {
     /// immediately return `Err` with JSON-RPC error code `-32002` ([read more]).
     ///
     /// [read more]: https://microsoft.github.io/language-server-protocol/specification#initialize
-    pub fn send_request<R>(&self, params: R::Params) -> jsonrpc::Result<R::Result>
+    pub async fn send_request<R>(&self, params: R::Params) -> jsonrpc::Result<R::Result>
     where
         R: lsp_types::request::Request,
     {
-        let inner = self.inner.clone();
-        let request = to_value(&R::METHOD.to_string()).unwrap();
-        let param = to_value(&params).unwrap();
-        let res = inner
-            .send_request_callback
-            .call2(&JsValue::NULL, &request, &param);
-
-        let res = match res {
-            Ok(res) => from_value::<R::Result>(res).unwrap(),
-            Err(_e) => {
-                return Err(jsonrpc::Error::internal_error());
-            }
-        };
-
-        Ok(res)
+        let params = serde_json::to_value(params).map_err(|_| jsonrpc::Error::internal_error())?;
+        let res = self.inner.transport.send_request(R::METHOD, params).await?;
+        serde_json::from_value(res).map_err(|_| jsonrpc::Error::internal_error())
     }
 }
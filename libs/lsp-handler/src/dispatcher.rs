@@ -1,7 +1,11 @@
+use crate::async_handler::AsyncHandler;
 use crate::connection::Connection;
 use crate::handler::Handler;
 use crate::helpers::log;
 use crate::jsonrpc::{Error, ErrorCode, Result};
+use crate::merge;
+use async_trait::async_trait;
+use futures::future::{join_all, LocalBoxFuture};
 use lsp_types::notification::*;
 use lsp_types::request::*;
 use lsp_types::{
@@ -12,30 +16,39 @@ use lsp_types::{
     CompletionResponse, CreateFilesParams, DeleteFilesParams, DidChangeConfigurationParams,
     DidChangeTextDocumentParams, DidChangeWatchedFilesParams, DidChangeWorkspaceFoldersParams,
     DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
-    DocumentColorParams, DocumentDiagnosticParams, DocumentDiagnosticReportResult,
-    DocumentFormattingParams, DocumentHighlight, DocumentHighlightParams, DocumentLink,
-    DocumentLinkParams, DocumentOnTypeFormattingParams, DocumentRangeFormattingParams,
-    DocumentSymbolParams, DocumentSymbolResponse, ExecuteCommandParams, FoldingRange,
-    FoldingRangeParams, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverParams,
-    InitializeParams, InitializeResult, InitializedParams, InlayHint, InlayHintParams, InlineValue,
-    InlineValueParams, LinkedEditingRangeParams, LinkedEditingRanges, Location, MessageType,
-    Moniker, MonikerParams, PrepareRenameResponse, ReferenceParams, RenameFilesParams,
-    RenameParams, SelectionRange, SelectionRangeParams, SemanticTokensDeltaParams,
-    SemanticTokensFullDeltaResult, SemanticTokensParams, SemanticTokensRangeParams,
+    DocumentColorParams, DocumentDiagnosticParams, DocumentDiagnosticReport,
+    DocumentDiagnosticReportResult, DocumentFormattingParams, DocumentHighlight,
+    DocumentHighlightParams, DocumentLink, DocumentLinkParams, DocumentOnTypeFormattingParams,
+    DocumentRangeFormattingParams, DocumentSymbolParams, DocumentSymbolResponse,
+    ExecuteCommandParams, FoldingRange, FoldingRangeParams, GotoDefinitionParams,
+    GotoDefinitionResponse, Hover, HoverParams, InitializeParams, InitializeResult,
+    InitializedParams, InlayHint, InlayHintParams, InlineValue, InlineValueParams,
+    LinkedEditingRangeParams, LinkedEditingRanges, Location, MessageType, Moniker, MonikerParams,
+    PrepareRenameResponse, ReferenceParams, RelatedUnchangedDocumentDiagnosticReport,
+    RenameFilesParams, RenameParams, SelectionRange, SelectionRangeParams, SemanticToken,
+    SemanticTokensDelta, SemanticTokensDeltaParams, SemanticTokensFullDeltaResult,
+    SemanticTokensLegend, SemanticTokensParams, SemanticTokensRangeParams,
     SemanticTokensRangeResult, SemanticTokensResult, SignatureHelp, SignatureHelpParams,
     SymbolInformation, TextDocumentPositionParams, TextEdit, TypeHierarchyItem,
     TypeHierarchyPrepareParams, TypeHierarchySubtypesParams, TypeHierarchySupertypesParams,
-    WillSaveTextDocumentParams, WorkspaceDiagnosticParams, WorkspaceDiagnosticReportResult,
-    WorkspaceEdit, WorkspaceSymbol, WorkspaceSymbolParams,
+    UnchangedDocumentDiagnosticReport, WillSaveTextDocumentParams, WorkspaceDiagnosticParams,
+    WorkspaceDiagnosticReport, WorkspaceDiagnosticReportResult, WorkspaceEdit, WorkspaceSymbol,
+    WorkspaceSymbolParams,
 };
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
-use serde_wasm_bindgen::{from_value, to_value};
+use std::cell::RefCell;
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct Dispatcher {
-    handlers: Vec<Box<dyn Handler>>,
+    handlers: Vec<Box<dyn AsyncHandler>>,
     connection: Connection,
+    /// Capabilities advertised by each handler, as JSON, once initialized
+    capabilities: RefCell<Vec<Value>>,
+    /// Semantic tokens legend advertised to the client, shared by all handlers
+    legend: RefCell<Option<SemanticTokensLegend>>,
 }
 
 impl Dispatcher {
@@ -43,12 +56,25 @@ impl Dispatcher {
         Dispatcher {
             handlers: Vec::new(),
             connection,
+            capabilities: RefCell::new(Vec::new()),
+            legend: RefCell::new(None),
         }
     }
 
     pub fn setup<F>(&mut self, creators: Vec<F>)
     where
         F: FnOnce(Connection) -> Box<dyn Handler>,
+    {
+        for creator in creators {
+            self.handlers
+                .push(Box::new(creator(self.connection.clone())));
+        }
+    }
+
+    /// Registers handlers implementing [`AsyncHandler`], which can await the responses of the client
+    pub fn setup_async<F>(&mut self, creators: Vec<F>)
+    where
+        F: FnOnce(Connection) -> Box<dyn AsyncHandler>,
     {
         for creator in creators {
             self.handlers.push(creator(self.connection.clone()));
@@ -56,1734 +82,822 @@ impl Dispatcher {
     }
 }
 
-#[wasm_bindgen]
 impl Dispatcher {
-    #[allow(unused_variables)]
-    #[wasm_bindgen(js_class = Dispatcher, js_name = onRequest)]
-    pub async fn on_request(
-        &mut self,
-        method: &str,
-        params: JsValue,
-    ) -> std::result::Result<JsValue, JsValue> {
+    /// Handles a request sent by the client and returns its JSON result,
+    /// independently of the transport used to receive it
+    pub async fn handle_request(&self, method: &str, params: Value) -> Result<Value> {
         log(method);
 
         match method {
-            Initialize::METHOD => {
-                let params: InitializeParams = from_value(params).unwrap();
-                let res = self.initialize(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-                Ok(result)
-            }
-            Shutdown::METHOD => {
-                let params: InitializeParams = from_value(params).unwrap();
-                let res = self.initialize(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-                Ok(result)
-            }
+            Initialize::METHOD => to_json(self.initialize(from_json(params)?).await),
+            Shutdown::METHOD => to_json(self.shutdown().await),
             WillSaveWaitUntil::METHOD => {
-                let params: WillSaveTextDocumentParams = from_value(params).unwrap();
-                let res = self.will_save_wait_until(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-                Ok(result)
-            }
-            GotoDeclaration::METHOD => {
-                let params: GotoDeclarationParams = from_value(params).unwrap();
-                let res = self.goto_declaration(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-                Ok(result)
-            }
-            GotoDefinition::METHOD => {
-                let params: GotoDefinitionParams = from_value(params).unwrap();
-                let res = self.goto_definition(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-                Ok(result)
+                to_json(self.will_save_wait_until(from_json(params)?).await)
             }
+            GotoDeclaration::METHOD => to_json(self.goto_declaration(from_json(params)?).await),
+            GotoDefinition::METHOD => to_json(self.goto_definition(from_json(params)?).await),
             GotoTypeDefinition::METHOD => {
-                let params: GotoTypeDefinitionParams = from_value(params).unwrap();
-                let res = self.goto_type_definition(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-                Ok(result)
+                to_json(self.goto_type_definition(from_json(params)?).await)
             }
             GotoImplementation::METHOD => {
-                let params: GotoImplementationParams = from_value(params).unwrap();
-                let res = self.goto_implementation(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-                Ok(result)
-            }
-            References::METHOD => {
-                let params: ReferenceParams = from_value(params).unwrap();
-                let res = self.references(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-                Ok(result)
+                to_json(self.goto_implementation(from_json(params)?).await)
             }
+            References::METHOD => to_json(self.references(from_json(params)?).await),
             CallHierarchyPrepare::METHOD => {
-                let params: CallHierarchyPrepareParams = from_value(params).unwrap();
-                let res = self.prepare_call_hierarchy(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-                Ok(result)
+                to_json(self.prepare_call_hierarchy(from_json(params)?).await)
             }
             CallHierarchyIncomingCalls::METHOD => {
-                let params: CallHierarchyIncomingCallsParams = from_value(params).unwrap();
-                let res = self.incoming_calls(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.incoming_calls(from_json(params)?).await)
             }
             CallHierarchyOutgoingCalls::METHOD => {
-                let params: CallHierarchyOutgoingCallsParams = from_value(params).unwrap();
-                let res = self.outgoing_calls(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.outgoing_calls(from_json(params)?).await)
             }
             TypeHierarchyPrepare::METHOD => {
-                let params: TypeHierarchyPrepareParams = from_value(params).unwrap();
-                let res = self.prepare_type_hierarchy(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            TypeHierarchySupertypes::METHOD => {
-                let params: TypeHierarchySupertypesParams = from_value(params).unwrap();
-                let res = self.supertypes(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            TypeHierarchySubtypes::METHOD => {
-                let params: TypeHierarchySubtypesParams = from_value(params).unwrap();
-                let res = self.subtypes(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.prepare_type_hierarchy(from_json(params)?).await)
             }
+            TypeHierarchySupertypes::METHOD => to_json(self.supertypes(from_json(params)?).await),
+            TypeHierarchySubtypes::METHOD => to_json(self.subtypes(from_json(params)?).await),
             DocumentHighlightRequest::METHOD => {
-                let params: DocumentHighlightParams = from_value(params).unwrap();
-                let res = self.document_highlight(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            DocumentLinkRequest::METHOD => {
-                let params: DocumentLinkParams = from_value(params).unwrap();
-                let res = self.document_link(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.document_highlight(from_json(params)?).await)
             }
+            DocumentLinkRequest::METHOD => to_json(self.document_link(from_json(params)?).await),
             DocumentLinkResolve::METHOD => {
-                let params: DocumentLink = from_value(params).unwrap();
-                let res = self.document_link_resolve(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            HoverRequest::METHOD => {
-                let params: HoverParams = from_value(params).unwrap();
-                let res = self.hover(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            CodeLensRequest::METHOD => {
-                let params: CodeLensParams = from_value(params).unwrap();
-                let res = self.code_lens(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            CodeLensResolve::METHOD => {
-                let params: CodeLens = from_value(params).unwrap();
-                let res = self.code_lens_resolve(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            FoldingRangeRequest::METHOD => {
-                let params: FoldingRangeParams = from_value(params).unwrap();
-                let res = self.folding_range(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.document_link_resolve(from_json(params)?).await)
             }
+            HoverRequest::METHOD => to_json(self.hover(from_json(params)?).await),
+            CodeLensRequest::METHOD => to_json(self.code_lens(from_json(params)?).await),
+            CodeLensResolve::METHOD => to_json(self.code_lens_resolve(from_json(params)?).await),
+            FoldingRangeRequest::METHOD => to_json(self.folding_range(from_json(params)?).await),
             SelectionRangeRequest::METHOD => {
-                let params: SelectionRangeParams = from_value(params).unwrap();
-                let res = self.selection_range(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.selection_range(from_json(params)?).await)
             }
             DocumentSymbolRequest::METHOD => {
-                let params: DocumentSymbolParams = from_value(params).unwrap();
-                let res = self.document_symbol(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.document_symbol(from_json(params)?).await)
             }
             SemanticTokensFullRequest::METHOD => {
-                let params: SemanticTokensParams = from_value(params).unwrap();
-                let res = self.semantic_tokens_full(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.semantic_tokens_full(from_json(params)?).await)
             }
             SemanticTokensFullDeltaRequest::METHOD => {
-                let params: SemanticTokensDeltaParams = from_value(params).unwrap();
-                let res = self.semantic_tokens_full_delta(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.semantic_tokens_full_delta(from_json(params)?).await)
             }
             SemanticTokensRangeRequest::METHOD => {
-                let params: SemanticTokensRangeParams = from_value(params).unwrap();
-                let res = self.semantic_tokens_range(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            InlineValueRequest::METHOD => {
-                let params: InlineValueParams = from_value(params).unwrap();
-                let res = self.inline_value(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            InlayHintRequest::METHOD => {
-                let params: InlayHintParams = from_value(params).unwrap();
-                let res = self.inlay_hint(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.semantic_tokens_range(from_json(params)?).await)
             }
+            InlineValueRequest::METHOD => to_json(self.inline_value(from_json(params)?).await),
+            InlayHintRequest::METHOD => to_json(self.inlay_hint(from_json(params)?).await),
             InlayHintResolveRequest::METHOD => {
-                let params: InlayHint = from_value(params).unwrap();
-                let res = self.inlay_hint_resolve(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            MonikerRequest::METHOD => {
-                let params: MonikerParams = from_value(params).unwrap();
-                let res = self.moniker(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.inlay_hint_resolve(from_json(params)?).await)
             }
-            Completion::METHOD => {
-                let params: CompletionParams = from_value(params).unwrap();
-                let res = self.completion(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            DocumentDiagnosticRequest::METHOD => {
-                let params: DocumentDiagnosticParams = from_value(params).unwrap();
-                let res = self.diagnostic(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+            MonikerRequest::METHOD => to_json(self.moniker(from_json(params)?).await),
+            Completion::METHOD => to_json(self.completion(from_json(params)?).await),
+            ResolveCompletionItem::METHOD => {
+                to_json(self.completion_resolve(from_json(params)?).await)
             }
+            DocumentDiagnosticRequest::METHOD => to_json(self.diagnostic(from_json(params)?).await),
             WorkspaceDiagnosticRequest::METHOD => {
-                let params: WorkspaceDiagnosticParams = from_value(params).unwrap();
-                let res = self.workspace_diagnostic(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            SignatureHelpRequest::METHOD => {
-                let params: SignatureHelpParams = from_value(params).unwrap();
-                let res = self.signature_help(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            CodeActionRequest::METHOD => {
-                let params: CodeActionParams = from_value(params).unwrap();
-                let res = self.code_action(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.workspace_diagnostic(from_json(params)?).await)
             }
+            SignatureHelpRequest::METHOD => to_json(self.signature_help(from_json(params)?).await),
+            CodeActionRequest::METHOD => to_json(self.code_action(from_json(params)?).await),
             CodeActionResolveRequest::METHOD => {
-                let params: CodeAction = from_value(params).unwrap();
-                let res = self.code_action_resolve(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            DocumentColor::METHOD => {
-                let params: DocumentColorParams = from_value(params).unwrap();
-                let res = self.document_color(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.code_action_resolve(from_json(params)?).await)
             }
+            DocumentColor::METHOD => to_json(self.document_color(from_json(params)?).await),
             ColorPresentationRequest::METHOD => {
-                let params: ColorPresentationParams = from_value(params).unwrap();
-                let res = self.color_presentation(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            Formatting::METHOD => {
-                let params: DocumentFormattingParams = from_value(params).unwrap();
-                let res = self.formatting(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            RangeFormatting::METHOD => {
-                let params: DocumentRangeFormattingParams = from_value(params).unwrap();
-                let res = self.range_formatting(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            OnTypeFormatting::METHOD => {
-                let params: DocumentOnTypeFormattingParams = from_value(params).unwrap();
-                let res = self.on_type_formatting(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            Rename::METHOD => {
-                let params: RenameParams = from_value(params).unwrap();
-                let res = self.rename(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            PrepareRenameRequest::METHOD => {
-                let params: TextDocumentPositionParams = from_value(params).unwrap();
-                let res = self.prepare_rename(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.color_presentation(from_json(params)?).await)
             }
+            Formatting::METHOD => to_json(self.formatting(from_json(params)?).await),
+            RangeFormatting::METHOD => to_json(self.range_formatting(from_json(params)?).await),
+            OnTypeFormatting::METHOD => to_json(self.on_type_formatting(from_json(params)?).await),
+            Rename::METHOD => to_json(self.rename(from_json(params)?).await),
+            PrepareRenameRequest::METHOD => to_json(self.prepare_rename(from_json(params)?).await),
             LinkedEditingRange::METHOD => {
-                let params: LinkedEditingRangeParams = from_value(params).unwrap();
-                let res = self.linked_editing_range(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            WorkspaceSymbolRequest::METHOD => {
-                let params: WorkspaceSymbolParams = from_value(params).unwrap();
-                let res = self.symbol(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
+                to_json(self.linked_editing_range(from_json(params)?).await)
             }
+            WorkspaceSymbolRequest::METHOD => to_json(self.symbol(from_json(params)?).await),
             WorkspaceSymbolResolve::METHOD => {
-                let params: WorkspaceSymbol = from_value(params).unwrap();
-                let res = self.symbol_resolve(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-
-                Ok(result)
-            }
-            WillDeleteFiles::METHOD => {
-                let params: DeleteFilesParams = from_value(params).unwrap();
-                let res = self.will_delete_files(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-                Ok(result)
-            }
-            WillCreateFiles::METHOD => {
-                let params: CreateFilesParams = from_value(params).unwrap();
-                let res = self.will_create_files(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-                Ok(result)
+                to_json(self.symbol_resolve(from_json(params)?).await)
             }
-            WillRenameFiles::METHOD => {
-                let params: RenameFilesParams = from_value(params).unwrap();
-                let res = self.will_rename_files(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-                Ok(result)
-            }
-            ExecuteCommand::METHOD => {
-                let params: ExecuteCommandParams = from_value(params).unwrap();
-                let res = self.execute_command(params);
-                let result = match res {
-                    Ok(init) => {
-                        let js = to_value(&init).unwrap();
-                        js_sys::Promise::resolve(&js)
-                    }
-                    Err(err) => {
-                        let js = to_value(&err).unwrap();
-                        js_sys::Promise::reject(&js)
-                    }
-                };
-                let result = wasm_bindgen_futures::JsFuture::from(result).await?;
-                Ok(result)
-            }
-            _ => Err(JsValue::from_str("Method not dispatched")),
+            WillDeleteFiles::METHOD => to_json(self.will_delete_files(from_json(params)?).await),
+            WillCreateFiles::METHOD => to_json(self.will_create_files(from_json(params)?).await),
+            WillRenameFiles::METHOD => to_json(self.will_rename_files(from_json(params)?).await),
+            ExecuteCommand::METHOD => to_json(self.execute_command(from_json(params)?).await),
+            _ => Err(Error::method_not_found()),
         }
     }
 
-    #[allow(unused_variables)]
-    #[wasm_bindgen(js_class = Dispatcher, js_name = onNotification)]
-    pub async fn on_notification(
-        &mut self,
-        method: &str,
-        params: JsValue,
-    ) -> std::result::Result<(), JsValue> {
+    /// Handles a notification sent by the client,
+    /// independently of the transport used to receive it
+    pub async fn handle_notification(&self, method: &str, params: Value) -> Result<()> {
         log(method);
 
         match method {
-            Initialized::METHOD => {
-                let params: InitializedParams = from_value(params).unwrap();
-                self.initialized(params);
-                Ok(())
-            }
-            DidOpenTextDocument::METHOD => {
-                let params: DidOpenTextDocumentParams = from_value(params).unwrap();
-                self.did_open(params);
-                Ok(())
-            }
-            DidChangeTextDocument::METHOD => {
-                let params: DidChangeTextDocumentParams = from_value(params).unwrap();
-                self.did_change(params);
-                Ok(())
-            }
-            WillSaveTextDocument::METHOD => {
-                let params: WillSaveTextDocumentParams = from_value(params).unwrap();
-                self.will_save(params);
-                Ok(())
-            }
-            DidSaveTextDocument::METHOD => {
-                let params: DidSaveTextDocumentParams = from_value(params).unwrap();
-                self.did_save(params);
-                Ok(())
-            }
-            DidCloseTextDocument::METHOD => {
-                let params: DidCloseTextDocumentParams = from_value(params).unwrap();
-                self.did_close(params);
-                Ok(())
-            }
+            Initialized::METHOD => self.initialized(from_json(params)?).await,
+            DidOpenTextDocument::METHOD => self.did_open(from_json(params)?).await,
+            DidChangeTextDocument::METHOD => self.did_change(from_json(params)?).await,
+            WillSaveTextDocument::METHOD => self.will_save(from_json(params)?).await,
+            DidSaveTextDocument::METHOD => self.did_save(from_json(params)?).await,
+            DidCloseTextDocument::METHOD => self.did_close(from_json(params)?).await,
             DidChangeConfiguration::METHOD => {
-                let params: DidChangeConfigurationParams = from_value(params).unwrap();
-                self.did_change_configuration(params);
-                Ok(())
+                self.did_change_configuration(from_json(params)?).await
             }
             DidChangeWatchedFiles::METHOD => {
-                let params: DidChangeWatchedFilesParams = from_value(params).unwrap();
-                self.did_change_watched_files(params);
-                Ok(())
+                self.did_change_watched_files(from_json(params)?).await
             }
             DidChangeWorkspaceFolders::METHOD => {
-                let params: DidChangeWorkspaceFoldersParams = from_value(params).unwrap();
-                self.did_change_workspace_folders(params);
-                Ok(())
+                self.did_change_workspace_folders(from_json(params)?).await
             }
-            DidRenameFiles::METHOD => {
-                let params: RenameFilesParams = from_value(params).unwrap();
-                self.did_rename_files(params);
-                Ok(())
-            }
-            DidCreateFiles::METHOD => {
-                let params: CreateFilesParams = from_value(params).unwrap();
-                self.did_create_files(params);
-                Ok(())
-            }
-            DidDeleteFiles::METHOD => {
-                let params: DeleteFilesParams = from_value(params).unwrap();
-                self.did_delete_files(params);
-                Ok(())
-            }
-
-            _ => Err(JsValue::from_str("Method not dispatched")),
+            DidRenameFiles::METHOD => self.did_rename_files(from_json(params)?).await,
+            DidCreateFiles::METHOD => self.did_create_files(from_json(params)?).await,
+            DidDeleteFiles::METHOD => self.did_delete_files(from_json(params)?).await,
+            _ => return Err(Error::method_not_found()),
         }
+        Ok(())
     }
 }
 
-impl Handler for Dispatcher {
-    fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
-        let mut res = vec![];
-        self.connection
-            .log_message(MessageType::INFO, "Dispatcher initializing");
-        for handler in &self.handlers {
-            res.push(handler.initialize(params.clone()));
-        }
-        let res: Vec<InitializeResult> = res.iter().filter_map(|i| i.clone().ok()).collect();
-        let mut result = InitializeResult::default();
-
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.position_encoding.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.position_encoding =
-                tmp_cap[0].capabilities.position_encoding.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.text_document_sync.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.text_document_sync =
-                tmp_cap[0].capabilities.text_document_sync.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.selection_range_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.selection_range_provider =
-                tmp_cap[0].capabilities.selection_range_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.hover_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.hover_provider = tmp_cap[0].capabilities.hover_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.completion_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.completion_provider =
-                tmp_cap[0].capabilities.completion_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.signature_help_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.signature_help_provider =
-                tmp_cap[0].capabilities.signature_help_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.definition_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.definition_provider =
-                tmp_cap[0].capabilities.definition_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.type_definition_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.type_definition_provider =
-                tmp_cap[0].capabilities.type_definition_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.implementation_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.implementation_provider =
-                tmp_cap[0].capabilities.implementation_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.references_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.references_provider =
-                tmp_cap[0].capabilities.references_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.document_highlight_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.document_highlight_provider =
-                tmp_cap[0].capabilities.document_highlight_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.document_symbol_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.document_symbol_provider =
-                tmp_cap[0].capabilities.document_symbol_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.workspace_symbol_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.workspace_symbol_provider =
-                tmp_cap[0].capabilities.workspace_symbol_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.code_action_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.code_action_provider =
-                tmp_cap[0].capabilities.code_action_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.code_lens_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.code_lens_provider = tmp_cap[0].capabilities.code_lens_provider;
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.document_formatting_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.document_formatting_provider =
-                tmp_cap[0].capabilities.document_formatting_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.document_range_formatting_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.document_range_formatting_provider = tmp_cap[0]
-                .capabilities
-                .document_range_formatting_provider
-                .clone();
-        }
-        let tmp_cap = res
+impl Dispatcher {
+    /// Handlers which advertised the capability at `path` when initialized,
+    /// or all of them if the client did not initialize the server yet
+    fn routed(&self, path: &[&str]) -> Vec<(usize, &dyn AsyncHandler)> {
+        let capabilities = self.capabilities.borrow();
+        self.handlers
             .iter()
-            .filter(|i| {
-                i.capabilities
-                    .document_on_type_formatting_provider
-                    .is_some()
+            .enumerate()
+            .filter(|(i, _)| {
+                capabilities
+                    .get(*i)
+                    .is_none_or(|c| merge::provides(c, path))
             })
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.document_on_type_formatting_provider = tmp_cap[0]
-                .capabilities
-                .document_on_type_formatting_provider
-                .clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.rename_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.rename_provider = tmp_cap[0].capabilities.rename_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.document_link_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.document_link_provider =
-                tmp_cap[0].capabilities.document_link_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.color_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.color_provider = tmp_cap[0].capabilities.color_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.folding_range_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.folding_range_provider =
-                tmp_cap[0].capabilities.folding_range_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.declaration_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.declaration_provider =
-                tmp_cap[0].capabilities.declaration_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.workspace.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.workspace = tmp_cap[0].capabilities.workspace.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.call_hierarchy_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.call_hierarchy_provider =
-                tmp_cap[0].capabilities.call_hierarchy_provider;
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.semantic_tokens_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.semantic_tokens_provider =
-                tmp_cap[0].capabilities.semantic_tokens_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.moniker_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.moniker_provider = tmp_cap[0].capabilities.moniker_provider.clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.linked_editing_range_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.linked_editing_range_provider = tmp_cap[0]
-                .capabilities
-                .linked_editing_range_provider
-                .clone();
-        }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.inline_value_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.inline_value_provider =
-                tmp_cap[0].capabilities.inline_value_provider.clone();
+            .map(|(i, handler)| (i, handler.as_ref()))
+            .collect()
+    }
+
+    /// Sends a request to the handlers supporting it and collects their results,
+    /// running the handlers concurrently
+    async fn collect<'a, T>(
+        &'a self,
+        path: &[&str],
+        request: impl Fn(&'a dyn AsyncHandler) -> LocalBoxFuture<'a, Result<Option<T>>>,
+    ) -> Vec<T> {
+        join_all(self.routed(path).into_iter().map(|(_, h)| request(h)))
+            .await
+            .into_iter()
+            .filter_map(|res| res.ok().flatten())
+            .collect()
+    }
+
+    /// Sends a request to the handlers supporting it until one of them answers.
+    /// Used for the requests whose results cannot be combined, such as edits of the same document.
+    async fn first<'a, T>(
+        &'a self,
+        path: &[&str],
+        request: impl Fn(&'a dyn AsyncHandler) -> LocalBoxFuture<'a, Result<Option<T>>>,
+    ) -> Result<Option<T>> {
+        for (_, handler) in self.routed(path) {
+            let res = request(handler).await;
+            if let Ok(Some(_)) = res {
+                return res;
+            }
         }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.inlay_hint_provider.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.inlay_hint_provider =
-                tmp_cap[0].capabilities.inlay_hint_provider.clone();
+        Ok(None)
+    }
+
+    /// Sends a resolve request to the handlers able to resolve items
+    async fn resolve<'a, T>(
+        &'a self,
+        path: &[&str],
+        request: impl Fn(&'a dyn AsyncHandler) -> LocalBoxFuture<'a, Result<T>>,
+    ) -> Result<T> {
+        for (_, handler) in self.routed(path) {
+            let res = request(handler).await;
+            if res.is_ok() {
+                return res;
+            }
         }
-        let tmp_cap = res
-            .iter()
-            .filter(|i| i.capabilities.experimental.is_some())
-            .collect::<Vec<&InitializeResult>>();
-        if !tmp_cap.is_empty() {
-            result.capabilities.experimental = tmp_cap[0].capabilities.experimental.clone();
+        Err(Error::new(ErrorCode::MethodNotFound))
+    }
+
+    /// Sends a semantic tokens request to the first handler answering it,
+    /// and translates its tokens to the legend advertised to the client
+    async fn semantic_tokens<'a, T>(
+        &'a self,
+        path: &[&str],
+        request: impl Fn(&'a dyn AsyncHandler) -> LocalBoxFuture<'a, Result<Option<T>>>,
+        tokens: impl Fn(&mut T) -> Vec<&mut [SemanticToken]>,
+    ) -> Result<Option<T>> {
+        for (i, handler) in self.routed(path) {
+            let mut res = request(handler).await;
+            if let Ok(Some(result)) = &mut res {
+                let from = self.capabilities.borrow().get(i).and_then(merge::legend);
+                if let (Some(from), Some(to)) = (from, self.legend.borrow().as_ref()) {
+                    let remap = merge::TokensRemap::new(&from, to);
+                    for data in tokens(result) {
+                        remap.apply(data);
+                    }
+                }
+                return res;
+            }
         }
-        Ok(result)
+        Ok(None)
     }
+}
 
-    fn initialized(&self, params: InitializedParams) {
+fn from_json<T: DeserializeOwned>(params: Value) -> Result<T> {
+    serde_json::from_value(params).map_err(|e| Error::invalid_params(e.to_string()))
+}
+
+fn to_json<T: Serialize>(res: Result<T>) -> Result<Value> {
+    serde_json::to_value(res?).map_err(|_| Error::internal_error())
+}
+
+#[async_trait(?Send)]
+impl AsyncHandler for Dispatcher {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        self.connection
+            .log_message(MessageType::INFO, "Dispatcher initializing");
+        let results = join_all(
+            self.handlers
+                .iter()
+                .map(|handler| handler.initialize(params.clone())),
+        )
+        .await;
+        let capabilities: Vec<Value> = results
+            .iter()
+            .map(|res| match res {
+                Ok(res) => serde_json::to_value(&res.capabilities).unwrap_or(Value::Null),
+                Err(_) => Value::Null,
+            })
+            .collect();
+
+        let merged = merge::union_capabilities(
+            &capabilities
+                .iter()
+                .filter(|c| !c.is_null())
+                .cloned()
+                .collect::<Vec<Value>>(),
+        );
+        *self.legend.borrow_mut() = serde_json::to_value(&merged)
+            .ok()
+            .and_then(|c| merge::legend(&c));
+        *self.capabilities.borrow_mut() = capabilities;
+        Ok(InitializeResult {
+            capabilities: merged,
+            server_info: results
+                .into_iter()
+                .find_map(|res| res.ok().and_then(|res| res.server_info)),
+        })
+    }
+
+    async fn initialized(&self, params: InitializedParams) {
         self.connection
             .log_message(MessageType::INFO, "Dispatcher initialized");
-        for handler in &self.handlers {
-            handler.initialized(params);
-        }
+        join_all(
+            self.handlers
+                .iter()
+                .map(|handler| handler.initialized(params)),
+        )
+        .await;
     }
 
-    fn shutdown(&self) -> Result<()> {
-        for handler in &self.handlers {
-            let _ = handler.shutdown();
-        }
+    async fn shutdown(&self) -> Result<()> {
+        join_all(self.handlers.iter().map(|handler| handler.shutdown())).await;
         Ok(())
     }
 
-    fn did_open(&self, params: DidOpenTextDocumentParams) {
-        for handler in &self.handlers {
-            handler.did_open(params.clone());
-        }
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        join_all(
+            self.handlers
+                .iter()
+                .map(|handler| handler.did_open(params.clone())),
+        )
+        .await;
     }
 
-    fn did_change(&self, params: DidChangeTextDocumentParams) {
-        for handler in &self.handlers {
-            handler.did_change(params.clone());
-        }
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        join_all(
+            self.handlers
+                .iter()
+                .map(|handler| handler.did_change(params.clone())),
+        )
+        .await;
     }
 
-    fn will_save(&self, params: WillSaveTextDocumentParams) {
-        for handler in &self.handlers {
-            handler.will_save(params.clone());
-        }
+    async fn will_save(&self, params: WillSaveTextDocumentParams) {
+        join_all(
+            self.handlers
+                .iter()
+                .map(|handler| handler.will_save(params.clone())),
+        )
+        .await;
     }
 
-    fn will_save_wait_until(
+    async fn will_save_wait_until(
         &self,
         params: WillSaveTextDocumentParams,
     ) -> Result<Option<Vec<TextEdit>>> {
-        let mut text_edit: Vec<TextEdit> = vec![];
-        for handler in &self.handlers {
-            let res = handler.will_save_wait_until(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+        self.first(&["textDocumentSync", "willSaveWaitUntil"], |h| {
+            h.will_save_wait_until(params.clone())
+        })
+        .await
     }
 
-    fn did_save(&self, params: DidSaveTextDocumentParams) {
-        for handler in &self.handlers {
-            handler.did_save(params.clone());
-        }
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        join_all(
+            self.handlers
+                .iter()
+                .map(|handler| handler.did_save(params.clone())),
+        )
+        .await;
     }
 
-    fn did_close(&self, params: DidCloseTextDocumentParams) {
-        for handler in &self.handlers {
-            handler.did_close(params.clone());
-        }
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        join_all(
+            self.handlers
+                .iter()
+                .map(|handler| handler.did_close(params.clone())),
+        )
+        .await;
     }
 
-    fn goto_declaration(
+    async fn goto_declaration(
         &self,
         params: GotoDeclarationParams,
     ) -> Result<Option<GotoDeclarationResponse>> {
-        for handler in &self.handlers {
-            let res = handler.goto_declaration(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
+        Ok(merge::locations(
+            self.collect(&["declarationProvider"], |h| {
+                h.goto_declaration(params.clone())
+            })
+            .await,
+        ))
     }
 
-    fn goto_definition(
+    async fn goto_definition(
         &self,
         params: GotoDefinitionParams,
     ) -> Result<Option<GotoDefinitionResponse>> {
-        for handler in &self.handlers {
-            let res = handler.goto_definition(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
+        Ok(merge::locations(
+            self.collect(&["definitionProvider"], |h| {
+                h.goto_definition(params.clone())
+            })
+            .await,
+        ))
     }
 
-    fn goto_type_definition(
+    async fn goto_type_definition(
         &self,
         params: GotoTypeDefinitionParams,
     ) -> Result<Option<GotoTypeDefinitionResponse>> {
-        for handler in &self.handlers {
-            let res = handler.goto_type_definition(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
+        Ok(merge::locations(
+            self.collect(&["typeDefinitionProvider"], |h| {
+                h.goto_type_definition(params.clone())
+            })
+            .await,
+        ))
     }
 
-    fn goto_implementation(
+    async fn goto_implementation(
         &self,
         params: GotoImplementationParams,
     ) -> Result<Option<GotoImplementationResponse>> {
-        for handler in &self.handlers {
-            let res = handler.goto_implementation(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
+        Ok(merge::locations(
+            self.collect(&["implementationProvider"], |h| {
+                h.goto_implementation(params.clone())
+            })
+            .await,
+        ))
     }
 
-    fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
-        let mut text_edit: Vec<Location> = vec![];
-        for handler in &self.handlers {
-            let res = handler.references(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        Ok(Some(merge::concat(
+            self.collect(&["referencesProvider"], |h| h.references(params.clone()))
+                .await,
+        )))
     }
 
-    fn prepare_call_hierarchy(
+    async fn prepare_call_hierarchy(
         &self,
         params: CallHierarchyPrepareParams,
     ) -> Result<Option<Vec<CallHierarchyItem>>> {
-        let mut text_edit: Vec<CallHierarchyItem> = vec![];
-        for handler in &self.handlers {
-            let res = handler.prepare_call_hierarchy(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+        Ok(Some(merge::concat(
+            self.collect(&["callHierarchyProvider"], |h| {
+                h.prepare_call_hierarchy(params.clone())
+            })
+            .await,
+        )))
     }
 
-    fn incoming_calls(
+    async fn incoming_calls(
         &self,
         params: CallHierarchyIncomingCallsParams,
     ) -> Result<Option<Vec<CallHierarchyIncomingCall>>> {
-        let mut text_edit: Vec<CallHierarchyIncomingCall> = vec![];
-        for handler in &self.handlers {
-            let res = handler.incoming_calls(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+        Ok(Some(merge::concat(
+            self.collect(&["callHierarchyProvider"], |h| {
+                h.incoming_calls(params.clone())
+            })
+            .await,
+        )))
     }
 
-    fn outgoing_calls(
+    async fn outgoing_calls(
         &self,
         params: CallHierarchyOutgoingCallsParams,
     ) -> Result<Option<Vec<CallHierarchyOutgoingCall>>> {
-        let mut text_edit: Vec<CallHierarchyOutgoingCall> = vec![];
-        for handler in &self.handlers {
-            let res = handler.outgoing_calls(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+        Ok(Some(merge::concat(
+            self.collect(&["callHierarchyProvider"], |h| {
+                h.outgoing_calls(params.clone())
+            })
+            .await,
+        )))
     }
 
-    fn prepare_type_hierarchy(
+    // lsp-types has no type hierarchy capability yet, so every handler is asked
+    async fn prepare_type_hierarchy(
         &self,
         params: TypeHierarchyPrepareParams,
     ) -> Result<Option<Vec<TypeHierarchyItem>>> {
-        let mut text_edit: Vec<TypeHierarchyItem> = vec![];
-        for handler in &self.handlers {
-            let res = handler.prepare_type_hierarchy(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+        Ok(Some(merge::concat(
+            self.collect(&[], |h| h.prepare_type_hierarchy(params.clone()))
+                .await,
+        )))
     }
 
-    fn supertypes(
+    async fn supertypes(
         &self,
         params: TypeHierarchySupertypesParams,
     ) -> Result<Option<Vec<TypeHierarchyItem>>> {
-        let mut text_edit: Vec<TypeHierarchyItem> = vec![];
-        for handler in &self.handlers {
-            let res = handler.supertypes(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+        Ok(Some(merge::concat(
+            self.collect(&[], |h| h.supertypes(params.clone())).await,
+        )))
     }
 
-    fn subtypes(
+    async fn subtypes(
         &self,
         params: TypeHierarchySubtypesParams,
     ) -> Result<Option<Vec<TypeHierarchyItem>>> {
-        let mut text_edit: Vec<TypeHierarchyItem> = vec![];
-        for handler in &self.handlers {
-            let res = handler.subtypes(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+        Ok(Some(merge::concat(
+            self.collect(&[], |h| h.subtypes(params.clone())).await,
+        )))
     }
 
-    fn document_highlight(
+    async fn document_highlight(
         &self,
         params: DocumentHighlightParams,
     ) -> Result<Option<Vec<DocumentHighlight>>> {
-        let mut text_edit: Vec<DocumentHighlight> = vec![];
-        for handler in &self.handlers {
-            let res = handler.document_highlight(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+        Ok(Some(merge::concat(
+            self.collect(&["documentHighlightProvider"], |h| {
+                h.document_highlight(params.clone())
+            })
+            .await,
+        )))
     }
 
-    fn document_link(&self, params: DocumentLinkParams) -> Result<Option<Vec<DocumentLink>>> {
-        let mut text_edit: Vec<DocumentLink> = vec![];
-        for handler in &self.handlers {
-            let res = handler.document_link(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+    async fn document_link(&self, params: DocumentLinkParams) -> Result<Option<Vec<DocumentLink>>> {
+        Ok(Some(merge::concat(
+            self.collect(&["documentLinkProvider"], |h| {
+                h.document_link(params.clone())
+            })
+            .await,
+        )))
     }
 
-    fn document_link_resolve(&self, params: DocumentLink) -> Result<DocumentLink> {
-        for handler in &self.handlers {
-            let res = handler.document_link_resolve(params.clone());
-            if res.is_ok() {
-                return res;
-            }
-        }
-        Err(Error::new(ErrorCode::MethodNotFound))
+    async fn document_link_resolve(&self, params: DocumentLink) -> Result<DocumentLink> {
+        self.resolve(&["documentLinkProvider", "resolveProvider"], |h| {
+            h.document_link_resolve(params.clone())
+        })
+        .await
     }
 
-    fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
-        for handler in &self.handlers {
-            let res = handler.hover(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        Ok(merge::hovers(
+            self.collect(&["hoverProvider"], |h| h.hover(params.clone()))
+                .await,
+        ))
     }
 
-    fn code_lens(&self, params: CodeLensParams) -> Result<Option<Vec<CodeLens>>> {
-        let mut text_edit: Vec<CodeLens> = vec![];
-        for handler in &self.handlers {
-            let res = handler.code_lens(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+    async fn code_lens(&self, params: CodeLensParams) -> Result<Option<Vec<CodeLens>>> {
+        Ok(Some(merge::concat(
+            self.collect(&["codeLensProvider"], |h| h.code_lens(params.clone()))
+                .await,
+        )))
     }
 
-    fn code_lens_resolve(&self, params: CodeLens) -> Result<CodeLens> {
-        for handler in &self.handlers {
-            let res = handler.code_lens_resolve(params.clone());
-            if res.is_ok() {
-                return res;
-            }
-        }
-        Err(Error::new(ErrorCode::MethodNotFound))
+    async fn code_lens_resolve(&self, params: CodeLens) -> Result<CodeLens> {
+        self.resolve(&["codeLensProvider", "resolveProvider"], |h| {
+            h.code_lens_resolve(params.clone())
+        })
+        .await
     }
 
-    fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
-        let mut text_edit: Vec<FoldingRange> = vec![];
-        for handler in &self.handlers {
-            let res = handler.folding_range(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+    async fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
+        Ok(Some(merge::concat(
+            self.collect(&["foldingRangeProvider"], |h| {
+                h.folding_range(params.clone())
+            })
+            .await,
+        )))
     }
 
-    fn selection_range(&self, params: SelectionRangeParams) -> Result<Option<Vec<SelectionRange>>> {
-        let mut text_edit: Vec<SelectionRange> = vec![];
-        for handler in &self.handlers {
-            let res = handler.selection_range(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+    // Selection ranges are given for each requested position, so they cannot be concatenated
+    async fn selection_range(
+        &self,
+        params: SelectionRangeParams,
+    ) -> Result<Option<Vec<SelectionRange>>> {
+        self.first(&["selectionRangeProvider"], |h| {
+            h.selection_range(params.clone())
+        })
+        .await
     }
 
-    fn document_symbol(
+    async fn document_symbol(
         &self,
         params: DocumentSymbolParams,
     ) -> Result<Option<DocumentSymbolResponse>> {
-        for handler in &self.handlers {
-            let res = handler.document_symbol(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
+        Ok(merge::document_symbols(
+            self.collect(&["documentSymbolProvider"], |h| {
+                h.document_symbol(params.clone())
+            })
+            .await,
+        ))
     }
 
-    fn semantic_tokens_full(
+    async fn semantic_tokens_full(
         &self,
         params: SemanticTokensParams,
     ) -> Result<Option<SemanticTokensResult>> {
-        for handler in &self.handlers {
-            let res = handler.semantic_tokens_full(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
-    }
-
-    fn semantic_tokens_full_delta(
+        self.semantic_tokens(
+            &["semanticTokensProvider", "full"],
+            |h| h.semantic_tokens_full(params.clone()),
+            |res| match res {
+                SemanticTokensResult::Tokens(tokens) => vec![tokens.data.as_mut_slice()],
+                SemanticTokensResult::Partial(partial) => vec![partial.data.as_mut_slice()],
+            },
+        )
+        .await
+    }
+
+    async fn semantic_tokens_full_delta(
         &self,
         params: SemanticTokensDeltaParams,
     ) -> Result<Option<SemanticTokensFullDeltaResult>> {
-        for handler in &self.handlers {
-            let res = handler.semantic_tokens_full_delta(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
-    }
-
-    fn semantic_tokens_range(
+        self.semantic_tokens(
+            &["semanticTokensProvider", "full", "delta"],
+            |h| h.semantic_tokens_full_delta(params.clone()),
+            |res| match res {
+                SemanticTokensFullDeltaResult::Tokens(tokens) => vec![tokens.data.as_mut_slice()],
+                SemanticTokensFullDeltaResult::TokensDelta(SemanticTokensDelta {
+                    edits, ..
+                })
+                | SemanticTokensFullDeltaResult::PartialTokensDelta { edits } => edits
+                    .iter_mut()
+                    .filter_map(|edit| edit.data.as_deref_mut())
+                    .collect(),
+            },
+        )
+        .await
+    }
+
+    async fn semantic_tokens_range(
         &self,
         params: SemanticTokensRangeParams,
     ) -> Result<Option<SemanticTokensRangeResult>> {
-        for handler in &self.handlers {
-            let res = handler.semantic_tokens_range(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
+        self.semantic_tokens(
+            &["semanticTokensProvider", "range"],
+            |h| h.semantic_tokens_range(params.clone()),
+            |res| match res {
+                SemanticTokensRangeResult::Tokens(tokens) => vec![tokens.data.as_mut_slice()],
+                SemanticTokensRangeResult::Partial(partial) => vec![partial.data.as_mut_slice()],
+            },
+        )
+        .await
     }
 
-    fn inline_value(&self, params: InlineValueParams) -> Result<Option<Vec<InlineValue>>> {
-        let mut text_edit: Vec<InlineValue> = vec![];
-        for handler in &self.handlers {
-            let res = handler.inline_value(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+    async fn inline_value(&self, params: InlineValueParams) -> Result<Option<Vec<InlineValue>>> {
+        Ok(Some(merge::concat(
+            self.collect(&["inlineValueProvider"], |h| h.inline_value(params.clone()))
+                .await,
+        )))
     }
 
-    fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
-        let mut text_edit: Vec<InlayHint> = vec![];
-        for handler in &self.handlers {
-            let res = handler.inlay_hint(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+    async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
+        Ok(Some(merge::concat(
+            self.collect(&["inlayHintProvider"], |h| h.inlay_hint(params.clone()))
+                .await,
+        )))
     }
 
-    fn inlay_hint_resolve(&self, params: InlayHint) -> Result<InlayHint> {
-        for handler in &self.handlers {
-            let res = handler.inlay_hint_resolve(params.clone());
-            if res.is_ok() {
-                return res;
-            }
-        }
-        Err(Error::new(ErrorCode::MethodNotFound))
+    async fn inlay_hint_resolve(&self, params: InlayHint) -> Result<InlayHint> {
+        self.resolve(&["inlayHintProvider", "resolveProvider"], |h| {
+            h.inlay_hint_resolve(params.clone())
+        })
+        .await
     }
 
-    fn moniker(&self, params: MonikerParams) -> Result<Option<Vec<Moniker>>> {
-        let mut text_edit: Vec<Moniker> = vec![];
-        for handler in &self.handlers {
-            let res = handler.moniker(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+    async fn moniker(&self, params: MonikerParams) -> Result<Option<Vec<Moniker>>> {
+        Ok(Some(merge::concat(
+            self.collect(&["monikerProvider"], |h| h.moniker(params.clone()))
+                .await,
+        )))
     }
 
-    fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
-        for handler in &self.handlers {
-            let res = handler.completion(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
+    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        Ok(merge::completions(
+            self.collect(&["completionProvider"], |h| h.completion(params.clone()))
+                .await,
+        ))
     }
 
-    fn completion_resolve(&self, params: CompletionItem) -> Result<CompletionItem> {
-        for handler in &self.handlers {
-            let res = handler.completion_resolve(params.clone());
-            if res.is_ok() {
-                return res;
-            }
-        }
-        Err(Error::new(ErrorCode::MethodNotFound))
+    async fn completion_resolve(&self, params: CompletionItem) -> Result<CompletionItem> {
+        self.resolve(&["completionProvider", "resolveProvider"], |h| {
+            h.completion_resolve(params.clone())
+        })
+        .await
     }
 
-    fn diagnostic(
+    async fn diagnostic(
         &self,
         params: DocumentDiagnosticParams,
     ) -> Result<DocumentDiagnosticReportResult> {
-        for handler in &self.handlers {
-            let res = handler.diagnostic(params.clone());
-            if res.is_ok() {
-                return res;
-            }
-        }
-        Err(Error::new(ErrorCode::MethodNotFound))
-    }
-
-    fn workspace_diagnostic(
+        let handlers = self.routed(&["diagnosticProvider"]);
+        match handlers.as_slice() {
+            [] => return Err(Error::new(ErrorCode::MethodNotFound)),
+            [(_, handler)] => return handler.diagnostic(params).await,
+            _ => {}
+        }
+
+        let previous =
+            merge::split_result_ids(params.previous_result_id.as_deref(), handlers.len());
+        let reports = join_all(handlers.iter().zip(previous).map(
+            |((_, handler), previous_result_id)| {
+                handler.diagnostic(DocumentDiagnosticParams {
+                    previous_result_id,
+                    ..params.clone()
+                })
+            },
+        ))
+        .await;
+        let unchanged = |report: &Result<DocumentDiagnosticReportResult>| {
+            matches!(
+                report,
+                Ok(DocumentDiagnosticReportResult::Report(
+                    DocumentDiagnosticReport::Unchanged(_)
+                ))
+            )
+        };
+        if let (true, Some(result_id)) = (
+            reports.iter().all(unchanged),
+            params.previous_result_id.clone(),
+        ) {
+            return Ok(DocumentDiagnosticReportResult::Report(
+                DocumentDiagnosticReport::Unchanged(RelatedUnchangedDocumentDiagnosticReport {
+                    related_documents: None,
+                    unchanged_document_diagnostic_report: UnchangedDocumentDiagnosticReport {
+                        result_id,
+                    },
+                }),
+            ));
+        }
+
+        // The items of unchanged reports are needed again, as the merged report is a full one
+        let mut full_reports = vec![];
+        for ((_, handler), report) in handlers.iter().zip(reports) {
+            let report = if unchanged(&report) {
+                handler
+                    .diagnostic(DocumentDiagnosticParams {
+                        previous_result_id: None,
+                        ..params.clone()
+                    })
+                    .await
+            } else {
+                report
+            };
+            full_reports.push(match report {
+                Ok(DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Full(
+                    report,
+                ))) => Some(report),
+                _ => None,
+            });
+        }
+        Ok(DocumentDiagnosticReportResult::Report(
+            DocumentDiagnosticReport::Full(merge::document_reports(full_reports)),
+        ))
+    }
+
+    async fn workspace_diagnostic(
         &self,
         params: WorkspaceDiagnosticParams,
     ) -> Result<WorkspaceDiagnosticReportResult> {
-        for handler in &self.handlers {
-            let res = handler.workspace_diagnostic(params.clone());
-            if res.is_ok() {
-                return res;
-            }
-        }
-        Err(Error::new(ErrorCode::MethodNotFound))
+        let handlers = self.routed(&["diagnosticProvider", "workspaceDiagnostics"]);
+        match handlers.as_slice() {
+            [] => return Err(Error::new(ErrorCode::MethodNotFound)),
+            [(_, handler)] => return handler.workspace_diagnostic(params).await,
+            _ => {}
+        }
+
+        // Result ids of several handlers cannot be told apart for each document,
+        // so full reports are always requested
+        let reports = join_all(handlers.iter().map(|(_, handler)| {
+            handler.workspace_diagnostic(WorkspaceDiagnosticParams {
+                previous_result_ids: vec![],
+                ..params.clone()
+            })
+        }))
+        .await
+        .into_iter()
+        .filter_map(|report| match report {
+            Ok(WorkspaceDiagnosticReportResult::Report(report)) => Some(report.items),
+            Ok(WorkspaceDiagnosticReportResult::Partial(partial)) => Some(partial.items),
+            Err(_) => None,
+        })
+        .collect();
+        Ok(WorkspaceDiagnosticReportResult::Report(
+            WorkspaceDiagnosticReport {
+                items: merge::workspace_reports(reports),
+            },
+        ))
+    }
+
+    async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        Ok(merge::signature_helps(
+            self.collect(&["signatureHelpProvider"], |h| {
+                h.signature_help(params.clone())
+            })
+            .await,
+        ))
     }
 
-    fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
-        for handler in &self.handlers {
-            let res = handler.signature_help(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        Ok(Some(merge::concat(
+            self.collect(&["codeActionProvider"], |h| h.code_action(params.clone()))
+                .await,
+        )))
     }
 
-    fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
-        for handler in &self.handlers {
-            let res = handler.code_action(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
-    }
-
-    fn code_action_resolve(&self, params: CodeAction) -> Result<CodeAction> {
-        for handler in &self.handlers {
-            let res = handler.code_action_resolve(params.clone());
-            if res.is_ok() {
-                return res;
-            }
-        }
-        Err(Error::new(ErrorCode::MethodNotFound))
+    async fn code_action_resolve(&self, params: CodeAction) -> Result<CodeAction> {
+        self.resolve(&["codeActionProvider", "resolveProvider"], |h| {
+            h.code_action_resolve(params.clone())
+        })
+        .await
     }
 
-    fn document_color(&self, params: DocumentColorParams) -> Result<Vec<ColorInformation>> {
-        let mut text_edit: Vec<ColorInformation> = vec![];
-        for handler in &self.handlers {
-            let res = handler.document_color(params.clone());
-            if let Ok(val) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(text_edit)
+    async fn document_color(&self, params: DocumentColorParams) -> Result<Vec<ColorInformation>> {
+        Ok(merge::concat(
+            self.collect(&["colorProvider"], |h| {
+                let params = params.clone();
+                Box::pin(async move { h.document_color(params).await.map(Some) })
+            })
+            .await,
+        ))
     }
 
-    fn color_presentation(
+    async fn color_presentation(
         &self,
         params: ColorPresentationParams,
     ) -> Result<Vec<ColorPresentation>> {
-        let mut text_edit: Vec<ColorPresentation> = vec![];
-        for handler in &self.handlers {
-            let res = handler.color_presentation(params.clone());
-            if let Ok(val) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(text_edit)
+        Ok(merge::concat(
+            self.collect(&["colorProvider"], |h| {
+                let params = params.clone();
+                Box::pin(async move { h.color_presentation(params).await.map(Some) })
+            })
+            .await,
+        ))
     }
 
-    fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
-        let mut text_edit: Vec<TextEdit> = vec![];
-        for handler in &self.handlers {
-            let res = handler.formatting(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+    // Edits of several formatters would overlap, so only one of them is used
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        self.first(&["documentFormattingProvider"], |h| {
+            h.formatting(params.clone())
+        })
+        .await
     }
 
-    fn range_formatting(
+    async fn range_formatting(
         &self,
         params: DocumentRangeFormattingParams,
     ) -> Result<Option<Vec<TextEdit>>> {
-        let mut text_edit: Vec<TextEdit> = vec![];
-        for handler in &self.handlers {
-            let res = handler.range_formatting(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+        self.first(&["documentRangeFormattingProvider"], |h| {
+            h.range_formatting(params.clone())
+        })
+        .await
     }
 
-    fn on_type_formatting(
+    async fn on_type_formatting(
         &self,
         params: DocumentOnTypeFormattingParams,
     ) -> Result<Option<Vec<TextEdit>>> {
-        let mut text_edit: Vec<TextEdit> = vec![];
-        for handler in &self.handlers {
-            let res = handler.on_type_formatting(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
-    }
-
-    fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
-        for handler in &self.handlers {
-            let res = handler.rename(params.clone());
+        let capabilities = self.capabilities.borrow().clone();
+        for (i, handler) in self.routed(&["documentOnTypeFormattingProvider"]) {
+            let triggered = capabilities.get(i).is_none_or(|c| {
+                let options = &c["documentOnTypeFormattingProvider"];
+                options["firstTriggerCharacter"] == params.ch.as_str()
+                    || options["moreTriggerCharacter"]
+                        .as_array()
+                        .is_some_and(|more| more.iter().any(|ch| ch == params.ch.as_str()))
+            });
+            if !triggered {
+                continue;
+            }
+            let res = handler.on_type_formatting(params.clone()).await;
             if let Ok(Some(_)) = res {
                 return res;
             }
@@ -1791,126 +905,365 @@ impl Handler for Dispatcher {
         Ok(None)
     }
 
-    fn prepare_rename(
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        Ok(merge::workspace_edits(
+            self.collect(&["renameProvider"], |h| h.rename(params.clone()))
+                .await,
+        ))
+    }
+
+    async fn prepare_rename(
         &self,
         params: TextDocumentPositionParams,
     ) -> Result<Option<PrepareRenameResponse>> {
-        for handler in &self.handlers {
-            let res = handler.prepare_rename(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
+        self.first(&["renameProvider", "prepareProvider"], |h| {
+            h.prepare_rename(params.clone())
+        })
+        .await
     }
 
-    fn linked_editing_range(
+    async fn linked_editing_range(
         &self,
         params: LinkedEditingRangeParams,
     ) -> Result<Option<LinkedEditingRanges>> {
-        for handler in &self.handlers {
-            let res = handler.linked_editing_range(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
+        self.first(&["linkedEditingRangeProvider"], |h| {
+            h.linked_editing_range(params.clone())
+        })
+        .await
     }
 
-    fn symbol(&self, params: WorkspaceSymbolParams) -> Result<Option<Vec<SymbolInformation>>> {
-        let mut text_edit: Vec<SymbolInformation> = vec![];
-        for handler in &self.handlers {
-            let res = handler.symbol(params.clone());
-            if let Ok(Some(val)) = res {
-                text_edit.extend(val);
-            }
-        }
-        Ok(Some(text_edit))
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<Vec<SymbolInformation>>> {
+        Ok(Some(merge::concat(
+            self.collect(&["workspaceSymbolProvider"], |h| h.symbol(params.clone()))
+                .await,
+        )))
+    }
+
+    async fn symbol_resolve(&self, params: WorkspaceSymbol) -> Result<WorkspaceSymbol> {
+        self.resolve(&["workspaceSymbolProvider", "resolveProvider"], |h| {
+            h.symbol_resolve(params.clone())
+        })
+        .await
+    }
+
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        join_all(
+            self.handlers
+                .iter()
+                .map(|handler| handler.did_change_configuration(params.clone())),
+        )
+        .await;
+    }
+
+    async fn did_change_workspace_folders(&self, params: DidChangeWorkspaceFoldersParams) {
+        join_all(
+            self.handlers
+                .iter()
+                .map(|handler| handler.did_change_workspace_folders(params.clone())),
+        )
+        .await;
+    }
+
+    async fn will_create_files(&self, params: CreateFilesParams) -> Result<Option<WorkspaceEdit>> {
+        Ok(merge::workspace_edits(
+            self.collect(&["workspace", "fileOperations", "willCreate"], |h| {
+                h.will_create_files(params.clone())
+            })
+            .await,
+        ))
     }
 
-    fn symbol_resolve(&self, params: WorkspaceSymbol) -> Result<WorkspaceSymbol> {
-        for handler in &self.handlers {
-            let res = handler.symbol_resolve(params.clone());
-            if res.is_ok() {
-                return res;
-            }
-        }
-        Err(Error::new(ErrorCode::MethodNotFound))
+    async fn did_create_files(&self, params: CreateFilesParams) {
+        join_all(
+            self.handlers
+                .iter()
+                .map(|handler| handler.did_create_files(params.clone())),
+        )
+        .await;
     }
 
-    fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
-        for handler in &self.handlers {
-            handler.did_change_configuration(params.clone());
-        }
+    async fn will_rename_files(&self, params: RenameFilesParams) -> Result<Option<WorkspaceEdit>> {
+        Ok(merge::workspace_edits(
+            self.collect(&["workspace", "fileOperations", "willRename"], |h| {
+                h.will_rename_files(params.clone())
+            })
+            .await,
+        ))
     }
 
-    fn did_change_workspace_folders(&self, params: DidChangeWorkspaceFoldersParams) {
-        for handler in &self.handlers {
-            handler.did_change_workspace_folders(params.clone());
-        }
+    async fn did_rename_files(&self, params: RenameFilesParams) {
+        join_all(
+            self.handlers
+                .iter()
+                .map(|handler| handler.did_rename_files(params.clone())),
+        )
+        .await;
     }
 
-    fn will_create_files(&self, params: CreateFilesParams) -> Result<Option<WorkspaceEdit>> {
-        for handler in &self.handlers {
-            let res = handler.will_create_files(params.clone());
+    async fn will_delete_files(&self, params: DeleteFilesParams) -> Result<Option<WorkspaceEdit>> {
+        Ok(merge::workspace_edits(
+            self.collect(&["workspace", "fileOperations", "willDelete"], |h| {
+                h.will_delete_files(params.clone())
+            })
+            .await,
+        ))
+    }
+
+    async fn did_delete_files(&self, params: DeleteFilesParams) {
+        join_all(
+            self.handlers
+                .iter()
+                .map(|handler| handler.did_delete_files(params.clone())),
+        )
+        .await;
+    }
+
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        join_all(
+            self.handlers
+                .iter()
+                .map(|handler| handler.did_change_watched_files(params.clone())),
+        )
+        .await;
+    }
+
+    // Commands are sent to the handler which registered them
+    async fn execute_command(&self, params: ExecuteCommandParams) -> Result<Option<Value>> {
+        let capabilities = self.capabilities.borrow().clone();
+        for (i, handler) in self.routed(&["executeCommandProvider"]) {
+            let registered = capabilities.get(i).is_none_or(|c| {
+                c["executeCommandProvider"]["commands"]
+                    .as_array()
+                    .is_some_and(|commands| commands.iter().any(|c| c == params.command.as_str()))
+            });
+            if !registered {
+                continue;
+            }
+            let res = handler.execute_command(params.clone()).await;
             if let Ok(Some(_)) = res {
                 return res;
             }
         }
         Ok(None)
     }
+}
 
-    fn did_create_files(&self, params: CreateFilesParams) {
-        for handler in &self.handlers {
-            handler.did_create_files(params.clone());
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::{ResponseFuture, Transport};
+    use futures::channel::oneshot;
+    use futures::executor::{block_on, LocalPool};
+    use futures::task::LocalSpawnExt;
+    use std::rc::Rc;
+
+    type PendingRequests = Rc<RefCell<Vec<(String, oneshot::Sender<Value>)>>>;
+
+    /// Records the notifications, and answers the requests once the test responds to them
+    #[derive(Clone, Default)]
+    struct RecordingTransport {
+        notifications: Rc<RefCell<Vec<(String, Value)>>>,
+        requests: PendingRequests,
     }
 
-    fn will_rename_files(&self, params: RenameFilesParams) -> Result<Option<WorkspaceEdit>> {
-        for handler in &self.handlers {
-            let res = handler.will_rename_files(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
+    impl Transport for RecordingTransport {
+        fn send_request(&self, method: &str, _: Value) -> ResponseFuture {
+            let (sender, receiver) = oneshot::channel();
+            self.requests
+                .borrow_mut()
+                .push((method.to_string(), sender));
+            Box::pin(async move { receiver.await.map_err(|_| Error::internal_error()) })
         }
-        Ok(None)
-    }
 
-    fn did_rename_files(&self, params: RenameFilesParams) {
-        for handler in &self.handlers {
-            handler.did_rename_files(params.clone());
+        fn send_notification(&self, method: &str, params: Value) {
+            self.notifications
+                .borrow_mut()
+                .push((method.to_string(), params));
         }
     }
 
-    fn will_delete_files(&self, params: DeleteFilesParams) -> Result<Option<WorkspaceEdit>> {
-        for handler in &self.handlers {
-            let res = handler.will_delete_files(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
-            }
-        }
-        Ok(None)
+    struct TestHandler {
+        connection: Connection,
     }
 
-    fn did_delete_files(&self, params: DeleteFilesParams) {
-        for handler in &self.handlers {
-            handler.did_delete_files(params.clone());
+    impl Handler for TestHandler {
+        fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+            Ok(InitializeResult::default())
         }
-    }
 
-    fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
-        for handler in &self.handlers {
-            handler.did_change_watched_files(params.clone());
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn did_open(&self, params: DidOpenTextDocumentParams) {
+            self.connection
+                .publish_diagnostics(params.text_document.uri, vec![], None);
         }
     }
 
-    fn execute_command(&self, params: ExecuteCommandParams) -> Result<Option<Value>> {
-        for handler in &self.handlers {
-            let res = handler.execute_command(params.clone());
-            if let Ok(Some(_)) = res {
-                return res;
+    #[test]
+    fn test_dispatch_through_transport() {
+        let transport = RecordingTransport::default();
+        let mut dispatcher = Dispatcher::new(Connection::with_transport(transport.clone()));
+        dispatcher.setup(vec![|connection| {
+            Box::new(TestHandler { connection }) as Box<dyn Handler>
+        }]);
+
+        let params = serde_json::json!({
+            "textDocument": {
+                "uri": "file:///test.sol",
+                "languageId": "solidity",
+                "version": 0,
+                "text": ""
             }
+        });
+        block_on(dispatcher.handle_notification(DidOpenTextDocument::METHOD, params)).unwrap();
+        assert!(block_on(dispatcher.handle_request(Shutdown::METHOD, Value::Null)).is_ok());
+        assert!(block_on(dispatcher.handle_request("osmium/unknown", Value::Null)).is_err());
+
+        let notifications = transport.notifications.borrow();
+        assert!(notifications
+            .iter()
+            .any(|(method, _)| method == PublishDiagnostics::METHOD));
+    }
+
+    struct HoverHandler {
+        hover: Option<&'static str>,
+        trigger: &'static str,
+    }
+
+    impl Handler for HoverHandler {
+        fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+            Ok(InitializeResult {
+                capabilities: serde_json::from_value(serde_json::json!({
+                    "hoverProvider": self.hover.is_some(),
+                    "completionProvider": { "triggerCharacters": [self.trigger] }
+                }))
+                .unwrap(),
+                server_info: None,
+            })
         }
-        Ok(None)
+
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn hover(&self, _: HoverParams) -> Result<Option<Hover>> {
+            Ok(Some(Hover {
+                contents: lsp_types::HoverContents::Scalar(lsp_types::MarkedString::String(
+                    self.hover.unwrap_or("not advertised").to_string(),
+                )),
+                range: None,
+            }))
+        }
+    }
+
+    #[test]
+    fn test_merge_results_of_advertising_handlers() {
+        let mut dispatcher =
+            Dispatcher::new(Connection::with_transport(RecordingTransport::default()));
+        dispatcher.setup(vec![
+            Box::new(|_| {
+                Box::new(HoverHandler {
+                    hover: Some("first"),
+                    trigger: ".",
+                }) as Box<dyn Handler>
+            }) as Box<dyn FnOnce(Connection) -> Box<dyn Handler>>,
+            Box::new(|_| {
+                Box::new(HoverHandler {
+                    hover: None,
+                    trigger: "@",
+                }) as Box<dyn Handler>
+            }),
+            Box::new(|_| {
+                Box::new(HoverHandler {
+                    hover: Some("second"),
+                    trigger: ".",
+                }) as Box<dyn Handler>
+            }),
+        ]);
+
+        let params = serde_json::json!({ "capabilities": {} });
+        let result = block_on(dispatcher.handle_request(Initialize::METHOD, params)).unwrap();
+        assert_eq!(
+            result["capabilities"]["completionProvider"]["triggerCharacters"],
+            serde_json::json!([".", "@"])
+        );
+
+        let hover = block_on(dispatcher.handle_request(
+            HoverRequest::METHOD,
+            serde_json::json!({
+                "textDocument": { "uri": "file:///test.sol" },
+                "position": { "line": 0, "character": 0 }
+            }),
+        ))
+        .unwrap();
+        assert_eq!(hover["contents"]["value"], "first\n\n---\n\nsecond");
+    }
+
+    struct ConfiguredHandler {
+        connection: Connection,
+    }
+
+    #[async_trait(?Send)]
+    impl AsyncHandler for ConfiguredHandler {
+        async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+            Ok(InitializeResult::default())
+        }
+
+        async fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn execute_command(&self, _: ExecuteCommandParams) -> Result<Option<Value>> {
+            let mut config = self
+                .connection
+                .configuration(vec![lsp_types::ConfigurationItem {
+                    scope_uri: None,
+                    section: Some("osmium".to_string()),
+                }])
+                .await?;
+            Ok(config.pop())
+        }
+    }
+
+    #[test]
+    fn test_async_handler_awaits_client_response() {
+        let transport = RecordingTransport::default();
+        let mut dispatcher = Dispatcher::new(Connection::with_transport(transport.clone()));
+        dispatcher.setup_async(vec![|connection| {
+            Box::new(ConfiguredHandler { connection }) as Box<dyn AsyncHandler>
+        }]);
+
+        let result = Rc::new(RefCell::new(None));
+        let mut pool = LocalPool::new();
+        let res = result.clone();
+        pool.spawner()
+            .spawn_local(async move {
+                let params = serde_json::json!({ "command": "osmium.test", "arguments": [] });
+                *res.borrow_mut() = Some(
+                    dispatcher
+                        .handle_request(ExecuteCommand::METHOD, params)
+                        .await,
+                );
+            })
+            .unwrap();
+
+        pool.run_until_stalled();
+        assert!(result.borrow().is_none());
+        let (method, sender) = transport.requests.borrow_mut().pop().unwrap();
+        assert_eq!(method, WorkspaceConfiguration::METHOD);
+        sender
+            .send(serde_json::json!([{ "enabled": true }]))
+            .unwrap();
+
+        pool.run_until_stalled();
+        assert_eq!(
+            result.borrow_mut().take().unwrap().unwrap(),
+            serde_json::json!({ "enabled": true })
+        );
     }
 }
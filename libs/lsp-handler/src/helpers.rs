@@ -1,12 +1,14 @@
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
 use wasm_bindgen::prelude::*;
 
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console, js_name = log)]
     fn console_log(s: &str);
 }
 
-#[cfg(not(test))]
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
 pub(crate) fn log(s: &str) {
     #[allow(unused_unsafe)]
     unsafe {
@@ -14,5 +16,8 @@ pub(crate) fn log(s: &str) {
     }
 }
 
-#[cfg(test)]
-pub(crate) fn log(_: &str) {}
+/// Logs on stderr, as stdout may be used to communicate with the client
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+pub(crate) fn log(s: &str) {
+    eprintln!("[osmium] {}", s);
+}
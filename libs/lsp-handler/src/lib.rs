@@ -1,12 +1,25 @@
+mod async_handler;
 mod connection;
 mod dispatcher;
 mod handler;
 mod jsonrpc;
+mod merge;
+#[cfg(feature = "native")]
+mod native;
+mod transport;
+#[cfg(feature = "wasm")]
+mod wasm;
 
 mod helpers;
 
+pub use async_handler::AsyncHandler;
 pub use connection::Connection;
 pub use dispatcher::Dispatcher;
 pub use handler::Handler;
 pub use jsonrpc::{Error, ErrorCode, Result};
 pub use lsp_types;
+#[cfg(feature = "native")]
+pub use native::ClientTransport;
+pub use transport::{ResponseFuture, Transport};
+#[cfg(feature = "wasm")]
+pub use wasm::JsTransport;
@@ -0,0 +1,584 @@
+use lsp_types::{
+    CompletionList, CompletionResponse, DocumentChangeOperation, DocumentChanges,
+    DocumentDiagnosticReportKind, DocumentSymbol, DocumentSymbolResponse,
+    FullDocumentDiagnosticReport, GotoDefinitionResponse, Hover, HoverContents, LocationLink,
+    MarkedString, MarkupContent, MarkupKind, RelatedFullDocumentDiagnosticReport, SemanticToken,
+    SemanticTokensLegend, ServerCapabilities, SignatureHelp, SymbolInformation, Url,
+    WorkspaceDocumentDiagnosticReport, WorkspaceEdit, WorkspaceFullDocumentDiagnosticReport,
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+/// Whether the capabilities advertised by a handler, as JSON, enable the option at `path`.
+/// An empty path only checks that the handler initialized successfully.
+pub(crate) fn provides(capabilities: &Value, path: &[&str]) -> bool {
+    let mut value = capabilities;
+    for key in path {
+        match value.get(key) {
+            Some(v) => value = v,
+            None => return false,
+        }
+    }
+    !matches!(value, Value::Null | Value::Bool(false))
+}
+
+/// Unions the capabilities advertised by the handlers, so that the client enables a feature
+/// as soon as one handler supports it
+pub(crate) fn union_capabilities(capabilities: &[Value]) -> ServerCapabilities {
+    let mut merged = Value::Object(Map::new());
+    let mut sync_kinds = vec![];
+    let mut on_type_triggers: Vec<Value> = vec![];
+
+    for capabilities in capabilities {
+        let mut capabilities = capabilities.clone();
+        if let Some(sync) = capabilities.get_mut("textDocumentSync") {
+            if sync.is_number() {
+                *sync = serde_json::json!({ "openClose": true, "change": sync.clone() });
+            }
+            if let Some(kind) = sync.get("change").and_then(Value::as_u64) {
+                sync_kinds.push(kind);
+            }
+        }
+        if let Some(on_type) = capabilities.get("documentOnTypeFormattingProvider") {
+            on_type_triggers.extend(on_type.get("firstTriggerCharacter").cloned());
+            if let Some(Value::Array(more)) = on_type.get("moreTriggerCharacter") {
+                on_type_triggers.extend(more.iter().cloned());
+            }
+        }
+        union(&mut merged, capabilities);
+    }
+
+    // Full content can be used by every handler, whereas incremental changes cannot
+    if let Some(kind) = sync_kinds
+        .iter()
+        .find(|k| **k == 1)
+        .or_else(|| sync_kinds.iter().max())
+    {
+        merged["textDocumentSync"]["change"] = Value::from(*kind);
+    }
+    // The client only knows one first trigger character, the others have to be moved
+    if let Some((first, more)) = dedup(on_type_triggers).split_first() {
+        merged["documentOnTypeFormattingProvider"]["firstTriggerCharacter"] = first.clone();
+        merged["documentOnTypeFormattingProvider"]["moreTriggerCharacter"] =
+            Value::Array(more.to_vec());
+    }
+
+    serde_json::from_value(merged).unwrap_or_default()
+}
+
+/// Deeply merges `other` into `into`: objects are merged key by key, arrays are concatenated
+/// without duplicates, options enabled by a boolean are replaced by their detailed form and
+/// the other scalar values are kept from the first handler
+fn union(into: &mut Value, other: Value) {
+    match (into, other) {
+        (Value::Object(into), Value::Object(other)) => {
+            for (key, value) in other {
+                match into.get_mut(&key) {
+                    Some(existing) => union(existing, value),
+                    None => {
+                        into.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Array(into), Value::Array(other)) => {
+            let values = std::mem::take(into).into_iter().chain(other).collect();
+            *into = dedup(values);
+        }
+        (into @ Value::Null, other) => *into = other,
+        (into @ Value::Bool(_), other @ Value::Object(_)) => *into = other,
+        (Value::Bool(into), Value::Bool(other)) => *into |= other,
+        _ => {}
+    }
+}
+
+fn dedup<T: Serialize>(values: Vec<T>) -> Vec<T> {
+    let mut seen = HashSet::new();
+    values
+        .into_iter()
+        .filter(|value| seen.insert(serde_json::to_string(value).unwrap_or_default()))
+        .collect()
+}
+
+/// Concatenates the lists returned by the handlers, removing the items returned several times
+pub(crate) fn concat<T: Serialize>(lists: Vec<Vec<T>>) -> Vec<T> {
+    dedup(lists.into_iter().flatten().collect())
+}
+
+/// Returns the semantic tokens legend of the capabilities, as JSON
+pub(crate) fn legend(capabilities: &Value) -> Option<SemanticTokensLegend> {
+    let legend = capabilities.get("semanticTokensProvider")?.get("legend")?;
+    serde_json::from_value(legend.clone()).ok()
+}
+
+/// Translates the token types and modifiers of a handler to the indexes of the merged legend
+pub(crate) struct TokensRemap {
+    types: Vec<u32>,
+    modifiers: Vec<u32>,
+}
+
+impl TokensRemap {
+    pub(crate) fn new(from: &SemanticTokensLegend, to: &SemanticTokensLegend) -> Self {
+        Self {
+            types: from
+                .token_types
+                .iter()
+                .map(|t| position(&to.token_types, t))
+                .collect(),
+            modifiers: from
+                .token_modifiers
+                .iter()
+                .map(|m| position(&to.token_modifiers, m))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn apply(&self, tokens: &mut [SemanticToken]) {
+        for token in tokens {
+            token.token_type = self
+                .types
+                .get(token.token_type as usize)
+                .copied()
+                .unwrap_or(token.token_type);
+            let mut bitset = 0;
+            for (bit, modifier) in self.modifiers.iter().enumerate() {
+                if token.token_modifiers_bitset & (1 << bit) != 0 {
+                    bitset |= 1 << modifier;
+                }
+            }
+            token.token_modifiers_bitset = bitset;
+        }
+    }
+}
+
+fn position<T: PartialEq>(list: &[T], item: &T) -> u32 {
+    list.iter().position(|i| i == item).unwrap_or(0) as u32
+}
+
+/// Merges locations into a single list, using links as soon as one handler returned some
+pub(crate) fn locations(responses: Vec<GotoDefinitionResponse>) -> Option<GotoDefinitionResponse> {
+    if responses.len() <= 1 {
+        return responses.into_iter().next();
+    }
+    if responses
+        .iter()
+        .all(|r| !matches!(r, GotoDefinitionResponse::Link(_)))
+    {
+        let locations = responses.into_iter().map(|r| match r {
+            GotoDefinitionResponse::Scalar(location) => vec![location],
+            GotoDefinitionResponse::Array(locations) => locations,
+            GotoDefinitionResponse::Link(_) => vec![],
+        });
+        return Some(GotoDefinitionResponse::Array(concat(locations.collect())));
+    }
+    let links = responses.into_iter().map(|r| match r {
+        GotoDefinitionResponse::Scalar(location) => vec![link(location)],
+        GotoDefinitionResponse::Array(locations) => locations.into_iter().map(link).collect(),
+        GotoDefinitionResponse::Link(links) => links,
+    });
+    Some(GotoDefinitionResponse::Link(concat(links.collect())))
+}
+
+fn link(location: lsp_types::Location) -> LocationLink {
+    LocationLink {
+        origin_selection_range: None,
+        target_uri: location.uri,
+        target_range: location.range,
+        target_selection_range: location.range,
+    }
+}
+
+/// Merges the hover contents of the handlers into one markdown document
+pub(crate) fn hovers(hovers: Vec<Hover>) -> Option<Hover> {
+    if hovers.len() <= 1 {
+        return hovers.into_iter().next();
+    }
+    let range = hovers.iter().find_map(|h| h.range);
+    let sections: Vec<String> = hovers
+        .into_iter()
+        .flat_map(|hover| match hover.contents {
+            HoverContents::Scalar(marked) => vec![markdown(marked)],
+            HoverContents::Array(marked) => marked.into_iter().map(markdown).collect(),
+            HoverContents::Markup(markup) => vec![markup.value],
+        })
+        .filter(|section| !section.is_empty())
+        .collect();
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: dedup(sections).join("\n\n---\n\n"),
+        }),
+        range,
+    })
+}
+
+fn markdown(marked: MarkedString) -> String {
+    match marked {
+        MarkedString::String(value) => value,
+        MarkedString::LanguageString(code) => {
+            format!("```{}\n{}\n```", code.language, code.value)
+        }
+    }
+}
+
+/// Combines the completions of the handlers into one list,
+/// which is incomplete if any of them is
+pub(crate) fn completions(responses: Vec<CompletionResponse>) -> Option<CompletionResponse> {
+    if responses.len() <= 1 {
+        return responses.into_iter().next();
+    }
+    let mut is_incomplete = false;
+    let items = responses
+        .into_iter()
+        .map(|response| match response {
+            CompletionResponse::Array(items) => items,
+            CompletionResponse::List(list) => {
+                is_incomplete |= list.is_incomplete;
+                list.items
+            }
+        })
+        .collect();
+    Some(CompletionResponse::List(CompletionList {
+        is_incomplete,
+        items: concat(items),
+    }))
+}
+
+/// Merges document symbols, nesting the flat ones if the handlers did not agree on a format
+pub(crate) fn document_symbols(
+    responses: Vec<DocumentSymbolResponse>,
+) -> Option<DocumentSymbolResponse> {
+    if responses.len() <= 1 {
+        return responses.into_iter().next();
+    }
+    if responses
+        .iter()
+        .all(|r| matches!(r, DocumentSymbolResponse::Flat(_)))
+    {
+        let symbols = responses.into_iter().map(|r| match r {
+            DocumentSymbolResponse::Flat(symbols) => symbols,
+            DocumentSymbolResponse::Nested(_) => vec![],
+        });
+        return Some(DocumentSymbolResponse::Flat(concat(symbols.collect())));
+    }
+    let symbols = responses.into_iter().map(|r| match r {
+        DocumentSymbolResponse::Flat(symbols) => symbols.into_iter().map(nested).collect(),
+        DocumentSymbolResponse::Nested(symbols) => symbols,
+    });
+    Some(DocumentSymbolResponse::Nested(concat(symbols.collect())))
+}
+
+#[allow(deprecated)]
+fn nested(symbol: SymbolInformation) -> DocumentSymbol {
+    DocumentSymbol {
+        name: symbol.name,
+        detail: symbol.container_name,
+        kind: symbol.kind,
+        tags: symbol.tags,
+        deprecated: symbol.deprecated,
+        range: symbol.location.range,
+        selection_range: symbol.location.range,
+        children: None,
+    }
+}
+
+/// Concatenates the signatures of the handlers, keeping the active one of the first handler
+pub(crate) fn signature_helps(helps: Vec<SignatureHelp>) -> Option<SignatureHelp> {
+    let mut helps = helps.into_iter();
+    let mut merged = helps.next()?;
+    for help in helps {
+        merged.signatures = concat(vec![merged.signatures, help.signatures]);
+    }
+    Some(merged)
+}
+
+/// Merges the edits of the handlers into one workspace edit
+pub(crate) fn workspace_edits(edits: Vec<WorkspaceEdit>) -> Option<WorkspaceEdit> {
+    let mut edits = edits.into_iter();
+    let mut merged = edits.next()?;
+    for edit in edits {
+        if let Some(changes) = edit.changes {
+            let merged_changes = merged.changes.get_or_insert_with(HashMap::new);
+            for (uri, text_edits) in changes {
+                let existing = merged_changes.remove(&uri).unwrap_or_default();
+                merged_changes.insert(uri, concat(vec![existing, text_edits]));
+            }
+        }
+        merged.document_changes = match (merged.document_changes, edit.document_changes) {
+            (Some(DocumentChanges::Edits(a)), Some(DocumentChanges::Edits(b))) => {
+                Some(DocumentChanges::Edits(concat(vec![a, b])))
+            }
+            (Some(a), Some(b)) => Some(DocumentChanges::Operations(concat(vec![
+                operations(a),
+                operations(b),
+            ]))),
+            (a, b) => a.or(b),
+        };
+        if let Some(annotations) = edit.change_annotations {
+            merged
+                .change_annotations
+                .get_or_insert_with(HashMap::new)
+                .extend(annotations);
+        }
+    }
+    Some(merged)
+}
+
+fn operations(changes: DocumentChanges) -> Vec<DocumentChangeOperation> {
+    match changes {
+        DocumentChanges::Edits(edits) => edits
+            .into_iter()
+            .map(DocumentChangeOperation::Edit)
+            .collect(),
+        DocumentChanges::Operations(operations) => operations,
+    }
+}
+
+/// Result ids of the handlers are packed into the one given to the client,
+/// so that each handler receives its own on the next request
+pub(crate) fn join_result_ids(ids: Vec<Option<String>>) -> String {
+    let ids: Vec<String> = ids.into_iter().map(Option::unwrap_or_default).collect();
+    serde_json::to_string(&ids).unwrap_or_default()
+}
+
+pub(crate) fn split_result_ids(id: Option<&str>, count: usize) -> Vec<Option<String>> {
+    match id.and_then(|id| serde_json::from_str::<Vec<String>>(id).ok()) {
+        Some(ids) if ids.len() == count => ids
+            .into_iter()
+            .map(|id| Some(id).filter(|id| !id.is_empty()))
+            .collect(),
+        _ => vec![None; count],
+    }
+}
+
+/// Merges the full document reports of the handlers.
+/// Handlers which failed are given as `None` and keep their place in the result id.
+pub(crate) fn document_reports(
+    reports: Vec<Option<RelatedFullDocumentDiagnosticReport>>,
+) -> RelatedFullDocumentDiagnosticReport {
+    let mut ids = vec![];
+    let mut items = vec![];
+    let mut related: Option<HashMap<Url, DocumentDiagnosticReportKind>> = None;
+    for report in reports {
+        let Some(report) = report else {
+            ids.push(None);
+            continue;
+        };
+        ids.push(report.full_document_diagnostic_report.result_id);
+        items.push(report.full_document_diagnostic_report.items);
+        for (uri, kind) in report.related_documents.into_iter().flatten() {
+            let related = related.get_or_insert_with(HashMap::new);
+            match (related.get_mut(&uri), kind) {
+                (
+                    Some(DocumentDiagnosticReportKind::Full(existing)),
+                    DocumentDiagnosticReportKind::Full(report),
+                ) => existing.items.extend(report.items),
+                (Some(_), _) => {}
+                (None, kind) => {
+                    related.insert(uri, kind);
+                }
+            }
+        }
+    }
+    RelatedFullDocumentDiagnosticReport {
+        related_documents: related,
+        full_document_diagnostic_report: FullDocumentDiagnosticReport {
+            result_id: Some(join_result_ids(ids)),
+            items: concat(items),
+        },
+    }
+}
+
+/// Groups the workspace reports of the handlers by document.
+/// Unchanged reports are dropped, as the handlers are asked for full reports.
+pub(crate) fn workspace_reports(
+    reports: Vec<Vec<WorkspaceDocumentDiagnosticReport>>,
+) -> Vec<WorkspaceDocumentDiagnosticReport> {
+    let mut merged: Vec<WorkspaceFullDocumentDiagnosticReport> = vec![];
+    for report in reports.into_iter().flatten() {
+        let WorkspaceDocumentDiagnosticReport::Full(mut report) = report else {
+            continue;
+        };
+        report.full_document_diagnostic_report.result_id = None;
+        match merged.iter_mut().find(|r| r.uri == report.uri) {
+            Some(existing) => existing
+                .full_document_diagnostic_report
+                .items
+                .extend(report.full_document_diagnostic_report.items),
+            None => merged.push(report),
+        }
+    }
+    merged
+        .into_iter()
+        .map(WorkspaceDocumentDiagnosticReport::Full)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{CompletionItem, Location, Position, Range};
+    use serde_json::json;
+
+    fn location(line: u32) -> Location {
+        Location {
+            uri: Url::parse("file:///test.sol").unwrap(),
+            range: Range::new(Position::new(line, 0), Position::new(line, 1)),
+        }
+    }
+
+    #[test]
+    fn test_union_capabilities() {
+        let merged = union_capabilities(&[
+            json!({
+                "textDocumentSync": 2,
+                "hoverProvider": true,
+                "completionProvider": { "triggerCharacters": [".", "@"] },
+                "codeActionProvider": true,
+                "semanticTokensProvider": {
+                    "legend": { "tokenTypes": ["type"], "tokenModifiers": [] },
+                    "full": true
+                }
+            }),
+            json!({
+                "textDocumentSync": { "openClose": true, "change": 1 },
+                "hoverProvider": { "workDoneProgress": true },
+                "completionProvider": { "triggerCharacters": ["@", "\""] },
+                "codeActionProvider": { "codeActionKinds": ["quickfix"] },
+                "semanticTokensProvider": {
+                    "legend": { "tokenTypes": ["function", "type"], "tokenModifiers": ["static"] },
+                    "range": true
+                }
+            }),
+        ]);
+        let merged = serde_json::to_value(merged).unwrap();
+
+        assert_eq!(merged["textDocumentSync"]["change"], 1);
+        assert_eq!(merged["hoverProvider"]["workDoneProgress"], true);
+        assert_eq!(
+            merged["completionProvider"]["triggerCharacters"],
+            json!([".", "@", "\""])
+        );
+        assert_eq!(
+            merged["codeActionProvider"]["codeActionKinds"],
+            json!(["quickfix"])
+        );
+        assert_eq!(
+            merged["semanticTokensProvider"]["legend"]["tokenTypes"],
+            json!(["type", "function"])
+        );
+        assert_eq!(merged["semanticTokensProvider"]["full"], true);
+        assert_eq!(merged["semanticTokensProvider"]["range"], true);
+    }
+
+    #[test]
+    fn test_provides() {
+        let capabilities = json!({
+            "hoverProvider": false,
+            "codeLensProvider": { "resolveProvider": true },
+            "executeCommandProvider": { "commands": [] }
+        });
+
+        assert!(!provides(&capabilities, &["hoverProvider"]));
+        assert!(!provides(&capabilities, &["definitionProvider"]));
+        assert!(provides(
+            &capabilities,
+            &["codeLensProvider", "resolveProvider"]
+        ));
+        assert!(provides(&capabilities, &[]));
+        assert!(!provides(&Value::Null, &[]));
+    }
+
+    #[test]
+    fn test_remap_semantic_tokens() {
+        let from = SemanticTokensLegend {
+            token_types: vec!["function".into()],
+            token_modifiers: vec!["static".into(), "readonly".into()],
+        };
+        let to = SemanticTokensLegend {
+            token_types: vec!["type".into(), "function".into()],
+            token_modifiers: vec!["readonly".into(), "static".into()],
+        };
+        let mut tokens = vec![SemanticToken {
+            delta_line: 1,
+            delta_start: 2,
+            length: 3,
+            token_type: 0,
+            token_modifiers_bitset: 0b01,
+        }];
+
+        TokensRemap::new(&from, &to).apply(&mut tokens);
+
+        assert_eq!(tokens[0].token_type, 1);
+        assert_eq!(tokens[0].token_modifiers_bitset, 0b10);
+    }
+
+    #[test]
+    fn test_merge_locations_and_completions() {
+        let merged = locations(vec![
+            GotoDefinitionResponse::Scalar(location(1)),
+            GotoDefinitionResponse::Array(vec![location(1), location(2)]),
+        ]);
+        assert_eq!(
+            merged,
+            Some(GotoDefinitionResponse::Array(vec![
+                location(1),
+                location(2)
+            ]))
+        );
+
+        let merged = completions(vec![
+            CompletionResponse::Array(vec![CompletionItem::new_simple("a".into(), "".into())]),
+            CompletionResponse::List(CompletionList {
+                is_incomplete: true,
+                items: vec![CompletionItem::new_simple("b".into(), "".into())],
+            }),
+        ]);
+        match merged {
+            Some(CompletionResponse::List(list)) => {
+                assert!(list.is_incomplete);
+                assert_eq!(list.items.len(), 2);
+            }
+            _ => panic!("expected a completion list"),
+        }
+    }
+
+    #[test]
+    fn test_merge_hovers() {
+        let merged = hovers(vec![
+            Hover {
+                contents: HoverContents::Scalar(MarkedString::String("first".into())),
+                range: None,
+            },
+            Hover {
+                contents: HoverContents::Markup(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value: "second".into(),
+                }),
+                range: Some(location(1).range),
+            },
+        ])
+        .unwrap();
+
+        assert_eq!(
+            merged.contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: "first\n\n---\n\nsecond".into(),
+            })
+        );
+        assert_eq!(merged.range, Some(location(1).range));
+    }
+
+    #[test]
+    fn test_result_ids_round_trip() {
+        let id = join_result_ids(vec![Some("1".into()), None]);
+        assert_eq!(
+            split_result_ids(Some(&id), 2),
+            vec![Some("1".to_string()), None]
+        );
+        assert_eq!(split_result_ids(Some(&id), 3), vec![None, None, None]);
+        assert_eq!(split_result_ids(Some("plain"), 1), vec![None]);
+    }
+}
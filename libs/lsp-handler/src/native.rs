@@ -0,0 +1,383 @@
+use crate::async_handler::AsyncHandler;
+use crate::connection::Connection;
+use crate::dispatcher::Dispatcher;
+use crate::jsonrpc;
+use crate::transport::{ResponseFuture, Transport};
+use futures::executor::block_on;
+use lsp_types::request::{
+    GotoDeclarationParams, GotoDeclarationResponse, GotoImplementationParams,
+    GotoImplementationResponse, GotoTypeDefinitionParams, GotoTypeDefinitionResponse,
+};
+use lsp_types::*;
+use osmium_libs_lsp_server_wrapper::{Client, Error, ErrorCode, LanguageServer, Result};
+use serde_json::Value;
+
+/// Transport sending messages through a native language server
+pub struct ClientTransport {
+    client: Client,
+}
+
+impl ClientTransport {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Transport for ClientTransport {
+    fn send_request(&self, method: &str, params: Value) -> ResponseFuture {
+        // The client blocks until the response is received, so the future is always ready
+        let res = self
+            .client
+            .send_raw_request(method, params)
+            .map_err(|err| jsonrpc::Error {
+                code: jsonrpc::ErrorCode::from(err.code.code()),
+                message: err.message,
+                data: err.data,
+            });
+        Box::pin(std::future::ready(res))
+    }
+
+    fn send_notification(&self, method: &str, params: Value) {
+        self.client.send_raw_notification(method, params);
+    }
+}
+
+impl From<Client> for Connection {
+    fn from(client: Client) -> Self {
+        Connection::with_transport(ClientTransport::new(client))
+    }
+}
+
+fn into_server_error(err: jsonrpc::Error) -> Error {
+    Error {
+        code: ErrorCode::from(err.code.code()),
+        message: err.message,
+        data: err.data,
+    }
+}
+
+/// Allows to serve the handlers from a native language server, such as
+/// `LspStdioServer::serve(server, |client| { let mut dispatcher = Dispatcher::new(client.into()); ... })`.
+/// The server expects synchronous results, so asynchronous handlers are run to completion.
+impl LanguageServer for Dispatcher {
+    fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        block_on(AsyncHandler::initialize(self, params)).map_err(into_server_error)
+    }
+
+    fn initialized(&self, params: InitializedParams) {
+        block_on(AsyncHandler::initialized(self, params))
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        block_on(AsyncHandler::shutdown(self)).map_err(into_server_error)
+    }
+
+    fn did_open(&self, params: DidOpenTextDocumentParams) {
+        block_on(AsyncHandler::did_open(self, params))
+    }
+
+    fn did_change(&self, params: DidChangeTextDocumentParams) {
+        block_on(AsyncHandler::did_change(self, params))
+    }
+
+    fn will_save(&self, params: WillSaveTextDocumentParams) {
+        block_on(AsyncHandler::will_save(self, params))
+    }
+
+    fn will_save_wait_until(
+        &self,
+        params: WillSaveTextDocumentParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        block_on(AsyncHandler::will_save_wait_until(self, params)).map_err(into_server_error)
+    }
+
+    fn did_save(&self, params: DidSaveTextDocumentParams) {
+        block_on(AsyncHandler::did_save(self, params))
+    }
+
+    fn did_close(&self, params: DidCloseTextDocumentParams) {
+        block_on(AsyncHandler::did_close(self, params))
+    }
+
+    fn goto_declaration(
+        &self,
+        params: GotoDeclarationParams,
+    ) -> Result<Option<GotoDeclarationResponse>> {
+        block_on(AsyncHandler::goto_declaration(self, params)).map_err(into_server_error)
+    }
+
+    fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        block_on(AsyncHandler::goto_definition(self, params)).map_err(into_server_error)
+    }
+
+    fn goto_type_definition(
+        &self,
+        params: GotoTypeDefinitionParams,
+    ) -> Result<Option<GotoTypeDefinitionResponse>> {
+        block_on(AsyncHandler::goto_type_definition(self, params)).map_err(into_server_error)
+    }
+
+    fn goto_implementation(
+        &self,
+        params: GotoImplementationParams,
+    ) -> Result<Option<GotoImplementationResponse>> {
+        block_on(AsyncHandler::goto_implementation(self, params)).map_err(into_server_error)
+    }
+
+    fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        block_on(AsyncHandler::references(self, params)).map_err(into_server_error)
+    }
+
+    fn prepare_call_hierarchy(
+        &self,
+        params: CallHierarchyPrepareParams,
+    ) -> Result<Option<Vec<CallHierarchyItem>>> {
+        block_on(AsyncHandler::prepare_call_hierarchy(self, params)).map_err(into_server_error)
+    }
+
+    fn incoming_calls(
+        &self,
+        params: CallHierarchyIncomingCallsParams,
+    ) -> Result<Option<Vec<CallHierarchyIncomingCall>>> {
+        block_on(AsyncHandler::incoming_calls(self, params)).map_err(into_server_error)
+    }
+
+    fn outgoing_calls(
+        &self,
+        params: CallHierarchyOutgoingCallsParams,
+    ) -> Result<Option<Vec<CallHierarchyOutgoingCall>>> {
+        block_on(AsyncHandler::outgoing_calls(self, params)).map_err(into_server_error)
+    }
+
+    fn prepare_type_hierarchy(
+        &self,
+        params: TypeHierarchyPrepareParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        block_on(AsyncHandler::prepare_type_hierarchy(self, params)).map_err(into_server_error)
+    }
+
+    fn supertypes(
+        &self,
+        params: TypeHierarchySupertypesParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        block_on(AsyncHandler::supertypes(self, params)).map_err(into_server_error)
+    }
+
+    fn subtypes(
+        &self,
+        params: TypeHierarchySubtypesParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        block_on(AsyncHandler::subtypes(self, params)).map_err(into_server_error)
+    }
+
+    fn document_highlight(
+        &self,
+        params: DocumentHighlightParams,
+    ) -> Result<Option<Vec<DocumentHighlight>>> {
+        block_on(AsyncHandler::document_highlight(self, params)).map_err(into_server_error)
+    }
+
+    fn document_link(&self, params: DocumentLinkParams) -> Result<Option<Vec<DocumentLink>>> {
+        block_on(AsyncHandler::document_link(self, params)).map_err(into_server_error)
+    }
+
+    fn document_link_resolve(&self, params: DocumentLink) -> Result<DocumentLink> {
+        block_on(AsyncHandler::document_link_resolve(self, params)).map_err(into_server_error)
+    }
+
+    fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        block_on(AsyncHandler::hover(self, params)).map_err(into_server_error)
+    }
+
+    fn code_lens(&self, params: CodeLensParams) -> Result<Option<Vec<CodeLens>>> {
+        block_on(AsyncHandler::code_lens(self, params)).map_err(into_server_error)
+    }
+
+    fn code_lens_resolve(&self, params: CodeLens) -> Result<CodeLens> {
+        block_on(AsyncHandler::code_lens_resolve(self, params)).map_err(into_server_error)
+    }
+
+    fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
+        block_on(AsyncHandler::folding_range(self, params)).map_err(into_server_error)
+    }
+
+    fn selection_range(&self, params: SelectionRangeParams) -> Result<Option<Vec<SelectionRange>>> {
+        block_on(AsyncHandler::selection_range(self, params)).map_err(into_server_error)
+    }
+
+    fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        block_on(AsyncHandler::document_symbol(self, params)).map_err(into_server_error)
+    }
+
+    fn semantic_tokens_full(
+        &self,
+        params: SemanticTokensParams,
+    ) -> Result<Option<SemanticTokensResult>> {
+        block_on(AsyncHandler::semantic_tokens_full(self, params)).map_err(into_server_error)
+    }
+
+    fn semantic_tokens_full_delta(
+        &self,
+        params: SemanticTokensDeltaParams,
+    ) -> Result<Option<SemanticTokensFullDeltaResult>> {
+        block_on(AsyncHandler::semantic_tokens_full_delta(self, params)).map_err(into_server_error)
+    }
+
+    fn semantic_tokens_range(
+        &self,
+        params: SemanticTokensRangeParams,
+    ) -> Result<Option<SemanticTokensRangeResult>> {
+        block_on(AsyncHandler::semantic_tokens_range(self, params)).map_err(into_server_error)
+    }
+
+    fn inline_value(&self, params: InlineValueParams) -> Result<Option<Vec<InlineValue>>> {
+        block_on(AsyncHandler::inline_value(self, params)).map_err(into_server_error)
+    }
+
+    fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
+        block_on(AsyncHandler::inlay_hint(self, params)).map_err(into_server_error)
+    }
+
+    fn inlay_hint_resolve(&self, params: InlayHint) -> Result<InlayHint> {
+        block_on(AsyncHandler::inlay_hint_resolve(self, params)).map_err(into_server_error)
+    }
+
+    fn moniker(&self, params: MonikerParams) -> Result<Option<Vec<Moniker>>> {
+        block_on(AsyncHandler::moniker(self, params)).map_err(into_server_error)
+    }
+
+    fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        block_on(AsyncHandler::completion(self, params)).map_err(into_server_error)
+    }
+
+    fn completion_resolve(&self, params: CompletionItem) -> Result<CompletionItem> {
+        block_on(AsyncHandler::completion_resolve(self, params)).map_err(into_server_error)
+    }
+
+    fn diagnostic(
+        &self,
+        params: DocumentDiagnosticParams,
+    ) -> Result<DocumentDiagnosticReportResult> {
+        block_on(AsyncHandler::diagnostic(self, params)).map_err(into_server_error)
+    }
+
+    fn workspace_diagnostic(
+        &self,
+        params: WorkspaceDiagnosticParams,
+    ) -> Result<WorkspaceDiagnosticReportResult> {
+        block_on(AsyncHandler::workspace_diagnostic(self, params)).map_err(into_server_error)
+    }
+
+    fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        block_on(AsyncHandler::signature_help(self, params)).map_err(into_server_error)
+    }
+
+    fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        block_on(AsyncHandler::code_action(self, params)).map_err(into_server_error)
+    }
+
+    fn code_action_resolve(&self, params: CodeAction) -> Result<CodeAction> {
+        block_on(AsyncHandler::code_action_resolve(self, params)).map_err(into_server_error)
+    }
+
+    fn document_color(&self, params: DocumentColorParams) -> Result<Vec<ColorInformation>> {
+        block_on(AsyncHandler::document_color(self, params)).map_err(into_server_error)
+    }
+
+    fn color_presentation(
+        &self,
+        params: ColorPresentationParams,
+    ) -> Result<Vec<ColorPresentation>> {
+        block_on(AsyncHandler::color_presentation(self, params)).map_err(into_server_error)
+    }
+
+    fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        block_on(AsyncHandler::formatting(self, params)).map_err(into_server_error)
+    }
+
+    fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        block_on(AsyncHandler::range_formatting(self, params)).map_err(into_server_error)
+    }
+
+    fn on_type_formatting(
+        &self,
+        params: DocumentOnTypeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        block_on(AsyncHandler::on_type_formatting(self, params)).map_err(into_server_error)
+    }
+
+    fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        block_on(AsyncHandler::rename(self, params)).map_err(into_server_error)
+    }
+
+    fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        block_on(AsyncHandler::prepare_rename(self, params)).map_err(into_server_error)
+    }
+
+    fn linked_editing_range(
+        &self,
+        params: LinkedEditingRangeParams,
+    ) -> Result<Option<LinkedEditingRanges>> {
+        block_on(AsyncHandler::linked_editing_range(self, params)).map_err(into_server_error)
+    }
+
+    fn symbol(&self, params: WorkspaceSymbolParams) -> Result<Option<Vec<SymbolInformation>>> {
+        block_on(AsyncHandler::symbol(self, params)).map_err(into_server_error)
+    }
+
+    fn symbol_resolve(&self, params: WorkspaceSymbol) -> Result<WorkspaceSymbol> {
+        block_on(AsyncHandler::symbol_resolve(self, params)).map_err(into_server_error)
+    }
+
+    fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        block_on(AsyncHandler::did_change_configuration(self, params))
+    }
+
+    fn did_change_workspace_folders(&self, params: DidChangeWorkspaceFoldersParams) {
+        block_on(AsyncHandler::did_change_workspace_folders(self, params))
+    }
+
+    fn will_create_files(&self, params: CreateFilesParams) -> Result<Option<WorkspaceEdit>> {
+        block_on(AsyncHandler::will_create_files(self, params)).map_err(into_server_error)
+    }
+
+    fn did_create_files(&self, params: CreateFilesParams) {
+        block_on(AsyncHandler::did_create_files(self, params))
+    }
+
+    fn will_rename_files(&self, params: RenameFilesParams) -> Result<Option<WorkspaceEdit>> {
+        block_on(AsyncHandler::will_rename_files(self, params)).map_err(into_server_error)
+    }
+
+    fn did_rename_files(&self, params: RenameFilesParams) {
+        block_on(AsyncHandler::did_rename_files(self, params))
+    }
+
+    fn will_delete_files(&self, params: DeleteFilesParams) -> Result<Option<WorkspaceEdit>> {
+        block_on(AsyncHandler::will_delete_files(self, params)).map_err(into_server_error)
+    }
+
+    fn did_delete_files(&self, params: DeleteFilesParams) {
+        block_on(AsyncHandler::did_delete_files(self, params))
+    }
+
+    fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        block_on(AsyncHandler::did_change_watched_files(self, params))
+    }
+
+    fn execute_command(&self, params: ExecuteCommandParams) -> Result<Option<Value>> {
+        block_on(AsyncHandler::execute_command(self, params)).map_err(into_server_error)
+    }
+}
@@ -0,0 +1,19 @@
+use crate::jsonrpc;
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Response of the client to a request, resolved once the client answered
+pub type ResponseFuture = Pin<Box<dyn Future<Output = jsonrpc::Result<Value>>>>;
+
+/// Sends messages from the server to the language client.
+///
+/// This is the only part of a [`Connection`](crate::Connection) which depends on the environment
+/// the handlers run in, such as the VS Code wasm bundle or a standalone language server.
+pub trait Transport {
+    /// Sends a request to the client and returns the JSON result of its response.
+    fn send_request(&self, method: &str, params: Value) -> ResponseFuture;
+
+    /// Sends a notification to the client.
+    fn send_notification(&self, method: &str, params: Value);
+}
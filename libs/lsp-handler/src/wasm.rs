@@ -0,0 +1,107 @@
+use crate::connection::Connection;
+use crate::dispatcher::Dispatcher;
+use crate::jsonrpc;
+use crate::transport::{ResponseFuture, Transport};
+use serde::Serialize;
+use serde_json::Value;
+use serde_wasm_bindgen::{from_value, Serializer};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+/// Transport calling back the JavaScript functions given by the VS Code extension
+pub struct JsTransport {
+    send_request_callback: js_sys::Function,
+    send_notification_callback: js_sys::Function,
+}
+
+impl JsTransport {
+    pub fn new(
+        send_request_callback: js_sys::Function,
+        send_notification_callback: js_sys::Function,
+    ) -> Self {
+        Self {
+            send_request_callback,
+            send_notification_callback,
+        }
+    }
+}
+
+impl Transport for JsTransport {
+    fn send_request(&self, method: &str, params: Value) -> ResponseFuture {
+        let method = JsValue::from_str(method);
+        let res = to_js(&params)
+            .map_err(|_| jsonrpc::Error::internal_error())
+            .and_then(|params| {
+                self.send_request_callback
+                    .call2(&JsValue::NULL, &method, &params)
+                    .map_err(|_| jsonrpc::Error::internal_error())
+            });
+        Box::pin(async move {
+            let mut res = res?;
+            // The callback returns a promise resolved with the response of the client
+            if let Some(promise) = res.dyn_ref::<js_sys::Promise>() {
+                res = JsFuture::from(promise.clone())
+                    .await
+                    .map_err(|_| jsonrpc::Error::internal_error())?;
+            }
+            from_value(res).map_err(|_| jsonrpc::Error::internal_error())
+        })
+    }
+
+    fn send_notification(&self, method: &str, params: Value) {
+        let method = JsValue::from_str(method);
+        if let Ok(params) = to_js(&params) {
+            let _ = self
+                .send_notification_callback
+                .call2(&JsValue::NULL, &method, &params);
+        }
+    }
+}
+
+impl Connection {
+    pub fn new(
+        send_request_callback: js_sys::Function,
+        send_notification_callback: js_sys::Function,
+    ) -> Self {
+        Connection::with_transport(JsTransport::new(
+            send_request_callback,
+            send_notification_callback,
+        ))
+    }
+}
+
+/// Converts a value to a plain JavaScript object, instead of a `Map`
+fn to_js<T: Serialize>(value: &T) -> std::result::Result<JsValue, serde_wasm_bindgen::Error> {
+    value.serialize(&Serializer::json_compatible())
+}
+
+#[wasm_bindgen]
+impl Dispatcher {
+    #[allow(unused_variables)]
+    #[wasm_bindgen(js_class = Dispatcher, js_name = onRequest)]
+    pub async fn on_request(
+        &self,
+        method: &str,
+        params: JsValue,
+    ) -> std::result::Result<JsValue, JsValue> {
+        let params: Value = from_value(params)?;
+        match self.handle_request(method, params).await {
+            Ok(result) => Ok(to_js(&result)?),
+            Err(err) => Err(to_js(&err)?),
+        }
+    }
+
+    #[allow(unused_variables)]
+    #[wasm_bindgen(js_class = Dispatcher, js_name = onNotification)]
+    pub async fn on_notification(
+        &self,
+        method: &str,
+        params: JsValue,
+    ) -> std::result::Result<(), JsValue> {
+        let params: Value = from_value(params)?;
+        self.handle_notification(method, params)
+            .await
+            .map_err(|err| JsValue::from_str(&err.to_string()))
+    }
+}
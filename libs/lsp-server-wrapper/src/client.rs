@@ -5,9 +5,9 @@ use lsp_types::request::*;
 use lsp_types::*;
 use serde::Serialize;
 use serde_json::Value;
-use std::cell::RefCell;
 use std::fmt::Display;
-use std::rc::Weak;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Weak};
 use tracing::error;
 
 use crate::server::LspServer;
@@ -15,13 +15,13 @@ use crate::server::LspServer;
 #[derive(Clone)]
 pub(crate) struct ClientInner {
     server: Option<Weak<dyn LspServer>>,
-    id: RefCell<u32>,
+    id: Arc<AtomicU32>,
 }
 
 /// Handle for communicating with the language client.
 ///
 /// This type provides a very cheap implementation of [`Clone`] so API consumers can cheaply clone
-/// and pass it around as needed.
+/// and pass it around as needed. It can also be sent to other threads.
 ///
 /// It also implements [`tower::Service`] in order to remain independent from the underlying
 /// transport and to facilitate further abstraction with middleware.
@@ -31,18 +31,14 @@ pub struct Client {
 }
 
 impl Client {
-    pub(super) fn new() -> Self {
+    pub(super) fn new(server: Weak<dyn LspServer>) -> Self {
         Client {
             inner: ClientInner {
-                server: None,
-                id: RefCell::new(0),
+                server: Some(server),
+                id: Arc::new(AtomicU32::new(0)),
             },
         }
     }
-
-    pub(super) fn set_server(&mut self, server: Weak<dyn LspServer>) {
-        self.inner.server = Some(server);
-    }
 }
 
 impl Client {
@@ -153,8 +149,38 @@ impl Client {
         Ok(response.success)
     }
 
-    // TODO: Add `work_done_progress_create()` here (since 3.15.0) when supported by `tower-lsp`.
-    // https://github.com/ebkalderon/tower-lsp/issues/176
+    /// Asks the client to create a work done progress, which can then be reported with
+    /// [`Client::send_progress`].
+    ///
+    /// This corresponds to the [`window/workDoneProgress/create`] request.
+    ///
+    /// [`window/workDoneProgress/create`]: https://microsoft.github.io/language-server-protocol/specification#window_workDoneProgress_create
+    ///
+    /// # Initialization
+    ///
+    /// If the request is sent to the client before the server has been initialized, this will
+    /// immediately return `Err` with JSON-RPC error code `-32002` ([read more]).
+    ///
+    /// [read more]: https://microsoft.github.io/language-server-protocol/specification#initialize
+    ///
+    /// # Compatibility
+    ///
+    /// This request was introduced in specification version 3.15.0.
+    pub fn work_done_progress_create(&self, token: ProgressToken) -> jsonrpc::Result<()> {
+        self.send_request::<WorkDoneProgressCreate>(WorkDoneProgressCreateParams { token })
+    }
+
+    /// Reports the begin, the progression or the end of a work done progress.
+    ///
+    /// This corresponds to the [`$/progress`] notification.
+    ///
+    /// [`$/progress`]: https://microsoft.github.io/language-server-protocol/specification#progress
+    pub fn send_progress(&self, token: ProgressToken, progress: WorkDoneProgress) {
+        self.send_notification::<Progress>(ProgressParams {
+            token,
+            value: ProgressParamsValue::WorkDone(progress),
+        });
+    }
 
     /// Notifies the client to log a telemetry event.
     ///
@@ -432,50 +458,75 @@ impl Client {
     where
         R: lsp_types::request::Request,
     {
+        self.send_request_unchecked::<R>(params)
+    }
+
+    fn send_request_unchecked<R>(&self, params: R::Params) -> jsonrpc::Result<R::Result>
+    where
+        R: lsp_types::request::Request,
+    {
+        let params = serde_json::to_value(params).map_err(|_| jsonrpc::Error::internal_error())?;
+        let result = self.send_raw_request(R::METHOD, params)?;
+        serde_json::from_value(result).map_err(|e| jsonrpc::Error::invalid_params(e.to_string()))
+    }
+
+    /// Sends a request whose method is only known at runtime, and returns the JSON result of its
+    /// response.
+    ///
+    /// # Initialization
+    ///
+    /// If the request is sent to the client before the server has been initialized, this will
+    /// immediately return `Err` with JSON-RPC error code `-32002` ([read more]).
+    ///
+    /// [read more]: https://microsoft.github.io/language-server-protocol/specification#initialize
+    pub fn send_raw_request(&self, method: &str, params: Value) -> jsonrpc::Result<Value> {
         let server_opt = self.inner.server.clone().unwrap().upgrade();
         if server_opt.is_none() {
             eprintln!("Cannot send request, server is not initialized");
             return Err(jsonrpc::not_initialized_error());
         }
-        server_opt
-            .as_ref()
+        let response = server_opt
             .unwrap()
-            .send(Message::Request(lsp_server::Request::new(
+            .send_request(lsp_server::Request::new(
                 RequestId::from(self.next_request_id().to_string()),
-                R::METHOD.to_string(),
+                method.to_string(),
                 params,
-            )));
-        Err(jsonrpc::not_initialized_error())
+            ))
+            .ok_or_else(jsonrpc::Error::internal_error)?;
+
+        if let Some(error) = response.error {
+            return Err(jsonrpc::Error {
+                code: jsonrpc::ErrorCode::from(error.code as i64),
+                message: error.message.into(),
+                data: error.data,
+            });
+        }
+        Ok(response.result.unwrap_or(Value::Null))
     }
 
-    fn send_request_unchecked<R>(&self, params: R::Params) -> jsonrpc::Result<R::Result>
-    where
-        R: lsp_types::request::Request,
-    {
+    /// Sends a notification whose method is only known at runtime.
+    ///
+    /// # Initialization
+    ///
+    /// This notification will only be sent if the server is initialized.
+    pub fn send_raw_notification(&self, method: &str, params: Value) {
         let server_opt = self.inner.server.clone().unwrap().upgrade();
         if server_opt.is_none() {
-            eprintln!("Cannot send request, server is not initialized");
-            return Err(jsonrpc::not_initialized_error());
+            eprintln!("Cannot send notification, server is not initialized");
+            return;
         }
         server_opt
             .unwrap()
-            .send(Message::Request(lsp_server::Request::new(
-                RequestId::from(self.next_request_id().to_string()),
-                R::METHOD.to_string(),
+            .send(Message::Notification(lsp_server::Notification::new(
+                method.to_string(),
                 params,
             )));
-        Err(jsonrpc::not_initialized_error())
     }
 }
 
 impl Client {
     fn next_request_id(&self) -> u32 {
-        let id = *self.inner.id.borrow_mut();
-        if id == u32::MAX {
-            *self.inner.id.borrow_mut() = 0;
-        } else {
-            *self.inner.id.borrow_mut() += 1;
-        }
-        id
+        // fetch_add wraps around on overflow
+        self.inner.id.fetch_add(1, Ordering::Relaxed)
     }
 }
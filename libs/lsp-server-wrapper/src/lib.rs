@@ -2,8 +2,9 @@ mod client;
 mod jsonrpc;
 mod server;
 mod service;
+mod transport;
 
-pub use crate::jsonrpc::{Error, Result};
+pub use crate::jsonrpc::{Error, ErrorCode, Result};
 pub use client::Client;
 pub use lsp_types;
 use lsp_types::request::{
@@ -14,6 +15,7 @@ use lsp_types::*;
 use serde_json::Value;
 pub use server::LspStdioServer;
 pub use service::LspService;
+pub use transport::{socket_addr, Transport};
 
 /// Trait implemented by language server backends.
 ///
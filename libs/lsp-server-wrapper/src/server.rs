@@ -1,38 +1,75 @@
-use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
 use std::error::Error;
+use std::io;
+use std::net::TcpStream;
 
-use std::rc::{Rc, Weak};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread::{self, ThreadId};
 
 use crate::service::LspService;
+use crate::transport::{tcp_connection, Io, Transport};
 use crate::{Client, LanguageServer};
-use lsp_server::{Connection, IoThreads, Message, Response};
+use lsp_server::{Connection, Message, Request, RequestId, Response};
 
-pub(crate) trait LspServer {
+pub(crate) trait LspServer: Send + Sync {
     fn send(&self, msg: Message);
+
+    /// Sends a request to the client and waits for its response.
+    /// Returns `None` if the connection is closed before the response is received.
+    fn send_request(&self, request: Request) -> Option<Response>;
 }
 
 struct InnerLspStdioServer {
     connection: Connection,
-    io_threads: IoThreads,
-    client: Rc<RefCell<Client>>,
-    self_ref: Weak<LspStdioServer>,
+    io: Io,
+    client: Client,
+    /// Senders waiting for the response of a request sent to the client
+    pending_responses: Mutex<HashMap<RequestId, Sender<Response>>>,
+    /// Messages received while the main loop was waiting for a response
+    deferred_messages: Mutex<VecDeque<Message>>,
+    /// Thread running the main loop
+    main_thread: Mutex<Option<ThreadId>>,
 }
 
+/// Language server communicating with a single client, over any of the supported [`Transport`]s
 pub struct LspStdioServer {
     inner: InnerLspStdioServer,
 }
 
 impl<'a> LspStdioServer {
-    pub fn new() -> Rc<LspStdioServer> {
-        let (connection, io_threads) = Connection::stdio();
-        let client = Rc::new(RefCell::new(Client::new()));
-        Rc::new_cyclic(|me| LspStdioServer {
-            inner: InnerLspStdioServer {
-                connection,
-                io_threads,
-                client,
-                self_ref: me.clone(),
-            },
+    /// Creates a server communicating over the standard input and output
+    pub fn new() -> Arc<LspStdioServer> {
+        let (connection, io) = Transport::Stdio.connection().unwrap();
+        Self::from_connection(connection, io)
+    }
+
+    /// Creates a server communicating over the given transport.
+    /// Blocks until the client is connected.
+    pub fn with_transport(transport: Transport) -> io::Result<Arc<LspStdioServer>> {
+        let (connection, io) = transport.connection()?;
+        Ok(Self::from_connection(connection, io))
+    }
+
+    /// Creates a server communicating over a TCP stream accepted by the caller
+    pub fn from_tcp_stream(stream: TcpStream) -> io::Result<Arc<LspStdioServer>> {
+        let (connection, io) = tcp_connection(stream)?;
+        Ok(Self::from_connection(connection, io))
+    }
+
+    fn from_connection(connection: Connection, io: Io) -> Arc<LspStdioServer> {
+        Arc::new_cyclic(|me: &Weak<LspStdioServer>| {
+            let server: Weak<dyn LspServer> = me.clone();
+            LspStdioServer {
+                inner: InnerLspStdioServer {
+                    connection,
+                    io,
+                    client: Client::new(server),
+                    pending_responses: Mutex::new(HashMap::new()),
+                    deferred_messages: Mutex::new(VecDeque::new()),
+                    main_thread: Mutex::new(None),
+                },
+            }
         })
     }
 
@@ -75,24 +112,24 @@ impl<'a> LspStdioServer {
         Ok(())
     }
 
-    pub fn serve<S: LanguageServer, F>(this: Rc<Self>, init: F) -> Result<(), Box<dyn Error>>
+    pub fn serve<S: LanguageServer, F>(this: Arc<Self>, init: F) -> Result<(), Box<dyn Error>>
     where
-        F: FnOnce(Rc<RefCell<Client>>) -> S + 'a,
+        F: FnOnce(Client) -> S + 'a,
     {
-        this.inner
-            .client
-            .borrow_mut()
-            .set_server(this.inner.self_ref.clone());
+        *this.inner.main_thread.lock().unwrap() = Some(thread::current().id());
         let client = this.inner.client.clone();
         let service = LspService::new(client, init);
         this.run_initialization(&service)?;
         this.serve_loop(service)?;
-        Rc::into_inner(this).unwrap().inner.io_threads.join()?;
+        let inner = Arc::into_inner(this).unwrap().inner;
+        // The writer thread stops once every sender of the connection is dropped
+        drop(inner.connection);
+        inner.io.join()?;
         Ok(())
     }
 
     fn serve_loop<S: LanguageServer>(&self, service: LspService<S>) -> Result<(), Box<dyn Error>> {
-        for msg in &self.inner.connection.receiver {
+        while let Some(msg) = self.next_message() {
             match msg {
                 Message::Request(req) => {
                     let id = req.id.clone();
@@ -128,7 +165,7 @@ impl<'a> LspStdioServer {
                     continue;
                 }
                 Message::Response(resp) => {
-                    eprintln!("got response: {resp:?}");
+                    self.dispatch_response(resp);
                 }
                 Message::Notification(not) => {
                     let status = service.call_notification(&not.method, not.params);
@@ -142,6 +179,26 @@ impl<'a> LspStdioServer {
         eprintln!("Out of loop");
         Ok(())
     }
+
+    fn next_message(&self) -> Option<Message> {
+        let deferred = self.inner.deferred_messages.lock().unwrap().pop_front();
+        deferred.or_else(|| self.inner.connection.receiver.recv().ok())
+    }
+
+    fn dispatch_response(&self, resp: Response) {
+        let sender = self
+            .inner
+            .pending_responses
+            .lock()
+            .unwrap()
+            .remove(&resp.id);
+        match sender {
+            Some(sender) => {
+                let _ = sender.send(resp);
+            }
+            None => eprintln!("got unexpected response: {resp:?}"),
+        }
+    }
 }
 
 impl LspServer for LspStdioServer {
@@ -150,4 +207,32 @@ impl LspServer for LspStdioServer {
             eprintln!("Error: {}", e);
         });
     }
+
+    fn send_request(&self, request: Request) -> Option<Response> {
+        let (sender, receiver) = mpsc::channel();
+        self.inner
+            .pending_responses
+            .lock()
+            .unwrap()
+            .insert(request.id.clone(), sender);
+        self.send(Message::Request(request));
+
+        let on_main_thread =
+            *self.inner.main_thread.lock().unwrap() == Some(thread::current().id());
+        if !on_main_thread {
+            return receiver.recv().ok();
+        }
+        // The main loop cannot dispatch the response while it is blocked here,
+        // so messages are read until the response arrives and the other ones are deferred
+        loop {
+            if let Ok(resp) = receiver.try_recv() {
+                return Some(resp);
+            }
+            match self.inner.connection.receiver.recv() {
+                Ok(Message::Response(resp)) => self.dispatch_response(resp),
+                Ok(msg) => self.inner.deferred_messages.lock().unwrap().push_back(msg),
+                Err(_) => return None,
+            }
+        }
+    }
 }
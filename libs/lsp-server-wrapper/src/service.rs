@@ -5,9 +5,7 @@ pub use crate::client::Client;
 use crate::{jsonrpc, LanguageServer};
 use lsp_types::request::*;
 use lsp_types::*;
-use std::cell::RefCell;
 use std::error::Error;
-use std::rc::Rc;
 use std::sync::Arc;
 
 pub(super) struct InnerService<S> {
@@ -21,9 +19,9 @@ pub struct LspService<S> {
 }
 
 impl<S: LanguageServer> LspService<S> {
-    pub fn new<F>(client: Rc<RefCell<Client>>, init: F) -> Self
+    pub fn new<F>(client: Client, init: F) -> Self
     where
-        F: FnOnce(Rc<RefCell<Client>>) -> S,
+        F: FnOnce(Client) -> S,
     {
         let backend = init(client);
         LspService {
@@ -77,12 +75,12 @@ impl<S: LanguageServer> LspService<S> {
                 let params: CallHierarchyPrepareParams = serde_json::from_value(params).unwrap();
                 serde_json::to_value(self.inner.backend.prepare_call_hierarchy(params)?)
             }
-            "textDocument/incomingCalls" => {
+            "callHierarchy/incomingCalls" => {
                 let params: CallHierarchyIncomingCallsParams =
                     serde_json::from_value(params).unwrap();
                 serde_json::to_value(self.inner.backend.incoming_calls(params)?)
             }
-            "textDocument/outgoingCalls" => {
+            "callHierarchy/outgoingCalls" => {
                 let params: CallHierarchyOutgoingCallsParams =
                     serde_json::from_value(params).unwrap();
                 serde_json::to_value(self.inner.backend.outgoing_calls(params)?)
@@ -91,11 +89,11 @@ impl<S: LanguageServer> LspService<S> {
                 let params: TypeHierarchyPrepareParams = serde_json::from_value(params).unwrap();
                 serde_json::to_value(self.inner.backend.prepare_type_hierarchy(params)?)
             }
-            "textDocument/supertypes" => {
+            "typeHierarchy/supertypes" => {
                 let params: TypeHierarchySupertypesParams = serde_json::from_value(params).unwrap();
                 serde_json::to_value(self.inner.backend.supertypes(params)?)
             }
-            "textDocument/subtypes" => {
+            "typeHierarchy/subtypes" => {
                 let params: TypeHierarchySubtypesParams = serde_json::from_value(params).unwrap();
                 serde_json::to_value(self.inner.backend.subtypes(params)?)
             }
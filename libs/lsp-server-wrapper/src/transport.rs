@@ -0,0 +1,182 @@
+use std::io::{self, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::thread;
+
+use crossbeam_channel::bounded;
+use lsp_server::{Connection, IoThreads, Message};
+
+/// Threads reading and writing the messages of a connection
+pub(crate) enum Io {
+    /// Threads created by `lsp_server`
+    Server(IoThreads),
+    /// Threads created for a stream `lsp_server` does not support
+    Stream {
+        reader: thread::JoinHandle<io::Result<()>>,
+        writer: thread::JoinHandle<io::Result<()>>,
+    },
+}
+
+impl Io {
+    pub(crate) fn join(self) -> io::Result<()> {
+        match self {
+            Io::Server(io_threads) => io_threads.join(),
+            Io::Stream { reader, writer } => {
+                match reader.join() {
+                    Ok(r) => r?,
+                    Err(err) => std::panic::panic_any(err),
+                }
+                match writer.join() {
+                    Ok(r) => r,
+                    Err(err) => std::panic::panic_any(err),
+                }
+            }
+        }
+    }
+}
+
+/// Transport used to communicate with the client
+pub enum Transport<'a> {
+    /// Standard input and output
+    Stdio,
+    /// Listens on a TCP address and waits for the client to connect
+    Listen(&'a str),
+    /// Connects to a TCP address on which the client listens
+    Connect(&'a str),
+    /// Connects to a named pipe on Windows, or to a Unix domain socket, on which the client listens
+    Pipe(&'a Path),
+}
+
+impl<'a> Transport<'a> {
+    pub(crate) fn connection(&self) -> io::Result<(Connection, Io)> {
+        match self {
+            Transport::Stdio => {
+                let (connection, io_threads) = Connection::stdio();
+                Ok((connection, Io::Server(io_threads)))
+            }
+            Transport::Listen(addr) => {
+                let (connection, io_threads) = Connection::listen(addr)?;
+                Ok((connection, Io::Server(io_threads)))
+            }
+            Transport::Connect(addr) => {
+                let (connection, io_threads) = Connection::connect(addr)?;
+                Ok((connection, Io::Server(io_threads)))
+            }
+            Transport::Pipe(path) => pipe_connection(path),
+        }
+    }
+}
+
+/// Creates a connection from a TCP stream accepted by the caller,
+/// which allows to serve several clients from the same listener
+pub(crate) fn tcp_connection(stream: TcpStream) -> io::Result<(Connection, Io)> {
+    Ok(stream_connection(stream.try_clone()?, stream))
+}
+
+#[cfg(unix)]
+fn pipe_connection(path: &Path) -> io::Result<(Connection, Io)> {
+    let stream = std::os::unix::net::UnixStream::connect(path)?;
+    Ok(stream_connection(stream.try_clone()?, stream))
+}
+
+#[cfg(windows)]
+fn pipe_connection(path: &Path) -> io::Result<(Connection, Io)> {
+    let pipe = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)?;
+    Ok(stream_connection(pipe.try_clone()?, pipe))
+}
+
+fn stream_connection<R, W>(reader: R, mut writer: W) -> (Connection, Io)
+where
+    R: Read + Send + 'static,
+    W: Write + Send + 'static,
+{
+    let (reader_sender, reader_receiver) = bounded::<Message>(0);
+    let reader = thread::spawn(move || {
+        let mut buf_read = BufReader::new(reader);
+        while let Some(msg) = Message::read(&mut buf_read)? {
+            let is_exit = matches!(&msg, Message::Notification(n) if n.method == "exit");
+            if reader_sender.send(msg).is_err() || is_exit {
+                break;
+            }
+        }
+        Ok(())
+    });
+
+    let (writer_sender, writer_receiver) = bounded::<Message>(0);
+    let writer = thread::spawn(move || {
+        writer_receiver
+            .into_iter()
+            .try_for_each(|msg| msg.write(&mut writer))
+    });
+
+    (
+        Connection {
+            sender: writer_sender,
+            receiver: reader_receiver,
+        },
+        Io::Stream { reader, writer },
+    )
+}
+
+/// Parses an address given either as a port or as a full socket address
+pub fn socket_addr(addr: &str) -> io::Result<std::net::SocketAddr> {
+    let addr = match addr.parse::<u16>() {
+        Ok(port) => format!("127.0.0.1:{}", port),
+        Err(_) => addr.to_string(),
+    };
+    addr.to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid socket address"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_server::Notification;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_socket_addr_from_port() {
+        assert_eq!(
+            socket_addr("9257").unwrap(),
+            "127.0.0.1:9257".parse().unwrap()
+        );
+        assert_eq!(
+            socket_addr("0.0.0.0:9257").unwrap(),
+            "0.0.0.0:9257".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_tcp_connection_exchanges_messages() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            Message::Notification(Notification::new("initialized".to_string(), ()))
+                .write(&mut stream)
+                .unwrap();
+            Message::read(&mut BufReader::new(stream)).unwrap()
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let (connection, io) = tcp_connection(stream).unwrap();
+        let msg = connection.receiver.recv().unwrap();
+        assert!(matches!(&msg, Message::Notification(n) if n.method == "initialized"));
+        connection
+            .sender
+            .send(Message::Notification(Notification::new(
+                "exit".to_string(),
+                (),
+            )))
+            .unwrap();
+
+        let msg = client.join().unwrap().unwrap();
+        assert!(matches!(&msg, Message::Notification(n) if n.method == "exit"));
+        drop(connection);
+        io.join().unwrap();
+    }
+}
@@ -0,0 +1,132 @@
+use crate::config::QuoteStyle;
+use osmium_libs_solidity_ast_extractor::scanner::{self, LexemeKind};
+use osmium_libs_solidity_ast_extractor::LineColumn;
+
+/// A comment of the source, which the tokenizer either drops or turns into a doc attribute
+#[derive(Debug, Clone)]
+pub(crate) struct Comment {
+    pub start: LineColumn,
+    pub end: LineColumn,
+    pub text: String,
+    /// Whether only whitespace precedes it on its line
+    pub own_line: bool,
+}
+
+/// Comments and string literals found by scanning the source text
+pub(crate) struct Trivia {
+    pub comments: Vec<Comment>,
+    comment_offsets: Vec<(usize, usize)>,
+    /// Byte ranges of the string literals, including their quotes
+    strings: Vec<(usize, usize)>,
+}
+
+pub(crate) fn scan(content: &str) -> Trivia {
+    let mut trivia = Trivia {
+        comments: Vec::new(),
+        comment_offsets: Vec::new(),
+        strings: Vec::new(),
+    };
+    // Line of the last code character, comments excluded
+    let mut code_line = 0;
+    let mut last = 0;
+
+    for lexeme in scanner::scan(content) {
+        let gap = &content[last..lexeme.start];
+        if let Some(offset) = gap.rfind(|c: char| !c.is_whitespace()) {
+            code_line = lexeme.start_position.line - gap[offset..].matches('\n').count();
+        }
+        last = lexeme.end;
+        if let LexemeKind::String(_) = lexeme.kind {
+            trivia.strings.push((lexeme.start, lexeme.end));
+            code_line = lexeme.end_position.line;
+            continue;
+        }
+        trivia.comments.push(Comment {
+            start: lexeme.start_position,
+            end: lexeme.end_position,
+            text: lexeme.text(content).trim_end().to_string(),
+            own_line: code_line != lexeme.start_position.line,
+        });
+        trivia.comment_offsets.push((lexeme.start, lexeme.end));
+    }
+    trivia
+}
+
+impl Trivia {
+    /// Replaces the comments by whitespace, keeping the line breaks
+    pub fn strip_comments(&self, content: &str) -> String {
+        let mut res = String::with_capacity(content.len());
+        let mut last = 0;
+        for (start, end) in &self.comment_offsets {
+            res.push_str(&content[last..*start]);
+            res.extend(
+                content[*start..*end]
+                    .chars()
+                    .map(|c| if c == '\n' { '\n' } else { ' ' }),
+            );
+            last = *end;
+        }
+        res.push_str(&content[last..]);
+        res
+    }
+
+    /// Source text of the string literals, in order
+    pub fn strings<'c>(&self, content: &'c str) -> Vec<&'c str> {
+        self.strings
+            .iter()
+            .map(|(start, end)| &content[*start..*end])
+            .collect()
+    }
+
+    /// Replaces the string literals, in order
+    pub fn replace_strings(&self, content: &str, strings: Vec<String>) -> String {
+        let mut res = String::with_capacity(content.len());
+        let mut last = 0;
+        for ((start, end), replacement) in self.strings.iter().zip(strings) {
+            res.push_str(&content[last..*start]);
+            res.push_str(&replacement);
+            last = *end;
+        }
+        res.push_str(&content[last..]);
+        res
+    }
+}
+
+/// Rewrites a string literal with the quotes of a style
+pub(crate) fn requote(literal: &str, style: QuoteStyle) -> String {
+    let quote = match style {
+        QuoteStyle::Double => '"',
+        QuoteStyle::Single => '\'',
+        QuoteStyle::Preserve => return literal.to_string(),
+    };
+    let mut chars = literal.chars();
+    let Some(original) = chars.next() else {
+        return literal.to_string();
+    };
+    if original == quote || !literal.ends_with(original) || literal.len() < 2 {
+        return literal.to_string();
+    }
+    let inner = &literal[1..literal.len() - 1];
+    let mut res = String::with_capacity(literal.len() + 2);
+    res.push(quote);
+    let mut inner = inner.chars();
+    while let Some(c) = inner.next() {
+        match c {
+            '\\' => match inner.next() {
+                Some(escaped) if escaped == original => res.push(escaped),
+                Some(escaped) => {
+                    res.push('\\');
+                    res.push(escaped);
+                }
+                None => res.push('\\'),
+            },
+            c if c == quote => {
+                res.push('\\');
+                res.push(c);
+            }
+            c => res.push(c),
+        }
+    }
+    res.push(quote);
+    res
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+/// Id of the linter rule whose limit is used as the default line length
+const MAX_LINE_LENGTH_RULE: &str = "max-line-length";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteStyle {
+    Double,
+    Single,
+    /// Keeps the quotes of the source
+    Preserve,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct FormatterConfig {
+    pub line_length: usize,
+    pub tab_width: usize,
+    /// Adds spaces inside the braces of imports, named arguments and call options
+    pub bracket_spacing: bool,
+    pub quote_style: QuoteStyle,
+}
+
+impl Default for FormatterConfig {
+    fn default() -> Self {
+        FormatterConfig {
+            line_length: 120,
+            tab_width: 4,
+            bracket_spacing: false,
+            quote_style: QuoteStyle::Double,
+        }
+    }
+}
+
+impl FormatterConfig {
+    /// Reads the `formatter` section of a `.solidhunter.json` file.
+    /// Without an explicit line length, the limit of the `max-line-length` rule is used
+    /// so that formatted files do not get reported by the linter.
+    pub fn from_rules_file(path: &Path) -> FormatterConfig {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+            .map(|rules| FormatterConfig::from_rules(&rules))
+            .unwrap_or_default()
+    }
+
+    pub fn from_rules(rules: &Value) -> FormatterConfig {
+        let section = rules.get("formatter");
+        let mut config: FormatterConfig = section
+            .and_then(|section| serde_json::from_value(section.clone()).ok())
+            .unwrap_or_default();
+
+        let explicit_length = section.is_some_and(|section| section.get("line_length").is_some());
+        if !explicit_length {
+            let max_line_length = rules
+                .get("rules")
+                .and_then(Value::as_array)
+                .and_then(|rules| {
+                    rules.iter().find(|rule| {
+                        rule.get("id").and_then(Value::as_str) == Some(MAX_LINE_LENGTH_RULE)
+                    })
+                })
+                .and_then(|rule| rule.get("data"))
+                .and_then(Value::as_u64);
+            if let Some(max_line_length) = max_line_length {
+                config.line_length = max_line_length as usize;
+            }
+        }
+        config
+    }
+}
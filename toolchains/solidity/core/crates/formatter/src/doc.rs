@@ -0,0 +1,225 @@
+/// Layout of a piece of code, in which line breaks are chosen by the renderer
+#[derive(Debug, Clone)]
+pub(crate) enum Doc {
+    Text(String),
+    /// A space, or a line break when the enclosing group does not fit on the line
+    Line,
+    /// Nothing, or a line break when the enclosing group does not fit on the line
+    SoftLine,
+    /// A line break, which breaks every enclosing group
+    HardLine,
+    Indent(Box<Doc>),
+    Group(Box<Doc>),
+    Concat(Vec<Doc>),
+    /// The first alternative whose lines all fit without breaking its groups, or the last one
+    Choice(Vec<Doc>),
+}
+
+pub(crate) fn text(text: impl Into<String>) -> Doc {
+    Doc::Text(text.into())
+}
+
+pub(crate) fn concat(docs: Vec<Doc>) -> Doc {
+    Doc::Concat(docs)
+}
+
+pub(crate) fn group(doc: Doc) -> Doc {
+    Doc::Group(Box::new(doc))
+}
+
+pub(crate) fn indent(doc: Doc) -> Doc {
+    Doc::Indent(Box::new(doc))
+}
+
+/// Joins documents with a separator, such as `,` followed by a line
+pub(crate) fn join(docs: Vec<Doc>, separator: Vec<Doc>) -> Doc {
+    let mut res = Vec::with_capacity(docs.len() * (separator.len() + 1));
+    for (index, doc) in docs.into_iter().enumerate() {
+        if index > 0 {
+            res.extend(separator.iter().cloned());
+        }
+        res.push(doc);
+    }
+    Doc::Concat(res)
+}
+
+/// A list between delimiters, with one element per line when it does not fit
+pub(crate) fn delimited(open: &str, docs: Vec<Doc>, close: &str, spacing: bool) -> Doc {
+    if docs.is_empty() {
+        return text(format!("{}{}", open, close));
+    }
+    let line = if spacing { Doc::Line } else { Doc::SoftLine };
+    group(concat(vec![
+        text(open),
+        indent(concat(vec![
+            line.clone(),
+            join(docs, vec![text(","), Doc::Line]),
+        ])),
+        line,
+        text(close),
+    ]))
+}
+
+impl Doc {
+    fn has_hard_line(&self) -> bool {
+        match self {
+            Doc::HardLine => true,
+            Doc::Text(_) | Doc::Line | Doc::SoftLine => false,
+            Doc::Indent(doc) | Doc::Group(doc) => doc.has_hard_line(),
+            Doc::Concat(docs) => docs.iter().any(Doc::has_hard_line),
+            Doc::Choice(docs) => docs.first().is_some_and(Doc::has_hard_line),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+type Command<'a> = (usize, Mode, &'a Doc);
+
+pub(crate) struct Renderer<'a> {
+    width: usize,
+    indent_unit: &'a str,
+    out: String,
+    column: usize,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(width: usize, indent_unit: &'a str) -> Self {
+        Renderer {
+            width,
+            indent_unit,
+            out: String::new(),
+            column: 0,
+        }
+    }
+
+    pub fn render(mut self, doc: &Doc) -> String {
+        self.print(vec![(0, Mode::Break, doc)]);
+        self.out
+    }
+
+    fn newline(&mut self, level: usize) {
+        let trimmed = self.out.trim_end_matches([' ', '\t']).len();
+        self.out.truncate(trimmed);
+        self.out.push('\n');
+        for _ in 0..level {
+            self.out.push_str(self.indent_unit);
+        }
+        self.column = level * self.indent_unit.chars().count();
+    }
+
+    fn print(&mut self, mut commands: Vec<Command>) {
+        while let Some((level, mode, doc)) = commands.pop() {
+            match doc {
+                Doc::Text(text) => {
+                    self.out.push_str(text);
+                    self.column += text.chars().count();
+                }
+                Doc::Line | Doc::SoftLine if mode == Mode::Flat => {
+                    if let Doc::Line = doc {
+                        self.out.push(' ');
+                        self.column += 1;
+                    }
+                }
+                Doc::Line | Doc::SoftLine | Doc::HardLine => self.newline(level),
+                Doc::Indent(doc) => commands.push((level + 1, mode, doc)),
+                Doc::Concat(docs) => {
+                    commands.extend(docs.iter().rev().map(|doc| (level, mode, doc)));
+                }
+                Doc::Group(inner) => {
+                    let mode = if mode == Mode::Flat
+                        || (!inner.has_hard_line()
+                            && self.fits((level, Mode::Flat, inner), &commands))
+                    {
+                        Mode::Flat
+                    } else {
+                        Mode::Break
+                    };
+                    commands.push((level, mode, inner));
+                }
+                Doc::Choice(docs) => {
+                    // Only the last alternative may break its groups
+                    let chosen = match mode {
+                        Mode::Flat => docs.first().map(|doc| (Mode::Flat, doc)),
+                        Mode::Break => docs.split_last().and_then(|(last, alternatives)| {
+                            alternatives
+                                .iter()
+                                .find(|doc| self.choice_fits(level, doc, &commands))
+                                .map(|doc| (Mode::Flat, doc))
+                                .or(Some((Mode::Break, last)))
+                        }),
+                    };
+                    if let Some((mode, doc)) = chosen {
+                        commands.push((level, mode, doc));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether a document fits on the rest of the line, until the next possible line break
+    fn fits(&self, next: Command, rest: &[Command]) -> bool {
+        let mut remaining = self.width as isize - self.column as isize;
+        let mut stack = vec![next];
+        let mut rest_index = rest.len();
+
+        loop {
+            let (level, mode, doc) = match stack.pop() {
+                Some(command) => command,
+                None => {
+                    if rest_index == 0 {
+                        return true;
+                    }
+                    rest_index -= 1;
+                    rest[rest_index]
+                }
+            };
+            match doc {
+                Doc::Text(text) => remaining -= text.chars().count() as isize,
+                Doc::Line if mode == Mode::Flat => remaining -= 1,
+                Doc::SoftLine if mode == Mode::Flat => {}
+                Doc::Line | Doc::SoftLine | Doc::HardLine => return true,
+                Doc::Indent(doc) => stack.push((level + 1, mode, doc)),
+                Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (level, mode, doc))),
+                Doc::Group(doc) => {
+                    let mode = if doc.has_hard_line() {
+                        Mode::Break
+                    } else {
+                        mode
+                    };
+                    stack.push((level, mode, doc));
+                }
+                Doc::Choice(docs) => {
+                    if let Some(doc) = docs.first() {
+                        stack.push((level, mode, doc));
+                    }
+                }
+            }
+            if remaining < 0 {
+                return false;
+            }
+        }
+    }
+
+    /// Whether every line of an alternative fits, as well as the rest of its last line
+    fn choice_fits(&self, level: usize, doc: &Doc, rest: &[Command]) -> bool {
+        let mut renderer = Renderer {
+            width: self.width,
+            indent_unit: self.indent_unit,
+            out: String::new(),
+            column: self.column,
+        };
+        renderer.print(vec![(level, Mode::Flat, doc)]);
+        let first_column = self.column;
+        let fits = renderer.out.lines().enumerate().all(|(index, line)| {
+            let start = if index == 0 { first_column } else { 0 };
+            start + line.chars().count() <= self.width
+        });
+        let empty = Doc::Text(String::new());
+        fits && renderer.fits((level, Mode::Flat, &empty), rest)
+    }
+}
@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum FormatError {
+    #[error("FormatError: Ast Extractor error occured")]
+    AstError(#[from] osmium_libs_solidity_ast_extractor::errors::ExtractError),
+    #[error("FormatError: Formatting would change the code, the file is left unformatted")]
+    Unstable,
+}
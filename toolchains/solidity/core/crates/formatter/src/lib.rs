@@ -0,0 +1,149 @@
+mod comments;
+pub mod config;
+mod doc;
+pub mod errors;
+mod printer;
+
+use comments::{requote, scan};
+use config::FormatterConfig;
+use doc::Renderer;
+use errors::FormatError;
+use osmium_libs_solidity_ast_extractor::extract::{extract_ast_from_content, tokenize};
+use printer::Printer;
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use similar::{DiffOp, TextDiff};
+
+/// Formats a Solidity source file following the Solidity style guide
+pub fn format(content: &str, config: &FormatterConfig) -> Result<String, FormatError> {
+    let file = extract_ast_from_content(content)?;
+    let comments = scan(content).comments;
+    let mut printer = Printer::new(content, config, comments);
+    let doc = printer.file(&file);
+    let indent_unit = " ".repeat(config.tab_width);
+    let formatted = Renderer::new(config.line_length, &indent_unit).render(&doc);
+
+    check_unchanged(content, &formatted)?;
+
+    let output_trivia = scan(&formatted);
+    let strings = output_trivia
+        .strings(&formatted)
+        .into_iter()
+        .map(|string| requote(string, config.quote_style))
+        .collect();
+    Ok(output_trivia.replace_strings(&formatted, strings))
+}
+
+/// Ensures that the formatting only changed the layout of the code and kept its comments
+fn check_unchanged(original: &str, formatted: &str) -> Result<(), FormatError> {
+    let original_trivia = scan(original);
+    let formatted_trivia = scan(formatted);
+
+    let comments = |trivia: &comments::Trivia| -> Vec<String> {
+        trivia
+            .comments
+            .iter()
+            .map(|comment| comment.text.split_whitespace().collect())
+            .collect()
+    };
+    if comments(&original_trivia) != comments(&formatted_trivia) {
+        return Err(FormatError::Unstable);
+    }
+
+    // Attributes get reordered, so the tokens are compared regardless of their order
+    let tokens = |content: &str| -> Option<Vec<String>> {
+        let stream = tokenize(content).ok()?;
+        let mut tokens = Vec::new();
+        flatten(stream, &mut tokens);
+        tokens.sort();
+        Some(tokens)
+    };
+    let original_tokens = tokens(&original_trivia.strip_comments(original));
+    let formatted_tokens = tokens(&formatted_trivia.strip_comments(formatted));
+    if original_tokens.is_none() || original_tokens != formatted_tokens {
+        return Err(FormatError::Unstable);
+    }
+    extract_ast_from_content(formatted).map_err(|_| FormatError::Unstable)?;
+    Ok(())
+}
+
+fn flatten(stream: TokenStream, tokens: &mut Vec<String>) {
+    for token in stream {
+        match token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => ("", ""),
+                };
+                tokens.push(open.to_string());
+                flatten(group.stream(), tokens);
+                tokens.push(close.to_string());
+            }
+            token => tokens.push(token.to_string()),
+        }
+    }
+}
+
+/// Replacement of a range of lines, whose end is exclusive and which are indexed from 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineEdit {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub new_text: String,
+}
+
+/// Line edits turning a file into its formatted version
+pub fn edits(original: &str, formatted: &str) -> Vec<LineEdit> {
+    let diff = TextDiff::from_lines(original, formatted);
+    let new_lines: Vec<&str> = diff.new_slices().to_vec();
+    diff.ops()
+        .iter()
+        .filter_map(|op| {
+            let (start_line, end_line, new_range) = match *op {
+                DiffOp::Equal { .. } => return None,
+                DiffOp::Delete {
+                    old_index,
+                    old_len,
+                    new_index,
+                } => (old_index, old_index + old_len, new_index..new_index),
+                DiffOp::Insert {
+                    old_index,
+                    new_index,
+                    new_len,
+                } => (old_index, old_index, new_index..new_index + new_len),
+                DiffOp::Replace {
+                    old_index,
+                    old_len,
+                    new_index,
+                    new_len,
+                } => (
+                    old_index,
+                    old_index + old_len,
+                    new_index..new_index + new_len,
+                ),
+            };
+            Some(LineEdit {
+                start_line,
+                end_line,
+                new_text: new_lines[new_range].concat(),
+            })
+        })
+        .collect()
+}
+
+/// Line edits formatting the lines of a range, which are indexed from 0 and inclusive
+pub fn format_range(
+    content: &str,
+    start_line: usize,
+    end_line: usize,
+    config: &FormatterConfig,
+) -> Result<Vec<LineEdit>, FormatError> {
+    let formatted = format(content, config)?;
+    Ok(edits(content, &formatted)
+        .into_iter()
+        .filter(|edit| {
+            edit.start_line <= end_line && edit.end_line.max(edit.start_line + 1) > start_line
+        })
+        .collect())
+}
@@ -0,0 +1,361 @@
+mod expressions;
+mod items;
+mod statements;
+
+use crate::comments::Comment;
+use crate::config::FormatterConfig;
+use crate::doc::*;
+use osmium_libs_solidity_ast_extractor::{
+    FunctionBody, Item, LineColumn, Spanned, Stmt, VariableDeclaration, Variant,
+};
+
+/// A node printed on its own lines, with the comments which precede it
+pub(crate) trait Node {
+    fn start(&self) -> LineColumn;
+    fn end(&self) -> LineColumn;
+    /// Comments cannot be placed back before this position, so a node with such comments is kept as is
+    fn header_end(&self) -> LineColumn {
+        self.start()
+    }
+}
+
+impl Node for Item {
+    fn start(&self) -> LineColumn {
+        match self {
+            Item::Contract(contract) => contract.kind.span().start(),
+            Item::Enum(enumm) => enumm.enum_token.span().start(),
+            Item::Error(error) => error.error_token.span().start(),
+            Item::Event(event) => event.event_token.span().start(),
+            Item::Function(function) => function.kind.span().start(),
+            Item::Import(import) => import.import_token.span().start(),
+            Item::Pragma(pragma) => pragma.pragma_token.span().start(),
+            Item::Struct(strukt) => strukt.struct_token.span().start(),
+            Item::Udt(udt) => udt.type_token.span().start(),
+            Item::Using(using) => using.using_token.span().start(),
+            Item::Variable(variable) => variable.ty.span().start(),
+        }
+    }
+
+    fn end(&self) -> LineColumn {
+        match self {
+            Item::Contract(contract) => contract.brace_token.span.close().end(),
+            Item::Enum(enumm) => enumm.brace_token.span.close().end(),
+            Item::Error(error) => error.semi_token.span().end(),
+            Item::Event(event) => event.semi_token.span().end(),
+            Item::Function(function) => match &function.body {
+                FunctionBody::Block(block) => block.brace_token.span.close().end(),
+                FunctionBody::Empty(semi) => semi.span().end(),
+            },
+            Item::Import(import) => import.semi_token.span().end(),
+            Item::Pragma(pragma) => pragma.semi_token.span().end(),
+            Item::Struct(strukt) => strukt.brace_token.span.close().end(),
+            Item::Udt(udt) => udt.semi_token.span().end(),
+            Item::Using(using) => using.semi_token.span().end(),
+            Item::Variable(variable) => variable.semi_token.span().end(),
+        }
+    }
+
+    fn header_end(&self) -> LineColumn {
+        match self {
+            Item::Contract(contract) => contract.brace_token.span.open().start(),
+            Item::Enum(enumm) => enumm.brace_token.span.open().start(),
+            Item::Struct(strukt) => strukt.brace_token.span.open().start(),
+            Item::Function(function) => match &function.body {
+                FunctionBody::Block(block) => block.brace_token.span.open().start(),
+                FunctionBody::Empty(semi) => semi.span().end(),
+            },
+            _ => self.start(),
+        }
+    }
+}
+
+impl Node for Stmt {
+    fn start(&self) -> LineColumn {
+        self.span().start()
+    }
+
+    fn end(&self) -> LineColumn {
+        self.span().end()
+    }
+
+    fn header_end(&self) -> LineColumn {
+        match self {
+            Stmt::Block(block) => block.brace_token.span.open().start(),
+            Stmt::If(stmt) => stmt.then_branch.start(),
+            Stmt::For(stmt) => stmt.body.start(),
+            Stmt::While(stmt) => stmt.body.start(),
+            Stmt::DoWhile(stmt) => stmt.body.start(),
+            Stmt::Try(stmt) => stmt.block.brace_token.span.open().start(),
+            Stmt::UncheckedBlock(stmt) => stmt.block.brace_token.span.open().start(),
+            _ => self.start(),
+        }
+    }
+}
+
+impl Node for VariableDeclaration {
+    fn start(&self) -> LineColumn {
+        self.ty.span().start()
+    }
+
+    fn end(&self) -> LineColumn {
+        self.span().end()
+    }
+}
+
+impl Node for Variant {
+    fn start(&self) -> LineColumn {
+        self.ident.span().start()
+    }
+
+    fn end(&self) -> LineColumn {
+        self.ident.span().end()
+    }
+}
+
+pub(crate) struct Printer<'a> {
+    config: &'a FormatterConfig,
+    lines: Vec<&'a str>,
+    comments: Vec<Comment>,
+    next_comment: usize,
+    /// Last source line printed, to preserve the blank lines which follow it
+    last_line: usize,
+}
+
+impl<'a> Printer<'a> {
+    pub fn new(content: &'a str, config: &'a FormatterConfig, comments: Vec<Comment>) -> Self {
+        Printer {
+            config,
+            lines: content.split('\n').collect(),
+            comments,
+            next_comment: 0,
+            last_line: 0,
+        }
+    }
+
+    fn source(&self, start: LineColumn, end: LineColumn) -> String {
+        let mut res = String::new();
+        for line in start.line..=end.line {
+            let Some(text) = self.lines.get(line - 1) else {
+                break;
+            };
+            let from = if line == start.line { start.column } else { 0 };
+            let chars = text.chars().skip(from);
+            if line == end.line {
+                res.extend(chars.take(end.column.saturating_sub(from)));
+            } else {
+                res.extend(chars);
+                res.push('\n');
+            }
+        }
+        res
+    }
+
+    fn is_blank(&self, line: usize) -> bool {
+        self.lines
+            .get(line.wrapping_sub(1))
+            .is_some_and(|text| text.trim().is_empty())
+    }
+
+    /// Whether the source has a blank line between the last printed line and a line
+    fn blank_line_before(&self, line: usize) -> bool {
+        (self.last_line + 1..line).any(|line| self.is_blank(line))
+    }
+
+    /// Whether a comment lies between two positions
+    fn has_comments(&self, start: LineColumn, end: LineColumn) -> bool {
+        self.comments[self.next_comment..]
+            .iter()
+            .take_while(|comment| comment.start < end)
+            .any(|comment| comment.start >= start)
+    }
+
+    /// Prints the source of a node as is, collapsing its whitespace when it fits on one line
+    fn verbatim(&mut self, start: LineColumn, end: LineColumn) -> Doc {
+        let mut has_comments = false;
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            if comment.start >= end {
+                break;
+            }
+            has_comments = true;
+            self.next_comment += 1;
+        }
+        let source = self.source(start, end);
+        if !has_comments && !source.contains('\n') {
+            return text(source.split_whitespace().collect::<Vec<_>>().join(" "));
+        }
+
+        // Lines keep their indentation relative to the line the node starts on
+        let base = self.lines[start.line - 1]
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .count();
+        let mut docs = Vec::new();
+        for (index, line) in source.split('\n').enumerate() {
+            if index == 0 {
+                docs.push(text(line.trim_end()));
+                continue;
+            }
+            docs.push(Doc::HardLine);
+            let indentation = line.chars().take_while(|c| c.is_whitespace()).count();
+            docs.push(text(
+                line.chars()
+                    .skip(indentation.min(base))
+                    .collect::<String>()
+                    .trim_end(),
+            ));
+        }
+        concat(docs)
+    }
+
+    fn comment(&self, comment: &Comment) -> Doc {
+        if !comment.text.contains('\n') {
+            return text(comment.text.clone());
+        }
+        let mut docs = Vec::new();
+        for (index, line) in comment.text.split('\n').enumerate() {
+            if index > 0 {
+                docs.push(Doc::HardLine);
+                let indentation = line.chars().take_while(|c| c.is_whitespace()).count();
+                docs.push(text(
+                    line.chars()
+                        .skip(indentation.min(comment.start.column))
+                        .collect::<String>()
+                        .trim_end(),
+                ));
+            } else {
+                docs.push(text(line.trim_end()));
+            }
+        }
+        concat(docs)
+    }
+
+    /// Prints the comments found before a position, each on its own lines
+    fn leading_comments(&mut self, before: LineColumn, docs: &mut Vec<Doc>) {
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            if comment.start >= before {
+                break;
+            }
+            let comment = comment.clone();
+            self.next_comment += 1;
+            docs.push(self.comment(&comment));
+            docs.push(Doc::HardLine);
+            if self.blank_line_before_next(comment.end.line, before) {
+                docs.push(Doc::HardLine);
+            }
+            self.last_line = comment.end.line;
+        }
+    }
+
+    /// Whether a blank line follows a comment, before the next comment or the node
+    fn blank_line_before_next(&self, line: usize, before: LineColumn) -> bool {
+        let next_line = match self.comments.get(self.next_comment) {
+            Some(comment) if comment.start < before => comment.start.line,
+            _ => before.line,
+        };
+        (line + 1..next_line).any(|line| self.is_blank(line))
+    }
+
+    /// Prints a comment which follows a node on its last line
+    fn trailing_comment(&mut self, end: LineColumn) -> Option<Doc> {
+        let comment = self.comments.get(self.next_comment)?;
+        if comment.own_line || comment.start.line != end.line || comment.start < end {
+            return None;
+        }
+        if comment.text.contains('\n') {
+            return None;
+        }
+        let doc = text(format!(" {}", comment.text));
+        self.next_comment += 1;
+        Some(doc)
+    }
+
+    /// Prints nodes on their own lines, along with their comments and the blank lines between them.
+    /// `blank_lines` gives the number of blank lines required between two nodes.
+    fn lines<T: Node>(
+        &mut self,
+        nodes: &[T],
+        close: LineColumn,
+        blank_lines: impl Fn(&T, &T) -> usize,
+        mut print: impl FnMut(&mut Self, &T, bool) -> Doc,
+    ) -> Vec<Doc> {
+        let mut docs = Vec::new();
+        for (index, node) in nodes.iter().enumerate() {
+            let first_line = match self.comments.get(self.next_comment) {
+                Some(comment) if comment.start < node.start() => comment.start.line,
+                _ => node.start().line,
+            };
+            if index > 0 {
+                let required = blank_lines(&nodes[index - 1], node);
+                let preserved = usize::from(self.blank_line_before(first_line));
+                docs.push(Doc::HardLine);
+                for _ in 0..required.max(preserved) {
+                    docs.push(Doc::HardLine);
+                }
+            }
+            self.leading_comments(node.start(), &mut docs);
+            if self.has_comments(node.start(), node.header_end()) {
+                docs.push(self.verbatim(node.start(), node.end()));
+            } else {
+                docs.push(print(self, node, index + 1 == nodes.len()));
+            }
+            docs.extend(self.trailing_comment(node.end()));
+            self.last_line = node.end().line;
+        }
+
+        // Comments after the last node
+        if self
+            .comments
+            .get(self.next_comment)
+            .is_some_and(|comment| comment.start < close)
+        {
+            if !docs.is_empty() {
+                docs.push(Doc::HardLine);
+                let line = self.comments[self.next_comment].start.line;
+                if self.blank_line_before(line) {
+                    docs.push(Doc::HardLine);
+                }
+            }
+            self.leading_comments(close, &mut docs);
+            // The last comment is followed by the closing delimiter, not another node
+            while let Some(Doc::HardLine) = docs.last() {
+                docs.pop();
+            }
+        }
+        docs
+    }
+
+    /// Prints nodes between braces, one per line
+    fn braced<T: Node>(
+        &mut self,
+        open: LineColumn,
+        close: LineColumn,
+        nodes: &[T],
+        blank_lines: impl Fn(&T, &T) -> usize,
+        print: impl FnMut(&mut Self, &T, bool) -> Doc,
+    ) -> Doc {
+        let mut docs = vec![text("{")];
+        // A comment after the first node on the same line belongs to the node
+        if nodes
+            .first()
+            .is_none_or(|node| node.start().line != open.line)
+        {
+            docs.extend(self.trailing_comment(open));
+        }
+        self.last_line = open.line;
+        let lines = self.lines(nodes, close, blank_lines, print);
+        if lines.is_empty() {
+            if docs.len() > 1 {
+                docs.push(Doc::HardLine);
+            }
+            docs.push(text("}"));
+            return concat(docs);
+        }
+        docs.push(indent(concat(
+            std::iter::once(Doc::HardLine).chain(lines).collect(),
+        )));
+        docs.push(Doc::HardLine);
+        docs.push(text("}"));
+        self.last_line = close.line;
+        concat(docs)
+    }
+}
@@ -0,0 +1,197 @@
+use super::Printer;
+use crate::doc::*;
+use osmium_libs_solidity_ast_extractor::{
+    ArgList, ArgListImpl, BinOp, Expr, LitStr, NamedArgList, Spanned, Type,
+};
+
+fn is_assignment(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::Assign(..)
+            | BinOp::AddAssign(..)
+            | BinOp::SubAssign(..)
+            | BinOp::MulAssign(..)
+            | BinOp::DivAssign(..)
+            | BinOp::RemAssign(..)
+            | BinOp::BitAndAssign(..)
+            | BinOp::BitOrAssign(..)
+            | BinOp::BitXorAssign(..)
+            | BinOp::SarAssign(..)
+            | BinOp::ShlAssign(..)
+            | BinOp::ShrAssign(..)
+    )
+}
+
+impl<'a> Printer<'a> {
+    pub(super) fn lit_str(&mut self, lit: &LitStr) -> Doc {
+        self.verbatim(lit.span().start(), lit.span().end())
+    }
+
+    pub(super) fn ty(&mut self, ty: &Type) -> Doc {
+        match ty {
+            Type::Array(array) => {
+                let mut docs = vec![self.ty(&array.ty), text("[")];
+                if let Some(size) = &array.size {
+                    docs.push(self.expr(size));
+                }
+                docs.push(text("]"));
+                concat(docs)
+            }
+            Type::Mapping(mapping) => {
+                let mut docs = vec![text("mapping("), self.ty(&mapping.key)];
+                if let Some(name) = &mapping.key_name {
+                    docs.push(text(format!(" {}", name)));
+                }
+                docs.push(text(" => "));
+                docs.push(self.ty(&mapping.value));
+                if let Some(name) = &mapping.value_name {
+                    docs.push(text(format!(" {}", name)));
+                }
+                docs.push(text(")"));
+                concat(docs)
+            }
+            ty => self.verbatim(ty.span().start(), ty.span().end()),
+        }
+    }
+
+    pub(super) fn expr(&mut self, expr: &Expr) -> Doc {
+        match expr {
+            expr if is_chain(expr) => self.binary(expr),
+            Expr::Array(array) => {
+                let elems = array.elems.iter().map(|elem| self.expr(elem)).collect();
+                delimited("[", elems, "]", false)
+            }
+            Expr::Binary(binary) => concat(vec![
+                self.expr(&binary.left),
+                text(format!(" {} ", binary.op)),
+                self.expr(&binary.right),
+            ]),
+            Expr::Call(call) => concat(vec![self.expr(&call.expr), self.args(&call.args)]),
+            Expr::CallOptions(call) => {
+                concat(vec![self.expr(&call.expr), self.named_args(&call.args)])
+            }
+            Expr::Delete(delete) => concat(vec![text("delete "), self.expr(&delete.expr)]),
+            Expr::Index(index) => {
+                let mut docs = vec![self.expr(&index.expr), text("[")];
+                if let Some(start) = &index.start {
+                    docs.push(self.expr(start));
+                }
+                if index.colon_token.is_some() {
+                    docs.push(text(":"));
+                }
+                if let Some(end) = &index.end {
+                    docs.push(self.expr(end));
+                }
+                docs.push(text("]"));
+                concat(docs)
+            }
+            Expr::Member(member) => concat(vec![
+                self.expr(&member.expr),
+                text("."),
+                self.expr(&member.member),
+            ]),
+            Expr::New(new) => concat(vec![text("new "), self.ty(&new.ty)]),
+            Expr::Payable(payable) => concat(vec![text("payable"), self.args(&payable.args)]),
+            Expr::Postfix(postfix) => {
+                concat(vec![self.expr(&postfix.expr), text(postfix.op.to_string())])
+            }
+            Expr::Ternary(ternary) => group(concat(vec![
+                self.expr(&ternary.cond),
+                indent(concat(vec![
+                    Doc::Line,
+                    text("? "),
+                    self.expr(&ternary.if_true),
+                    Doc::Line,
+                    text(": "),
+                    self.expr(&ternary.if_false),
+                ])),
+            ])),
+            Expr::Tuple(tuple) => {
+                let elems = tuple.elems.iter().map(|elem| self.expr(elem)).collect();
+                delimited("(", elems, ")", false)
+            }
+            Expr::Type(ty) => self.ty(ty),
+            Expr::TypeCall(call) => concat(vec![text("type("), self.ty(&call.ty), text(")")]),
+            Expr::Unary(unary) => concat(vec![text(unary.op.to_string()), self.expr(&unary.expr)]),
+            Expr::Ident(_) | Expr::Lit(_) | Expr::LitDenominated(_) => {
+                self.verbatim(expr.span().start(), expr.span().end())
+            }
+        }
+    }
+
+    /// Prints a chain of binary operations, breaking the line before each operand when it does not fit
+    fn binary(&mut self, expr: &Expr) -> Doc {
+        let mut operands = Vec::new();
+        let mut ops = Vec::new();
+        self.flatten_binary(expr, &mut operands, &mut ops);
+        let mut operands = operands.into_iter();
+        let mut docs: Vec<Doc> = operands.next().into_iter().collect();
+        let mut rest = Vec::new();
+        for (op, operand) in ops.into_iter().zip(operands) {
+            rest.push(text(format!(" {}", op)));
+            rest.push(Doc::Line);
+            rest.push(operand);
+        }
+        docs.push(indent(concat(rest)));
+        group(concat(docs))
+    }
+
+    /// Operands and operators of a chain of binary operations, in source order.
+    /// The parser nests the operations following a member access or a unary operator inside it,
+    /// so the prefix is added back to the first operand.
+    fn flatten_binary(&mut self, expr: &Expr, operands: &mut Vec<Doc>, ops: &mut Vec<BinOp>) {
+        let prefix = match expr {
+            Expr::Binary(binary) if !is_assignment(&binary.op) => {
+                self.flatten_binary(&binary.left, operands, ops);
+                ops.push(binary.op);
+                self.flatten_binary(&binary.right, operands, ops);
+                return;
+            }
+            Expr::Member(member) if is_chain(&member.member) => {
+                concat(vec![self.expr(&member.expr), text(".")])
+            }
+            Expr::Unary(unary) if is_chain(&unary.expr) => text(unary.op.to_string()),
+            expr => {
+                operands.push(self.expr(expr));
+                return;
+            }
+        };
+        let first = operands.len();
+        match expr {
+            Expr::Member(member) => self.flatten_binary(&member.member, operands, ops),
+            Expr::Unary(unary) => self.flatten_binary(&unary.expr, operands, ops),
+            _ => unreachable!(),
+        }
+        let operand = std::mem::replace(&mut operands[first], text(""));
+        operands[first] = concat(vec![prefix, operand]);
+    }
+
+    fn args(&mut self, args: &ArgList) -> Doc {
+        match &args.list {
+            ArgListImpl::Unnamed(list) => {
+                let args = list.iter().map(|arg| self.expr(arg)).collect();
+                delimited("(", args, ")", false)
+            }
+            ArgListImpl::Named(list) => concat(vec![text("("), self.named_args(list), text(")")]),
+        }
+    }
+
+    fn named_args(&mut self, args: &NamedArgList) -> Doc {
+        let args = args
+            .list
+            .iter()
+            .map(|arg| concat(vec![text(format!("{}: ", arg.name)), self.expr(&arg.arg)]))
+            .collect();
+        delimited("{", args, "}", self.config.bracket_spacing)
+    }
+}
+
+/// Whether an expression is printed as a chain of binary operations
+fn is_chain(expr: &Expr) -> bool {
+    match expr {
+        Expr::Binary(binary) => !is_assignment(&binary.op),
+        Expr::Member(member) => is_chain(&member.member),
+        Expr::Unary(unary) => is_chain(&unary.expr),
+        _ => false,
+    }
+}
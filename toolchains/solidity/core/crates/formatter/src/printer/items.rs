@@ -0,0 +1,433 @@
+use super::{Node, Printer};
+use crate::doc::*;
+use osmium_libs_solidity_ast_extractor::{
+    EventParameter, File, FunctionAttribute, FunctionBody, FunctionKind, ImportDirective,
+    ImportPath, Item, ItemContract, ItemEnum, ItemError, ItemEvent, ItemFunction, ItemStruct,
+    LineColumn, Modifier, ParameterList, Spanned, UsingDirective, UsingList, UsingType,
+    VariableAttribute, VariableDeclaration, VariableDefinition,
+};
+
+/// Items which the style guide surrounds with blank lines
+fn is_definition(item: &Item) -> bool {
+    match item {
+        Item::Contract(_) => true,
+        Item::Function(function) => matches!(function.body, FunctionBody::Block(_)),
+        _ => false,
+    }
+}
+
+fn is_directive(item: &Item) -> bool {
+    matches!(item, Item::Pragma(_) | Item::Import(_) | Item::Using(_))
+}
+
+/// Two blank lines between top level definitions, and one around them otherwise
+fn file_blank_lines(previous: &Item, next: &Item) -> usize {
+    if !is_definition(previous) && !is_definition(next) {
+        0
+    } else if is_directive(previous) || is_directive(next) {
+        1
+    } else {
+        2
+    }
+}
+
+/// One blank line around the functions of a contract
+fn member_blank_lines(previous: &Item, next: &Item) -> usize {
+    let multiline = |item: &Item| {
+        is_definition(item) || matches!(item, Item::Struct(_) | Item::Enum(_) | Item::Contract(_))
+    };
+    usize::from(multiline(previous) || multiline(next))
+}
+
+impl<'a> Printer<'a> {
+    pub fn file(&mut self, file: &File) -> Doc {
+        let end = LineColumn {
+            line: usize::MAX,
+            column: 0,
+        };
+        let lines = self.lines(&file.items, end, file_blank_lines, |printer, item, _| {
+            printer.item(item)
+        });
+        let mut docs = lines;
+        if !docs.is_empty() {
+            docs.push(Doc::HardLine);
+        }
+        concat(docs)
+    }
+
+    fn item(&mut self, item: &Item) -> Doc {
+        let has_body = matches!(
+            item,
+            Item::Contract(_) | Item::Enum(_) | Item::Function(_) | Item::Struct(_)
+        );
+        // Comments cannot be placed back inside a declaration, so such declarations are kept as is
+        if !has_body && self.has_comments(item.start(), item.end()) {
+            return self.verbatim(item.start(), item.end());
+        }
+
+        match item {
+            Item::Contract(contract) => self.contract(contract),
+            Item::Enum(enumm) => self.enumm(enumm),
+            Item::Error(error) => self.error(error),
+            Item::Event(event) => self.event(event),
+            Item::Function(function) => self.function(function),
+            Item::Import(import) => self.import(import),
+            Item::Pragma(pragma) => self.verbatim(
+                pragma.pragma_token.span().start(),
+                pragma.semi_token.span().end(),
+            ),
+            Item::Struct(strukt) => self.strukt(strukt),
+            Item::Udt(udt) => concat(vec![
+                text(format!("type {} is ", udt.name)),
+                self.ty(&udt.ty),
+                text(";"),
+            ]),
+            Item::Using(using) => self.using(using),
+            Item::Variable(variable) => self.variable(variable),
+        }
+    }
+
+    fn import(&mut self, import: &ImportDirective) -> Doc {
+        let path = match &import.path {
+            ImportPath::Plain(plain) => {
+                let mut docs = vec![self.lit_str(&plain.path)];
+                if let Some(alias) = &plain.alias {
+                    docs.push(text(format!(" as {}", alias.alias)));
+                }
+                concat(docs)
+            }
+            ImportPath::Aliases(aliases) => {
+                let names = aliases
+                    .imports
+                    .iter()
+                    .map(|(name, alias)| match alias {
+                        Some(alias) => text(format!("{} as {}", name, alias.alias)),
+                        None => text(name.to_string()),
+                    })
+                    .collect();
+                concat(vec![
+                    delimited("{", names, "}", self.config.bracket_spacing),
+                    text(" from "),
+                    self.lit_str(&aliases.path),
+                ])
+            }
+            ImportPath::Glob(glob) => {
+                let alias = match &glob.alias {
+                    Some(alias) => format!("* as {} from ", alias.alias),
+                    None => "* from ".to_string(),
+                };
+                concat(vec![text(alias), self.lit_str(&glob.path)])
+            }
+        };
+        concat(vec![text("import "), path, text(";")])
+    }
+
+    fn using(&mut self, using: &UsingDirective) -> Doc {
+        let list = match &using.list {
+            UsingList::Single(path) => self.verbatim(path.span().start(), path.span().end()),
+            UsingList::Multiple(_, items) => {
+                let items = items
+                    .iter()
+                    .map(|item| {
+                        let path = self.verbatim(item.path.span().start(), item.path.span().end());
+                        match &item.op {
+                            Some((_, op)) => concat(vec![path, text(format!(" as {}", op))]),
+                            None => path,
+                        }
+                    })
+                    .collect();
+                delimited("{", items, "}", self.config.bracket_spacing)
+            }
+        };
+        let ty = match &using.ty {
+            UsingType::Star(_) => text("*"),
+            UsingType::Type(ty) => self.ty(ty),
+        };
+        let global = if using.global_token.is_some() {
+            " global"
+        } else {
+            ""
+        };
+        concat(vec![
+            text("using "),
+            list,
+            text(" for "),
+            ty,
+            text(format!("{};", global)),
+        ])
+    }
+
+    fn modifier(&mut self, modifier: &Modifier) -> Doc {
+        let name = self.verbatim(modifier.name.span().start(), modifier.name.span().end());
+        if modifier.paren_token.is_none() {
+            return name;
+        }
+        let args = modifier
+            .arguments
+            .iter()
+            .map(|arg| self.expr(arg))
+            .collect();
+        concat(vec![name, delimited("(", args, ")", false)])
+    }
+
+    fn contract(&mut self, contract: &ItemContract) -> Doc {
+        let head = format!("{} {}", contract.kind.as_str(), contract.name);
+        let header = match &contract.inheritance {
+            Some(inheritance) => {
+                let bases: Vec<Doc> = inheritance
+                    .inheritance
+                    .iter()
+                    .map(|base| self.modifier(base))
+                    .collect();
+                Doc::Choice(vec![
+                    concat(vec![
+                        text(format!("{} is ", head)),
+                        join(bases.clone(), vec![text(", ")]),
+                        text(" "),
+                    ]),
+                    concat(vec![
+                        text(format!("{} is", head)),
+                        indent(concat(vec![
+                            Doc::HardLine,
+                            join(bases, vec![text(","), Doc::HardLine]),
+                        ])),
+                        Doc::HardLine,
+                    ]),
+                ])
+            }
+            None => text(format!("{} ", head)),
+        };
+        let body = self.braced(
+            contract.brace_token.span.open().end(),
+            contract.brace_token.span.close().start(),
+            &contract.body,
+            member_blank_lines,
+            |printer, item, _| printer.item(item),
+        );
+        concat(vec![header, body])
+    }
+
+    fn strukt(&mut self, strukt: &ItemStruct) -> Doc {
+        let fields: Vec<VariableDeclaration> = strukt.fields.iter().cloned().collect();
+        let body = self.braced(
+            strukt.brace_token.span.open().end(),
+            strukt.brace_token.span.close().start(),
+            &fields,
+            |_, _| 0,
+            |printer, field, _| concat(vec![printer.parameter(field), text(";")]),
+        );
+        concat(vec![text(format!("struct {} ", strukt.name)), body])
+    }
+
+    fn enumm(&mut self, enumm: &ItemEnum) -> Doc {
+        let variants: Vec<_> = enumm.variants.iter().cloned().collect();
+        let body = self.braced(
+            enumm.brace_token.span.open().end(),
+            enumm.brace_token.span.close().start(),
+            &variants,
+            |_, _| 0,
+            |_, variant, last| {
+                let separator = if last { "" } else { "," };
+                text(format!("{}{}", variant.ident, separator))
+            },
+        );
+        concat(vec![text(format!("enum {} ", enumm.name)), body])
+    }
+
+    fn event(&mut self, event: &ItemEvent) -> Doc {
+        let parameters = event
+            .parameters
+            .iter()
+            .map(|parameter| self.event_parameter(parameter))
+            .collect();
+        let anonymous = if event.anonymous.is_some() {
+            " anonymous"
+        } else {
+            ""
+        };
+        concat(vec![
+            text(format!("event {}", event.name)),
+            delimited("(", parameters, ")", false),
+            text(format!("{};", anonymous)),
+        ])
+    }
+
+    fn event_parameter(&mut self, parameter: &EventParameter) -> Doc {
+        let mut docs = vec![self.ty(&parameter.ty)];
+        if parameter.indexed.is_some() {
+            docs.push(text(" indexed"));
+        }
+        if let Some(name) = &parameter.name {
+            docs.push(text(format!(" {}", name)));
+        }
+        concat(docs)
+    }
+
+    fn error(&mut self, error: &ItemError) -> Doc {
+        let parameters = self.parameters(&error.parameters);
+        concat(vec![
+            text(format!("error {}", error.name)),
+            delimited("(", parameters, ")", false),
+            text(";"),
+        ])
+    }
+
+    pub(super) fn parameter(&mut self, parameter: &VariableDeclaration) -> Doc {
+        let mut docs = vec![self.ty(&parameter.ty)];
+        if let Some(storage) = &parameter.storage {
+            docs.push(text(format!(" {}", storage)));
+        }
+        if let Some(name) = &parameter.name {
+            docs.push(text(format!(" {}", name)));
+        }
+        concat(docs)
+    }
+
+    pub(super) fn parameters(&mut self, parameters: &ParameterList) -> Vec<Doc> {
+        parameters
+            .iter()
+            .map(|parameter| self.parameter(parameter))
+            .collect()
+    }
+
+    fn variable(&mut self, variable: &VariableDefinition) -> Doc {
+        let mut docs = vec![self.ty(&variable.ty)];
+        // Visibility first, as required by the `visibility-modifier-order` rule
+        let mut attributes: Vec<&VariableAttribute> = variable.attributes.0.iter().collect();
+        attributes.sort_by_key(|attribute| match attribute {
+            VariableAttribute::Visibility(_) => 0,
+            VariableAttribute::Constant(_) | VariableAttribute::Immutable(_) => 1,
+            VariableAttribute::Override(_) => 2,
+        });
+        for attribute in attributes {
+            docs.push(text(" "));
+            docs.push(match attribute {
+                VariableAttribute::Override(attribute) => {
+                    self.verbatim(attribute.span().start(), attribute.span().end())
+                }
+                attribute => text(attribute.to_string()),
+            });
+        }
+        docs.push(text(format!(" {}", variable.name)));
+        if let Some((_, initializer)) = &variable.initializer {
+            docs.push(text(" = "));
+            docs.push(self.expr(initializer));
+        }
+        docs.push(text(";"));
+        concat(docs)
+    }
+
+    /// Attributes in the order of the style guide: visibility, mutability, virtual, override and modifiers
+    fn function_attributes(&mut self, attributes: &[FunctionAttribute]) -> Vec<Doc> {
+        let mut attributes: Vec<&FunctionAttribute> = attributes.iter().collect();
+        attributes.sort_by_key(|attribute| match attribute {
+            FunctionAttribute::Visibility(_) => 0,
+            FunctionAttribute::Mutability(_) => 1,
+            FunctionAttribute::Virtual(_) => 2,
+            FunctionAttribute::Override(_) => 3,
+            FunctionAttribute::Modifier(_) => 4,
+        });
+        attributes
+            .into_iter()
+            .map(|attribute| match attribute {
+                FunctionAttribute::Override(attribute) => {
+                    self.verbatim(attribute.span().start(), attribute.span().end())
+                }
+                FunctionAttribute::Modifier(modifier) => self.modifier(modifier),
+                attribute => text(attribute.to_string()),
+            })
+            .collect()
+    }
+
+    fn function(&mut self, function: &ItemFunction) -> Doc {
+        let mut head = function.kind.as_str().to_string();
+        if let Some(name) = &function.name {
+            head = format!("{} {}", head, name);
+        }
+        let has_parens =
+            function.paren_token.is_some() || !matches!(function.kind, FunctionKind::Modifier(_));
+        let parameters = self.parameters(&function.arguments);
+        let mut attributes = self.function_attributes(&function.attributes.0);
+        if let Some(returns) = &function.returns {
+            let returns = self.parameters(&returns.returns);
+            attributes.push(concat(vec![
+                text("returns ("),
+                join(returns, vec![text(", ")]),
+                text(")"),
+            ]));
+        }
+        let has_body = matches!(function.body, FunctionBody::Block(_));
+        let open = if has_body { " {" } else { ";" };
+
+        let flat_parameters = if has_parens {
+            concat(vec![
+                text("("),
+                join(parameters.clone(), vec![text(", ")]),
+                text(")"),
+            ])
+        } else {
+            text("")
+        };
+        let broken_parameters = if parameters.is_empty() {
+            flat_parameters.clone()
+        } else {
+            concat(vec![
+                text("("),
+                indent(concat(vec![
+                    Doc::HardLine,
+                    join(parameters, vec![text(","), Doc::HardLine]),
+                ])),
+                Doc::HardLine,
+                text(")"),
+            ])
+        };
+        let flat_attributes = concat(
+            attributes
+                .iter()
+                .flat_map(|attribute| [text(" "), attribute.clone()])
+                .collect(),
+        );
+
+        let mut alternatives = vec![concat(vec![
+            text(head.clone()),
+            flat_parameters,
+            flat_attributes.clone(),
+            text(open),
+        ])];
+        if !function.arguments.is_empty() {
+            alternatives.push(concat(vec![
+                text(head.clone()),
+                broken_parameters.clone(),
+                flat_attributes,
+                text(open),
+            ]));
+        }
+        let mut broken = vec![text(head), broken_parameters];
+        if !attributes.is_empty() {
+            broken.push(indent(concat(
+                attributes
+                    .into_iter()
+                    .flat_map(|attribute| [Doc::HardLine, attribute])
+                    .collect(),
+            )));
+        }
+        if has_body {
+            broken.push(Doc::HardLine);
+            broken.push(text("{"));
+        } else {
+            broken.push(text(";"));
+        }
+        alternatives.push(concat(broken));
+
+        let mut docs = vec![Doc::Choice(alternatives)];
+        if let FunctionBody::Block(block) = &function.body {
+            // The opening brace is printed with the header
+            let body = self.block(block);
+            if let Doc::Concat(mut body) = body {
+                body.remove(0);
+                docs.extend(body);
+            }
+        }
+        concat(docs)
+    }
+}
@@ -0,0 +1,181 @@
+use super::{Node, Printer};
+use crate::doc::*;
+use osmium_libs_solidity_ast_extractor::{
+    Block, ForInitStmt, Stmt, StmtIf, StmtTry, StmtVarDecl, VarDeclDecl,
+};
+
+impl<'a> Printer<'a> {
+    pub(super) fn block(&mut self, block: &Block) -> Doc {
+        self.braced(
+            block.brace_token.span.open().end(),
+            block.brace_token.span.close().start(),
+            &block.stmts,
+            |_, _| 0,
+            |printer, stmt, _| printer.stmt(stmt),
+        )
+    }
+
+    /// The body of a control flow statement, on the next line when it is not a block
+    fn body(&mut self, body: &Stmt) -> Doc {
+        match body {
+            Stmt::Block(block) => concat(vec![text(" "), self.block(block)]),
+            body => group(indent(concat(vec![Doc::Line, self.stmt(body)]))),
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) -> Doc {
+        let is_compound = matches!(
+            stmt,
+            Stmt::Block(_)
+                | Stmt::If(_)
+                | Stmt::For(_)
+                | Stmt::While(_)
+                | Stmt::DoWhile(_)
+                | Stmt::Try(_)
+                | Stmt::UncheckedBlock(_)
+        );
+        // Comments cannot be placed back inside an expression, so such statements are kept as is
+        if !is_compound && self.has_comments(stmt.start(), stmt.end()) {
+            return self.verbatim(stmt.start(), stmt.end());
+        }
+
+        match stmt {
+            Stmt::Assembly(_) => self.verbatim(stmt.start(), stmt.end()),
+            Stmt::Block(block) => self.block(block),
+            Stmt::Break(_) => text("break;"),
+            Stmt::Continue(_) => text("continue;"),
+            Stmt::DoWhile(stmt) => {
+                let body = match &*stmt.body {
+                    Stmt::Block(block) => concat(vec![self.block(block), text(" ")]),
+                    body => concat(vec![
+                        indent(concat(vec![Doc::HardLine, self.stmt(body)])),
+                        Doc::HardLine,
+                    ]),
+                };
+                concat(vec![
+                    text("do "),
+                    body,
+                    text("while ("),
+                    self.expr(&stmt.cond),
+                    text(");"),
+                ])
+            }
+            Stmt::Emit(stmt) => concat(vec![text("emit "), self.expr(&stmt.expr), text(";")]),
+            Stmt::Expr(stmt) => concat(vec![self.expr(&stmt.expr), text(";")]),
+            Stmt::For(stmt) => {
+                let init = match &stmt.init {
+                    ForInitStmt::Empty(_) => text(";"),
+                    ForInitStmt::VarDecl(decl) => self.var_decl(decl),
+                    ForInitStmt::Expr(init) => concat(vec![self.expr(&init.expr), text(";")]),
+                };
+                let mut docs = vec![text("for ("), init];
+                if let Some(cond) = &stmt.cond {
+                    docs.push(text(" "));
+                    docs.push(self.expr(cond));
+                }
+                docs.push(text(";"));
+                if let Some(post) = &stmt.post {
+                    docs.push(text(" "));
+                    docs.push(self.expr(post));
+                }
+                docs.push(text(")"));
+                docs.push(self.body(&stmt.body));
+                concat(docs)
+            }
+            Stmt::If(stmt) => self.if_stmt(stmt),
+            Stmt::Return(stmt) => match &stmt.expr {
+                Some(expr) => concat(vec![text("return "), self.expr(expr), text(";")]),
+                None => text("return;"),
+            },
+            Stmt::Revert(stmt) => concat(vec![text("revert "), self.expr(&stmt.expr), text(";")]),
+            Stmt::Try(stmt) => self.try_stmt(stmt),
+            Stmt::UncheckedBlock(stmt) => concat(vec![text("unchecked "), self.block(&stmt.block)]),
+            Stmt::VarDecl(decl) => self.var_decl(decl),
+            Stmt::While(stmt) => concat(vec![
+                text("while ("),
+                self.expr(&stmt.cond),
+                text(")"),
+                self.body(&stmt.body),
+            ]),
+        }
+    }
+
+    fn if_stmt(&mut self, stmt: &StmtIf) -> Doc {
+        let mut docs = vec![
+            text("if ("),
+            self.expr(&stmt.cond),
+            text(")"),
+            self.body(&stmt.then_branch),
+        ];
+        if let Some((_, else_branch)) = &stmt.else_branch {
+            // `else` follows the closing brace, or starts a new line after a single statement
+            if matches!(&*stmt.then_branch, Stmt::Block(_)) {
+                docs.push(text(" else"));
+            } else {
+                docs.push(Doc::HardLine);
+                docs.push(text("else"));
+            }
+            match &**else_branch {
+                Stmt::If(else_if) => {
+                    docs.push(text(" "));
+                    docs.push(self.if_stmt(else_if));
+                }
+                else_branch => docs.push(self.body(else_branch)),
+            }
+        }
+        concat(docs)
+    }
+
+    fn try_stmt(&mut self, stmt: &StmtTry) -> Doc {
+        let mut docs = vec![text("try "), self.expr(&stmt.expr)];
+        if let Some(returns) = &stmt.returns {
+            let returns = self.parameters(&returns.returns);
+            docs.push(text(" returns "));
+            docs.push(delimited("(", returns, ")", false));
+        }
+        docs.push(text(" "));
+        docs.push(self.block(&stmt.block));
+        for catch in &stmt.catch {
+            docs.push(text(" catch"));
+            match &catch.name {
+                Some(name) => docs.push(text(format!(" {}", name))),
+                None if catch.paren_token.is_some() => docs.push(text(" ")),
+                None => {}
+            }
+            if catch.paren_token.is_some() {
+                let parameters = self.parameters(&catch.list);
+                docs.push(delimited("(", parameters, ")", false));
+            }
+            docs.push(text(" "));
+            docs.push(self.block(&catch.block));
+        }
+        concat(docs)
+    }
+
+    fn var_decl(&mut self, decl: &StmtVarDecl) -> Doc {
+        let mut docs = vec![match &decl.declaration {
+            VarDeclDecl::VarDecl(declaration) => self.parameter(declaration),
+            VarDeclDecl::Tuple(tuple) => {
+                let mut vars: Vec<Doc> = tuple
+                    .vars
+                    .iter()
+                    .map(|var| match var {
+                        Some(var) => self.parameter(var),
+                        None => text(""),
+                    })
+                    .collect();
+                // A trailing comma skips the last value, as in `(bool success, ) = ...`
+                if tuple.vars.trailing_punct() {
+                    vars.push(text(""));
+                }
+                concat(vec![text("("), join(vars, vec![text(", ")]), text(")")])
+            }
+        }];
+        if let Some((_, value)) = &decl.assignment {
+            docs.push(text(" = "));
+            docs.push(self.expr(value));
+        }
+        docs.push(text(";"));
+        concat(docs)
+    }
+}
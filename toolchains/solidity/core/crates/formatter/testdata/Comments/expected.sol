@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+/* block
+   comment */
+contract Commented { // after brace
+    // before variable
+    uint256 value; // after variable
+
+    /**
+     * @notice Documented function
+     */
+    function documented(uint256 a /* inline */, uint256 b) public {
+        foo(a, // first argument
+            b);
+
+        // before statement
+        value = a;
+        // dangling
+    }
+
+    function empty() public {
+        // only comment
+    }
+
+    function parameters(
+        uint256 a, // first parameter
+        uint256 b
+    ) public {
+        if (a > b) { // after brace
+            value  =  a;
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: MIT
+pragma solidity   ^0.8.0;
+import {A,B as C} from 'foo.sol';
+import "bar.sol";
+
+/// @title Test
+contract Foo is A, B {
+    using SafeMath for uint256;
+    uint256 public constant X=1; // trailing
+    mapping(address=>uint) balances;
+    event E(address indexed a, uint b);
+    error Err();
+    struct S { uint a; // field
+      bool b; }
+    enum En {One,Two}
+
+    // leading comment
+    function f(uint a, uint b) external onlyOwner view virtual returns (uint) {
+        if(a>b) return a; else if (a == b) { return 0; } else {
+            revert Err();
+        }
+        for (uint i=0;i<10;i++) { a+=i; }
+        while (true) break;
+        (uint x, , bool y) = g();
+        require(msg.sender == owner, 'not owner');
+        uint c = veryLongFunctionNameThatIsLong(someArgumentNumberOne, someArgumentNumberTwo, someArgumentNumberThree, four);
+        unchecked { ++a; }
+        try foo.bar{value: 1}(x) returns (uint r) { a = r; } catch Error(string memory reason) { } catch (bytes memory) {}
+        assembly {
+            let x := 1
+        }
+        return a>b?a:b;
+    }
+    function g() internal pure returns (uint, uint, bool) {}
+    constructor() {}
+}
+interface I { function h() external; }
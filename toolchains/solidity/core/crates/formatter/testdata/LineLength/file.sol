@@ -0,0 +1,23 @@
+pragma solidity ^0.8.0;
+
+/* block
+   comment */
+contract VeryLongContractNameForTesting is FirstBaseContract, SecondBaseContract, ThirdBaseContract {
+    /**
+     * @notice doc
+     */
+    function someVeryLongFunctionName(uint256 firstArgument, address secondArgument) public view returns (uint256) {
+        return firstArgument + secondArgument.balance + someOtherVeryLongVariableName + anotherLongVariableName * 2;
+    }
+
+    function withManyModifiers(uint256 a) external payable onlyOwner whenNotPaused nonReentrant checkSomething(a) returns (uint256 result, bool ok) {
+        foo(a, // first
+            b);
+        // dangling
+    }
+
+
+    function empty() public {
+        // only comment
+    }
+}
@@ -0,0 +1,13 @@
+pragma solidity ^0.8.0;
+import { Ownable } from 'ownable.sol';
+
+contract Quotes is Ownable {
+  string constant NAME = 'it\'s';
+  string constant QUOTED = 'a "quoted" word';
+
+  function send(address to) public {
+    (bool ok, ) = to.call{ value: 1, gas: 2 }('');
+    require(ok, 'failed');
+    emit Sent({ to: to, label: 'say "hi"' });
+  }
+}
@@ -0,0 +1,36 @@
+pragma solidity ^0.8.0;
+
+contract Statements {
+    function loops(uint256[] memory values) public pure returns (uint256 total) {
+        for (uint256 i; i < values.length;) {
+            total += values[i];
+            unchecked {
+                ++i;
+            }
+        }
+        uint256 j = 0;
+        do {
+            j++;
+        } while (j < 10);
+        while (j > 0) j--;
+        if (total > 100) {
+            total = 100;
+        } else {
+            total = total * 2;
+        }
+        delete j;
+        uint256[] memory copy = new uint256[](values.length);
+        bytes memory slice = msg.data[4:];
+        uint256 x = type(uint256).max;
+        address payable owner = payable(msg.sender);
+        return total > 0 ? total : 1 ether;
+    }
+
+    fallback() external payable {}
+
+    receive() external payable {}
+
+    modifier onlyOwner {
+        _;
+    }
+}
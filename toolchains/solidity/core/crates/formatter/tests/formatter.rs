@@ -0,0 +1,36 @@
+use solidity_formatter::config::FormatterConfig;
+use solidity_formatter::format;
+use std::{fs, path::PathBuf};
+
+fn test_directory(base_name: &str) {
+    let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata")
+        .join(base_name);
+    let source = fs::read_to_string(directory.join("file.sol")).unwrap();
+    let expected = fs::read_to_string(directory.join("expected.sol")).unwrap();
+    let config = FormatterConfig::from_rules_file(&directory.join(".solidhunter.json"));
+
+    let formatted = format(&source, &config).unwrap();
+    assert_eq!(formatted, expected);
+
+    let reformatted = format(&formatted, &config).unwrap();
+    assert_eq!(reformatted, formatted, "Formatting is not idempotent");
+}
+
+macro_rules! test_directories {
+    ($($dir:ident),+ $(,)?) => {$(
+        #[allow(non_snake_case)]
+        #[test]
+        fn $dir() {
+            test_directory(stringify!($dir));
+        }
+    )+};
+}
+
+test_directories! {
+    Comments,
+    Contract,
+    LineLength,
+    Quotes,
+    Statements,
+}
@@ -0,0 +1,25 @@
+use glob::glob;
+use std::path::{Path, PathBuf};
+
+fn solidity_files(path: &str) -> Vec<PathBuf> {
+    if Path::new(path).is_file() {
+        return vec![PathBuf::from(path)];
+    }
+    match glob(&format!("{}/**/*.sol", path)) {
+        Ok(entries) => entries.flatten().collect(),
+        Err(_) => vec![],
+    }
+}
+
+/// Lists the Solidity files of the paths, the excluded ones left out
+pub fn included_files(paths: &[String], excluded: &[String]) -> Vec<PathBuf> {
+    paths
+        .iter()
+        .flat_map(|path| solidity_files(path))
+        .filter(|file| {
+            !excluded
+                .iter()
+                .any(|excluded| Path::new(excluded) == file.as_path())
+        })
+        .collect()
+}
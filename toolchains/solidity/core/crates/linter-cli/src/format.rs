@@ -0,0 +1,36 @@
+use crate::files::included_files;
+use solidhunter_lib::errors::SolidHunterError;
+use solidity_formatter::config::FormatterConfig;
+
+/// Formats the Solidity files of the paths in place, or only lists the ones which are not formatted.
+/// Returns whether every file was already formatted.
+pub fn format_paths(
+    paths: &[String],
+    excluded: &[String],
+    config: &FormatterConfig,
+    check: bool,
+) -> Result<bool, SolidHunterError> {
+    let mut formatted_files = true;
+    for file in included_files(paths, excluded) {
+        let content = std::fs::read_to_string(&file)?;
+        let formatted = match solidity_formatter::format(&content, config) {
+            Ok(formatted) => formatted,
+            Err(e) => {
+                println!("{}: {}", file.display(), e);
+                formatted_files = false;
+                continue;
+            }
+        };
+        if formatted == content {
+            continue;
+        }
+        formatted_files = false;
+        if check {
+            println!("{}", file.display());
+        } else {
+            std::fs::write(&file, formatted)?;
+            println!("Formatted {}", file.display());
+        }
+    }
+    Ok(formatted_files)
+}
@@ -1,8 +1,14 @@
-use clap::{arg, Parser};
+use clap::Parser;
 use solidhunter_lib::errors::SolidHunterError;
 use solidhunter_lib::linter::SolidLinter;
 use solidhunter_lib::rules::rule_impl::create_rules_file;
 use solidhunter_lib::types::LintResult;
+use solidity_formatter::config::FormatterConfig;
+use std::path::Path;
+
+mod files;
+mod format;
+mod metrics;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -44,6 +50,36 @@ struct Args {
 
     #[arg(short = 'e', long = "exclude", help = "Specify excluded files")]
     exclude: Option<Vec<String>>,
+
+    #[arg(
+        short = 'f',
+        long = "format",
+        default_value = "false",
+        help = "Format the files in place instead of linting them"
+    )]
+    format: bool,
+
+    #[arg(
+        short = 'c',
+        long = "check",
+        default_value = "false",
+        help = "List the files which are not formatted and exit with an error if there are any"
+    )]
+    check: bool,
+
+    #[arg(
+        long = "baseline-layout",
+        help = "Check the storage layouts against a JSON file mapping contract names to their previous solc storage layout"
+    )]
+    baseline_layout: Option<String>,
+
+    #[arg(
+        short = 'm',
+        long = "metrics",
+        value_parser = ["json", "csv"],
+        help = "Print the complexity metrics of each function instead of linting, as json or csv"
+    )]
+    metrics: Option<String>,
 }
 
 fn print_result(results: Vec<LintResult>) {
@@ -62,7 +98,7 @@ fn print_result(results: Vec<LintResult>) {
 fn main() -> Result<(), SolidHunterError> {
     let mut args = Args::parse();
 
-    if !args.to_json {
+    if !args.to_json && args.metrics.is_none() && !args.format && !args.check {
         println!();
         println!("SolidHunter: Fast and efficient Solidity linter");
         println!(
@@ -92,7 +128,25 @@ fn main() -> Result<(), SolidHunterError> {
         args.paths.push(String::from("."));
     }
 
+    if args.format || args.check {
+        let config = FormatterConfig::from_rules_file(Path::new(&args.rules_file));
+        let excluded = args.exclude.unwrap_or_default();
+        let formatted = format::format_paths(&args.paths, &excluded, &config, args.check)?;
+        if args.check && !formatted {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(format) = &args.metrics {
+        let excluded = args.exclude.unwrap_or_default();
+        return metrics::print_metrics(&args.paths, &excluded, format);
+    }
+
     let mut linter: SolidLinter = SolidLinter::new();
+    if let Some(path) = &args.baseline_layout {
+        linter.set_baseline_layout(path)?;
+    }
     linter.initialize_rules(&args.rules_file)?;
     linter.initialize_excluded_files(args.exclude.as_ref(), &args.paths)?;
 
@@ -0,0 +1,38 @@
+use crate::files::included_files;
+use solidhunter_lib::errors::SolidHunterError;
+use solidhunter_lib::metrics::{parse_file_metrics, FileMetrics, CSV_HEADER};
+
+fn paths_metrics(
+    paths: &[String],
+    excluded: &[String],
+) -> Result<Vec<FileMetrics>, SolidHunterError> {
+    let mut metrics = Vec::new();
+    for file in included_files(paths, excluded) {
+        let content = std::fs::read_to_string(&file)?;
+        match parse_file_metrics(&file.to_string_lossy(), &content) {
+            Ok(file_metrics) => metrics.push(file_metrics),
+            Err(e) => eprintln!("{}: {}", file.display(), e),
+        }
+    }
+    Ok(metrics)
+}
+
+/// Prints the complexity metrics of the functions of the paths, as JSON or as CSV
+pub fn print_metrics(
+    paths: &[String],
+    excluded: &[String],
+    format: &str,
+) -> Result<(), SolidHunterError> {
+    let metrics = paths_metrics(paths, excluded)?;
+    if format == "csv" {
+        println!("{}", CSV_HEADER);
+        for file_metrics in &metrics {
+            for row in file_metrics.csv_rows() {
+                println!("{}", row);
+            }
+        }
+    } else {
+        println!("{}", serde_json::to_string_pretty(&metrics)?);
+    }
+    Ok(())
+}
@@ -0,0 +1,299 @@
+use crate::linter::SolidFile;
+use crate::types::{Position, Range};
+use osmium_libs_solidity_ast_extractor::{Item, Spanned};
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Remapping {
+    pub context: Option<String>,
+    pub prefix: String,
+    pub target: String,
+}
+
+impl Remapping {
+    /// Parses a remapping in the `[context:]prefix=target` format
+    pub fn parse(line: &str) -> Option<Self> {
+        let (left, target) = line.trim().split_once('=')?;
+        let (context, prefix) = match left.split_once(':') {
+            Some((context, prefix)) => (Some(context.to_string()), prefix),
+            None => (None, left),
+        };
+        if prefix.is_empty() {
+            return None;
+        }
+        Some(Remapping {
+            context,
+            prefix: prefix.to_string(),
+            target: target.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for Remapping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.context {
+            Some(context) => write!(f, "{}:{}={}", context, self.prefix, self.target),
+            None => write!(f, "{}={}", self.prefix, self.target),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolvedImport {
+    /// The imported path, as written in the import directive.
+    pub path: String,
+
+    /// The range of the path string literal, quotes included.
+    pub range: Range,
+
+    /// The imported file, if it could be found.
+    pub resolved: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportResolver {
+    root: PathBuf,
+    remappings: Vec<Remapping>,
+}
+
+impl ImportResolver {
+    pub fn new(root: &Path, remappings: Vec<Remapping>) -> Self {
+        ImportResolver {
+            root: root.to_path_buf(),
+            remappings,
+        }
+    }
+
+    /// Creates a resolver using the `remappings.txt` file found at the root of the project
+    pub fn from_root(root: &Path) -> Self {
+        let remappings = std::fs::read_to_string(root.join("remappings.txt"))
+            .map(|content| content.lines().filter_map(Remapping::parse).collect())
+            .unwrap_or_default();
+        ImportResolver::new(root, remappings)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn remappings(&self) -> &[Remapping] {
+        &self.remappings
+    }
+
+    /// Resolves an import path found in `from` to an existing file.
+    /// Relative imports are resolved from the importing file directory, other ones are
+    /// resolved through the remappings, then from the project root, then from `node_modules` folders.
+    pub fn resolve(&self, from: &Path, import: &str) -> Option<PathBuf> {
+        let directory = from.parent().unwrap_or(&self.root);
+
+        if import.starts_with("./") || import.starts_with("../") {
+            return existing_file(directory.join(import));
+        }
+
+        if let Some(path) = self.remap(from, import) {
+            if let Some(path) = existing_file(path) {
+                return Some(path);
+            }
+        }
+
+        if let Some(path) = existing_file(self.root.join(import)) {
+            return Some(path);
+        }
+
+        directory
+            .ancestors()
+            .map(|ancestor| ancestor.join("node_modules").join(import))
+            .find_map(existing_file)
+    }
+
+    /// Returns a remapping which would make an unresolved import resolvable,
+    /// by looking for the imported file in the `lib` and `node_modules` folders
+    pub fn suggest_remapping(&self, import: &str) -> Option<Remapping> {
+        let segments: Vec<&str> = import.split('/').collect();
+        let mut candidates: Vec<PathBuf> = Vec::new();
+        for folder in ["lib", "node_modules"] {
+            if let Ok(entries) = std::fs::read_dir(self.root.join(folder)) {
+                let mut entries: Vec<PathBuf> = entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_dir())
+                    .collect();
+                entries.sort();
+                candidates.append(&mut entries);
+            }
+        }
+
+        for candidate in candidates {
+            for idx in 1..segments.len() {
+                let prefix = segments[..idx].join("/");
+                let rest = segments[idx..].join("/");
+                for target in [
+                    candidate.clone(),
+                    candidate.join("src"),
+                    candidate.join("contracts"),
+                ] {
+                    if target.join(&rest).is_file() {
+                        let target = target.strip_prefix(&self.root).unwrap_or(&target);
+                        return Some(Remapping {
+                            context: None,
+                            prefix: format!("{}/", prefix),
+                            target: format!("{}/", target.to_string_lossy()),
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Lists the imports of a file along with the file each of them resolves to
+    pub fn resolve_file_imports(&self, file: &SolidFile) -> Vec<ResolvedImport> {
+        file.data
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Import(import) => Some(import.path.path()),
+                _ => None,
+            })
+            .map(|literal| {
+                let path = literal.value();
+                let span = literal.span();
+                ResolvedImport {
+                    resolved: self.resolve(Path::new(&file.path), &path),
+                    range: Range {
+                        start: Position {
+                            line: span.start().line,
+                            character: span.start().column,
+                        },
+                        end: Position {
+                            line: span.end().line,
+                            character: span.end().column,
+                        },
+                    },
+                    path,
+                }
+            })
+            .collect()
+    }
+
+    fn remap(&self, from: &Path, import: &str) -> Option<PathBuf> {
+        let from = from.strip_prefix(&self.root).unwrap_or(from);
+        self.remappings
+            .iter()
+            .filter(|remapping| import.starts_with(&remapping.prefix))
+            .filter(|remapping| match &remapping.context {
+                Some(context) => from.starts_with(context),
+                None => true,
+            })
+            .max_by_key(|remapping| {
+                (
+                    remapping.context.as_ref().map(|c| c.len()).unwrap_or(0),
+                    remapping.prefix.len(),
+                )
+            })
+            .map(|remapping| {
+                let path = format!("{}{}", remapping.target, &import[remapping.prefix.len()..]);
+                self.root.join(path)
+            })
+    }
+}
+
+fn existing_file(path: PathBuf) -> Option<PathBuf> {
+    let path = normalize(&path);
+    if path.is_file() {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut res = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                res.pop();
+            }
+            component => res.push(component),
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_project(name: &str, files: &[&str]) -> PathBuf {
+        let root = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&root);
+        for file in files {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_remapping_precedence() {
+        let root = create_project(
+            "osmium-imports-remappings",
+            &[
+                "src/Vault.sol",
+                "test/Vault.t.sol",
+                "lib/oz/token/ERC20.sol",
+                "lib/oz-v4/token/ERC20.sol",
+                "lib/erc20/ERC20.sol",
+            ],
+        );
+        let remappings = ["oz/=lib/oz/", "test:oz/=lib/oz-v4/", "oz/token/=lib/erc20/"]
+            .into_iter()
+            .filter_map(Remapping::parse)
+            .collect();
+        let resolver = ImportResolver::new(&root, remappings);
+
+        // The remapping with the longest context is used, then the one with the longest prefix
+        assert_eq!(
+            resolver.resolve(&root.join("test/Vault.t.sol"), "oz/token/ERC20.sol"),
+            Some(root.join("lib/oz-v4/token/ERC20.sol"))
+        );
+        assert_eq!(
+            resolver.resolve(&root.join("src/Vault.sol"), "oz/token/ERC20.sol"),
+            Some(root.join("lib/erc20/ERC20.sol"))
+        );
+    }
+
+    #[test]
+    fn test_node_modules_fallback() {
+        let root = create_project(
+            "osmium-imports-node-modules",
+            &["src/Vault.sol", "node_modules/@oz/token/ERC20.sol"],
+        );
+        let resolver = ImportResolver::new(&root, vec![]);
+
+        assert_eq!(
+            resolver.resolve(&root.join("src/Vault.sol"), "@oz/token/ERC20.sol"),
+            Some(root.join("node_modules/@oz/token/ERC20.sol"))
+        );
+    }
+
+    #[test]
+    fn test_unresolvable_import() {
+        let root = create_project(
+            "osmium-imports-unresolvable",
+            &["src/Vault.sol", "lib/forge-std/src/Test.sol"],
+        );
+        let resolver = ImportResolver::new(&root, vec![]);
+        let from = root.join("src/Vault.sol");
+
+        assert_eq!(resolver.resolve(&from, "./Missing.sol"), None);
+        assert_eq!(resolver.resolve(&from, "forge-std/Test.sol"), None);
+        assert_eq!(
+            resolver.suggest_remapping("forge-std/Test.sol"),
+            Remapping::parse("forge-std/=lib/forge-std/src/")
+        );
+        assert_eq!(resolver.suggest_remapping("missing/Missing.sol"), None);
+    }
+}
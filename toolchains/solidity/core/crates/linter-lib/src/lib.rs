@@ -1,5 +1,7 @@
 pub mod errors;
 mod ignore;
+pub mod imports;
 pub mod linter;
+pub mod metrics;
 pub mod rules;
 pub mod types;
@@ -1,14 +1,20 @@
 use crate::errors::SolidHunterError;
+use crate::rules::compiler::pragma_consistency;
 use crate::rules::create_default_rules;
 use crate::rules::factory::RuleFactory;
+use crate::rules::miscellaneous::duplicated_imports;
 use crate::rules::rule_impl::parse_rules;
 use crate::rules::types::*;
+use crate::rules::upgradeability::storage_layout;
 use crate::types::*;
 use std::fs;
 
 use crate::ignore::get_excluded_files;
 use glob::glob;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Rules resolving imports, which are given the project root in their data
+const IMPORT_RULES: [&str; 2] = [pragma_consistency::RULE_ID, duplicated_imports::RULE_ID];
 
 #[derive(Debug, Clone)]
 pub struct SolidFile {
@@ -22,6 +28,8 @@ pub struct SolidLinter {
     rule_factory: RuleFactory,
     rules: Vec<Box<dyn RuleType>>,
     excluded_files: Vec<String>,
+    baseline_layout: Option<String>,
+    root: Option<PathBuf>,
 }
 
 impl Default for SolidLinter {
@@ -37,33 +45,77 @@ impl SolidLinter {
             rule_factory: RuleFactory::default(),
             rules: vec![],
             excluded_files: Vec::new(),
+            baseline_layout: None,
+            root: None,
         }
     }
 
     pub fn new_fileless() -> Self {
-        let default_rules = create_default_rules();
-        let mut linter = SolidLinter {
-            files: Vec::new(),
-            rule_factory: RuleFactory::default(),
-            rules: Vec::new(),
-            excluded_files: Vec::new(),
-        };
+        let mut linter = SolidLinter::new();
+        linter.initialize_default_rules();
+        linter
+    }
 
-        for rule in default_rules {
-            linter.rules.push(linter.rule_factory.create_rule(rule));
+    pub fn initialize_default_rules(&mut self) {
+        for rule in create_default_rules() {
+            self.rules.push(self.create_rule(rule));
         }
-
-        linter
     }
 
     pub fn initialize_rules(&mut self, rules_config: &str) -> Result<(), SolidHunterError> {
-        let res = parse_rules(rules_config)?;
+        let mut res = parse_rules(rules_config)?;
+        if let Some(path) = &self.baseline_layout {
+            let data = serde_json::json!({ "baselineLayout": path });
+            match res
+                .rules
+                .iter_mut()
+                .find(|rule| rule.id == storage_layout::RULE_ID)
+            {
+                Some(rule) => rule.data = Some(data),
+                None => res.rules.push(RuleEntry {
+                    data: Some(data),
+                    ..storage_layout::StorageLayout::create_default()
+                }),
+            }
+        }
+        if self.root.is_none() {
+            let root = Path::new(rules_config).parent().unwrap_or(Path::new(""));
+            self.root = Some(root.to_path_buf());
+        }
         for rule in res.rules {
-            self.rules.push(self.rule_factory.create_rule(rule));
+            self.rules.push(self.create_rule(rule));
         }
         Ok(())
     }
 
+    /// Sets the root of the project the imports are resolved from, which defaults to the folder
+    /// of the rules file. It must be called before the rules are initialized.
+    pub fn set_root(&mut self, root: &Path) {
+        self.root = Some(root.to_path_buf());
+    }
+
+    fn create_rule(&self, mut rule: RuleEntry) -> Box<dyn RuleType> {
+        if let Some(root) = &self.root {
+            if IMPORT_RULES.contains(&rule.id.as_str()) {
+                let data = rule.data.get_or_insert_with(|| serde_json::json!({}));
+                if let Some(data) = data.as_object_mut() {
+                    if data.get("root").is_none_or(|root| root.is_null()) {
+                        data.insert("root".to_string(), serde_json::json!(root));
+                    }
+                }
+            }
+        }
+        self.rule_factory.create_rule(rule)
+    }
+
+    /// Sets the storage layouts the contracts are checked against for upgrade compatibility,
+    /// overriding the one of the rules file. It must be called before `initialize_rules`.
+    pub fn set_baseline_layout(&mut self, path: &str) -> Result<(), SolidHunterError> {
+        storage_layout::load_baseline_layouts(path)?;
+        self.baseline_layout = Some(path.to_string());
+        Ok(())
+    }
+
     pub fn initialize_excluded_files(
         &mut self,
         excluded_filepaths: Option<&Vec<String>>,
@@ -80,6 +132,10 @@ impl SolidLinter {
         Ok(())
     }
 
+    pub fn get_documents(&self) -> &[SolidFile] {
+        &self.files
+    }
+
     fn _file_exists(&self, path: &str) -> bool {
         for file in &self.files {
             if file.path == path {
@@ -126,8 +182,13 @@ impl SolidLinter {
         self._add_file(filepath, res, content);
         let mut res: Vec<LintDiag> = Vec::new();
 
+        let file = self
+            .files
+            .iter()
+            .find(|file| file.path == filepath)
+            .unwrap();
         for rule in &self.rules {
-            let mut diags = rule.diagnose(&self.files[self.files.len() - 1], &self.files);
+            let mut diags = rule.diagnose(file, &self.files);
             res.append(&mut diags);
         }
         Ok(FileDiags::new(content.to_string(), res))
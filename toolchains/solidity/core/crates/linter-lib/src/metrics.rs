@@ -0,0 +1,273 @@
+use crate::errors::SolidHunterError;
+use crate::linter::SolidFile;
+use osmium_libs_solidity_ast_extractor::*;
+use serde::{Deserialize, Serialize};
+
+/// Header of the CSV report, one row per function
+pub const CSV_HEADER: &str =
+    "file,contract,function,line,cyclomaticComplexity,cognitiveComplexity,nestingDepth,parameters,returnValues";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionMetrics {
+    pub name: String,
+    pub line: usize,
+    pub cyclomatic_complexity: usize,
+    pub cognitive_complexity: usize,
+    pub nesting_depth: usize,
+    pub parameters: usize,
+    pub return_values: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContractMetrics {
+    pub name: String,
+    pub line: usize,
+    pub functions: Vec<FunctionMetrics>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FileMetrics {
+    pub path: String,
+    pub contracts: Vec<ContractMetrics>,
+    /// Free functions, declared outside of any contract
+    pub functions: Vec<FunctionMetrics>,
+}
+
+impl FileMetrics {
+    /// Rows of the CSV report, in the order of the `CSV_HEADER` columns
+    pub fn csv_rows(&self) -> Vec<String> {
+        let row = |contract: &str, function: &FunctionMetrics| {
+            format!(
+                "{},{},{},{},{},{},{},{},{}",
+                csv_field(&self.path),
+                csv_field(contract),
+                csv_field(&function.name),
+                function.line,
+                function.cyclomatic_complexity,
+                function.cognitive_complexity,
+                function.nesting_depth,
+                function.parameters,
+                function.return_values
+            )
+        };
+        let mut rows: Vec<String> = self
+            .contracts
+            .iter()
+            .flat_map(|contract| {
+                contract
+                    .functions
+                    .iter()
+                    .map(|function| row(&contract.name, function))
+            })
+            .collect();
+        rows.extend(self.functions.iter().map(|function| row("", function)));
+        rows
+    }
+}
+
+/// Quotes a CSV field containing a separator, a quote or a line break, doubling its quotes
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Computes the complexities of a function body.
+///
+/// The cyclomatic complexity counts the decision points: conditions, loops, catch clauses, ternary
+/// and logical operators. The cognitive complexity counts the same structures, plus `else` branches,
+/// with an increment for each level of nesting, and a single increment per sequence of identical
+/// logical operators.
+#[derive(Default)]
+struct ComplexityVisitor {
+    cyclomatic: usize,
+    cognitive: usize,
+    nesting: usize,
+    max_nesting: usize,
+}
+
+impl ComplexityVisitor {
+    fn add_structure(&mut self) {
+        self.cyclomatic += 1;
+        self.cognitive += 1 + self.nesting;
+    }
+
+    fn nested(&mut self, visit: impl FnOnce(&mut Self)) {
+        self.nesting += 1;
+        self.max_nesting = self.max_nesting.max(self.nesting);
+        visit(self);
+        self.nesting -= 1;
+    }
+
+    fn visit_if(&mut self, stmt: &StmtIf, is_else_if: bool) {
+        if is_else_if {
+            self.cyclomatic += 1;
+            self.cognitive += 1;
+        } else {
+            self.add_structure();
+        }
+        self.visit_expr(&stmt.cond);
+        self.nested(|v| v.visit_stmt(&stmt.then_branch));
+        match stmt.else_branch.as_ref().map(|(_, stmt)| stmt.as_ref()) {
+            Some(Stmt::If(else_if)) => self.visit_if(else_if, true),
+            Some(else_branch) => {
+                self.cognitive += 1;
+                self.nested(|v| v.visit_stmt(else_branch));
+            }
+            None => {}
+        }
+    }
+
+    /// Collects the operators of a chain of logical operations in source order, and its operands
+    fn logical_chain<'a>(expr: &'a Expr, operators: &mut Vec<bool>, operands: &mut Vec<&'a Expr>) {
+        match expr {
+            Expr::Binary(binary) if matches!(binary.op, BinOp::And(_) | BinOp::Or(_)) => {
+                Self::logical_chain(&binary.left, operators, operands);
+                operators.push(matches!(binary.op, BinOp::And(_)));
+                Self::logical_chain(&binary.right, operators, operands);
+            }
+            _ => operands.push(expr),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ComplexityVisitor {
+    fn visit_stmt_if(&mut self, stmt: &'ast StmtIf) {
+        self.visit_if(stmt, false);
+    }
+
+    fn visit_stmt_for(&mut self, stmt: &'ast StmtFor) {
+        self.add_structure();
+        match &stmt.init {
+            ForInitStmt::Expr(expr) => self.visit_expr(&expr.expr),
+            ForInitStmt::VarDecl(variable) => self.visit_stmt_var_decl(variable),
+            ForInitStmt::Empty(_) => {}
+        }
+        if let Some(cond) = &stmt.cond {
+            self.visit_expr(cond);
+        }
+        if let Some(post) = &stmt.post {
+            self.visit_expr(post);
+        }
+        self.nested(|v| v.visit_stmt(&stmt.body));
+    }
+
+    fn visit_stmt_while(&mut self, stmt: &'ast StmtWhile) {
+        self.add_structure();
+        self.visit_expr(&stmt.cond);
+        self.nested(|v| v.visit_stmt(&stmt.body));
+    }
+
+    fn visit_stmt_dowhile(&mut self, stmt: &'ast StmtDoWhile) {
+        self.add_structure();
+        self.visit_expr(&stmt.cond);
+        self.nested(|v| v.visit_stmt(&stmt.body));
+    }
+
+    fn visit_stmt_try(&mut self, stmt: &'ast StmtTry) {
+        self.visit_expr(&stmt.expr);
+        self.visit_block(&stmt.block);
+        for catch in &stmt.catch {
+            self.add_structure();
+            self.nested(|v| v.visit_block(&catch.block));
+        }
+    }
+
+    fn visit_expr_ternary(&mut self, ternary: &'ast ExprTernary) {
+        self.add_structure();
+        self.visit_expr(&ternary.cond);
+        self.nested(|v| {
+            v.visit_expr(&ternary.if_true);
+            v.visit_expr(&ternary.if_false);
+        });
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        let Expr::Binary(binary) = expr else {
+            return visit::visit_expr(self, expr);
+        };
+        if !matches!(binary.op, BinOp::And(_) | BinOp::Or(_)) {
+            return visit::visit_expr(self, expr);
+        }
+        let mut operators = Vec::new();
+        let mut operands = Vec::new();
+        ComplexityVisitor::logical_chain(expr, &mut operators, &mut operands);
+        self.cyclomatic += operators.len();
+        self.cognitive += 1 + operators.windows(2).filter(|w| w[0] != w[1]).count();
+        for operand in operands {
+            self.visit_expr(operand);
+        }
+    }
+}
+
+fn function_name(function: &ItemFunction) -> String {
+    match (&function.name, &function.kind) {
+        (Some(name), _) => name.to_string(),
+        (None, FunctionKind::Constructor(_)) => "constructor".to_string(),
+        (None, FunctionKind::Fallback(_)) => "fallback".to_string(),
+        (None, FunctionKind::Receive(_)) => "receive".to_string(),
+        (None, _) => String::new(),
+    }
+}
+
+pub fn function_metrics(function: &ItemFunction) -> FunctionMetrics {
+    let mut visitor = ComplexityVisitor {
+        cyclomatic: 1,
+        ..Default::default()
+    };
+    if let FunctionBody::Block(block) = &function.body {
+        visitor.visit_block(block);
+    }
+    FunctionMetrics {
+        name: function_name(function),
+        line: function.span().start().line,
+        cyclomatic_complexity: visitor.cyclomatic,
+        cognitive_complexity: visitor.cognitive,
+        nesting_depth: visitor.max_nesting,
+        parameters: function.arguments.len(),
+        return_values: function
+            .returns
+            .as_ref()
+            .map_or(0, |returns| returns.returns.len()),
+    }
+}
+
+pub fn file_metrics(file: &SolidFile) -> FileMetrics {
+    let mut metrics = FileMetrics {
+        path: file.path.clone(),
+        contracts: Vec::new(),
+        functions: Vec::new(),
+    };
+    for item in &file.data.items {
+        match item {
+            Item::Contract(contract) => metrics.contracts.push(ContractMetrics {
+                name: contract.name.to_string(),
+                line: contract.name.span().start().line,
+                functions: contract
+                    .body
+                    .iter()
+                    .filter_map(|item| match item {
+                        Item::Function(function) => Some(function_metrics(function)),
+                        _ => None,
+                    })
+                    .collect(),
+            }),
+            Item::Function(function) => metrics.functions.push(function_metrics(function)),
+            _ => {}
+        }
+    }
+    metrics
+}
+
+/// Parses a file and computes the metrics of its contracts and functions
+pub fn parse_file_metrics(path: &str, content: &str) -> Result<FileMetrics, SolidHunterError> {
+    let data = osmium_libs_solidity_ast_extractor::extract::extract_ast_from_content(content)?;
+    Ok(file_metrics(&SolidFile {
+        data,
+        path: path.to_string(),
+        content: content.to_string(),
+    }))
+}
@@ -0,0 +1,738 @@
+use crate::linter::SolidFile;
+use crate::rules::version::*;
+use osmium_libs_solidity_ast_extractor::storage_layout::Definitions;
+use osmium_libs_solidity_ast_extractor::*;
+use std::collections::{HashMap, HashSet};
+
+/// Rebuilds an expression with the usual precedence of member accesses and operators.
+/// The parser nests everything following a `.` or a unary operator inside it,
+/// so `a.b(c) + d` is parsed as `a.(b(c) + d)` and becomes `(a.b)(c) + d`,
+/// and it reads binary operations from right to left, so `a > 0 && b` is parsed as `a > (0 && b)`.
+pub(crate) fn normalize_expr(expr: &Expr) -> Expr {
+    match expr {
+        Expr::Member(member) => attach_member(
+            normalize_expr(&member.expr),
+            member,
+            normalize_expr(&member.member),
+        ),
+        Expr::Unary(unary) => prefix_unary(unary.op, normalize_expr(&unary.expr)),
+        Expr::Binary(binary) if is_assignment(&binary.op) => Expr::Binary(ExprBinary {
+            left: Box::new(normalize_expr(&binary.left)),
+            op: binary.op,
+            right: Box::new(normalize_expr(&binary.right)),
+        }),
+        Expr::Binary(binary) => {
+            let mut operands = Vec::new();
+            let mut ops = Vec::new();
+            flatten_binary(normalize_expr(&binary.left), &mut operands, &mut ops);
+            ops.push(binary.op);
+            flatten_binary(normalize_expr(&binary.right), &mut operands, &mut ops);
+            // The condition of a ternary is parsed as its last operand, as in `a > (0 ? b : c)`
+            match operands.pop() {
+                Some(Expr::Ternary(mut ternary)) => {
+                    flatten_binary(*ternary.cond, &mut operands, &mut ops);
+                    ternary.cond = Box::new(build_binary(operands, ops));
+                    Expr::Ternary(ternary)
+                }
+                last => {
+                    operands.extend(last);
+                    build_binary(operands, ops)
+                }
+            }
+        }
+        Expr::Call(call) => {
+            let mut call = call.clone();
+            call.expr = Box::new(normalize_expr(&call.expr));
+            normalize_args(&mut call.args);
+            Expr::Call(call)
+        }
+        Expr::CallOptions(call) => {
+            let mut call = call.clone();
+            call.expr = Box::new(normalize_expr(&call.expr));
+            for arg in call.args.list.iter_mut() {
+                arg.arg = normalize_expr(&arg.arg);
+            }
+            Expr::CallOptions(call)
+        }
+        Expr::Payable(payable) => {
+            let mut payable = payable.clone();
+            normalize_args(&mut payable.args);
+            Expr::Payable(payable)
+        }
+        Expr::Index(index) => {
+            let mut index = index.clone();
+            index.expr = Box::new(normalize_expr(&index.expr));
+            index.start = index.start.map(|start| Box::new(normalize_expr(&start)));
+            index.end = index.end.map(|end| Box::new(normalize_expr(&end)));
+            Expr::Index(index)
+        }
+        Expr::Postfix(postfix) => Expr::Postfix(ExprPostfix {
+            expr: Box::new(normalize_expr(&postfix.expr)),
+            op: postfix.op,
+        }),
+        Expr::Delete(delete) => Expr::Delete(ExprDelete {
+            delete_token: delete.delete_token,
+            expr: Box::new(normalize_expr(&delete.expr)),
+        }),
+        Expr::Ternary(ternary) => {
+            let mut ternary = ternary.clone();
+            ternary.cond = Box::new(normalize_expr(&ternary.cond));
+            ternary.if_true = Box::new(normalize_expr(&ternary.if_true));
+            ternary.if_false = Box::new(normalize_expr(&ternary.if_false));
+            Expr::Ternary(ternary)
+        }
+        Expr::Tuple(tuple) => {
+            let mut tuple = tuple.clone();
+            for elem in tuple.elems.iter_mut() {
+                *elem = normalize_expr(elem);
+            }
+            Expr::Tuple(tuple)
+        }
+        Expr::Array(array) => {
+            let mut array = array.clone();
+            for elem in array.elems.iter_mut() {
+                *elem = normalize_expr(elem);
+            }
+            Expr::Array(array)
+        }
+        expr => expr.clone(),
+    }
+}
+
+fn normalize_args(args: &mut ArgList) {
+    match &mut args.list {
+        ArgListImpl::Unnamed(list) => {
+            for arg in list.iter_mut() {
+                *arg = normalize_expr(arg);
+            }
+        }
+        ArgListImpl::Named(list) => {
+            for arg in list.list.iter_mut() {
+                arg.arg = normalize_expr(&arg.arg);
+            }
+        }
+    }
+}
+
+/// Operands and operators of a normalized chain of binary operations, in source order
+fn flatten_binary(expr: Expr, operands: &mut Vec<Expr>, ops: &mut Vec<BinOp>) {
+    match expr {
+        Expr::Binary(binary) if !is_assignment(&binary.op) => {
+            flatten_binary(*binary.left, operands, ops);
+            ops.push(binary.op);
+            flatten_binary(*binary.right, operands, ops);
+        }
+        expr => operands.push(expr),
+    }
+}
+
+fn precedence(op: &BinOp) -> u8 {
+    match op {
+        BinOp::Pow(..) => 11,
+        BinOp::Mul(..) | BinOp::Div(..) | BinOp::Rem(..) => 10,
+        BinOp::Add(..) | BinOp::Sub(..) => 9,
+        BinOp::Shl(..) | BinOp::Shr(..) | BinOp::Sar(..) => 8,
+        BinOp::BitAnd(..) => 7,
+        BinOp::BitXor(..) => 6,
+        BinOp::BitOr(..) => 5,
+        BinOp::Lt(..) | BinOp::Gt(..) | BinOp::Le(..) | BinOp::Ge(..) => 4,
+        BinOp::Eq(..) | BinOp::Neq(..) => 3,
+        BinOp::And(..) => 2,
+        BinOp::Or(..) => 1,
+        _ => 0,
+    }
+}
+
+/// Builds a chain of binary operations according to the precedence of its operators
+fn build_binary(operands: Vec<Expr>, ops: Vec<BinOp>) -> Expr {
+    fn reduce(operands: &mut Vec<Expr>, ops: &mut Vec<BinOp>) {
+        let right = operands.pop().unwrap();
+        let left = operands.pop().unwrap();
+        operands.push(Expr::Binary(ExprBinary {
+            left: Box::new(left),
+            op: ops.pop().unwrap(),
+            right: Box::new(right),
+        }));
+    }
+
+    let mut operands = operands.into_iter();
+    let mut stack: Vec<Expr> = operands.next().into_iter().collect();
+    let mut pending: Vec<BinOp> = Vec::new();
+    for (op, operand) in ops.into_iter().zip(operands) {
+        // `**` is right associative
+        while pending.last().is_some_and(|last| {
+            precedence(last) > precedence(&op)
+                || (precedence(last) == precedence(&op) && !matches!(op, BinOp::Pow(..)))
+        }) {
+            reduce(&mut stack, &mut pending);
+        }
+        pending.push(op);
+        stack.push(operand);
+    }
+    while !pending.is_empty() {
+        reduce(&mut stack, &mut pending);
+    }
+    stack.pop().unwrap()
+}
+
+/// Makes `base.` the prefix of the leftmost operand of a normalized expression,
+/// `parsed` being the member access as parsed
+fn attach_member(base: Expr, parsed: &ExprMember, member: Expr) -> Expr {
+    match member {
+        Expr::Call(mut call) => {
+            call.expr = Box::new(attach_member(base, parsed, *call.expr));
+            Expr::Call(call)
+        }
+        Expr::CallOptions(mut call) => {
+            call.expr = Box::new(attach_member(base, parsed, *call.expr));
+            Expr::CallOptions(call)
+        }
+        Expr::Index(mut index) => {
+            index.expr = Box::new(attach_member(base, parsed, *index.expr));
+            Expr::Index(index)
+        }
+        Expr::Member(mut member) => {
+            member.expr = Box::new(attach_member(base, parsed, *member.expr));
+            Expr::Member(member)
+        }
+        Expr::Binary(mut binary) => {
+            binary.left = Box::new(attach_member(base, parsed, *binary.left));
+            Expr::Binary(binary)
+        }
+        Expr::Postfix(mut postfix) => {
+            postfix.expr = Box::new(attach_member(base, parsed, *postfix.expr));
+            Expr::Postfix(postfix)
+        }
+        Expr::Ternary(mut ternary) => {
+            ternary.cond = Box::new(attach_member(base, parsed, *ternary.cond));
+            Expr::Ternary(ternary)
+        }
+        member => Expr::Member(ExprMember {
+            expr: Box::new(base),
+            dot_token: parsed.dot_token,
+            member: Box::new(member),
+        }),
+    }
+}
+
+/// Applies a unary operator to the leftmost operand of a normalized expression
+fn prefix_unary(op: UnOp, expr: Expr) -> Expr {
+    match expr {
+        Expr::Binary(mut binary) => {
+            binary.left = Box::new(prefix_unary(op, *binary.left));
+            Expr::Binary(binary)
+        }
+        Expr::Ternary(mut ternary) => {
+            ternary.cond = Box::new(prefix_unary(op, *ternary.cond));
+            Expr::Ternary(ternary)
+        }
+        expr => Expr::Unary(ExprUnary {
+            op,
+            expr: Box::new(expr),
+        }),
+    }
+}
+
+pub(crate) fn is_assignment(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::Assign(..)
+            | BinOp::AddAssign(..)
+            | BinOp::SubAssign(..)
+            | BinOp::MulAssign(..)
+            | BinOp::DivAssign(..)
+            | BinOp::RemAssign(..)
+            | BinOp::BitAndAssign(..)
+            | BinOp::BitOrAssign(..)
+            | BinOp::BitXorAssign(..)
+            | BinOp::SarAssign(..)
+            | BinOp::ShlAssign(..)
+            | BinOp::ShrAssign(..)
+    )
+}
+
+/// The variable an lvalue such as `a[i].b` refers to
+pub(crate) fn root_ident(expr: &Expr) -> Option<&SolIdent> {
+    match expr {
+        Expr::Ident(ident) => Some(ident),
+        Expr::Index(index) => root_ident(&index.expr),
+        Expr::Member(member) => root_ident(&member.expr),
+        _ => None,
+    }
+}
+
+/// The receiver and the name of a normalized `base.name(...)` call, with or without call options
+pub(crate) fn member_call(call: &ExprCall) -> Option<(&Expr, &SolIdent)> {
+    let callee = match &*call.expr {
+        Expr::CallOptions(options) => &*options.expr,
+        callee => callee,
+    };
+    match callee {
+        Expr::Member(member) => match &*member.member {
+            Expr::Ident(name) => Some((&member.expr, name)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether a call passes a `value` option, sending ether
+pub(crate) fn sends_value(call: &ExprCall) -> bool {
+    match &*call.expr {
+        Expr::CallOptions(options) => options.args.list.iter().any(|arg| arg.name == "value"),
+        _ => false,
+    }
+}
+
+pub(crate) fn all_contracts(files: &[SolidFile]) -> impl Iterator<Item = &ItemContract> {
+    files.iter().flat_map(|file| {
+        file.data.items.iter().filter_map(|item| match item {
+            Item::Contract(contract) => Some(contract),
+            _ => None,
+        })
+    })
+}
+
+/// Names and types known in a contract: its state variables, including the inherited ones,
+/// and the contracts and interfaces which can be called
+pub(crate) struct ContractContext<'a> {
+    pub state_variables: HashMap<String, &'a VariableDefinition>,
+    contract_types: HashSet<String>,
+    other_types: HashSet<String>,
+}
+
+impl<'a> ContractContext<'a> {
+    pub fn new(contract: &'a ItemContract, files: &'a [SolidFile]) -> Self {
+        let mut context = ContractContext {
+            state_variables: HashMap::new(),
+            contract_types: HashSet::new(),
+            other_types: HashSet::new(),
+        };
+        for file in files {
+            for item in &file.data.items {
+                context.add_type(item);
+            }
+        }
+        for contract in all_contracts(files) {
+            if !contract.kind.is_library() {
+                context.contract_types.insert(contract.name.to_string());
+            }
+            for item in &contract.body {
+                context.add_type(item);
+            }
+        }
+
+        // Bases are visited before the contracts inheriting from them, as in the storage layout
+        let mut visited = HashSet::new();
+        context.add_state_variables(contract, files, &mut visited);
+        context
+    }
+
+    fn add_type(&mut self, item: &Item) {
+        match item {
+            Item::Struct(strukt) => self.other_types.insert(strukt.name.to_string()),
+            Item::Enum(enumm) => self.other_types.insert(enumm.name.to_string()),
+            Item::Udt(udt) => self.other_types.insert(udt.name.to_string()),
+            _ => false,
+        };
+    }
+
+    fn add_state_variables(
+        &mut self,
+        contract: &'a ItemContract,
+        files: &'a [SolidFile],
+        visited: &mut HashSet<String>,
+    ) {
+        if !visited.insert(contract.name.to_string()) {
+            return;
+        }
+        if let Some(inheritance) = &contract.inheritance {
+            for base in &inheritance.inheritance {
+                let name = base.name.last().to_string();
+                if let Some(base) = all_contracts(files).find(|contract| contract.name == name) {
+                    self.add_state_variables(base, files, visited);
+                }
+            }
+        }
+        for item in &contract.body {
+            if let Item::Variable(variable) = item {
+                self.state_variables
+                    .insert(variable.name.to_string(), variable);
+            }
+        }
+    }
+
+    /// Whether a type is a contract or an interface, assuming that the custom types which are not declared
+    /// in the known files are imported contracts
+    pub fn is_contract_type(&self, ty: &Type) -> bool {
+        match ty {
+            Type::Custom(path) => {
+                let name = path.last().to_string();
+                self.contract_types.contains(&name) || !self.other_types.contains(&name)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_contract_name(&self, name: &str) -> bool {
+        self.contract_types.contains(name)
+    }
+}
+
+/// Collects the expressions of a function body, normalized, and the loops evaluating them
+struct ExprsVisitor {
+    exprs: Vec<(Expr, bool)>,
+    loops: Vec<Vec<Expr>>,
+    active_loops: Vec<usize>,
+}
+
+impl ExprsVisitor {
+    fn new() -> Self {
+        Self {
+            exprs: Vec::new(),
+            loops: Vec::new(),
+            active_loops: Vec::new(),
+        }
+    }
+
+    fn enter_loop(&mut self) {
+        self.active_loops.push(self.loops.len());
+        self.loops.push(Vec::new());
+    }
+}
+
+impl<'ast> Visit<'ast> for ExprsVisitor {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        let expr = normalize_expr(expr);
+        for active in &self.active_loops {
+            self.loops[*active].push(expr.clone());
+        }
+        self.exprs.push((expr, !self.active_loops.is_empty()));
+    }
+
+    fn visit_stmt_for(&mut self, stmt: &'ast StmtFor) {
+        match &stmt.init {
+            ForInitStmt::Expr(init) => self.visit_expr(&init.expr),
+            ForInitStmt::VarDecl(init) => self.visit_stmt_var_decl(init),
+            ForInitStmt::Empty(_) => {}
+        }
+        self.enter_loop();
+        self.visit_stmt(&stmt.body);
+        if let Some(cond) = &stmt.cond {
+            self.visit_expr(cond);
+        }
+        if let Some(post) = &stmt.post {
+            self.visit_expr(post);
+        }
+        self.active_loops.pop();
+    }
+
+    fn visit_stmt_while(&mut self, stmt: &'ast StmtWhile) {
+        self.enter_loop();
+        visit::visit_stmt_while(self, stmt);
+        self.active_loops.pop();
+    }
+
+    fn visit_stmt_dowhile(&mut self, stmt: &'ast StmtDoWhile) {
+        self.enter_loop();
+        visit::visit_stmt_dowhile(self, stmt);
+        self.active_loops.pop();
+    }
+}
+
+fn visit_function_exprs(function: &ItemFunction) -> ExprsVisitor {
+    let mut visitor = ExprsVisitor::new();
+    if let FunctionBody::Block(block) = &function.body {
+        visitor.visit_block(block);
+    }
+    visitor
+}
+
+/// The expressions of a function body, normalized, and whether they are evaluated in a loop
+pub(crate) fn function_exprs(function: &ItemFunction) -> Vec<(Expr, bool)> {
+    visit_function_exprs(function).exprs
+}
+
+/// The normalized expressions evaluated at each iteration of each loop of a function,
+/// including the ones of the nested loops
+pub(crate) fn function_loops(function: &ItemFunction) -> Vec<Vec<Expr>> {
+    visit_function_exprs(function).loops
+}
+
+struct CallsVisitor<'a> {
+    calls: Vec<&'a ExprCall>,
+}
+
+impl<'a> Visit<'a> for CallsVisitor<'a> {
+    fn visit_expr_call(&mut self, call: &'a ExprCall) {
+        self.calls.push(call);
+        visit::visit_expr_call(self, call);
+    }
+}
+
+/// The calls made by an expression, outermost first
+pub(crate) fn expr_calls(expr: &Expr) -> Vec<&ExprCall> {
+    let mut visitor = CallsVisitor { calls: Vec::new() };
+    visitor.visit_expr(expr);
+    visitor.calls
+}
+
+struct VariablesVisitor {
+    variables: HashMap<String, Type>,
+}
+
+impl<'ast> Visit<'ast> for VariablesVisitor {
+    fn visit_variable_declaration(&mut self, declaration: &'ast VariableDeclaration) {
+        if let Some(name) = &declaration.name {
+            self.variables
+                .insert(name.to_string(), declaration.ty.clone());
+        }
+    }
+}
+
+/// Types of the parameters and local variables of a function, regardless of their scope
+pub(crate) fn function_variables(function: &ItemFunction) -> HashMap<String, Type> {
+    let mut visitor = VariablesVisitor {
+        variables: HashMap::new(),
+    };
+    visitor.visit_item_function(function);
+    visitor.variables
+}
+
+/// Strips the conversions of an address, as in `payable(address(target))`
+pub(crate) fn strip_conversions(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Payable(payable) => match &payable.args.list {
+            ArgListImpl::Unnamed(args) if args.len() == 1 => strip_conversions(&args[0]),
+            _ => expr,
+        },
+        Expr::Call(call) if matches!(&*call.expr, Expr::Type(_)) => match &call.args.list {
+            ArgListImpl::Unnamed(args) if args.len() == 1 => strip_conversions(&args[0]),
+            _ => expr,
+        },
+        expr => expr,
+    }
+}
+
+struct VariableIdentsVisitor<'a> {
+    idents: Vec<&'a SolIdent>,
+}
+
+impl<'a> Visit<'a> for VariableIdentsVisitor<'a> {
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        match expr {
+            Expr::Ident(ident) => self.idents.push(ident),
+            // The member is a name in the type of the base, not a variable
+            Expr::Member(member) => self.visit_expr(&member.expr),
+            Expr::Type(_) | Expr::TypeCall(_) | Expr::New(_) => {}
+            expr => visit::visit_expr(self, expr),
+        }
+    }
+}
+
+/// The identifiers used as variables by a normalized expression
+pub(crate) fn variable_idents(expr: &Expr) -> Vec<&SolIdent> {
+    let mut visitor = VariableIdentsVisitor { idents: Vec::new() };
+    visitor.visit_expr(expr);
+    visitor.idents
+}
+
+struct WritesVisitor {
+    variables: Vec<String>,
+}
+
+impl WritesVisitor {
+    fn write(&mut self, lvalue: &Expr) {
+        match lvalue {
+            Expr::Tuple(tuple) => {
+                for elem in &tuple.elems {
+                    self.write(elem);
+                }
+            }
+            lvalue => self
+                .variables
+                .extend(root_ident(lvalue).map(|ident| ident.to_string())),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for WritesVisitor {
+    fn visit_expr_binary(&mut self, binary: &'ast ExprBinary) {
+        if is_assignment(&binary.op) {
+            self.write(&binary.left);
+        }
+        visit::visit_expr_binary(self, binary);
+    }
+
+    fn visit_expr_unary(&mut self, unary: &'ast ExprUnary) {
+        if matches!(unary.op, UnOp::Increment(..) | UnOp::Decrement(..)) {
+            self.write(&unary.expr);
+        }
+        visit::visit_expr_unary(self, unary);
+    }
+
+    fn visit_expr_postfix(&mut self, postfix: &'ast ExprPostfix) {
+        self.write(&postfix.expr);
+        visit::visit_expr_postfix(self, postfix);
+    }
+
+    fn visit_expr_delete(&mut self, delete: &'ast ExprDelete) {
+        self.write(&delete.expr);
+        visit::visit_expr_delete(self, delete);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if let Some((base, name)) = member_call(call) {
+            if name == "push" || name == "pop" {
+                self.write(base);
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+}
+
+/// The variables modified by a normalized expression, through an assignment, an increment,
+/// a `delete`, or a `push` or `pop` on an array
+pub(crate) fn written_variables(expr: &Expr) -> Vec<String> {
+    let mut visitor = WritesVisitor {
+        variables: Vec::new(),
+    };
+    visitor.visit_expr(expr);
+    visitor.variables
+}
+
+/// Collects the names referenced by expressions, types, modifier invocations, inheritance lists
+/// and using directives, but not the names being declared
+#[derive(Default)]
+struct ReferencesVisitor {
+    names: HashSet<String>,
+}
+
+impl ReferencesVisitor {
+    fn add_path(&mut self, path: &SolPath) {
+        for segment in path.iter() {
+            self.names.insert(segment.to_string());
+        }
+    }
+
+    fn add_modifier(&mut self, modifier: &Modifier) {
+        self.add_path(&modifier.name);
+        for argument in &modifier.arguments {
+            self.visit_expr(argument);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ReferencesVisitor {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::Ident(ident) = expr {
+            self.names.insert(ident.to_string());
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_path(&mut self, path: &'ast SolPath) {
+        self.add_path(path);
+    }
+
+    fn visit_stmt_asm(&mut self, assembly: &'ast StmtAssembly) {
+        // Yul is not visited, its paths may reference declarations made outside the block
+        for ident in retriever::retrieve_yul_path_roots(&assembly.block) {
+            self.names.insert(ident.to_string());
+        }
+    }
+
+    fn visit_variable_definition(&mut self, variable: &'ast VariableDefinition) {
+        self.visit_type(&variable.ty);
+        if let Some((_, initializer)) = &variable.initializer {
+            self.visit_expr(initializer);
+        }
+    }
+
+    fn visit_item_function(&mut self, function: &'ast ItemFunction) {
+        for attribute in function.attributes.iter() {
+            if let FunctionAttribute::Modifier(modifier) = attribute {
+                self.add_modifier(modifier);
+            }
+        }
+        visit::visit_item_function(self, function);
+    }
+
+    fn visit_item_contract(&mut self, contract: &'ast ItemContract) {
+        if let Some(inheritance) = &contract.inheritance {
+            for base in &inheritance.inheritance {
+                self.add_modifier(base);
+            }
+        }
+        visit::visit_item_contract(self, contract);
+    }
+
+    fn visit_using_directive(&mut self, using: &'ast UsingDirective) {
+        match &using.list {
+            UsingList::Single(path) => self.add_path(path),
+            UsingList::Multiple(_, items) => {
+                for item in items {
+                    self.add_path(&item.path);
+                }
+            }
+        }
+        if let UsingType::Type(ty) = &using.ty {
+            self.visit_type(ty);
+        }
+    }
+}
+
+/// Names referenced in a set of files
+pub(crate) fn files_references(files: &[SolidFile]) -> HashSet<String> {
+    let mut visitor = ReferencesVisitor::default();
+    for file in files {
+        visitor.visit_file(&file.data);
+    }
+    visitor.names
+}
+
+/// Names referenced in a contract
+pub(crate) fn contract_references(contract: &ItemContract) -> HashSet<String> {
+    let mut visitor = ReferencesVisitor::default();
+    visitor.visit_item_contract(contract);
+    visitor.names
+}
+
+/// Names referenced in a function, including its modifier invocations
+pub(crate) fn function_references(function: &ItemFunction) -> HashSet<String> {
+    let mut visitor = ReferencesVisitor::default();
+    visitor.visit_item_function(function);
+    visitor.names
+}
+
+/// Whether a contract is deployed behind a proxy: it inherits from `Initializable` or from an upgradeable base
+/// such as `OwnableUpgradeable`, even when those bases are imported from files which are not linted
+pub(crate) fn is_upgradeable(contract: &ItemContract, definitions: &Definitions<'_>) -> bool {
+    !contract.kind.is_interface()
+        && !contract.kind.is_library()
+        && definitions
+            .linearize(contract)
+            .unwrap_or_default()
+            .iter()
+            .skip(1)
+            .any(|base| base == "Initializable" || base.ends_with("Upgradeable"))
+}
+
+/// Whether the file only compiles with solidity 0.8 or later, where arithmetic operations are checked.
+/// Files without version pragma are assumed to target a recent compiler.
+pub(crate) fn has_checked_arithmetic(file: &File) -> bool {
+    requires_version(file, Version::new(0, 8, 0))
+}
+
+/// Names an import brings in the scope of the file
+pub(crate) fn imported_names(import: &ImportDirective) -> Vec<&SolIdent> {
+    match &import.path {
+        ImportPath::Plain(plain) => plain.alias.iter().map(|alias| &alias.alias).collect(),
+        ImportPath::Glob(glob) => glob.alias.iter().map(|alias| &alias.alias).collect(),
+        ImportPath::Aliases(aliases) => aliases
+            .imports
+            .iter()
+            .map(|(name, alias)| alias.as_ref().map_or(name, |alias| &alias.alias))
+            .collect(),
+    }
+}
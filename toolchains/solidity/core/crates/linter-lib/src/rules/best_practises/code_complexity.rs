@@ -0,0 +1,99 @@
+use crate::linter::SolidFile;
+use crate::metrics::function_metrics;
+use crate::rules::scope::file_functions;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "code-complexity";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+const DEFAULT_MAX_COMPLEXITY: usize = 7;
+
+pub struct CodeComplexity {
+    max_complexity: usize,
+    data: RuleEntry,
+}
+
+impl CodeComplexity {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for CodeComplexity {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for function in file_functions(&file.data) {
+            let metrics = function_metrics(function);
+            if metrics.cyclomatic_complexity > self.max_complexity {
+                let span = function.span();
+                res.push(self.create_diag(
+                    (span.start(), span.end()),
+                    format!(
+                        "Function has a cyclomatic complexity of {} but allowed no more than {}",
+                        metrics.cyclomatic_complexity, self.max_complexity
+                    ),
+                    file,
+                ));
+            }
+        }
+        res
+    }
+}
+
+impl CodeComplexity {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let mut max_complexity = DEFAULT_MAX_COMPLEXITY;
+
+        if let Some(data) = &data.data {
+            let parsed: Result<usize, serde_json::Error> = serde_json::from_value(data.clone());
+            match parsed {
+                Ok(val) => max_complexity = val,
+                Err(_) => {
+                    eprintln!("{} rule : bad config data", RULE_ID);
+                }
+            }
+        } else {
+            eprintln!("{} rule : bad config data", RULE_ID);
+        }
+        let rule = CodeComplexity {
+            max_complexity,
+            data,
+        };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: Some(DEFAULT_MAX_COMPLEXITY.into()),
+        }
+    }
+}
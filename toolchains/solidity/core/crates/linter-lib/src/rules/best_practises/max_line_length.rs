@@ -44,13 +44,11 @@ impl MaxLineLength {
 impl RuleType for MaxLineLength {
     fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
         let mut res = Vec::new();
-        let mut line_idx = 1;
 
-        for line in file.content.lines() {
+        for (line_idx, line) in (1..).zip(file.content.lines()) {
             if line.len() > self.max_len {
                 res.push(self.create_diag(file, line_idx, line));
             }
-            line_idx += 1;
         }
         res
     }
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 
 #[macro_use]
 pub mod max_line_length;
+pub mod code_complexity;
 pub mod custom_errors;
 pub mod explicit_types;
 pub mod function_max_lines;
@@ -13,8 +14,10 @@ pub mod no_global_import;
 pub mod one_contract_per_file;
 pub mod payable_fallback;
 pub mod reason_string;
+pub mod state_mutability;
 
 // List all rules
+use crate::rules::best_practises::code_complexity::CodeComplexity;
 use crate::rules::best_practises::custom_errors::CustomErrors;
 use crate::rules::best_practises::explicit_types::ExplicitTypes;
 use crate::rules::best_practises::function_max_lines::FunctionMaxLines;
@@ -26,6 +29,7 @@ use crate::rules::best_practises::no_global_import::NoGlobalImport;
 use crate::rules::best_practises::one_contract_per_file::OneContractPerFile;
 use crate::rules::best_practises::payable_fallback::PayableFallback;
 use crate::rules::best_practises::reason_string::ReasonString;
+use crate::rules::best_practises::state_mutability::StateMutability;
 use crate::rules::RuleBuilder;
 
 pub fn create_default_rules() -> Vec<RuleEntry> {
@@ -41,6 +45,8 @@ pub fn create_default_rules() -> Vec<RuleEntry> {
         NoEmptyBlock::create_default(),
         ExplicitTypes::create_default(),
         PayableFallback::create_default(),
+        CodeComplexity::create_default(),
+        StateMutability::create_default(),
     ]
 }
 
@@ -74,6 +80,11 @@ pub fn create_rules() -> RulesMap {
         payable_fallback::RULE_ID.to_string(),
         PayableFallback::create,
     );
+    rules.insert(code_complexity::RULE_ID.to_string(), CodeComplexity::create);
+    rules.insert(
+        state_mutability::RULE_ID.to_string(),
+        StateMutability::create,
+    );
 
     rules
 }
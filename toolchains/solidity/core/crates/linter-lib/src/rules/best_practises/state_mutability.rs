@@ -0,0 +1,114 @@
+use crate::linter::SolidFile;
+use crate::rules::mutability::{MutabilityAnalyzer, StateMutability as Mutability};
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "state-mutability";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct StateMutability {
+    data: RuleEntry,
+}
+
+impl StateMutability {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for StateMutability {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            if contract.kind.is_interface() {
+                continue;
+            }
+            let analyzer = MutabilityAnalyzer::new(&contract, files);
+            for item in &contract.body {
+                let Item::Function(function) = item else {
+                    continue;
+                };
+                if !function.kind.is_function()
+                    || function.attributes.has_virtual()
+                    || !matches!(function.body, FunctionBody::Block(_))
+                {
+                    continue;
+                }
+                let Some(name) = &function.name else {
+                    continue;
+                };
+                let declared = Mutability::declared(function);
+                if declared == Mutability::Payable {
+                    continue;
+                }
+                let requirement = analyzer.requirement(function);
+                let message = if requirement.mutability > declared {
+                    let action = if requirement.mutability == Mutability::View {
+                        "reads"
+                    } else {
+                        "modifies"
+                    };
+                    format!(
+                        "Function '{}' is declared {} but {} the state",
+                        name,
+                        declared.describe(),
+                        action
+                    )
+                } else if requirement.mutability < declared && requirement.inferred {
+                    format!(
+                        "Function '{}' can be declared {}",
+                        name,
+                        requirement.mutability.describe()
+                    )
+                } else {
+                    continue;
+                };
+                let span = name.span();
+                res.push(self.create_diag((span.start(), span.end()), message, file));
+            }
+        }
+        res
+    }
+}
+
+impl StateMutability {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = StateMutability { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
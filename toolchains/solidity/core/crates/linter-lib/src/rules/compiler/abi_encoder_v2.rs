@@ -0,0 +1,88 @@
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::rules::version::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "abi-encoder-v2";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct AbiEncoderV2 {
+    data: RuleEntry,
+}
+
+impl AbiEncoderV2 {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for AbiEncoderV2 {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        if !requires_version(&file.data, Version::new(0, 8, 0)) {
+            return res;
+        }
+        for item in &file.data.items {
+            if let Item::Pragma(
+                pragma @ PragmaDirective {
+                    tokens: PragmaTokens::Experimental(_, feature),
+                    ..
+                },
+            ) = item
+            {
+                if *feature == "ABIEncoderV2" {
+                    let span = pragma.span();
+                    res.push(self.create_diag(
+                        (span.start(), span.end()),
+                        "ABI coder v2 is enabled by default since solidity 0.8, remove 'pragma experimental ABIEncoderV2'"
+                            .to_string(),
+                        file,
+                    ));
+                }
+            }
+        }
+        res
+    }
+}
+
+impl AbiEncoderV2 {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = AbiEncoderV2 { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::rules::version::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "compiler-version";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::ERROR;
+const DEFAULT_ALLOWED: &str = "^0.8.0";
+
+pub struct CompilerVersion {
+    constraint: String,
+    allowed: VersionRange,
+    data: RuleEntry,
+}
+
+impl CompilerVersion {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for CompilerVersion {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for pragma in version_pragmas(&file.data) {
+            let Some(range) = &pragma.range else {
+                continue;
+            };
+            if !range.is_subset(&self.allowed) {
+                let span = pragma.directive.span();
+                res.push(self.create_diag(
+                    (span.start(), span.end()),
+                    format!(
+                        "Compiler version '{}' does not satisfy the allowed range '{}'",
+                        pragma.constraint, self.constraint
+                    ),
+                    file,
+                ));
+            }
+        }
+        res
+    }
+}
+
+impl CompilerVersion {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let mut constraint = DEFAULT_ALLOWED.to_string();
+
+        if let Some(data) = &data.data {
+            let parsed: Result<String, serde_json::Error> = serde_json::from_value(data.clone());
+            match parsed {
+                Ok(val) if VersionRange::parse(&val).is_some() => constraint = val,
+                _ => {
+                    eprintln!("{} rule : bad config data", RULE_ID);
+                }
+            }
+        } else {
+            eprintln!("{} rule : bad config data", RULE_ID);
+        }
+        let allowed = VersionRange::parse(&constraint).unwrap_or_else(VersionRange::any);
+        let rule = CompilerVersion {
+            constraint,
+            allowed,
+            data,
+        };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: Some(DEFAULT_ALLOWED.into()),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::rules::version::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "floating-pragma";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct FloatingPragma {
+    data: RuleEntry,
+}
+
+impl FloatingPragma {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for FloatingPragma {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        // Libraries and interfaces are meant to be compiled with the version chosen by their users
+        let contracts = retriever::retrieve_contract_nodes(&file.data);
+        if !contracts.is_empty()
+            && contracts
+                .iter()
+                .all(|contract| contract.kind.is_library() || contract.kind.is_interface())
+        {
+            return res;
+        }
+        for pragma in version_pragmas(&file.data) {
+            if pragma.range.as_ref().is_some_and(|range| !range.is_exact()) {
+                let span = pragma.directive.span();
+                res.push(self.create_diag(
+                    (span.start(), span.end()),
+                    format!(
+                        "Floating compiler version '{}', pin the version the code is tested with",
+                        pragma.constraint
+                    ),
+                    file,
+                ));
+            }
+        }
+        res
+    }
+}
+
+impl FloatingPragma {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = FloatingPragma { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
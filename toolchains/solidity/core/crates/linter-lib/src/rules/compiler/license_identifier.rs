@@ -0,0 +1,134 @@
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "license-identifier";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+const SPDX_MARKER: &str = "SPDX-License-Identifier:";
+
+pub struct LicenseIdentifier {
+    allowed: Vec<String>,
+    data: RuleEntry,
+}
+
+impl LicenseIdentifier {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+
+    /// Licenses of an SPDX expression such as `MIT OR Apache-2.0`, with their offset in the expression
+    fn expression_licenses(expression: &str) -> Vec<(usize, &str)> {
+        let mut licenses = Vec::new();
+        let mut offset = 0;
+        for word in expression.split(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+            if !word.is_empty() && !matches!(word, "AND" | "OR" | "WITH" | "and" | "or" | "with") {
+                licenses.push((offset, word));
+            }
+            offset += word.len() + 1;
+        }
+        licenses
+    }
+}
+
+impl RuleType for LicenseIdentifier {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        let identifier = (1..)
+            .zip(file.content.lines())
+            .find_map(|(line_idx, line)| {
+                let start = line.find(SPDX_MARKER)? + SPDX_MARKER.len();
+                let expression = line[start..].split("*/").next().unwrap_or_default();
+                let skipped = expression.len() - expression.trim_start().len();
+                Some((line_idx, start + skipped, expression.trim()))
+            });
+        let Some((line, start, expression)) = identifier else {
+            let start = LineColumn { line: 1, column: 0 };
+            res.push(self.create_diag(
+                (start, start),
+                "Missing SPDX license identifier".to_string(),
+                file,
+            ));
+            return res;
+        };
+        if self.allowed.is_empty() {
+            return res;
+        }
+        for (offset, license) in LicenseIdentifier::expression_licenses(expression) {
+            if !self.allowed.iter().any(|allowed| allowed == license) {
+                let column = start + offset;
+                res.push(self.create_diag(
+                    (
+                        LineColumn { line, column },
+                        LineColumn {
+                            line,
+                            column: column + license.len(),
+                        },
+                    ),
+                    format!(
+                        "License '{}' is not allowed, use one of {}",
+                        license,
+                        self.allowed.join(", ")
+                    ),
+                    file,
+                ));
+            }
+        }
+        res
+    }
+}
+
+impl LicenseIdentifier {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let mut allowed: Vec<String> = Vec::new();
+
+        if let Some(data) = &data.data {
+            let parsed: Result<Vec<String>, serde_json::Error> =
+                serde_json::from_value(data.clone());
+            match parsed {
+                Ok(val) => allowed = val,
+                Err(_) => {
+                    eprintln!("{} rule : bad config data", RULE_ID);
+                }
+            }
+        } else {
+            eprintln!("{} rule : bad config data", RULE_ID);
+        }
+        let rule = LicenseIdentifier { allowed, data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: Some(serde_json::json!([])),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::rules::types::{RuleEntry, RulesMap};
+use std::collections::HashMap;
+
+pub(crate) mod abi_encoder_v2;
+pub(crate) mod compiler_version;
+pub(crate) mod floating_pragma;
+pub(crate) mod license_identifier;
+pub(crate) mod pragma_consistency;
+pub(crate) mod solidity_pragma;
+
+// List all rules
+use crate::rules::compiler::abi_encoder_v2::AbiEncoderV2;
+use crate::rules::compiler::compiler_version::CompilerVersion;
+use crate::rules::compiler::floating_pragma::FloatingPragma;
+use crate::rules::compiler::license_identifier::LicenseIdentifier;
+use crate::rules::compiler::pragma_consistency::PragmaConsistency;
+use crate::rules::compiler::solidity_pragma::SolidityPragma;
+use crate::rules::RuleBuilder;
+
+pub fn create_default_rules() -> Vec<RuleEntry> {
+    vec![
+        AbiEncoderV2::create_default(),
+        CompilerVersion::create_default(),
+        FloatingPragma::create_default(),
+        LicenseIdentifier::create_default(),
+        PragmaConsistency::create_default(),
+        SolidityPragma::create_default(),
+    ]
+}
+
+pub fn create_rules() -> RulesMap {
+    let mut rules: HashMap<String, RuleBuilder> = HashMap::new();
+
+    rules.insert(abi_encoder_v2::RULE_ID.to_string(), AbiEncoderV2::create);
+    rules.insert(
+        compiler_version::RULE_ID.to_string(),
+        CompilerVersion::create,
+    );
+    rules.insert(floating_pragma::RULE_ID.to_string(), FloatingPragma::create);
+    rules.insert(
+        license_identifier::RULE_ID.to_string(),
+        LicenseIdentifier::create,
+    );
+    rules.insert(
+        pragma_consistency::RULE_ID.to_string(),
+        PragmaConsistency::create,
+    );
+    rules.insert(solidity_pragma::RULE_ID.to_string(), SolidityPragma::create);
+
+    rules
+}
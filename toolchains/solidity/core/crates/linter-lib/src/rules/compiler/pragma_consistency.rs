@@ -0,0 +1,184 @@
+use crate::imports::ImportResolver;
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::rules::version::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::extract::extract_ast_from_content;
+use osmium_libs_solidity_ast_extractor::*;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+// global
+pub const RULE_ID: &str = "pragma-consistency";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+/// What the check needs to know about a file of the import graph
+#[derive(Clone)]
+struct ImportedFile {
+    range: Option<VersionRange>,
+    constraints: Vec<String>,
+    imports: Vec<String>,
+}
+
+impl ImportedFile {
+    fn new(file: &File) -> Self {
+        ImportedFile {
+            range: file_version_range(file),
+            constraints: version_pragmas(file)
+                .into_iter()
+                .map(|pragma| pragma.constraint)
+                .collect(),
+            imports: file
+                .items
+                .iter()
+                .filter_map(|item| match item {
+                    Item::Import(import) => Some(import.path.path().value()),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+pub struct PragmaConsistency {
+    resolver: ImportResolver,
+    /// Imported files which are not linted, parsed again only once modified
+    parsed: Mutex<HashMap<PathBuf, (SystemTime, Option<ImportedFile>)>>,
+    data: RuleEntry,
+}
+
+impl PragmaConsistency {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+
+    /// Parses an imported file read from the disk, reusing its last parsing if it was not modified
+    fn parse_import(&self, path: &Path) -> Option<ImportedFile> {
+        let modified = std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()?;
+        let mut parsed = self.parsed.lock().unwrap();
+        if let Some((parsed_at, file)) = parsed.get(path) {
+            if *parsed_at == modified {
+                return file.clone();
+            }
+        }
+        let file = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| extract_ast_from_content(&content).ok())
+            .map(|file| ImportedFile::new(&file));
+        parsed.insert(path.to_path_buf(), (modified, file.clone()));
+        file
+    }
+
+    fn resolve_imports(&self, from: &Path, file: &ImportedFile) -> Vec<PathBuf> {
+        file.imports
+            .iter()
+            .filter_map(|import| self.resolver.resolve(from, import))
+            .collect()
+    }
+}
+
+impl RuleType for PragmaConsistency {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        let pragmas = version_pragmas(&file.data);
+        let (Some(pragma), Some(range)) = (pragmas.first(), file_version_range(&file.data)) else {
+            return res;
+        };
+
+        // Every file of the import graph is compiled with the same compiler
+        let path = PathBuf::from(&file.path);
+        let mut visited = HashSet::from([path.clone()]);
+        let mut queue = self.resolve_imports(&path, &ImportedFile::new(&file.data));
+        while let Some(path) = queue.pop() {
+            if !visited.insert(path.clone()) {
+                continue;
+            }
+            let imported = match files.iter().find(|file| Path::new(&file.path) == path) {
+                Some(file) => ImportedFile::new(&file.data),
+                None => match self.parse_import(&path) {
+                    Some(imported) => imported,
+                    None => continue,
+                },
+            };
+            if let Some(imported_range) = &imported.range {
+                if range.intersection(imported_range).is_empty() {
+                    let span = pragma.directive.span();
+                    res.push(self.create_diag(
+                        (span.start(), span.end()),
+                        format!(
+                            "Compiler version '{}' has no version in common with '{}' required by '{}'",
+                            pragma.constraint,
+                            imported.constraints.join(" "),
+                            path.strip_prefix(self.resolver.root())
+                                .unwrap_or(&path)
+                                .display()
+                        ),
+                        file,
+                    ));
+                }
+            }
+            queue.extend(self.resolve_imports(&path, &imported));
+        }
+        res
+    }
+}
+
+impl PragmaConsistency {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let mut resolver = ImportResolver::default();
+
+        if let Some(data) = &data.data {
+            match &data["root"] {
+                Value::Null => {}
+                Value::String(root) => resolver = ImportResolver::from_root(Path::new(root)),
+                _ => eprintln!("{} rule : bad config data", RULE_ID),
+            }
+        }
+        let rule = PragmaConsistency {
+            resolver,
+            parsed: Mutex::new(HashMap::new()),
+            data,
+        };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: Some(serde_json::json!({
+                "root": null,
+            })),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::rules::version::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "solidity-pragma";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct SolidityPragma {
+    data: RuleEntry,
+}
+
+impl SolidityPragma {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for SolidityPragma {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let pragmas = version_pragmas(&file.data);
+
+        if pragmas.is_empty() {
+            let start = LineColumn { line: 1, column: 0 };
+            res.push(self.create_diag(
+                (start, start),
+                "Missing 'pragma solidity' directive, the file would be compiled with any version"
+                    .to_string(),
+                file,
+            ));
+        }
+        for pragma in pragmas {
+            let message = match &pragma.range {
+                None => format!("Invalid compiler version '{}'", pragma.constraint),
+                Some(range) if range.is_empty() => {
+                    format!("No compiler version satisfies '{}'", pragma.constraint)
+                }
+                Some(_) => continue,
+            };
+            let span = pragma.directive.span();
+            res.push(self.create_diag((span.start(), span.end()), message, file));
+        }
+        res
+    }
+}
+
+impl SolidityPragma {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = SolidityPragma { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
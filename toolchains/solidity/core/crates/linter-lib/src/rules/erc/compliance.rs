@@ -0,0 +1,402 @@
+use crate::linter::SolidFile;
+use osmium_libs_solidity_ast_extractor::abi::abi_type;
+use osmium_libs_solidity_ast_extractor::storage_layout::Definitions;
+use osmium_libs_solidity_ast_extractor::*;
+
+/// A function of a standard, with the ABI types of its parameters and returns
+pub(crate) struct FunctionSpec {
+    pub name: &'static str,
+    pub params: &'static [&'static str],
+    pub returns: &'static [&'static str],
+}
+
+/// An event of a standard, with the ABI type of its parameters and whether they are indexed
+pub(crate) struct EventSpec {
+    pub name: &'static str,
+    pub params: &'static [(&'static str, bool)],
+}
+
+/// The members a contract implementing a token standard must declare
+pub(crate) struct Standard {
+    pub name: &'static str,
+    /// Interfaces whose inheritance claims the standard
+    pub interfaces: &'static [&'static str],
+    /// Functions only found in implementations of the standard, which claim it when all are declared
+    pub distinctive: &'static [&'static str],
+    pub functions: &'static [FunctionSpec],
+    /// Functions of the usual extensions, checked when they are declared
+    pub optional_functions: &'static [FunctionSpec],
+    pub events: &'static [EventSpec],
+    /// Interface and its ERC-165 identifier, which `supportsInterface` must report
+    pub interface_id: Option<(&'static str, &'static str)>,
+}
+
+pub(crate) struct Violation {
+    pub location: (LineColumn, LineColumn),
+    pub message: String,
+}
+
+/// A function of the ABI of a contract, declared or generated for a public state variable
+struct AbiFunction<'a> {
+    name: String,
+    params: Vec<String>,
+    returns: Vec<String>,
+    location: (LineColumn, LineColumn),
+    function: Option<&'a ItemFunction>,
+}
+
+struct AbiEvent {
+    name: String,
+    params: Vec<(String, bool)>,
+    location: (LineColumn, LineColumn),
+}
+
+fn signature(name: &str, params: &[&str], returns: &[&str]) -> String {
+    if returns.is_empty() {
+        format!("{}({})", name, params.join(","))
+    } else {
+        format!(
+            "{}({}) returns ({})",
+            name,
+            params.join(","),
+            returns.join(",")
+        )
+    }
+}
+
+fn event_signature(name: &str, params: &[(&str, bool)]) -> String {
+    let params: Vec<String> = params
+        .iter()
+        .map(|(ty, indexed)| {
+            if *indexed {
+                format!("{} indexed", ty)
+            } else {
+                ty.to_string()
+            }
+        })
+        .collect();
+    format!("{}({})", name, params.join(","))
+}
+
+/// Whether a `supportsInterface` implementation reports an interface, or defers to an unknown base
+struct InterfaceIdVisitor<'a> {
+    interface: &'a str,
+    id: &'a str,
+    reported: bool,
+    calls_super: bool,
+}
+
+impl<'a, 'ast> Visit<'ast> for InterfaceIdVisitor<'a> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::TypeCall(call) => {
+                if let Type::Custom(path) = &call.ty {
+                    self.reported |= path.last() == self.interface;
+                }
+            }
+            Expr::Lit(Lit::Number(LitNumber::Int(number))) => {
+                self.reported |= number.to_string().eq_ignore_ascii_case(self.id);
+            }
+            Expr::Member(member) => {
+                if let Expr::Ident(base) = &*member.expr {
+                    self.calls_super |= base == "super";
+                }
+            }
+            _ => {}
+        }
+        visit::visit_expr(self, expr);
+    }
+}
+
+/// The ABI of a contract, including its known bases, from the most derived declarations
+struct ContractAbi<'a> {
+    functions: Vec<AbiFunction<'a>>,
+    events: Vec<AbiEvent>,
+    /// Linearized bases of the contract
+    bases: Vec<String>,
+    /// Bases which are not declared in the linted files
+    unknown_bases: Vec<String>,
+}
+
+impl<'a> ContractAbi<'a> {
+    fn new(
+        contract: &ItemContract,
+        definitions: &Definitions<'a>,
+        file: &'a SolidFile,
+    ) -> Option<Self> {
+        let mut abi = ContractAbi {
+            functions: Vec::new(),
+            events: Vec::new(),
+            bases: definitions.linearize(contract)?,
+            unknown_bases: Vec::new(),
+        };
+        for name in abi.bases.clone() {
+            let Some(base) = definitions.contract(&name) else {
+                abi.unknown_bases.push(name);
+                continue;
+            };
+            for item in &base.body {
+                abi.add_item(item, definitions);
+            }
+        }
+        // Events can also be declared at the file level since solidity 0.8.22
+        for item in &file.data.items {
+            if let Item::Event(_) = item {
+                abi.add_item(item, definitions);
+            }
+        }
+        Some(abi)
+    }
+
+    fn add_item(&mut self, item: &'a Item, definitions: &Definitions<'a>) {
+        match item {
+            Item::Function(function) => {
+                let Some(name) = &function.name else {
+                    return;
+                };
+                if matches!(
+                    function.attributes.visibility(),
+                    Some(Visibility::Internal(_) | Visibility::Private(_))
+                ) {
+                    return;
+                }
+                let types = |list: &ParameterList| {
+                    list.iter()
+                        .map(|param| abi_type(definitions, &param.ty))
+                        .collect::<Vec<String>>()
+                };
+                self.add_function(AbiFunction {
+                    name: name.to_string(),
+                    params: types(&function.arguments),
+                    returns: function
+                        .returns
+                        .as_ref()
+                        .map(|returns| types(&returns.returns))
+                        .unwrap_or_default(),
+                    location: (name.span().start(), name.span().end()),
+                    function: Some(function),
+                });
+            }
+            Item::Variable(variable) if variable.attributes.has_public() => {
+                // Getters take the keys of mappings and the indexes of arrays
+                let mut ty = &variable.ty;
+                let mut params = Vec::new();
+                loop {
+                    match ty {
+                        Type::Mapping(mapping) => {
+                            params.push(abi_type(definitions, &mapping.key));
+                            ty = &mapping.value;
+                        }
+                        Type::Array(array) => {
+                            params.push("uint256".to_string());
+                            ty = &array.ty;
+                        }
+                        _ => break,
+                    }
+                }
+                self.add_function(AbiFunction {
+                    name: variable.name.to_string(),
+                    params,
+                    returns: vec![abi_type(definitions, ty)],
+                    location: (variable.name.span().start(), variable.name.span().end()),
+                    function: None,
+                });
+            }
+            Item::Event(event) => {
+                if self.events.iter().any(|other| event.name == other.name) {
+                    return;
+                }
+                self.events.push(AbiEvent {
+                    name: event.name.to_string(),
+                    params: event
+                        .parameters
+                        .iter()
+                        .map(|param| (abi_type(definitions, &param.ty), param.indexed.is_some()))
+                        .collect(),
+                    location: (event.name.span().start(), event.name.span().end()),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Keeps the most derived declaration of overridden functions
+    fn add_function(&mut self, function: AbiFunction<'a>) {
+        if !self
+            .functions
+            .iter()
+            .any(|other| other.name == function.name && other.params == function.params)
+        {
+            self.functions.push(function);
+        }
+    }
+
+    fn named(&self, name: &str) -> impl Iterator<Item = &AbiFunction<'a>> + '_ {
+        let name = name.to_string();
+        self.functions
+            .iter()
+            .filter(move |function| function.name == name)
+    }
+}
+
+/// Whether a contract claims to implement a standard, by inheriting its interface or declaring its functions
+fn claims(standard: &Standard, abi: &ContractAbi<'_>) -> bool {
+    let inherits = abi
+        .bases
+        .iter()
+        .any(|base| standard.interfaces.contains(&base.as_str()));
+    inherits
+        || standard
+            .distinctive
+            .iter()
+            .all(|name| abi.named(name).next().is_some())
+}
+
+/// Members of a standard which a contract misses or declares differently
+pub(crate) fn check_compliance(
+    standard: &Standard,
+    contract: &ItemContract,
+    file: &SolidFile,
+    files: &[SolidFile],
+) -> Vec<Violation> {
+    let mut res = Vec::new();
+    if !contract.kind.is_contract() {
+        return res;
+    }
+    let definitions = Definitions::new(files.iter().map(|file| &file.data));
+    let Some(abi) = ContractAbi::new(contract, &definitions, file) else {
+        return res;
+    };
+    if !claims(standard, &abi) {
+        return res;
+    }
+    let contract_location = (contract.name.span().start(), contract.name.span().end());
+    // Members can be declared by bases we do not know, except by the interfaces of the standard
+    let complete = abi
+        .unknown_bases
+        .iter()
+        .all(|base| standard.interfaces.contains(&base.as_str()));
+    let events_complete = abi.unknown_bases.is_empty();
+
+    let required = standard.functions.iter().map(|spec| (spec, true));
+    let optional = standard.optional_functions.iter().map(|spec| (spec, false));
+    for (spec, is_required) in required.chain(optional) {
+        let expected = signature(spec.name, spec.params, spec.returns);
+        if let Some(function) = abi
+            .named(spec.name)
+            .find(|function| function.params == spec.params)
+        {
+            if function.returns != spec.returns {
+                res.push(Violation {
+                    location: function.location,
+                    message: format!(
+                        "'{}' returns {} but {} requires '{}'",
+                        spec.name,
+                        if function.returns.is_empty() {
+                            "nothing".to_string()
+                        } else {
+                            format!("({})", function.returns.join(","))
+                        },
+                        standard.name,
+                        expected
+                    ),
+                });
+            }
+            continue;
+        }
+        // A declaration with the same name and other parameters which is not another overload of the standard
+        let mismatched = abi.named(spec.name).find(|function| {
+            !standard
+                .functions
+                .iter()
+                .chain(standard.optional_functions)
+                .any(|other| other.name == spec.name && function.params == other.params)
+        });
+        match mismatched {
+            Some(function) => res.push(Violation {
+                location: function.location,
+                message: format!(
+                    "'{}({})' does not match {} '{}'",
+                    spec.name,
+                    function.params.join(","),
+                    standard.name,
+                    expected
+                ),
+            }),
+            None if is_required && complete => res.push(Violation {
+                location: contract_location,
+                message: format!(
+                    "'{}' is missing {} function '{}'",
+                    contract.name, standard.name, expected
+                ),
+            }),
+            None => {}
+        }
+    }
+
+    for spec in standard.events {
+        let expected = event_signature(spec.name, spec.params);
+        match abi.events.iter().find(|event| event.name == spec.name) {
+            Some(event) => {
+                let matches = event.params.len() == spec.params.len()
+                    && event.params.iter().zip(spec.params).all(
+                        |((ty, indexed), (spec_ty, spec_indexed))| {
+                            ty == spec_ty && indexed == spec_indexed
+                        },
+                    );
+                if !matches {
+                    res.push(Violation {
+                        location: event.location,
+                        message: format!(
+                            "Event '{}' does not match {} '{}'",
+                            spec.name, standard.name, expected
+                        ),
+                    });
+                }
+            }
+            None if events_complete => res.push(Violation {
+                location: contract_location,
+                message: format!(
+                    "'{}' is missing {} event '{}'",
+                    contract.name, standard.name, expected
+                ),
+            }),
+            None => {}
+        }
+    }
+
+    if let Some((interface, id)) = standard.interface_id {
+        let implementations: Vec<&AbiFunction<'_>> = abi
+            .named("supportsInterface")
+            .filter(|function| {
+                function
+                    .function
+                    .is_some_and(|function| !function.body.is_empty())
+            })
+            .collect();
+        let mut visitor = InterfaceIdVisitor {
+            interface,
+            id,
+            reported: false,
+            calls_super: false,
+        };
+        for implementation in &implementations {
+            if let Some(function) = implementation.function {
+                visitor.visit_item_function(function);
+            }
+        }
+        let deferred = visitor.calls_super && !abi.unknown_bases.is_empty();
+        if let Some(implementation) = implementations.first() {
+            if !visitor.reported && !deferred {
+                res.push(Violation {
+                    location: implementation.location,
+                    message: format!(
+                        "supportsInterface does not report the {} interface id {} (type({}).interfaceId)",
+                        standard.name, id, interface
+                    ),
+                });
+            }
+        }
+    }
+    res
+}
@@ -0,0 +1,146 @@
+use crate::linter::SolidFile;
+use crate::rules::erc::compliance::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "erc1155-compliance";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+/// [ERC-1155](https://eips.ethereum.org/EIPS/eip-1155), with the metadata URI extension
+const ERC1155: Standard = Standard {
+    name: "ERC-1155",
+    interfaces: &["IERC1155", "IERC1155MetadataURI"],
+    distinctive: &["safeBatchTransferFrom", "balanceOfBatch"],
+    functions: &[
+        FunctionSpec {
+            name: "safeTransferFrom",
+            params: &["address", "address", "uint256", "uint256", "bytes"],
+            returns: &[],
+        },
+        FunctionSpec {
+            name: "safeBatchTransferFrom",
+            params: &["address", "address", "uint256[]", "uint256[]", "bytes"],
+            returns: &[],
+        },
+        FunctionSpec {
+            name: "balanceOf",
+            params: &["address", "uint256"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "balanceOfBatch",
+            params: &["address[]", "uint256[]"],
+            returns: &["uint256[]"],
+        },
+        FunctionSpec {
+            name: "setApprovalForAll",
+            params: &["address", "bool"],
+            returns: &[],
+        },
+        FunctionSpec {
+            name: "isApprovedForAll",
+            params: &["address", "address"],
+            returns: &["bool"],
+        },
+        FunctionSpec {
+            name: "supportsInterface",
+            params: &["bytes4"],
+            returns: &["bool"],
+        },
+    ],
+    optional_functions: &[FunctionSpec {
+        name: "uri",
+        params: &["uint256"],
+        returns: &["string"],
+    }],
+    events: &[
+        EventSpec {
+            name: "TransferSingle",
+            params: &[
+                ("address", true),
+                ("address", true),
+                ("address", true),
+                ("uint256", false),
+                ("uint256", false),
+            ],
+        },
+        EventSpec {
+            name: "TransferBatch",
+            params: &[
+                ("address", true),
+                ("address", true),
+                ("address", true),
+                ("uint256[]", false),
+                ("uint256[]", false),
+            ],
+        },
+        EventSpec {
+            name: "ApprovalForAll",
+            params: &[("address", true), ("address", true), ("bool", false)],
+        },
+        EventSpec {
+            name: "URI",
+            params: &[("string", false), ("uint256", true)],
+        },
+    ],
+    interface_id: Some(("IERC1155", "0xd9b67a26")),
+};
+
+pub struct Erc1155Compliance {
+    data: RuleEntry,
+}
+
+impl Erc1155Compliance {
+    fn create_diag(&self, violation: Violation, file: &SolidFile) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: violation.location.0.line,
+                    character: violation.location.0.column,
+                },
+                end: Position {
+                    line: violation.location.1.line,
+                    character: violation.location.1.column,
+                },
+            },
+            message: violation.message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for Erc1155Compliance {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for violation in check_compliance(&ERC1155, &contract, file, files) {
+                res.push(self.create_diag(violation, file));
+            }
+        }
+        res
+    }
+}
+
+impl Erc1155Compliance {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = Erc1155Compliance { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::linter::SolidFile;
+use crate::rules::erc::compliance::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "erc20-compliance";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+/// [ERC-20](https://eips.ethereum.org/EIPS/eip-20), with the metadata extension
+const ERC20: Standard = Standard {
+    name: "ERC-20",
+    interfaces: &["IERC20", "IERC20Metadata"],
+    distinctive: &["transfer", "allowance"],
+    functions: &[
+        FunctionSpec {
+            name: "totalSupply",
+            params: &[],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "balanceOf",
+            params: &["address"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "transfer",
+            params: &["address", "uint256"],
+            returns: &["bool"],
+        },
+        FunctionSpec {
+            name: "transferFrom",
+            params: &["address", "address", "uint256"],
+            returns: &["bool"],
+        },
+        FunctionSpec {
+            name: "approve",
+            params: &["address", "uint256"],
+            returns: &["bool"],
+        },
+        FunctionSpec {
+            name: "allowance",
+            params: &["address", "address"],
+            returns: &["uint256"],
+        },
+    ],
+    optional_functions: &[
+        FunctionSpec {
+            name: "name",
+            params: &[],
+            returns: &["string"],
+        },
+        FunctionSpec {
+            name: "symbol",
+            params: &[],
+            returns: &["string"],
+        },
+        FunctionSpec {
+            name: "decimals",
+            params: &[],
+            returns: &["uint8"],
+        },
+    ],
+    events: &[
+        EventSpec {
+            name: "Transfer",
+            params: &[("address", true), ("address", true), ("uint256", false)],
+        },
+        EventSpec {
+            name: "Approval",
+            params: &[("address", true), ("address", true), ("uint256", false)],
+        },
+    ],
+    interface_id: None,
+};
+
+pub struct Erc20Compliance {
+    data: RuleEntry,
+}
+
+impl Erc20Compliance {
+    fn create_diag(&self, violation: Violation, file: &SolidFile) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: violation.location.0.line,
+                    character: violation.location.0.column,
+                },
+                end: Position {
+                    line: violation.location.1.line,
+                    character: violation.location.1.column,
+                },
+            },
+            message: violation.message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for Erc20Compliance {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for violation in check_compliance(&ERC20, &contract, file, files) {
+                res.push(self.create_diag(violation, file));
+            }
+        }
+        res
+    }
+}
+
+impl Erc20Compliance {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = Erc20Compliance { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::linter::SolidFile;
+use crate::rules::erc::compliance::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "erc4626-compliance";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+/// [ERC-4626](https://eips.ethereum.org/EIPS/eip-4626), the ERC-20 part is checked by its own rule
+const ERC4626: Standard = Standard {
+    name: "ERC-4626",
+    interfaces: &["IERC4626"],
+    distinctive: &["asset", "totalAssets", "convertToShares"],
+    functions: &[
+        FunctionSpec {
+            name: "asset",
+            params: &[],
+            returns: &["address"],
+        },
+        FunctionSpec {
+            name: "totalAssets",
+            params: &[],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "convertToShares",
+            params: &["uint256"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "convertToAssets",
+            params: &["uint256"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "maxDeposit",
+            params: &["address"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "previewDeposit",
+            params: &["uint256"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "deposit",
+            params: &["uint256", "address"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "maxMint",
+            params: &["address"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "previewMint",
+            params: &["uint256"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "mint",
+            params: &["uint256", "address"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "maxWithdraw",
+            params: &["address"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "previewWithdraw",
+            params: &["uint256"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "withdraw",
+            params: &["uint256", "address", "address"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "maxRedeem",
+            params: &["address"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "previewRedeem",
+            params: &["uint256"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "redeem",
+            params: &["uint256", "address", "address"],
+            returns: &["uint256"],
+        },
+    ],
+    optional_functions: &[],
+    events: &[
+        EventSpec {
+            name: "Deposit",
+            params: &[
+                ("address", true),
+                ("address", true),
+                ("uint256", false),
+                ("uint256", false),
+            ],
+        },
+        EventSpec {
+            name: "Withdraw",
+            params: &[
+                ("address", true),
+                ("address", true),
+                ("address", true),
+                ("uint256", false),
+                ("uint256", false),
+            ],
+        },
+    ],
+    interface_id: None,
+};
+
+pub struct Erc4626Compliance {
+    data: RuleEntry,
+}
+
+impl Erc4626Compliance {
+    fn create_diag(&self, violation: Violation, file: &SolidFile) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: violation.location.0.line,
+                    character: violation.location.0.column,
+                },
+                end: Position {
+                    line: violation.location.1.line,
+                    character: violation.location.1.column,
+                },
+            },
+            message: violation.message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for Erc4626Compliance {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for violation in check_compliance(&ERC4626, &contract, file, files) {
+                res.push(self.create_diag(violation, file));
+            }
+        }
+        res
+    }
+}
+
+impl Erc4626Compliance {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = Erc4626Compliance { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
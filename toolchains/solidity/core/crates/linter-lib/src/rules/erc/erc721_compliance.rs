@@ -0,0 +1,157 @@
+use crate::linter::SolidFile;
+use crate::rules::erc::compliance::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "erc721-compliance";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+/// [ERC-721](https://eips.ethereum.org/EIPS/eip-721), with the metadata extension
+const ERC721: Standard = Standard {
+    name: "ERC-721",
+    interfaces: &["IERC721", "IERC721Metadata"],
+    distinctive: &["ownerOf", "getApproved"],
+    functions: &[
+        FunctionSpec {
+            name: "balanceOf",
+            params: &["address"],
+            returns: &["uint256"],
+        },
+        FunctionSpec {
+            name: "ownerOf",
+            params: &["uint256"],
+            returns: &["address"],
+        },
+        FunctionSpec {
+            name: "safeTransferFrom",
+            params: &["address", "address", "uint256", "bytes"],
+            returns: &[],
+        },
+        FunctionSpec {
+            name: "safeTransferFrom",
+            params: &["address", "address", "uint256"],
+            returns: &[],
+        },
+        FunctionSpec {
+            name: "transferFrom",
+            params: &["address", "address", "uint256"],
+            returns: &[],
+        },
+        FunctionSpec {
+            name: "approve",
+            params: &["address", "uint256"],
+            returns: &[],
+        },
+        FunctionSpec {
+            name: "setApprovalForAll",
+            params: &["address", "bool"],
+            returns: &[],
+        },
+        FunctionSpec {
+            name: "getApproved",
+            params: &["uint256"],
+            returns: &["address"],
+        },
+        FunctionSpec {
+            name: "isApprovedForAll",
+            params: &["address", "address"],
+            returns: &["bool"],
+        },
+        FunctionSpec {
+            name: "supportsInterface",
+            params: &["bytes4"],
+            returns: &["bool"],
+        },
+    ],
+    optional_functions: &[
+        FunctionSpec {
+            name: "name",
+            params: &[],
+            returns: &["string"],
+        },
+        FunctionSpec {
+            name: "symbol",
+            params: &[],
+            returns: &["string"],
+        },
+        FunctionSpec {
+            name: "tokenURI",
+            params: &["uint256"],
+            returns: &["string"],
+        },
+    ],
+    events: &[
+        EventSpec {
+            name: "Transfer",
+            params: &[("address", true), ("address", true), ("uint256", true)],
+        },
+        EventSpec {
+            name: "Approval",
+            params: &[("address", true), ("address", true), ("uint256", true)],
+        },
+        EventSpec {
+            name: "ApprovalForAll",
+            params: &[("address", true), ("address", true), ("bool", false)],
+        },
+    ],
+    interface_id: Some(("IERC721", "0x80ac58cd")),
+};
+
+pub struct Erc721Compliance {
+    data: RuleEntry,
+}
+
+impl Erc721Compliance {
+    fn create_diag(&self, violation: Violation, file: &SolidFile) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: violation.location.0.line,
+                    character: violation.location.0.column,
+                },
+                end: Position {
+                    line: violation.location.1.line,
+                    character: violation.location.1.column,
+                },
+            },
+            message: violation.message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for Erc721Compliance {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for violation in check_compliance(&ERC721, &contract, file, files) {
+                res.push(self.create_diag(violation, file));
+            }
+        }
+        res
+    }
+}
+
+impl Erc721Compliance {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = Erc721Compliance { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::rules::types::{RuleEntry, RulesMap};
+use std::collections::HashMap;
+
+pub(crate) mod compliance;
+pub(crate) mod erc1155_compliance;
+pub(crate) mod erc20_compliance;
+pub(crate) mod erc4626_compliance;
+pub(crate) mod erc721_compliance;
+
+// List all rules
+use crate::rules::erc::erc1155_compliance::Erc1155Compliance;
+use crate::rules::erc::erc20_compliance::Erc20Compliance;
+use crate::rules::erc::erc4626_compliance::Erc4626Compliance;
+use crate::rules::erc::erc721_compliance::Erc721Compliance;
+use crate::rules::RuleBuilder;
+
+pub fn create_default_rules() -> Vec<RuleEntry> {
+    vec![
+        Erc1155Compliance::create_default(),
+        Erc20Compliance::create_default(),
+        Erc4626Compliance::create_default(),
+        Erc721Compliance::create_default(),
+    ]
+}
+
+pub fn create_rules() -> RulesMap {
+    let mut rules: HashMap<String, RuleBuilder> = HashMap::new();
+
+    rules.insert(
+        erc1155_compliance::RULE_ID.to_string(),
+        Erc1155Compliance::create,
+    );
+    rules.insert(
+        erc20_compliance::RULE_ID.to_string(),
+        Erc20Compliance::create,
+    );
+    rules.insert(
+        erc4626_compliance::RULE_ID.to_string(),
+        Erc4626Compliance::create,
+    );
+    rules.insert(
+        erc721_compliance::RULE_ID.to_string(),
+        Erc721Compliance::create,
+    );
+
+    rules
+}
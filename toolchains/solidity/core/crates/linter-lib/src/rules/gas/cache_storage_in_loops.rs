@@ -0,0 +1,107 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "gas-cache-storage-in-loops";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::HINT;
+
+/// Whether a state variable is read from storage, and can be cached as a whole in a local variable
+fn is_cacheable(variable: &VariableDefinition) -> bool {
+    !variable.attributes.has_constant()
+        && !variable.attributes.has_immutable()
+        && !matches!(variable.ty, Type::Mapping(_) | Type::Array(_))
+}
+
+pub struct CacheStorageInLoops {
+    data: RuleEntry,
+}
+
+impl CacheStorageInLoops {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        variable: &SolIdent,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: format!(
+                "State variable '{}' is read from storage at each iteration, cache it in a local variable before the loop",
+                variable
+            ),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for CacheStorageInLoops {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            let context = ContractContext::new(&contract, files);
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                let locals = function_variables(&function);
+                let mut reported = Vec::new();
+                for exprs in function_loops(&function) {
+                    let written: Vec<String> = exprs.iter().flat_map(written_variables).collect();
+                    let mut cached = Vec::new();
+                    for ident in exprs.iter().flat_map(variable_idents) {
+                        let name = ident.to_string();
+                        if locals.contains_key(&name)
+                            || written.contains(&name)
+                            || cached.contains(&name)
+                            || !context
+                                .state_variables
+                                .get(&name)
+                                .is_some_and(|variable| is_cacheable(variable))
+                        {
+                            continue;
+                        }
+                        let location = (ident.span().start(), ident.span().end());
+                        cached.push(name);
+                        // A read in a nested loop is reported once
+                        if !reported.contains(&location) {
+                            reported.push(location);
+                            res.push(self.create_diag(location, ident, file));
+                        }
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl CacheStorageInLoops {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = CacheStorageInLoops { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "gas-calldata-parameters";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::HINT;
+
+pub struct CalldataParameters {
+    data: RuleEntry,
+}
+
+impl CalldataParameters {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        name: &SolIdent,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: format!(
+                "Parameter '{}' is not modified, declare it calldata instead of memory to avoid copying it",
+                name
+            ),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for CalldataParameters {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                if !matches!(
+                    function.attributes.visibility(),
+                    Some(Visibility::External(_))
+                ) || !matches!(function.body, FunctionBody::Block(_))
+                {
+                    continue;
+                }
+                let written: Vec<String> = function_exprs(&function)
+                    .iter()
+                    .flat_map(|(expr, _)| written_variables(expr))
+                    .collect();
+                for parameter in function.arguments.iter() {
+                    let Some(name) = &parameter.name else {
+                        continue;
+                    };
+                    if matches!(parameter.storage, Some(Storage::Memory(_)))
+                        && !written.contains(&name.to_string())
+                    {
+                        let span = parameter.span();
+                        res.push(self.create_diag((span.start(), span.end()), name, file));
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl CalldataParameters {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = CalldataParameters { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+use std::collections::HashSet;
+
+// global
+pub const RULE_ID: &str = "gas-immutable-state";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::HINT;
+
+/// Builtins which can be evaluated at compile time in the initializer of a constant
+const CONSTANT_BUILTINS: [&str; 3] = ["keccak256", "sha256", "ripemd160"];
+
+/// Whether an expression can be evaluated at compile time
+fn is_compile_time(expr: &Expr, context: &ContractContext<'_>) -> bool {
+    variable_idents(&normalize_expr(expr)).iter().all(|ident| {
+        CONSTANT_BUILTINS.iter().any(|builtin| ident == builtin)
+            || context
+                .state_variables
+                .get(&ident.to_string())
+                .is_some_and(|variable| variable.attributes.has_constant())
+    })
+}
+
+fn is_value_type(ty: &Type, structs: &HashSet<String>) -> bool {
+    match ty {
+        Type::Address(..)
+        | Type::Bool(_)
+        | Type::Int(..)
+        | Type::Uint(..)
+        | Type::FixedBytes(..) => true,
+        Type::Custom(path) => !structs.contains(&path.last().to_string()),
+        _ => false,
+    }
+}
+
+pub struct ImmutableState {
+    data: RuleEntry,
+}
+
+impl ImmutableState {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for ImmutableState {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let structs: HashSet<String> = files
+            .iter()
+            .flat_map(|file| file.data.items.iter())
+            .chain(all_contracts(files).flat_map(|contract| contract.body.iter()))
+            .filter_map(|item| match item {
+                Item::Struct(strukt) => Some(strukt.name.to_string()),
+                _ => None,
+            })
+            .collect();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            let context = ContractContext::new(&contract, files);
+
+            // Immutables can only be assigned in the constructor of their contract,
+            // and any contract may inherit from this one and write the variables
+            let mut constructor_writes = HashSet::new();
+            let mut other_writes = HashSet::new();
+            for other in all_contracts(files) {
+                for function in retriever::retrieve_functions_nodes(other) {
+                    let writes = function_exprs(&function)
+                        .iter()
+                        .flat_map(|(expr, _)| written_variables(expr))
+                        .collect::<Vec<_>>();
+                    if function.kind.is_constructor() && other.name == contract.name {
+                        constructor_writes.extend(writes);
+                    } else {
+                        other_writes.extend(writes);
+                    }
+                }
+            }
+
+            for item in &contract.body {
+                let Item::Variable(variable) = item else {
+                    continue;
+                };
+                let name = variable.name.to_string();
+                if variable.attributes.has_constant()
+                    || variable.attributes.has_immutable()
+                    || other_writes.contains(&name)
+                {
+                    continue;
+                }
+                let constant = match &variable.initializer {
+                    Some((_, value)) => {
+                        !constructor_writes.contains(&name) && is_compile_time(value, &context)
+                    }
+                    None => false,
+                };
+                let kind = if constant
+                    && (is_value_type(&variable.ty, &structs)
+                        || matches!(variable.ty, Type::String(_) | Type::Bytes(_)))
+                {
+                    "constant"
+                } else if is_value_type(&variable.ty, &structs)
+                    && (variable.initializer.is_some() || constructor_writes.contains(&name))
+                {
+                    "immutable"
+                } else {
+                    continue;
+                };
+                let span = variable.name.span();
+                res.push(self.create_diag(
+                    (span.start(), span.end()),
+                    format!(
+                        "State variable '{}' is never modified after the deployment, declare it {} to avoid reading the storage",
+                        name, kind
+                    ),
+                    file,
+                ));
+            }
+        }
+        res
+    }
+}
+
+impl ImmutableState {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = ImmutableState { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "gas-length-in-loops";
+
+// specific
+const DEFAULT_MESSAGE: &str =
+    "The length of the array is read at each iteration, cache it in a local variable before the loop";
+const DEFAULT_SEVERITY: Severity = Severity::HINT;
+
+struct LengthVisitor {
+    lengths: Vec<(LineColumn, LineColumn)>,
+}
+
+impl LengthVisitor {
+    fn check_cond(&mut self, cond: &Expr) {
+        let mut visitor = MemberVisitor {
+            members: Vec::new(),
+        };
+        visitor.visit_expr(&normalize_expr(cond));
+        self.lengths.extend(visitor.members);
+    }
+}
+
+impl<'ast> Visit<'ast> for LengthVisitor {
+    fn visit_stmt_for(&mut self, stmt: &'ast StmtFor) {
+        if let Some(cond) = &stmt.cond {
+            self.check_cond(cond);
+        }
+        visit::visit_stmt_for(self, stmt);
+    }
+
+    fn visit_stmt_while(&mut self, stmt: &'ast StmtWhile) {
+        self.check_cond(&stmt.cond);
+        visit::visit_stmt_while(self, stmt);
+    }
+
+    fn visit_stmt_dowhile(&mut self, stmt: &'ast StmtDoWhile) {
+        self.check_cond(&stmt.cond);
+        visit::visit_stmt_dowhile(self, stmt);
+    }
+}
+
+/// Collects the `.length` members of an expression
+struct MemberVisitor {
+    members: Vec<(LineColumn, LineColumn)>,
+}
+
+impl<'ast> Visit<'ast> for MemberVisitor {
+    fn visit_expr_member(&mut self, member: &'ast ExprMember) {
+        if let Expr::Ident(name) = &*member.member {
+            if name == "length" {
+                self.members
+                    .push((member.span().start(), member.span().end()));
+            }
+        }
+        visit::visit_expr_member(self, member);
+    }
+}
+
+pub struct LengthInLoops {
+    data: RuleEntry,
+}
+
+impl LengthInLoops {
+    fn create_diag(&self, location: (LineColumn, LineColumn), file: &SolidFile) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: DEFAULT_MESSAGE.to_string(),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for LengthInLoops {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let mut visitor = LengthVisitor {
+            lengths: Vec::new(),
+        };
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            visitor.visit_item_contract(&contract);
+        }
+        for location in visitor.lengths {
+            res.push(self.create_diag(location, file));
+        }
+        res
+    }
+}
+
+impl LengthInLoops {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = LengthInLoops { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
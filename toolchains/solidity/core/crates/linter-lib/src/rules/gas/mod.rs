@@ -0,0 +1,76 @@
+use crate::rules::types::{RuleEntry, RulesMap};
+use std::collections::HashMap;
+
+pub(crate) mod cache_storage_in_loops;
+pub(crate) mod calldata_parameters;
+pub(crate) mod immutable_state;
+pub(crate) mod length_in_loops;
+pub(crate) mod prefix_increment;
+pub(crate) mod public_array;
+pub(crate) mod state_variable_packing;
+pub(crate) mod struct_packing;
+pub(crate) mod unchecked_increment;
+pub(crate) mod unsigned_not_zero;
+
+// List all rules
+use crate::rules::gas::cache_storage_in_loops::CacheStorageInLoops;
+use crate::rules::gas::calldata_parameters::CalldataParameters;
+use crate::rules::gas::immutable_state::ImmutableState;
+use crate::rules::gas::length_in_loops::LengthInLoops;
+use crate::rules::gas::prefix_increment::PrefixIncrement;
+use crate::rules::gas::public_array::PublicArray;
+use crate::rules::gas::state_variable_packing::StateVariablePacking;
+use crate::rules::gas::struct_packing::StructPacking;
+use crate::rules::gas::unchecked_increment::UncheckedIncrement;
+use crate::rules::gas::unsigned_not_zero::UnsignedNotZero;
+use crate::rules::RuleBuilder;
+
+pub fn create_default_rules() -> Vec<RuleEntry> {
+    vec![
+        CacheStorageInLoops::create_default(),
+        CalldataParameters::create_default(),
+        ImmutableState::create_default(),
+        LengthInLoops::create_default(),
+        PrefixIncrement::create_default(),
+        PublicArray::create_default(),
+        StateVariablePacking::create_default(),
+        StructPacking::create_default(),
+        UncheckedIncrement::create_default(),
+        UnsignedNotZero::create_default(),
+    ]
+}
+
+pub fn create_rules() -> RulesMap {
+    let mut rules: HashMap<String, RuleBuilder> = HashMap::new();
+
+    rules.insert(
+        cache_storage_in_loops::RULE_ID.to_string(),
+        CacheStorageInLoops::create,
+    );
+    rules.insert(
+        calldata_parameters::RULE_ID.to_string(),
+        CalldataParameters::create,
+    );
+    rules.insert(immutable_state::RULE_ID.to_string(), ImmutableState::create);
+    rules.insert(length_in_loops::RULE_ID.to_string(), LengthInLoops::create);
+    rules.insert(
+        prefix_increment::RULE_ID.to_string(),
+        PrefixIncrement::create,
+    );
+    rules.insert(public_array::RULE_ID.to_string(), PublicArray::create);
+    rules.insert(
+        state_variable_packing::RULE_ID.to_string(),
+        StateVariablePacking::create,
+    );
+    rules.insert(struct_packing::RULE_ID.to_string(), StructPacking::create);
+    rules.insert(
+        unchecked_increment::RULE_ID.to_string(),
+        UncheckedIncrement::create,
+    );
+    rules.insert(
+        unsigned_not_zero::RULE_ID.to_string(),
+        UnsignedNotZero::create,
+    );
+
+    rules
+}
@@ -0,0 +1,103 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "gas-prefix-increment";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::HINT;
+
+/// Collects the postfix increments and decrements whose value is not used
+struct PostfixVisitor {
+    postfixes: Vec<ExprPostfix>,
+}
+
+impl PostfixVisitor {
+    fn check(&mut self, expr: &Expr) {
+        if let Expr::Postfix(postfix) = normalize_expr(expr) {
+            self.postfixes.push(postfix);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for PostfixVisitor {
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        if let Stmt::Expr(stmt) = stmt {
+            self.check(&stmt.expr);
+        }
+        visit::visit_stmt(self, stmt);
+    }
+
+    fn visit_stmt_for(&mut self, stmt: &'ast StmtFor) {
+        if let Some(post) = &stmt.post {
+            self.check(post);
+        }
+        visit::visit_stmt_for(self, stmt);
+    }
+}
+
+pub struct PrefixIncrement {
+    data: RuleEntry,
+}
+
+impl PrefixIncrement {
+    fn create_diag(&self, postfix: &ExprPostfix, file: &SolidFile) -> LintDiag {
+        let op = postfix.op.to_string();
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: postfix.span().start().line,
+                    character: postfix.span().start().column,
+                },
+                end: Position {
+                    line: postfix.span().end().line,
+                    character: postfix.span().end().column,
+                },
+            },
+            message: format!(
+                "Use '{}i' instead of 'i{}' when the previous value is not used, it saves a copy",
+                op, op
+            ),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for PrefixIncrement {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let mut visitor = PostfixVisitor {
+            postfixes: Vec::new(),
+        };
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            visitor.visit_item_contract(&contract);
+        }
+        for postfix in &visitor.postfixes {
+            res.push(self.create_diag(postfix, file));
+        }
+        res
+    }
+}
+
+impl PrefixIncrement {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = PrefixIncrement { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
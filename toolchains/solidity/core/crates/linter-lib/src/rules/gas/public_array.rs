@@ -0,0 +1,85 @@
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "gas-public-array";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::HINT;
+
+pub struct PublicArray {
+    data: RuleEntry,
+}
+
+impl PublicArray {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        name: &SolIdent,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: format!(
+                "Public array '{}' generates a getter by index, make it private and write an external getter for what is needed",
+                name
+            ),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for PublicArray {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for item in &contract.body {
+                if let Item::Variable(variable) = item {
+                    if matches!(variable.ty, Type::Array(_))
+                        && variable.attributes.has_public()
+                        && !variable.attributes.has_constant()
+                    {
+                        let span = variable.name.span();
+                        res.push(self.create_diag(
+                            (span.start(), span.end()),
+                            &variable.name,
+                            file,
+                        ));
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl PublicArray {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = PublicArray { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
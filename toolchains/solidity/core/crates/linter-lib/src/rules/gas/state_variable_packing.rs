@@ -0,0 +1,103 @@
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::storage_layout::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "gas-state-variable-packing";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::HINT;
+
+pub struct StateVariablePacking {
+    data: RuleEntry,
+}
+
+impl StateVariablePacking {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for StateVariablePacking {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let definitions = Definitions::new(files.iter().map(|file| &file.data));
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            let Some(layout) = definitions.contract_layout(&contract) else {
+                continue;
+            };
+            // Only the state variables declared in the contract can be reordered,
+            // they are stored after the inherited ones
+            let name = contract.name.to_string();
+            let (inherited, own): (Vec<StorageEntry>, Vec<StorageEntry>) =
+                layout.into_iter().partition(|entry| entry.parent != name);
+            let inherited: Vec<StorageSize> = inherited.iter().map(|entry| entry.size).collect();
+            let (_, start) = assign_positions(&inherited, StoragePosition::default());
+
+            let sizes: Vec<StorageSize> = own.iter().map(|entry| entry.size).collect();
+            let order = packed_order(&sizes, start);
+            let ordered: Vec<StorageSize> = order.iter().map(|index| sizes[*index]).collect();
+            let current = slot_count(&sizes, start);
+            let packed = slot_count(&ordered, start);
+            if packed < current {
+                let names: Vec<&str> = order
+                    .iter()
+                    .map(|index| own[*index].name.as_str())
+                    .collect();
+                let span = contract.name.span();
+                res.push(self.create_diag(
+                    (span.start(), span.end()),
+                    format!(
+                        "State variables of '{}' take {} storage slots, declaring them as {} would save {}",
+                        name,
+                        current,
+                        names.join(", "),
+                        current - packed
+                    ),
+                    file,
+                ));
+            }
+        }
+        res
+    }
+}
+
+impl StateVariablePacking {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = StateVariablePacking { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::storage_layout::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "gas-struct-packing";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::HINT;
+
+pub struct StructPacking {
+    data: RuleEntry,
+}
+
+impl StructPacking {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for StructPacking {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let definitions = Definitions::new(files.iter().map(|file| &file.data));
+        let contracts = retriever::retrieve_contract_nodes(&file.data);
+        let items = file
+            .data
+            .items
+            .iter()
+            .chain(contracts.iter().flat_map(|contract| contract.body.iter()));
+
+        for item in items {
+            let Item::Struct(strukt) = item else {
+                continue;
+            };
+            let entries = definitions.struct_layout(strukt);
+            let sizes: Vec<StorageSize> = entries.iter().map(|entry| entry.size).collect();
+            let order = packed_order(&sizes, StoragePosition::default());
+            let ordered: Vec<StorageSize> = order.iter().map(|index| sizes[*index]).collect();
+            let current = slot_count(&sizes, StoragePosition::default());
+            let packed = slot_count(&ordered, StoragePosition::default());
+            if packed < current {
+                let names: Vec<&str> = order
+                    .iter()
+                    .map(|index| entries[*index].name.as_str())
+                    .collect();
+                let span = strukt.name.span();
+                res.push(self.create_diag(
+                    (span.start(), span.end()),
+                    format!(
+                        "Struct '{}' takes {} storage slots, ordering its fields as {} would save {}",
+                        strukt.name,
+                        current,
+                        names.join(", "),
+                        current - packed
+                    ),
+                    file,
+                ));
+            }
+        }
+        res
+    }
+}
+
+impl StructPacking {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = StructPacking { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "gas-unchecked-increment";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::HINT;
+
+/// The counter incremented by the post expression of a loop, as in `i++`, `++i` or `i += 1`
+fn incremented_counter(post: &Expr) -> Option<&SolIdent> {
+    match post {
+        Expr::Postfix(postfix) if matches!(postfix.op, PostUnOp::Increment(..)) => {
+            match &*postfix.expr {
+                Expr::Ident(ident) => Some(ident),
+                _ => None,
+            }
+        }
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Increment(..)) => match &*unary.expr {
+            Expr::Ident(ident) => Some(ident),
+            _ => None,
+        },
+        Expr::Binary(binary) if matches!(binary.op, BinOp::AddAssign(..)) => {
+            match (&*binary.left, &*binary.right) {
+                (Expr::Ident(ident), Expr::Lit(Lit::Number(LitNumber::Int(one))))
+                    if one.base10_digits() == "1" =>
+                {
+                    Some(ident)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Collects the increments of the loops bounded by `counter < bound`, which cannot overflow
+struct LoopVisitor {
+    increments: Vec<(String, (LineColumn, LineColumn))>,
+}
+
+impl<'ast> Visit<'ast> for LoopVisitor {
+    fn visit_stmt_for(&mut self, stmt: &'ast StmtFor) {
+        if let (Some(cond), Some(post)) = (&stmt.cond, &stmt.post) {
+            let post = normalize_expr(post);
+            let cond = normalize_expr(cond);
+            if let (Some(counter), Expr::Binary(cond)) = (incremented_counter(&post), &cond) {
+                let bounded = matches!(cond.op, BinOp::Lt(..))
+                    && matches!(&*cond.left, Expr::Ident(ident) if ident == counter);
+                let mut body = ExprsCollector { exprs: Vec::new() };
+                body.visit_stmt(&stmt.body);
+                let modified = body
+                    .exprs
+                    .iter()
+                    .flat_map(written_variables)
+                    .any(|variable| counter == variable.as_str());
+                if bounded && !modified {
+                    self.increments.push((
+                        counter.to_string(),
+                        (post.span().start(), post.span().end()),
+                    ));
+                }
+            }
+        }
+        visit::visit_stmt_for(self, stmt);
+    }
+}
+
+struct ExprsCollector {
+    exprs: Vec<Expr>,
+}
+
+impl<'ast> Visit<'ast> for ExprsCollector {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        self.exprs.push(normalize_expr(expr));
+    }
+}
+
+pub struct UncheckedIncrement {
+    data: RuleEntry,
+}
+
+impl UncheckedIncrement {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        counter: &str,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: format!(
+                "'{}' cannot overflow, increment it in an unchecked block at the end of the loop body",
+                counter
+            ),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for UncheckedIncrement {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        // Before solidity 0.8, arithmetic operations are not checked anyway
+        if !has_checked_arithmetic(&file.data) {
+            return res;
+        }
+        let mut visitor = LoopVisitor {
+            increments: Vec::new(),
+        };
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            visitor.visit_item_contract(&contract);
+        }
+        for (counter, location) in visitor.increments {
+            res.push(self.create_diag(location, &counter, file));
+        }
+        res
+    }
+}
+
+impl UncheckedIncrement {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = UncheckedIncrement { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+use std::collections::HashMap;
+
+// global
+pub const RULE_ID: &str = "gas-unsigned-not-zero";
+
+// specific
+const DEFAULT_MESSAGE: &str = "Use '!= 0' instead of '> 0' to compare an unsigned value to zero";
+const DEFAULT_SEVERITY: Severity = Severity::HINT;
+
+fn is_zero(expr: &Expr) -> bool {
+    matches!(expr, Expr::Lit(Lit::Number(LitNumber::Int(lit))) if lit.base10_digits() == "0")
+}
+
+/// Finds the type of variables, and of the elements of mappings and arrays
+struct Types<'a> {
+    locals: HashMap<String, Type>,
+    context: &'a ContractContext<'a>,
+}
+
+impl<'a> Types<'a> {
+    fn expr_type(&self, expr: &Expr) -> Option<&Type> {
+        match expr {
+            Expr::Ident(ident) => {
+                let name = ident.to_string();
+                self.locals.get(&name).or_else(|| {
+                    self.context
+                        .state_variables
+                        .get(&name)
+                        .map(|variable| &variable.ty)
+                })
+            }
+            Expr::Index(index) => match self.expr_type(&index.expr)? {
+                Type::Mapping(mapping) => Some(&mapping.value),
+                Type::Array(array) => Some(&array.ty),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn is_unsigned(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Member(member) => {
+                matches!(&*member.member, Expr::Ident(name) if name == "length" || name == "balance")
+            }
+            Expr::Call(call) => matches!(&*call.expr, Expr::Type(Type::Uint(..))),
+            expr => matches!(self.expr_type(expr), Some(Type::Uint(..))),
+        }
+    }
+}
+
+struct ComparisonVisitor<'a> {
+    types: Types<'a>,
+    comparisons: Vec<(LineColumn, LineColumn)>,
+}
+
+impl<'a, 'ast> Visit<'ast> for ComparisonVisitor<'a> {
+    fn visit_expr_binary(&mut self, binary: &'ast ExprBinary) {
+        let greater_than_zero = match binary.op {
+            BinOp::Gt(..) => is_zero(&binary.right) && self.types.is_unsigned(&binary.left),
+            BinOp::Lt(..) => is_zero(&binary.left) && self.types.is_unsigned(&binary.right),
+            _ => false,
+        };
+        if greater_than_zero {
+            self.comparisons
+                .push((binary.span().start(), binary.span().end()));
+        }
+        visit::visit_expr_binary(self, binary);
+    }
+}
+
+pub struct UnsignedNotZero {
+    data: RuleEntry,
+}
+
+impl UnsignedNotZero {
+    fn create_diag(&self, location: (LineColumn, LineColumn), file: &SolidFile) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: DEFAULT_MESSAGE.to_string(),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for UnsignedNotZero {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            let context = ContractContext::new(&contract, files);
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                let mut visitor = ComparisonVisitor {
+                    types: Types {
+                        locals: function_variables(&function),
+                        context: &context,
+                    },
+                    comparisons: Vec::new(),
+                };
+                for (expr, _) in function_exprs(&function) {
+                    visitor.visit_expr(&expr);
+                }
+                for location in visitor.comparisons {
+                    res.push(self.create_diag(location, file));
+                }
+            }
+        }
+        res
+    }
+}
+
+impl UnsignedNotZero {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = UnsignedNotZero { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
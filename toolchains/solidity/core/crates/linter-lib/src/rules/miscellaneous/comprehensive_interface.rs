@@ -0,0 +1,95 @@
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "comprehensive-interface";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::HINT;
+
+pub struct ComprehensiveInterface {
+    data: RuleEntry,
+}
+
+impl ComprehensiveInterface {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for ComprehensiveInterface {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            if contract.kind.is_interface() || contract.kind.is_library() {
+                continue;
+            }
+            for item in &contract.body {
+                let Item::Function(function) = item else {
+                    continue;
+                };
+                let Some(name) = &function.name else {
+                    continue;
+                };
+                // Functions without visibility are public before solidity 0.5
+                let exposed = !matches!(
+                    function.attributes.visibility(),
+                    Some(Visibility::Internal(_)) | Some(Visibility::Private(_))
+                );
+                if function.kind.is_function() && exposed && !function.attributes.has_override(None)
+                {
+                    let span = name.span();
+                    res.push(self.create_diag(
+                        (span.start(), span.end()),
+                        format!(
+                            "Function '{}' is public or external but does not override an interface",
+                            name
+                        ),
+                        file,
+                    ));
+                }
+            }
+        }
+        res
+    }
+}
+
+impl ComprehensiveInterface {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = ComprehensiveInterface { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
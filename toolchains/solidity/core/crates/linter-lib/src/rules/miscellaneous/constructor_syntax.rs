@@ -0,0 +1,84 @@
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "constructor-syntax";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct ConstructorSyntax {
+    data: RuleEntry,
+}
+
+impl ConstructorSyntax {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for ConstructorSyntax {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for item in &contract.body {
+                let Item::Function(function) = item else {
+                    continue;
+                };
+                match &function.name {
+                    Some(name) if function.kind.is_function() && *name == contract.name => {
+                        let span = name.span();
+                        res.push(self.create_diag(
+                            (span.start(), span.end()),
+                            "Use the 'constructor' keyword instead of a function named after the contract"
+                                .to_string(),
+                            file,
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        res
+    }
+}
+
+impl ConstructorSyntax {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = ConstructorSyntax { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
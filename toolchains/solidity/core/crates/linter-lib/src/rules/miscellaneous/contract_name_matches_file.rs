@@ -0,0 +1,89 @@
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+use std::path::Path;
+
+// global
+pub const RULE_ID: &str = "contract-name-matches-file";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct ContractNameMatchesFile {
+    data: RuleEntry,
+}
+
+impl ContractNameMatchesFile {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for ContractNameMatchesFile {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let contracts = retriever::retrieve_contract_nodes(&file.data);
+        let file_name = Path::new(&file.path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        // `Token.t.sol` or `Token.s.sol` are named after the `Token` contract
+        let expected = file_name.split('.').next().unwrap_or_default();
+
+        if contracts.iter().any(|contract| contract.name == expected) {
+            return Vec::new();
+        }
+        contracts
+            .first()
+            .map(|contract| {
+                let span = contract.name.span();
+                self.create_diag(
+                    (span.start(), span.end()),
+                    format!(
+                        "File '{}' does not declare a contract named '{}'",
+                        file_name, expected
+                    ),
+                    file,
+                )
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+impl ContractNameMatchesFile {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = ContractNameMatchesFile { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
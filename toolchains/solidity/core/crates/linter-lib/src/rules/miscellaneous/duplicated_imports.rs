@@ -0,0 +1,121 @@
+use crate::imports::ImportResolver;
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::Path;
+
+// global
+pub const RULE_ID: &str = "duplicated-imports";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct DuplicatedImports {
+    resolver: ImportResolver,
+    data: RuleEntry,
+}
+
+impl DuplicatedImports {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for DuplicatedImports {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let mut imported_files = HashSet::new();
+        let mut names = HashSet::new();
+
+        for item in &file.data.items {
+            let Item::Import(import) = item else {
+                continue;
+            };
+            let literal = import.path.path();
+            let path = literal.value();
+
+            // Plain imports bring all the names of the file in the scope
+            if let ImportPath::Plain(plain) = &import.path {
+                if plain.alias.is_none() {
+                    let key = self
+                        .resolver
+                        .resolve(Path::new(&file.path), &path)
+                        .and_then(|resolved| resolved.canonicalize().ok())
+                        .map_or(path.clone(), |resolved| resolved.display().to_string());
+                    if !imported_files.insert(key) {
+                        let span = literal.span();
+                        res.push(self.create_diag(
+                            (span.start(), span.end()),
+                            format!("'{}' is already imported", path),
+                            file,
+                        ));
+                    }
+                    continue;
+                }
+            }
+            for name in imported_names(import) {
+                if !names.insert(name.to_string()) {
+                    let span = name.span();
+                    res.push(self.create_diag(
+                        (span.start(), span.end()),
+                        format!("'{}' is already imported", name),
+                        file,
+                    ));
+                }
+            }
+        }
+        res
+    }
+}
+
+impl DuplicatedImports {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let mut resolver = ImportResolver::default();
+
+        if let Some(data) = &data.data {
+            match &data["root"] {
+                Value::Null => {}
+                Value::String(root) => resolver = ImportResolver::from_root(Path::new(root)),
+                _ => eprintln!("{} rule : bad config data", RULE_ID),
+            }
+        }
+        let rule = DuplicatedImports { resolver, data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: Some(serde_json::json!({
+                "root": null,
+            })),
+        }
+    }
+}
@@ -1,10 +1,57 @@
 use crate::rules::types::{RuleEntry, RulesMap};
 use std::collections::HashMap;
 
+pub mod comprehensive_interface;
+pub mod constructor_syntax;
+pub mod contract_name_matches_file;
+pub mod duplicated_imports;
+pub mod no_complex_fallback;
+pub mod quotes;
+
+// List all rules
+use crate::rules::miscellaneous::comprehensive_interface::ComprehensiveInterface;
+use crate::rules::miscellaneous::constructor_syntax::ConstructorSyntax;
+use crate::rules::miscellaneous::contract_name_matches_file::ContractNameMatchesFile;
+use crate::rules::miscellaneous::duplicated_imports::DuplicatedImports;
+use crate::rules::miscellaneous::no_complex_fallback::NoComplexFallback;
+use crate::rules::miscellaneous::quotes::Quotes;
+use crate::rules::RuleBuilder;
+
 pub fn create_default_rules() -> Vec<RuleEntry> {
-    vec![]
+    vec![
+        Quotes::create_default(),
+        ComprehensiveInterface::create_default(),
+        ContractNameMatchesFile::create_default(),
+        DuplicatedImports::create_default(),
+        ConstructorSyntax::create_default(),
+        NoComplexFallback::create_default(),
+    ]
 }
 
 pub fn create_rules() -> RulesMap {
-    HashMap::new()
+    let mut rules: HashMap<String, RuleBuilder> = HashMap::new();
+
+    rules.insert(quotes::RULE_ID.to_string(), Quotes::create);
+    rules.insert(
+        comprehensive_interface::RULE_ID.to_string(),
+        ComprehensiveInterface::create,
+    );
+    rules.insert(
+        contract_name_matches_file::RULE_ID.to_string(),
+        ContractNameMatchesFile::create,
+    );
+    rules.insert(
+        duplicated_imports::RULE_ID.to_string(),
+        DuplicatedImports::create,
+    );
+    rules.insert(
+        constructor_syntax::RULE_ID.to_string(),
+        ConstructorSyntax::create,
+    );
+    rules.insert(
+        no_complex_fallback::RULE_ID.to_string(),
+        NoComplexFallback::create,
+    );
+
+    rules
 }
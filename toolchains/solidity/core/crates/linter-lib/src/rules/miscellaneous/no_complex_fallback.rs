@@ -0,0 +1,97 @@
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "no-complex-fallback";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+/// Calls sending ether only forward 2300 gas, which leaves room for little more than logging an event
+const MAX_STATEMENTS: usize = 2;
+
+pub struct NoComplexFallback {
+    data: RuleEntry,
+}
+
+impl NoComplexFallback {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for NoComplexFallback {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for item in &contract.body {
+                let Item::Function(function) = item else {
+                    continue;
+                };
+                let kind = match function.kind {
+                    FunctionKind::Fallback(_) => "Fallback",
+                    FunctionKind::Receive(_) => "Receive",
+                    // Fallback functions are unnamed before solidity 0.6
+                    FunctionKind::Function(_) if function.name.is_none() => "Fallback",
+                    _ => continue,
+                };
+                let FunctionBody::Block(block) = &function.body else {
+                    continue;
+                };
+                if block.stmts.len() > MAX_STATEMENTS {
+                    let span = function.span();
+                    res.push(self.create_diag(
+                        (span.start(), span.end()),
+                        format!(
+                            "{} function must be simple: it has {} statements but allowed no more than {}",
+                            kind,
+                            block.stmts.len(),
+                            MAX_STATEMENTS
+                        ),
+                        file,
+                    ));
+                }
+            }
+        }
+        res
+    }
+}
+
+impl NoComplexFallback {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = NoComplexFallback { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::scanner::{scan, LexemeKind};
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "quotes";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+const DEFAULT_QUOTES: &str = "double";
+
+pub struct Quotes {
+    double: bool,
+    data: RuleEntry,
+}
+
+impl Quotes {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for Quotes {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let (expected, other, description) = if self.double {
+            ('"', '\'', "double")
+        } else {
+            ('\'', '"', "single")
+        };
+        scan(&file.content)
+            .into_iter()
+            .filter(|lexeme| {
+                lexeme.kind == LexemeKind::String(other)
+                    && !lexeme.inner_text(&file.content).contains(expected)
+            })
+            .map(|lexeme| {
+                self.create_diag(
+                    (lexeme.start_position, lexeme.end_position),
+                    format!("Use {} quotes for string literals", description),
+                    file,
+                )
+            })
+            .collect()
+    }
+}
+
+impl Quotes {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let mut double = true;
+
+        if let Some(data) = &data.data {
+            match data.as_str() {
+                Some("double") => double = true,
+                Some("single") => double = false,
+                _ => {
+                    eprintln!("{} rule : bad config data", RULE_ID);
+                }
+            }
+        } else {
+            eprintln!("{} rule : bad config data", RULE_ID);
+        }
+        let rule = Quotes { double, data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: Some(DEFAULT_QUOTES.into()),
+        }
+    }
+}
@@ -1,26 +1,42 @@
 use crate::rules::types::{RuleEntry, RuleType, RulesMap};
 use std::collections::HashMap;
 
+pub(crate) mod analysis;
 pub mod factory;
+pub(crate) mod mutability;
 pub mod rule_impl;
+pub(crate) mod scope;
 pub mod types;
 pub mod utils;
+pub(crate) mod version;
 
 // List all rules
 pub mod best_practises;
+pub mod compiler;
+pub mod erc;
+pub mod gas;
 pub mod miscellaneous;
 pub mod naming;
 pub mod order;
 pub mod security;
+pub mod shadowing;
+pub mod unused;
+pub mod upgradeability;
 
 pub fn create_default_rules() -> Vec<RuleEntry> {
     let mut rules = Vec::new();
 
     rules.append(&mut best_practises::create_default_rules());
+    rules.append(&mut compiler::create_default_rules());
+    rules.append(&mut erc::create_default_rules());
+    rules.append(&mut gas::create_default_rules());
     rules.append(&mut miscellaneous::create_default_rules());
     rules.append(&mut naming::create_default_rules());
     rules.append(&mut order::create_default_rules());
     rules.append(&mut security::create_default_rules());
+    rules.append(&mut shadowing::create_default_rules());
+    rules.append(&mut unused::create_default_rules());
+    rules.append(&mut upgradeability::create_default_rules());
 
     rules
 }
@@ -37,10 +53,16 @@ pub fn create_rules() -> RulesMap {
     let mut rules = HashMap::new();
 
     add_rules(&mut rules, best_practises::create_rules());
+    add_rules(&mut rules, compiler::create_rules());
+    add_rules(&mut rules, erc::create_rules());
+    add_rules(&mut rules, gas::create_rules());
     add_rules(&mut rules, naming::create_rules());
     add_rules(&mut rules, order::create_rules());
     add_rules(&mut rules, miscellaneous::create_rules());
     add_rules(&mut rules, security::create_rules());
+    add_rules(&mut rules, shadowing::create_rules());
+    add_rules(&mut rules, unused::create_rules());
+    add_rules(&mut rules, upgradeability::create_rules());
 
     rules
 }
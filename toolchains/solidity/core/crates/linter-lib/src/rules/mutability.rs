@@ -0,0 +1,553 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::scope::*;
+use osmium_libs_solidity_ast_extractor::storage_layout::Definitions;
+use osmium_libs_solidity_ast_extractor::*;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+/// Builtin functions which neither read nor modify the state
+const PURE_BUILTINS: [&str; 9] = [
+    "addmod",
+    "assert",
+    "ecrecover",
+    "keccak256",
+    "mulmod",
+    "require",
+    "revert",
+    "ripemd160",
+    "sha256",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum StateMutability {
+    Pure,
+    View,
+    NonPayable,
+    Payable,
+}
+
+impl StateMutability {
+    pub fn declared(function: &ItemFunction) -> Self {
+        match function.attributes.mutability() {
+            Some(Mutability::Pure(_)) => StateMutability::Pure,
+            Some(Mutability::View(_)) | Some(Mutability::Constant(_)) => StateMutability::View,
+            Some(Mutability::Payable(_)) => StateMutability::Payable,
+            None => StateMutability::NonPayable,
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            StateMutability::Pure => "pure",
+            StateMutability::View => "view",
+            StateMutability::NonPayable => "non-payable",
+            StateMutability::Payable => "payable",
+        }
+    }
+}
+
+/// The mutability a function body requires. It is `inferred` only when the whole body could be analyzed:
+/// inline assembly, calls on other contracts, function pointers or unknown functions may modify the state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Requirement {
+    pub mutability: StateMutability,
+    pub inferred: bool,
+}
+
+impl Requirement {
+    fn require(&mut self, mutability: StateMutability) {
+        self.mutability = self.mutability.max(mutability);
+    }
+
+    fn merge(&mut self, other: Requirement) {
+        self.require(other.mutability);
+        self.inferred &= other.inferred;
+    }
+}
+
+fn functions_named<'a>(contract: &'a ItemContract, name: &str) -> Vec<&'a ItemFunction> {
+    contract
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            Item::Function(function) if function.name.as_ref().is_some_and(|n| *n == name) => {
+                Some(function)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Infers the state mutability required by the functions of a contract, resolving identifiers to state
+/// variables and following the calls to the functions and modifiers of the contract, of its bases,
+/// of the libraries and the free functions declared in the linted files
+pub(crate) struct MutabilityAnalyzer<'a> {
+    scope: ContractScope<'a>,
+    definitions: Definitions<'a>,
+    free_functions: HashMap<String, Vec<&'a ItemFunction>>,
+    type_names: HashSet<String>,
+    cache: RefCell<HashMap<*const ItemFunction, Requirement>>,
+    visiting: RefCell<HashSet<*const ItemFunction>>,
+}
+
+impl<'a> MutabilityAnalyzer<'a> {
+    pub fn new(contract: &ItemContract, files: &'a [SolidFile]) -> Self {
+        let definitions = Definitions::new(files.iter().map(|file| &file.data));
+        let scope = ContractScope::new(contract, &definitions);
+        let mut free_functions: HashMap<String, Vec<&'a ItemFunction>> = HashMap::new();
+        let mut type_names = HashSet::new();
+
+        for file in files {
+            for item in &file.data.items {
+                match item {
+                    Item::Function(function) => {
+                        if let Some(name) = &function.name {
+                            free_functions
+                                .entry(name.to_string())
+                                .or_default()
+                                .push(function);
+                        }
+                    }
+                    Item::Contract(contract) => {
+                        type_names.insert(contract.name.to_string());
+                        for item in &contract.body {
+                            if let Some((name, kind, _)) = member_kind(item) {
+                                if matches!(
+                                    kind,
+                                    DeclarationKind::Struct
+                                        | DeclarationKind::Enum
+                                        | DeclarationKind::Udt
+                                ) {
+                                    type_names.insert(name.to_string());
+                                }
+                            }
+                        }
+                    }
+                    item => {
+                        if let Some((name, _, _)) = member_kind(item) {
+                            type_names.insert(name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        MutabilityAnalyzer {
+            scope,
+            definitions,
+            free_functions,
+            type_names,
+            cache: RefCell::new(HashMap::new()),
+            visiting: RefCell::new(HashSet::new()),
+        }
+    }
+
+    /// The mutability required by the body of a function and by its modifiers.
+    /// A function without body, or a recursive call, requires its declared mutability.
+    pub fn requirement(&self, function: &ItemFunction) -> Requirement {
+        let key = function as *const ItemFunction;
+        let FunctionBody::Block(block) = &function.body else {
+            return Requirement {
+                mutability: StateMutability::declared(function),
+                inferred: true,
+            };
+        };
+        if let Some(requirement) = self.cache.borrow().get(&key) {
+            return *requirement;
+        }
+        if !self.visiting.borrow_mut().insert(key) {
+            return Requirement {
+                mutability: StateMutability::declared(function),
+                inferred: true,
+            };
+        }
+
+        let mut visitor = BodyVisitor::new(self, function);
+        visitor.visit_block(block);
+        let mut requirement = visitor.requirement;
+        for attribute in function.attributes.iter() {
+            if let FunctionAttribute::Modifier(modifier) = attribute {
+                requirement.merge(self.modifier_requirement(modifier, &visitor.locals));
+            }
+        }
+
+        self.visiting.borrow_mut().remove(&key);
+        self.cache.borrow_mut().insert(key, requirement);
+        requirement
+    }
+
+    fn modifier_requirement(&self, modifier: &Modifier, locals: &Locals) -> Requirement {
+        let name = modifier.name.last().to_string();
+        let mut requirement = Requirement {
+            mutability: StateMutability::Pure,
+            inferred: true,
+        };
+        for argument in &modifier.arguments {
+            requirement.merge(self.expr_requirement(argument, locals));
+        }
+        match self.scope.lookup(&name).next() {
+            Some(member) if member.kind == DeclarationKind::Modifier => {
+                for modifier in functions_named(member.contract, &name) {
+                    requirement.merge(self.requirement(modifier));
+                }
+            }
+            // Base constructor calls are only found on constructors
+            Some(_) => {}
+            None => requirement.inferred = false,
+        }
+        requirement
+    }
+
+    /// The requirement of the functions a call may resolve to, `None` when none of them is known
+    fn functions_requirement(&self, functions: &[&ItemFunction]) -> Option<Requirement> {
+        let mut functions = functions.iter();
+        let mut requirement = self.requirement(functions.next()?);
+        for function in functions {
+            requirement.merge(self.requirement(function));
+        }
+        Some(requirement)
+    }
+
+    /// The functions a name refers to in the contract: the most derived ones first, then the free functions
+    fn resolve_function(&self, name: &str, inherited_only: bool) -> Vec<&'a ItemFunction> {
+        let members: Vec<&Member<'a>> = if inherited_only {
+            self.scope
+                .inherited()
+                .filter(|member| *member.name == name)
+                .collect()
+        } else {
+            self.scope.lookup(name).collect()
+        };
+        match members.first() {
+            Some(member) if member.kind == DeclarationKind::Function => {
+                functions_named(member.contract, name)
+            }
+            Some(_) => Vec::new(),
+            None => self.free_functions.get(name).cloned().unwrap_or_default(),
+        }
+    }
+
+    fn expr_requirement(&self, expr: &Expr, locals: &Locals) -> Requirement {
+        let expr = normalize_expr(expr);
+        let mut visitor = ExprVisitor::new(self, locals);
+        visitor.visit_expr(&expr);
+        visitor.requirement
+    }
+
+    fn write_requirement(&self, lvalue: &Expr, locals: &Locals) -> Requirement {
+        let mut visitor = ExprVisitor::new(self, locals);
+        visitor.write(lvalue);
+        visitor.requirement
+    }
+}
+
+/// Parameters and local variables of a function, and the ones which point to storage
+#[derive(Default)]
+struct Locals {
+    names: HashSet<String>,
+    storage: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for Locals {
+    fn visit_variable_declaration(&mut self, variable: &'ast VariableDeclaration) {
+        if let Some(name) = &variable.name {
+            self.names.insert(name.to_string());
+            if matches!(variable.storage, Some(Storage::Storage(_))) {
+                self.storage.insert(name.to_string());
+            }
+        }
+    }
+}
+
+struct BodyVisitor<'s, 'a> {
+    analyzer: &'s MutabilityAnalyzer<'a>,
+    locals: Locals,
+    requirement: Requirement,
+}
+
+impl<'s, 'a> BodyVisitor<'s, 'a> {
+    fn new(analyzer: &'s MutabilityAnalyzer<'a>, function: &ItemFunction) -> Self {
+        let mut locals = Locals::default();
+        locals.visit_item_function(function);
+        BodyVisitor {
+            analyzer,
+            locals,
+            requirement: Requirement {
+                mutability: StateMutability::Pure,
+                inferred: true,
+            },
+        }
+    }
+
+    /// The arguments of an event emission or of a custom error
+    fn visit_call_args(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Call(call) => match &call.args.list {
+                ArgListImpl::Unnamed(args) => {
+                    for arg in args {
+                        self.visit_expr(arg);
+                    }
+                }
+                ArgListImpl::Named(args) => {
+                    for arg in args.list.iter() {
+                        self.visit_expr(&arg.arg);
+                    }
+                }
+            },
+            expr => self.visit_expr(expr),
+        }
+    }
+}
+
+impl<'ast, 's, 'a> Visit<'ast> for BodyVisitor<'s, 'a> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        let requirement = self.analyzer.expr_requirement(expr, &self.locals);
+        self.requirement.merge(requirement);
+    }
+
+    fn visit_stmt_asm(&mut self, _assembly: &'ast StmtAssembly) {
+        self.requirement.inferred = false;
+    }
+
+    fn visit_stmt_emit(&mut self, emit: &'ast StmtEmit) {
+        self.requirement.require(StateMutability::NonPayable);
+        self.visit_call_args(&emit.expr);
+    }
+
+    fn visit_stmt_revert(&mut self, revert: &'ast StmtRevert) {
+        self.visit_call_args(&revert.expr);
+    }
+
+    fn visit_stmt_var_decl(&mut self, stmt: &'ast StmtVarDecl) {
+        // `(a, b) = ...` assigning existing variables is parsed as a declaration of unnamed ones
+        if let VarDeclDecl::Tuple(tuple) = &stmt.declaration {
+            for variable in tuple.vars.iter().flatten() {
+                if let (None, Type::Custom(path)) = (&variable.name, &variable.ty) {
+                    let lvalue = Expr::Ident(path.first().clone());
+                    let requirement = self.analyzer.write_requirement(&lvalue, &self.locals);
+                    self.requirement.merge(requirement);
+                }
+            }
+        }
+        visit::visit_stmt_var_decl(self, stmt);
+    }
+}
+
+struct ExprVisitor<'s, 'a> {
+    analyzer: &'s MutabilityAnalyzer<'a>,
+    locals: &'s Locals,
+    requirement: Requirement,
+}
+
+impl<'s, 'a> ExprVisitor<'s, 'a> {
+    fn new(analyzer: &'s MutabilityAnalyzer<'a>, locals: &'s Locals) -> Self {
+        ExprVisitor {
+            analyzer,
+            locals,
+            requirement: Requirement {
+                mutability: StateMutability::Pure,
+                inferred: true,
+            },
+        }
+    }
+
+    fn state_variable(&self, name: &str) -> Option<&VariableDefinition> {
+        if self.locals.names.contains(name) {
+            return None;
+        }
+        let member = self.analyzer.scope.lookup(name).next()?;
+        if member.kind != DeclarationKind::StateVariable {
+            return None;
+        }
+        member.contract.body.iter().find_map(|item| match item {
+            Item::Variable(variable) if variable.name == *member.name => Some(variable),
+            _ => None,
+        })
+    }
+
+    fn write(&mut self, lvalue: &Expr) {
+        match lvalue {
+            Expr::Tuple(tuple) => {
+                for elem in &tuple.elems {
+                    self.write(elem);
+                }
+            }
+            lvalue => {
+                if let Some(name) = root_ident(lvalue).map(|ident| ident.to_string()) {
+                    if self.locals.storage.contains(&name) || self.state_variable(&name).is_some() {
+                        self.requirement.require(StateMutability::NonPayable);
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_args(&mut self, args: &ArgList) {
+        match &args.list {
+            ArgListImpl::Unnamed(args) => {
+                for arg in args {
+                    self.visit_expr(arg);
+                }
+            }
+            ArgListImpl::Named(args) => {
+                for arg in args.list.iter() {
+                    self.visit_expr(&arg.arg);
+                }
+            }
+        }
+    }
+
+    fn call_ident(&mut self, name: &SolIdent) {
+        let text = name.to_string();
+        if self.locals.names.contains(&text) {
+            // Function pointer
+            self.requirement.inferred = false;
+        } else if PURE_BUILTINS.contains(&text.as_str()) || self.analyzer.type_names.contains(&text)
+        {
+        } else if text == "gasleft" || text == "blockhash" {
+            self.requirement.require(StateMutability::View);
+        } else if text == "selfdestruct" || text == "suicide" {
+            self.requirement.require(StateMutability::NonPayable);
+        } else {
+            let functions = self.analyzer.resolve_function(&text, false);
+            match self.analyzer.functions_requirement(&functions) {
+                Some(requirement) => self.requirement.merge(requirement),
+                None => match self.analyzer.scope.lookup(&text).next().map(|m| m.kind) {
+                    // Emission without the `emit` keyword, before solidity 0.5
+                    Some(DeclarationKind::Event) => {
+                        self.requirement.require(StateMutability::NonPayable)
+                    }
+                    Some(DeclarationKind::Error) => {}
+                    _ => self.requirement.inferred = false,
+                },
+            }
+        }
+    }
+
+    fn call_member(&mut self, base: &Expr, name: &SolIdent) {
+        let text = name.to_string();
+        match (base, text.as_str()) {
+            (Expr::Ident(base), _) if *base == "super" || *base == "this" => {
+                if *base == "this" {
+                    self.requirement.require(StateMutability::View);
+                }
+                let functions = self.analyzer.resolve_function(&text, *base == "super");
+                match self.analyzer.functions_requirement(&functions) {
+                    Some(requirement) => self.requirement.merge(requirement),
+                    None => self.requirement.inferred = false,
+                }
+                return;
+            }
+            (Expr::Ident(base), _) if *base == "abi" => {}
+            (Expr::Type(_), _) => {}
+            (_, "call" | "delegatecall" | "send" | "transfer") => {
+                self.requirement.require(StateMutability::NonPayable)
+            }
+            (_, "staticcall") => self.requirement.require(StateMutability::View),
+            (_, "push" | "pop") => self.write(base),
+            (Expr::Ident(library), _)
+                if self.analyzer.type_names.contains(&library.to_string()) =>
+            {
+                let functions = self
+                    .analyzer
+                    .definitions
+                    .contract(&library.to_string())
+                    .map(|contract| functions_named(contract, &text))
+                    .unwrap_or_default();
+                match self.analyzer.functions_requirement(&functions) {
+                    Some(requirement) => self.requirement.merge(requirement),
+                    None => self.requirement.inferred = false,
+                }
+            }
+            _ => self.requirement.inferred = false,
+        }
+        self.visit_expr(base);
+    }
+}
+
+impl<'ast> Visit<'ast> for ExprVisitor<'_, '_> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Ident(ident) => {
+                if *ident == "this" || *ident == "now" {
+                    self.requirement.require(StateMutability::View);
+                } else if let Some(variable) = self.state_variable(&ident.to_string()) {
+                    if !variable.attributes.has_constant() {
+                        self.requirement.require(StateMutability::View);
+                    }
+                }
+            }
+            expr => visit::visit_expr(self, expr),
+        }
+    }
+
+    fn visit_expr_member(&mut self, member: &'ast ExprMember) {
+        if let Expr::Ident(name) = &*member.member {
+            match (&*member.expr, name.to_string().as_str()) {
+                (Expr::Ident(base), "value") if *base == "msg" => {
+                    self.requirement.require(StateMutability::NonPayable)
+                }
+                (Expr::Ident(base), "data" | "sig") if *base == "msg" => {}
+                (Expr::Ident(base), _) if *base == "msg" || *base == "block" || *base == "tx" => {
+                    self.requirement.require(StateMutability::View)
+                }
+                (_, "balance" | "code" | "codehash") => {
+                    self.requirement.require(StateMutability::View)
+                }
+                _ => {}
+            }
+        }
+        self.visit_expr(&member.expr);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        let callee = match &*call.expr {
+            Expr::CallOptions(options) => &*options.expr,
+            callee => callee,
+        };
+        match callee {
+            Expr::Ident(name) => self.call_ident(name),
+            Expr::Member(member) => match &*member.member {
+                Expr::Ident(name) => self.call_member(&member.expr, name),
+                _ => self.requirement.inferred = false,
+            },
+            Expr::New(new) => {
+                if matches!(new.ty, Type::Custom(_)) {
+                    self.requirement.require(StateMutability::NonPayable);
+                }
+            }
+            Expr::Type(_) | Expr::Payable(_) => {}
+            _ => self.requirement.inferred = false,
+        }
+        if let Expr::CallOptions(options) = &*call.expr {
+            for arg in options.args.list.iter() {
+                self.visit_expr(&arg.arg);
+            }
+        }
+        self.visit_args(&call.args);
+    }
+
+    fn visit_expr_binary(&mut self, binary: &'ast ExprBinary) {
+        if is_assignment(&binary.op) {
+            self.write(&binary.left);
+        }
+        visit::visit_expr_binary(self, binary);
+    }
+
+    fn visit_expr_unary(&mut self, unary: &'ast ExprUnary) {
+        if matches!(unary.op, UnOp::Increment(..) | UnOp::Decrement(..)) {
+            self.write(&unary.expr);
+        }
+        visit::visit_expr_unary(self, unary);
+    }
+
+    fn visit_expr_postfix(&mut self, postfix: &'ast ExprPostfix) {
+        self.write(&postfix.expr);
+        visit::visit_expr_postfix(self, postfix);
+    }
+
+    fn visit_expr_delete(&mut self, delete: &'ast ExprDelete) {
+        self.write(&delete.expr);
+        visit::visit_expr_delete(self, delete);
+    }
+}
@@ -58,8 +58,7 @@ impl RuleType for VarNameMixedCase {
                 &file.data,
             );
         for variable in variables_declaration {
-            if variable.name.is_some() {
-                let name = variable.name.unwrap();
+            if let Some(name) = variable.name {
                 if name.to_string()[1..].find('_').is_some() {
                     let span = name.span();
                     res.push(self.create_diag((span.start(), span.end()), file));
@@ -0,0 +1,283 @@
+use osmium_libs_solidity_ast_extractor::storage_layout::Definitions;
+use osmium_libs_solidity_ast_extractor::*;
+
+/// Names declared by the compiler in every scope
+pub(crate) const BUILTINS: [&str; 22] = [
+    "abi",
+    "addmod",
+    "assert",
+    "block",
+    "blockhash",
+    "ecrecover",
+    "gasleft",
+    "keccak256",
+    "msg",
+    "mulmod",
+    "now",
+    "require",
+    "revert",
+    "ripemd160",
+    "selfdestruct",
+    "sha256",
+    "sha3",
+    "suicide",
+    "super",
+    "this",
+    "tx",
+    "type",
+];
+
+/// What a name is declared as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DeclarationKind {
+    Contract,
+    StateVariable,
+    Function,
+    Modifier,
+    Event,
+    Error,
+    Struct,
+    Enum,
+    Udt,
+    Parameter,
+    ReturnParameter,
+    LocalVariable,
+}
+
+impl DeclarationKind {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            DeclarationKind::Contract => "contract",
+            DeclarationKind::StateVariable => "state variable",
+            DeclarationKind::Function => "function",
+            DeclarationKind::Modifier => "modifier",
+            DeclarationKind::Event => "event",
+            DeclarationKind::Error => "error",
+            DeclarationKind::Struct => "struct",
+            DeclarationKind::Enum => "enum",
+            DeclarationKind::Udt => "type",
+            DeclarationKind::Parameter => "parameter",
+            DeclarationKind::ReturnParameter => "return parameter",
+            DeclarationKind::LocalVariable => "local variable",
+        }
+    }
+}
+
+/// A member of a contract, with the contract declaring it
+pub(crate) struct Member<'a> {
+    pub name: &'a SolIdent,
+    pub kind: DeclarationKind,
+    pub contract: &'a ItemContract,
+    pub private: bool,
+}
+
+/// The kind and visibility of a contract body item, `None` for the items which do not declare a name
+pub(crate) fn member_kind(item: &Item) -> Option<(&SolIdent, DeclarationKind, bool)> {
+    match item {
+        Item::Variable(variable) => Some((
+            &variable.name,
+            DeclarationKind::StateVariable,
+            variable.attributes.has_private(),
+        )),
+        Item::Function(function) => {
+            let kind = match function.kind {
+                FunctionKind::Function(_) => DeclarationKind::Function,
+                FunctionKind::Modifier(_) => DeclarationKind::Modifier,
+                _ => return None,
+            };
+            Some((
+                function.name.as_ref()?,
+                kind,
+                function.attributes.has_private(),
+            ))
+        }
+        Item::Event(event) => Some((&event.name, DeclarationKind::Event, false)),
+        Item::Error(error) => Some((&error.name, DeclarationKind::Error, false)),
+        Item::Struct(strukt) => Some((&strukt.name, DeclarationKind::Struct, false)),
+        Item::Enum(enumm) => Some((&enumm.name, DeclarationKind::Enum, false)),
+        Item::Udt(udt) => Some((&udt.name, DeclarationKind::Udt, false)),
+        _ => None,
+    }
+}
+
+/// The members of a contract and of the bases it inherits from, following the linearization.
+/// Bases which are not declared in the known files are ignored.
+pub(crate) struct ContractScope<'a> {
+    name: String,
+    members: Vec<Member<'a>>,
+}
+
+impl<'a> ContractScope<'a> {
+    pub fn new(contract: &ItemContract, definitions: &Definitions<'a>) -> Self {
+        let name = contract.name.to_string();
+        let linearization = definitions
+            .linearize(contract)
+            .unwrap_or_else(|| vec![name.clone()]);
+        let mut members = Vec::new();
+        for base in linearization {
+            let Some(base) = definitions.contract(&base) else {
+                continue;
+            };
+            for item in &base.body {
+                if let Some((ident, kind, private)) = member_kind(item) {
+                    members.push(Member {
+                        name: ident,
+                        kind,
+                        contract: base,
+                        private,
+                    });
+                }
+            }
+        }
+        ContractScope { name, members }
+    }
+
+    /// Members declared by the contract itself
+    pub fn own(&self) -> impl Iterator<Item = &Member<'a>> {
+        self.members
+            .iter()
+            .filter(|member| member.contract.name == self.name)
+    }
+
+    /// Members inherited from the bases, private ones excluded, the most derived first
+    pub fn inherited(&self) -> impl Iterator<Item = &Member<'a>> {
+        self.members
+            .iter()
+            .filter(|member| member.contract.name != self.name && !member.private)
+    }
+
+    /// Members visible from the body of the contract with a given name
+    pub fn lookup(&self, name: &str) -> impl Iterator<Item = &Member<'a>> {
+        let name = name.to_string();
+        self.own()
+            .chain(self.inherited())
+            .filter(move |member| *member.name == name)
+    }
+}
+
+/// A parameter or a local variable of a function, and the kind of the declaration it hides in the enclosing
+/// scopes of the same function
+pub(crate) struct LocalDeclaration<'a> {
+    pub name: &'a SolIdent,
+    pub kind: DeclarationKind,
+    pub shadowed: Option<DeclarationKind>,
+}
+
+/// Walks a function body, opening a scope for each block, `for` statement and `try`/`catch` clause
+struct LocalsVisitor<'a> {
+    scopes: Vec<Vec<(String, DeclarationKind)>>,
+    declarations: Vec<LocalDeclaration<'a>>,
+}
+
+impl<'a> LocalsVisitor<'a> {
+    fn declare(&mut self, name: &'a SolIdent, kind: DeclarationKind) {
+        let text = name.to_string();
+        let shadowed = self
+            .scopes
+            .iter()
+            .rev()
+            .flatten()
+            .find(|(declared, _)| *declared == text)
+            .map(|(_, kind)| *kind);
+        self.declarations.push(LocalDeclaration {
+            name,
+            kind,
+            shadowed,
+        });
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push((text, kind));
+        }
+    }
+
+    fn declare_parameters(&mut self, parameters: &'a ParameterList, kind: DeclarationKind) {
+        for parameter in parameters.iter() {
+            if let Some(name) = &parameter.name {
+                self.declare(name, kind);
+            }
+        }
+    }
+}
+
+impl<'a> Visit<'a> for LocalsVisitor<'a> {
+    fn visit_block(&mut self, block: &'a Block) {
+        self.scopes.push(Vec::new());
+        visit::visit_block(self, block);
+        self.scopes.pop();
+    }
+
+    fn visit_stmt_for(&mut self, stmt: &'a StmtFor) {
+        self.scopes.push(Vec::new());
+        visit::visit_stmt_for(self, stmt);
+        self.scopes.pop();
+    }
+
+    fn visit_stmt_var_decl(&mut self, stmt: &'a StmtVarDecl) {
+        // The variable is only visible after its declaration
+        if let Some((_, expr)) = &stmt.assignment {
+            self.visit_expr(expr);
+        }
+        match &stmt.declaration {
+            VarDeclDecl::VarDecl(variable) => {
+                if let Some(name) = &variable.name {
+                    self.declare(name, DeclarationKind::LocalVariable);
+                }
+            }
+            VarDeclDecl::Tuple(tuple) => {
+                for name in tuple.vars.iter().flatten().filter_map(|v| v.name.as_ref()) {
+                    self.declare(name, DeclarationKind::LocalVariable);
+                }
+            }
+        }
+    }
+
+    fn visit_stmt_try(&mut self, stmt: &'a StmtTry) {
+        self.visit_expr(&stmt.expr);
+        self.scopes.push(Vec::new());
+        if let Some(returns) = &stmt.returns {
+            self.declare_parameters(&returns.returns, DeclarationKind::LocalVariable);
+        }
+        self.visit_block(&stmt.block);
+        self.scopes.pop();
+        for catch in &stmt.catch {
+            self.scopes.push(Vec::new());
+            self.declare_parameters(&catch.list, DeclarationKind::LocalVariable);
+            self.visit_block(&catch.block);
+            self.scopes.pop();
+        }
+    }
+}
+
+/// Parameters, return parameters and local variables of a function, in declaration order
+pub(crate) fn function_locals(function: &ItemFunction) -> Vec<LocalDeclaration<'_>> {
+    let mut visitor = LocalsVisitor {
+        scopes: vec![Vec::new()],
+        declarations: Vec::new(),
+    };
+    visitor.declare_parameters(&function.arguments, DeclarationKind::Parameter);
+    if let Some(returns) = &function.returns {
+        visitor.declare_parameters(&returns.returns, DeclarationKind::ReturnParameter);
+    }
+    if let FunctionBody::Block(block) = &function.body {
+        visitor.visit_block(block);
+    }
+    visitor.declarations
+}
+
+/// Functions and modifiers of a file, free functions included
+pub(crate) fn file_functions(file: &File) -> Vec<&ItemFunction> {
+    let mut functions = Vec::new();
+    for item in &file.items {
+        match item {
+            Item::Function(function) => functions.push(function),
+            Item::Contract(contract) => {
+                for item in &contract.body {
+                    if let Item::Function(function) = item {
+                        functions.push(function);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    functions
+}
@@ -0,0 +1,118 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "arbitrary-from-transfer";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+/// Whether the tokens are taken from the caller or from the contract itself
+fn is_safe_from(from: &Expr) -> bool {
+    match strip_conversions(from) {
+        Expr::Ident(ident) => ident == "this",
+        Expr::Member(member) => match (&*member.expr, &*member.member) {
+            (Expr::Ident(base), Expr::Ident(name)) => base == "msg" && name == "sender",
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+pub struct ArbitraryFromTransfer {
+    data: RuleEntry,
+}
+
+impl ArbitraryFromTransfer {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        name: &SolIdent,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: format!(
+                "'{}' from an arbitrary address lets anyone spend the tokens approved to this contract, use msg.sender",
+                name
+            ),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for ArbitraryFromTransfer {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            let context = ContractContext::new(&contract, files);
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                let variables = function_variables(&function);
+                for (expr, _) in function_exprs(&function) {
+                    for call in expr_calls(&expr) {
+                        let Some((base, name)) = member_call(call) else {
+                            continue;
+                        };
+                        if name != "transferFrom" && name != "safeTransferFrom" {
+                            continue;
+                        }
+                        let ArgListImpl::Unnamed(args) = &call.args.list else {
+                            continue;
+                        };
+                        // Libraries such as SafeERC20 called directly take the token first
+                        let is_library_call = match base {
+                            Expr::Ident(ident) => {
+                                let ident = ident.to_string();
+                                !variables.contains_key(&ident)
+                                    && !context.state_variables.contains_key(&ident)
+                                    && !context.is_contract_name(&ident)
+                            }
+                            _ => false,
+                        };
+                        let from = if is_library_call { 1 } else { 0 };
+                        if args.len() < from + 3 {
+                            continue;
+                        }
+                        if !is_safe_from(&args[from]) {
+                            let location = (call.span().start(), call.span().end());
+                            res.push(self.create_diag(location, name, file));
+                        }
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl ArbitraryFromTransfer {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = ArbitraryFromTransfer { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
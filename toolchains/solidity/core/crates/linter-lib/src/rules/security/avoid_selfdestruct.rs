@@ -0,0 +1,78 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "avoid-selfdestruct";
+
+// specific
+const DEFAULT_MESSAGE: &str =
+    "Avoid to use selfdestruct, which is deprecated and lets anyone able to call it destroy the contract";
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct AvoidSelfdestruct {
+    data: RuleEntry,
+}
+
+impl AvoidSelfdestruct {
+    fn create_diag(&self, location: (LineColumn, LineColumn), file: &SolidFile) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: DEFAULT_MESSAGE.to_string(),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for AvoidSelfdestruct {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                for (expr, _) in function_exprs(&function) {
+                    for call in expr_calls(&expr) {
+                        if let Expr::Ident(ident) = &*call.expr {
+                            // `suicide` is the name of `selfdestruct` before solidity 0.5
+                            if ident == "selfdestruct" || ident == "suicide" {
+                                let location = (call.span().start(), call.span().end());
+                                res.push(self.create_diag(location, file));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl AvoidSelfdestruct {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = AvoidSelfdestruct { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "calls-in-loop";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct CallsInLoop {
+    data: RuleEntry,
+}
+
+impl CallsInLoop {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        name: &SolIdent,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: format!(
+                "Avoid to use '{}' in a loop, a single failing or expensive callee can block the whole loop",
+                name
+            ),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for CallsInLoop {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                for (expr, in_loop) in function_exprs(&function) {
+                    if !in_loop {
+                        continue;
+                    }
+                    for call in expr_calls(&expr) {
+                        // The calls sending ether are reported by ether-transfer-in-loop
+                        if let Some((_, name)) = member_call(call) {
+                            if (name == "call" || name == "delegatecall") && !sends_value(call) {
+                                let location = (call.span().start(), call.span().end());
+                                res.push(self.create_diag(location, name, file));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl CallsInLoop {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = CallsInLoop { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
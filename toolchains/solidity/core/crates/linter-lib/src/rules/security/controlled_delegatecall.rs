@@ -0,0 +1,106 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "controlled-delegatecall";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct ControlledDelegatecall {
+    data: RuleEntry,
+}
+
+impl ControlledDelegatecall {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        parameter: &str,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: format!(
+                "Delegatecall to the address given by the caller in '{}', which can run any code with the storage of the contract",
+                parameter
+            ),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for ControlledDelegatecall {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                // Only the parameters of the functions callable from outside are supplied by the users
+                if !function.kind.is_function()
+                    || matches!(
+                        function.attributes.visibility(),
+                        Some(Visibility::Internal(_) | Visibility::Private(_))
+                    )
+                {
+                    continue;
+                }
+                let parameters: Vec<String> = function
+                    .arguments
+                    .iter()
+                    .filter_map(|parameter| parameter.name.as_ref().map(|name| name.to_string()))
+                    .collect();
+
+                for (expr, _) in function_exprs(&function) {
+                    for call in expr_calls(&expr) {
+                        let Some((base, name)) = member_call(call) else {
+                            continue;
+                        };
+                        if name != "delegatecall" {
+                            continue;
+                        }
+                        let Some(target) = root_ident(strip_conversions(base)) else {
+                            continue;
+                        };
+                        let target = target.to_string();
+                        if parameters.contains(&target) {
+                            let location = (call.span().start(), call.span().end());
+                            res.push(self.create_diag(location, &target, file));
+                        }
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl ControlledDelegatecall {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = ControlledDelegatecall { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+use std::collections::HashMap;
+
+// global
+pub const RULE_ID: &str = "encode-packed-collision";
+
+// specific
+const DEFAULT_MESSAGE: &str = "Hashing abi.encodePacked with several dynamic arguments allows collisions, as in ('a', 'bc') and ('ab', 'c'). Use abi.encode instead";
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+/// Whether a type has a variable length once packed
+fn is_dynamic(ty: &Type) -> bool {
+    match ty {
+        Type::String(_) | Type::Bytes(_) => true,
+        Type::Array(array) => array.size.is_none(),
+        _ => false,
+    }
+}
+
+/// Whether an argument of `abi.encodePacked` is a variable or a conversion of a dynamic type
+fn is_dynamic_arg(
+    arg: &Expr,
+    variables: &HashMap<String, Type>,
+    context: &ContractContext<'_>,
+) -> bool {
+    match arg {
+        Expr::Ident(ident) => {
+            let name = ident.to_string();
+            variables
+                .get(&name)
+                .or_else(|| {
+                    context
+                        .state_variables
+                        .get(&name)
+                        .map(|variable| &variable.ty)
+                })
+                .is_some_and(is_dynamic)
+        }
+        Expr::Call(call) => match &*call.expr {
+            Expr::Type(ty) => is_dynamic(ty),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+pub struct EncodePackedCollision {
+    data: RuleEntry,
+}
+
+impl EncodePackedCollision {
+    fn create_diag(&self, location: (LineColumn, LineColumn), file: &SolidFile) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: DEFAULT_MESSAGE.to_string(),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for EncodePackedCollision {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            let context = ContractContext::new(&contract, files);
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                let variables = function_variables(&function);
+                for (expr, _) in function_exprs(&function) {
+                    for call in expr_calls(&expr) {
+                        let Expr::Ident(ident) = &*call.expr else {
+                            continue;
+                        };
+                        let ArgListImpl::Unnamed(args) = &call.args.list else {
+                            continue;
+                        };
+                        if ident != "keccak256" || args.len() != 1 {
+                            continue;
+                        }
+                        let Expr::Call(packed) = &args[0] else {
+                            continue;
+                        };
+                        let Some((Expr::Ident(abi), name)) = member_call(packed) else {
+                            continue;
+                        };
+                        if abi != "abi" || name != "encodePacked" {
+                            continue;
+                        }
+                        let ArgListImpl::Unnamed(packed_args) = &packed.args.list else {
+                            continue;
+                        };
+                        let dynamic_args = packed_args
+                            .iter()
+                            .filter(|arg| is_dynamic_arg(arg, &variables, &context))
+                            .count();
+                        if dynamic_args > 1 {
+                            let location = (packed.span().start(), packed.span().end());
+                            res.push(self.create_diag(location, file));
+                        }
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl EncodePackedCollision {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = EncodePackedCollision { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "ether-transfer-in-loop";
+
+// specific
+const DEFAULT_MESSAGE: &str =
+    "Avoid to send ether in a loop, a single reverting recipient blocks every other one";
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+/// Whether a call sends ether: `transfer` and `send` on an address take the amount as only argument,
+/// while `call` takes it as `value` option
+fn is_ether_transfer(call: &ExprCall) -> bool {
+    let Some((_, name)) = member_call(call) else {
+        return false;
+    };
+    let args = match &call.args.list {
+        ArgListImpl::Unnamed(args) => args.len(),
+        ArgListImpl::Named(args) => args.list.len(),
+    };
+    ((name == "transfer" || name == "send") && args == 1) || (name == "call" && sends_value(call))
+}
+
+pub struct EtherTransferInLoop {
+    data: RuleEntry,
+}
+
+impl EtherTransferInLoop {
+    fn create_diag(&self, location: (LineColumn, LineColumn), file: &SolidFile) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: DEFAULT_MESSAGE.to_string(),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for EtherTransferInLoop {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                for (expr, in_loop) in function_exprs(&function) {
+                    if !in_loop {
+                        continue;
+                    }
+                    for call in expr_calls(&expr) {
+                        if is_ether_transfer(call) {
+                            let location = (call.span().start(), call.span().end());
+                            res.push(self.create_diag(location, file));
+                        }
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl EtherTransferInLoop {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = EtherTransferInLoop { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -3,17 +3,35 @@ use std::collections::HashMap;
 
 #[macro_use]
 pub(crate) mod no_inline_assembly;
+pub(crate) mod arbitrary_from_transfer;
+pub(crate) mod avoid_selfdestruct;
 pub(crate) mod avoid_tx_origin;
+pub(crate) mod calls_in_loop;
+pub(crate) mod controlled_delegatecall;
+pub(crate) mod encode_packed_collision;
+pub(crate) mod ether_transfer_in_loop;
 pub(crate) mod func_visibility;
 pub(crate) mod not_rely_on_time;
+pub(crate) mod reentrancy;
 pub(crate) mod state_visibility;
+pub(crate) mod unchecked_low_level_call;
+pub(crate) mod weak_randomness;
 
 // List all rules
+use crate::rules::security::arbitrary_from_transfer::ArbitraryFromTransfer;
+use crate::rules::security::avoid_selfdestruct::AvoidSelfdestruct;
 use crate::rules::security::avoid_tx_origin::AvoidTxOrigin;
+use crate::rules::security::calls_in_loop::CallsInLoop;
+use crate::rules::security::controlled_delegatecall::ControlledDelegatecall;
+use crate::rules::security::encode_packed_collision::EncodePackedCollision;
+use crate::rules::security::ether_transfer_in_loop::EtherTransferInLoop;
 use crate::rules::security::func_visibility::FuncVisibility;
 use crate::rules::security::no_inline_assembly::NoInlineAssembly;
 use crate::rules::security::not_rely_on_time::NotRelyOnTime;
+use crate::rules::security::reentrancy::Reentrancy;
 use crate::rules::security::state_visibility::StateVisibility;
+use crate::rules::security::unchecked_low_level_call::UncheckedLowLevelCall;
+use crate::rules::security::weak_randomness::WeakRandomness;
 
 use crate::rules::RuleBuilder;
 
@@ -24,6 +42,15 @@ pub fn create_default_rules() -> Vec<RuleEntry> {
         NotRelyOnTime::create_default(),
         FuncVisibility::create_default(),
         AvoidTxOrigin::create_default(),
+        Reentrancy::create_default(),
+        UncheckedLowLevelCall::create_default(),
+        ArbitraryFromTransfer::create_default(),
+        AvoidSelfdestruct::create_default(),
+        CallsInLoop::create_default(),
+        ControlledDelegatecall::create_default(),
+        EncodePackedCollision::create_default(),
+        EtherTransferInLoop::create_default(),
+        WeakRandomness::create_default(),
     ]
 }
 
@@ -41,6 +68,33 @@ pub fn create_rules() -> RulesMap {
     );
     rules.insert(avoid_tx_origin::RULE_ID.to_string(), AvoidTxOrigin::create);
     rules.insert(not_rely_on_time::RULE_ID.to_string(), NotRelyOnTime::create);
+    rules.insert(reentrancy::RULE_ID.to_string(), Reentrancy::create);
+    rules.insert(
+        unchecked_low_level_call::RULE_ID.to_string(),
+        UncheckedLowLevelCall::create,
+    );
+    rules.insert(
+        arbitrary_from_transfer::RULE_ID.to_string(),
+        ArbitraryFromTransfer::create,
+    );
+    rules.insert(
+        avoid_selfdestruct::RULE_ID.to_string(),
+        AvoidSelfdestruct::create,
+    );
+    rules.insert(calls_in_loop::RULE_ID.to_string(), CallsInLoop::create);
+    rules.insert(
+        controlled_delegatecall::RULE_ID.to_string(),
+        ControlledDelegatecall::create,
+    );
+    rules.insert(
+        encode_packed_collision::RULE_ID.to_string(),
+        EncodePackedCollision::create,
+    );
+    rules.insert(
+        ether_transfer_in_loop::RULE_ID.to_string(),
+        EtherTransferInLoop::create,
+    );
+    rules.insert(weak_randomness::RULE_ID.to_string(), WeakRandomness::create);
 
     rules
 }
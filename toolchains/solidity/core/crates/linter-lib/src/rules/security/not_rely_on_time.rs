@@ -40,9 +40,8 @@ impl NotRelyOnTime {
 impl RuleType for NotRelyOnTime {
     fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
         let mut res = Vec::new();
-        let mut i = 1;
 
-        for line in file.content.lines() {
+        for (i, line) in (1..).zip(file.content.lines()) {
             if let Some(index) = line.find("now") {
                 res.push(self.create_diag(
                     (
@@ -73,7 +72,6 @@ impl RuleType for NotRelyOnTime {
                     file,
                 ));
             }
-            i += 1;
         }
         res
     }
@@ -0,0 +1,483 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+use std::collections::HashMap;
+
+// global
+pub const RULE_ID: &str = "reentrancy";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+pub const DEFAULT_NON_REENTRANT_MODIFIERS: [&str; 1] = ["nonReentrant"];
+
+/// Members whose call sends ether or hands over the execution, whatever the type of the receiver
+const EXTERNAL_CALL_MEMBERS: [&str; 4] = ["call", "delegatecall", "send", "transfer"];
+
+type Location = (LineColumn, LineColumn);
+
+fn location<T: Spanned>(node: &T) -> Location {
+    (node.span().start(), node.span().end())
+}
+
+/// External calls made on a path, or `None` once the path returned or reverted
+type Pending = Option<Vec<Location>>;
+
+fn merge(a: Pending, b: Pending) -> Pending {
+    match (a, b) {
+        (Some(mut a), Some(b)) => {
+            for call in b {
+                if !a.contains(&call) {
+                    a.push(call);
+                }
+            }
+            Some(a)
+        }
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+struct Finding {
+    call: Location,
+    write: Location,
+    variable: String,
+}
+
+/// Walks the statements of a function in execution order, keeping track of the external calls
+/// made on the current path
+struct FunctionWalker<'a> {
+    context: &'a ContractContext<'a>,
+    locals: HashMap<String, Type>,
+    findings: Vec<Finding>,
+}
+
+impl<'a> FunctionWalker<'a> {
+    fn new(context: &'a ContractContext<'a>, function: &ItemFunction) -> Self {
+        let mut walker = FunctionWalker {
+            context,
+            locals: HashMap::new(),
+            findings: Vec::new(),
+        };
+        let returns = function
+            .returns
+            .iter()
+            .flat_map(|returns| returns.returns.iter());
+        for parameter in function.arguments.iter().chain(returns) {
+            walker.declare(parameter);
+        }
+        walker
+    }
+
+    fn declare(&mut self, declaration: &VariableDeclaration) {
+        if let Some(name) = &declaration.name {
+            self.locals.insert(name.to_string(), declaration.ty.clone());
+        }
+    }
+
+    fn stmts(&mut self, stmts: &[Stmt], pending: Vec<Location>) -> Pending {
+        let mut pending = pending;
+        for stmt in stmts {
+            pending = self.stmt(stmt, pending)?;
+        }
+        Some(pending)
+    }
+
+    fn stmt(&mut self, stmt: &Stmt, mut pending: Vec<Location>) -> Pending {
+        match stmt {
+            Stmt::Block(block) => self.stmts(&block.stmts, pending),
+            Stmt::UncheckedBlock(block) => self.stmts(&block.block.stmts, pending),
+            Stmt::Expr(stmt) => {
+                self.expr(&stmt.expr, &mut pending);
+                Some(pending)
+            }
+            Stmt::VarDecl(stmt) => {
+                self.var_decl(stmt, &mut pending);
+                Some(pending)
+            }
+            Stmt::Emit(stmt) => {
+                self.expr(&stmt.expr, &mut pending);
+                Some(pending)
+            }
+            Stmt::Revert(stmt) => {
+                self.expr(&stmt.expr, &mut pending);
+                None
+            }
+            Stmt::Return(stmt) => {
+                if let Some(expr) = &stmt.expr {
+                    self.expr(expr, &mut pending);
+                }
+                None
+            }
+            Stmt::If(stmt) => {
+                self.expr(&stmt.cond, &mut pending);
+                let then_branch = self.stmt(&stmt.then_branch, pending.clone());
+                let else_branch = match &stmt.else_branch {
+                    Some((_, else_branch)) => self.stmt(else_branch, pending),
+                    None => Some(pending),
+                };
+                merge(then_branch, else_branch)
+            }
+            Stmt::For(stmt) => {
+                match &stmt.init {
+                    ForInitStmt::VarDecl(init) => self.var_decl(init, &mut pending),
+                    ForInitStmt::Expr(init) => self.expr(&init.expr, &mut pending),
+                    ForInitStmt::Empty(_) => {}
+                }
+                self.loop_body(
+                    &stmt.body,
+                    stmt.cond.as_deref(),
+                    stmt.post.as_deref(),
+                    pending,
+                )
+            }
+            Stmt::While(stmt) => self.loop_body(&stmt.body, Some(&stmt.cond), None, pending),
+            Stmt::DoWhile(stmt) => self.loop_body(&stmt.body, None, Some(&stmt.cond), pending),
+            Stmt::Try(stmt) => {
+                self.expr(&stmt.expr, &mut pending);
+                if let Some(returns) = &stmt.returns {
+                    for parameter in returns.returns.iter() {
+                        self.declare(parameter);
+                    }
+                }
+                let mut res = self.stmts(&stmt.block.stmts, pending.clone());
+                for catch in &stmt.catch {
+                    for parameter in catch.list.iter() {
+                        self.declare(parameter);
+                    }
+                    let caught = self.stmts(&catch.block.stmts, pending.clone());
+                    res = merge(res, caught);
+                }
+                res
+            }
+            Stmt::Assembly(_) | Stmt::Break(_) | Stmt::Continue(_) => Some(pending),
+        }
+    }
+
+    /// Walks a loop body twice, so that the calls made at the end of an iteration
+    /// precede the writes made at the beginning of the next one
+    fn loop_body(
+        &mut self,
+        body: &Stmt,
+        before: Option<&Expr>,
+        after: Option<&Expr>,
+        pending: Vec<Location>,
+    ) -> Pending {
+        let mut entry = pending;
+        for _ in 0..2 {
+            if let Some(before) = before {
+                self.expr(before, &mut entry);
+            }
+            if let Some(mut exit) = self.stmt(body, entry.clone()) {
+                if let Some(after) = after {
+                    self.expr(after, &mut exit);
+                }
+                entry = merge(Some(entry), Some(exit)).unwrap_or_default();
+            }
+        }
+        Some(entry)
+    }
+
+    fn var_decl(&mut self, stmt: &StmtVarDecl, pending: &mut Vec<Location>) {
+        if let Some((_, value)) = &stmt.assignment {
+            self.expr(value, pending);
+        }
+        match &stmt.declaration {
+            VarDeclDecl::VarDecl(declaration) => self.declare(declaration),
+            VarDeclDecl::Tuple(tuple) => {
+                for declaration in tuple.vars.iter().flatten() {
+                    self.declare(declaration);
+                }
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr, pending: &mut Vec<Location>) {
+        self.events(&normalize_expr(expr), pending);
+    }
+
+    /// Records the external calls and the state writes of an expression, in evaluation order
+    fn events(&mut self, expr: &Expr, pending: &mut Vec<Location>) {
+        match expr {
+            Expr::Binary(binary) if is_assignment(&binary.op) => {
+                self.events(&binary.right, pending);
+                self.events(&binary.left, pending);
+                self.write(&binary.left, pending);
+            }
+            Expr::Binary(binary) => {
+                self.events(&binary.left, pending);
+                self.events(&binary.right, pending);
+            }
+            Expr::Unary(unary) => {
+                self.events(&unary.expr, pending);
+                if matches!(unary.op, UnOp::Increment(..) | UnOp::Decrement(..)) {
+                    self.write(&unary.expr, pending);
+                }
+            }
+            Expr::Postfix(postfix) => {
+                self.events(&postfix.expr, pending);
+                self.write(&postfix.expr, pending);
+            }
+            Expr::Delete(delete) => {
+                self.events(&delete.expr, pending);
+                self.write(&delete.expr, pending);
+            }
+            Expr::Call(call) => {
+                self.events(&call.expr, pending);
+                match &call.args.list {
+                    ArgListImpl::Unnamed(args) => {
+                        for arg in args {
+                            self.events(arg, pending);
+                        }
+                    }
+                    ArgListImpl::Named(args) => {
+                        for arg in &args.list {
+                            self.events(&arg.arg, pending);
+                        }
+                    }
+                }
+                if let Some((base, name)) = member_call(call) {
+                    if name == "push" || name == "pop" {
+                        self.write(base, pending);
+                    } else if self.is_external_call(base, name) {
+                        let call = location(call);
+                        if !pending.contains(&call) {
+                            pending.push(call);
+                        }
+                    }
+                }
+            }
+            Expr::CallOptions(call) => {
+                self.events(&call.expr, pending);
+                for arg in &call.args.list {
+                    self.events(&arg.arg, pending);
+                }
+            }
+            Expr::Member(member) => self.events(&member.expr, pending),
+            Expr::Index(index) => {
+                self.events(&index.expr, pending);
+                for bound in index.start.iter().chain(index.end.iter()) {
+                    self.events(bound, pending);
+                }
+            }
+            Expr::Ternary(ternary) => {
+                self.events(&ternary.cond, pending);
+                self.events(&ternary.if_true, pending);
+                self.events(&ternary.if_false, pending);
+            }
+            Expr::Tuple(tuple) => {
+                for elem in &tuple.elems {
+                    self.events(elem, pending);
+                }
+            }
+            Expr::Array(array) => {
+                for elem in &array.elems {
+                    self.events(elem, pending);
+                }
+            }
+            Expr::Payable(payable) => {
+                if let ArgListImpl::Unnamed(args) = &payable.args.list {
+                    for arg in args {
+                        self.events(arg, pending);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn write(&mut self, lvalue: &Expr, pending: &[Location]) {
+        if let Expr::Tuple(tuple) = lvalue {
+            for elem in &tuple.elems {
+                self.write(elem, pending);
+            }
+            return;
+        }
+        let Some(name) = root_ident(lvalue).map(|ident| ident.to_string()) else {
+            return;
+        };
+        if self.locals.contains_key(&name) || !self.context.state_variables.contains_key(&name) {
+            return;
+        }
+        for call in pending {
+            self.findings.push(Finding {
+                call: *call,
+                write: location(lvalue),
+                variable: name.clone(),
+            });
+        }
+    }
+
+    fn variable_type(&self, name: &str) -> Option<&Type> {
+        self.locals.get(name).or_else(|| {
+            self.context
+                .state_variables
+                .get(name)
+                .map(|variable| &variable.ty)
+        })
+    }
+
+    /// The type of an expression naming a variable, or an element of a mapping or array variable
+    fn expr_type(&self, expr: &Expr) -> Option<&Type> {
+        match expr {
+            Expr::Ident(ident) => self.variable_type(&ident.to_string()),
+            Expr::Index(index) => match self.expr_type(&index.expr)? {
+                Type::Mapping(mapping) => Some(&mapping.value),
+                Type::Array(array) => Some(&array.ty),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn is_external_call(&self, base: &Expr, name: &SolIdent) -> bool {
+        if EXTERNAL_CALL_MEMBERS.iter().any(|member| name == member) {
+            return true;
+        }
+        match base {
+            Expr::Ident(ident) if ident == "this" || ident == "super" => false,
+            // Conversion of an address to a contract, as in `IERC20(token).transfer(...)`
+            Expr::Call(call) => match &*call.expr {
+                Expr::Ident(ident) => self.context.is_contract_name(&ident.to_string()),
+                _ => false,
+            },
+            base => self
+                .expr_type(base)
+                .is_some_and(|ty| self.context.is_contract_type(ty)),
+        }
+    }
+}
+
+pub struct Reentrancy {
+    non_reentrant_modifiers: Vec<String>,
+    data: RuleEntry,
+}
+
+impl Reentrancy {
+    fn create_diag(&self, location: Location, message: String, file: &SolidFile) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+
+    fn is_protected(&self, function: &ItemFunction) -> bool {
+        function.attributes.iter().any(|attribute| match attribute {
+            FunctionAttribute::Modifier(modifier) => self
+                .non_reentrant_modifiers
+                .iter()
+                .any(|name| modifier.name.last() == name),
+            _ => false,
+        })
+    }
+}
+
+impl RuleType for Reentrancy {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            let context = ContractContext::new(&contract, files);
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                let FunctionBody::Block(body) = &function.body else {
+                    continue;
+                };
+                if !function.kind.is_function()
+                    && !function.kind.is_fallback()
+                    && !function.kind.is_receive()
+                {
+                    continue;
+                }
+                if matches!(
+                    function.attributes.mutability(),
+                    Some(Mutability::Pure(_) | Mutability::View(_) | Mutability::Constant(_))
+                ) || self.is_protected(&function)
+                {
+                    continue;
+                }
+
+                let mut walker = FunctionWalker::new(&context, &function);
+                walker.stmts(&body.stmts, Vec::new());
+
+                let mut reported_calls = Vec::new();
+                let mut reported_writes = Vec::new();
+                for finding in walker.findings {
+                    if !reported_calls.contains(&finding.call) {
+                        reported_calls.push(finding.call);
+                        res.push(self.create_diag(
+                            finding.call,
+                            format!(
+                                "External call is followed by a write to state variable '{}', which allows reentrancy",
+                                finding.variable
+                            ),
+                            file,
+                        ));
+                    }
+                    if !reported_writes.contains(&finding.write) {
+                        reported_writes.push(finding.write);
+                        res.push(self.create_diag(
+                            finding.write,
+                            format!(
+                                "State variable '{}' is written after an external call, which allows reentrancy",
+                                finding.variable
+                            ),
+                            file,
+                        ));
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl Reentrancy {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let mut non_reentrant_modifiers: Vec<String> = DEFAULT_NON_REENTRANT_MODIFIERS
+            .iter()
+            .map(|modifier| modifier.to_string())
+            .collect();
+
+        if let Some(data) = &data.data {
+            match data["nonReentrantModifiers"].as_array() {
+                Some(modifiers) => {
+                    non_reentrant_modifiers = modifiers
+                        .iter()
+                        .filter_map(|modifier| modifier.as_str().map(str::to_string))
+                        .collect();
+                }
+                None => eprintln!("{} rule : bad config data", RULE_ID),
+            }
+        }
+        let rule = Reentrancy {
+            non_reentrant_modifiers,
+            data,
+        };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: Some(serde_json::json!({
+                "nonReentrantModifiers": DEFAULT_NON_REENTRANT_MODIFIERS,
+            })),
+        }
+    }
+}
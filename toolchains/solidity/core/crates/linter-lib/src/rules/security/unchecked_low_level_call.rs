@@ -0,0 +1,252 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+use std::collections::HashSet;
+
+// global
+pub const RULE_ID: &str = "unchecked-low-level-call";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+/// Members returning a boolean which is false when the call failed, instead of reverting
+const LOW_LEVEL_CALLS: [&str; 4] = ["call", "delegatecall", "staticcall", "send"];
+
+type Location = (LineColumn, LineColumn);
+
+/// The name of a normalized low-level call, and its location
+fn low_level_call(expr: &Expr) -> Option<(String, Location)> {
+    let Expr::Call(call) = expr else {
+        return None;
+    };
+    let (_, name) = member_call(call)?;
+    if LOW_LEVEL_CALLS.iter().any(|low_level| name == low_level) {
+        Some((name.to_string(), (call.span().start(), call.span().end())))
+    } else {
+        None
+    }
+}
+
+/// Identifiers read by an expression
+struct IdentVisitor {
+    idents: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for IdentVisitor {
+    fn visit_ident(&mut self, ident: &'ast SolIdent) {
+        self.idents.push(ident.to_string());
+    }
+}
+
+fn idents(expr: &Expr) -> Vec<String> {
+    let mut visitor = IdentVisitor { idents: Vec::new() };
+    visitor.visit_expr(expr);
+    visitor.idents
+}
+
+struct AssignedCall {
+    name: String,
+    location: Location,
+    variable: String,
+}
+
+/// Collects the low-level calls of a function, and the variables it checks
+struct CallVisitor {
+    discarded: Vec<(String, Location)>,
+    assigned: Vec<AssignedCall>,
+    checked: HashSet<String>,
+}
+
+impl CallVisitor {
+    fn new() -> Self {
+        Self {
+            discarded: Vec::new(),
+            assigned: Vec::new(),
+            checked: HashSet::new(),
+        }
+    }
+
+    /// Records a low-level call stored in `variable`, or discarded without one
+    fn assign(&mut self, value: &Expr, variable: Option<String>) {
+        if let Some((name, location)) = low_level_call(&normalize_expr(value)) {
+            match variable {
+                Some(variable) => self.assigned.push(AssignedCall {
+                    name,
+                    location,
+                    variable,
+                }),
+                None => self.discarded.push((name, location)),
+            }
+        }
+    }
+
+    fn check(&mut self, expr: &Expr) {
+        self.checked.extend(idents(expr));
+    }
+}
+
+impl<'ast> Visit<'ast> for CallVisitor {
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        if let Stmt::Expr(stmt) = stmt {
+            match normalize_expr(&stmt.expr) {
+                Expr::Binary(binary) if matches!(binary.op, BinOp::Assign(..)) => {
+                    // The status is the first element of the tuple returned by `call`
+                    let variable = match &*binary.left {
+                        Expr::Tuple(tuple) => tuple.elems.first(),
+                        left => Some(left),
+                    };
+                    let variable = variable.and_then(root_ident).map(|ident| ident.to_string());
+                    self.assign(&binary.right, variable);
+                }
+                expr => self.assign(&expr, None),
+            }
+        }
+        visit::visit_stmt(self, stmt);
+    }
+
+    fn visit_stmt_var_decl(&mut self, stmt: &'ast StmtVarDecl) {
+        if let Some((_, value)) = &stmt.assignment {
+            let variable = match &stmt.declaration {
+                VarDeclDecl::VarDecl(declaration) => declaration.name.as_ref(),
+                VarDeclDecl::Tuple(tuple) => match tuple.vars.first() {
+                    // The parser reads the assignment `(ok, ) = ...` as the declaration of a nameless `ok`
+                    Some(Some(VariableDeclaration {
+                        ty: Type::Custom(path),
+                        name: None,
+                        ..
+                    })) if path.len() == 1 => Some(path.last()),
+                    Some(Some(declaration)) => declaration.name.as_ref(),
+                    _ => None,
+                },
+            };
+            self.assign(value, variable.map(|name| name.to_string()));
+        }
+        visit::visit_stmt_var_decl(self, stmt);
+    }
+
+    fn visit_stmt_if(&mut self, stmt: &'ast StmtIf) {
+        self.check(&stmt.cond);
+        visit::visit_stmt_if(self, stmt);
+    }
+
+    fn visit_stmt_while(&mut self, stmt: &'ast StmtWhile) {
+        self.check(&stmt.cond);
+        visit::visit_stmt_while(self, stmt);
+    }
+
+    fn visit_stmt_dowhile(&mut self, stmt: &'ast StmtDoWhile) {
+        self.check(&stmt.cond);
+        visit::visit_stmt_dowhile(self, stmt);
+    }
+
+    fn visit_stmt_for(&mut self, stmt: &'ast StmtFor) {
+        if let Some(cond) = &stmt.cond {
+            self.check(cond);
+        }
+        visit::visit_stmt_for(self, stmt);
+    }
+
+    // A returned status is left to the caller to check
+    fn visit_stmt_return(&mut self, stmt: &'ast StmtReturn) {
+        if let Some(expr) = &stmt.expr {
+            self.check(expr);
+        }
+        visit::visit_stmt_return(self, stmt);
+    }
+
+    fn visit_expr_ternary(&mut self, expr: &'ast ExprTernary) {
+        self.check(&expr.cond);
+        visit::visit_expr_ternary(self, expr);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if let Expr::Ident(ident) = &*call.expr {
+            if ident == "require" || ident == "assert" {
+                if let ArgListImpl::Unnamed(args) = &call.args.list {
+                    if let Some(cond) = args.first() {
+                        self.check(cond);
+                    }
+                }
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+}
+
+pub struct UncheckedLowLevelCall {
+    data: RuleEntry,
+}
+
+impl UncheckedLowLevelCall {
+    fn create_diag(&self, location: Location, message: String, file: &SolidFile) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for UncheckedLowLevelCall {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                let mut visitor = CallVisitor::new();
+                visitor.visit_item_function(&function);
+
+                for (name, location) in visitor.discarded {
+                    res.push(self.create_diag(
+                        location,
+                        format!("Return value of low-level '{}' is not checked", name),
+                        file,
+                    ));
+                }
+                for call in visitor.assigned {
+                    if !visitor.checked.contains(&call.variable) {
+                        res.push(self.create_diag(
+                            call.location,
+                            format!(
+                                "Return value of low-level '{}' is stored in '{}' but never checked",
+                                call.name, call.variable
+                            ),
+                            file,
+                        ));
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl UncheckedLowLevelCall {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = UncheckedLowLevelCall { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "weak-randomness";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+/// Members of `block` which can be predicted or influenced by the block producers.
+/// `difficulty` is `prevrandao` since the merge, and `block.blockhash` the old name of `blockhash`.
+const BLOCK_MEMBERS: [&str; 3] = ["prevrandao", "difficulty", "blockhash"];
+
+struct RandomnessVisitor {
+    sources: Vec<(String, (LineColumn, LineColumn))>,
+}
+
+impl<'ast> Visit<'ast> for RandomnessVisitor {
+    fn visit_expr_member(&mut self, member: &'ast ExprMember) {
+        if let (Expr::Ident(base), Expr::Ident(name)) = (&*member.expr, &*member.member) {
+            if base == "block" && BLOCK_MEMBERS.iter().any(|source| name == source) {
+                self.sources.push((
+                    format!("block.{}", name),
+                    (member.span().start(), member.span().end()),
+                ));
+            }
+        }
+        visit::visit_expr_member(self, member);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if let Expr::Ident(ident) = &*call.expr {
+            if ident == "blockhash" {
+                self.sources.push((
+                    "blockhash".to_string(),
+                    (call.span().start(), call.span().end()),
+                ));
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+}
+
+pub struct WeakRandomness {
+    data: RuleEntry,
+}
+
+impl WeakRandomness {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        source: &str,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: format!(
+                "Avoid to use {} as a source of randomness, it can be predicted or influenced by block producers",
+                source
+            ),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for WeakRandomness {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let mut visitor = RandomnessVisitor {
+            sources: Vec::new(),
+        };
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                for (expr, _) in function_exprs(&function) {
+                    visitor.visit_expr(&expr);
+                }
+            }
+        }
+        for (source, location) in visitor.sources {
+            res.push(self.create_diag(location, &source, file));
+        }
+        res
+    }
+}
+
+impl WeakRandomness {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = WeakRandomness { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::rules::types::{RuleEntry, RulesMap};
+use std::collections::HashMap;
+
+pub(crate) mod shadowing_builtin;
+pub(crate) mod shadowing_inherited_state;
+pub(crate) mod shadowing_local;
+pub(crate) mod shadowing_member;
+pub(crate) mod shadowing_state;
+
+// List all rules
+use crate::rules::shadowing::shadowing_builtin::ShadowingBuiltin;
+use crate::rules::shadowing::shadowing_inherited_state::ShadowingInheritedState;
+use crate::rules::shadowing::shadowing_local::ShadowingLocal;
+use crate::rules::shadowing::shadowing_member::ShadowingMember;
+use crate::rules::shadowing::shadowing_state::ShadowingState;
+use crate::rules::RuleBuilder;
+
+pub fn create_default_rules() -> Vec<RuleEntry> {
+    vec![
+        ShadowingBuiltin::create_default(),
+        ShadowingInheritedState::create_default(),
+        ShadowingLocal::create_default(),
+        ShadowingMember::create_default(),
+        ShadowingState::create_default(),
+    ]
+}
+
+pub fn create_rules() -> RulesMap {
+    let mut rules: HashMap<String, RuleBuilder> = HashMap::new();
+
+    rules.insert(
+        shadowing_builtin::RULE_ID.to_string(),
+        ShadowingBuiltin::create,
+    );
+    rules.insert(
+        shadowing_inherited_state::RULE_ID.to_string(),
+        ShadowingInheritedState::create,
+    );
+    rules.insert(shadowing_local::RULE_ID.to_string(), ShadowingLocal::create);
+    rules.insert(
+        shadowing_member::RULE_ID.to_string(),
+        ShadowingMember::create,
+    );
+    rules.insert(shadowing_state::RULE_ID.to_string(), ShadowingState::create);
+
+    rules
+}
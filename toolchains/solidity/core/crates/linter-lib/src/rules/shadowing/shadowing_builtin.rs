@@ -0,0 +1,100 @@
+use crate::linter::SolidFile;
+use crate::rules::scope::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "shadowing-builtin";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct ShadowingBuiltin {
+    data: RuleEntry,
+}
+
+impl ShadowingBuiltin {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for ShadowingBuiltin {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut declarations: Vec<(&SolIdent, DeclarationKind)> = Vec::new();
+
+        for item in &file.data.items {
+            if let Some((name, kind, _)) = member_kind(item) {
+                declarations.push((name, kind));
+            }
+            if let Item::Contract(contract) = item {
+                declarations.push((&contract.name, DeclarationKind::Contract));
+                for item in &contract.body {
+                    if let Some((name, kind, _)) = member_kind(item) {
+                        declarations.push((name, kind));
+                    }
+                }
+            }
+        }
+        for function in file_functions(&file.data) {
+            for local in function_locals(function) {
+                declarations.push((local.name, local.kind));
+            }
+        }
+
+        declarations
+            .into_iter()
+            .filter(|(name, _)| BUILTINS.contains(&name.to_string().as_str()))
+            .map(|(name, kind)| {
+                let span = name.span();
+                self.create_diag(
+                    (span.start(), span.end()),
+                    format!(
+                        "The {} '{}' shadows a builtin symbol",
+                        kind.describe(),
+                        name
+                    ),
+                    file,
+                )
+            })
+            .collect()
+    }
+}
+
+impl ShadowingBuiltin {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = ShadowingBuiltin { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
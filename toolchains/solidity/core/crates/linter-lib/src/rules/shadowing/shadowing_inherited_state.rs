@@ -0,0 +1,90 @@
+use crate::linter::SolidFile;
+use crate::rules::scope::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::storage_layout::Definitions;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "shadowing-inherited-state";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct ShadowingInheritedState {
+    data: RuleEntry,
+}
+
+impl ShadowingInheritedState {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for ShadowingInheritedState {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let definitions = Definitions::new(files.iter().map(|file| &file.data));
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            let scope = ContractScope::new(&contract, &definitions);
+            for variable in scope
+                .own()
+                .filter(|member| member.kind == DeclarationKind::StateVariable)
+            {
+                let Some(inherited) = scope.inherited().find(|member| {
+                    member.kind == DeclarationKind::StateVariable && member.name == variable.name
+                }) else {
+                    continue;
+                };
+                let span = variable.name.span();
+                res.push(self.create_diag(
+                    (span.start(), span.end()),
+                    format!(
+                        "State variable '{}' shadows the one inherited from '{}'",
+                        variable.name, inherited.contract.name
+                    ),
+                    file,
+                ));
+            }
+        }
+        res
+    }
+}
+
+impl ShadowingInheritedState {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = ShadowingInheritedState { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
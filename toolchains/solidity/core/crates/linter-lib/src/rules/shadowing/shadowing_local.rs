@@ -0,0 +1,84 @@
+use crate::linter::SolidFile;
+use crate::rules::scope::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "shadowing-local";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct ShadowingLocal {
+    data: RuleEntry,
+}
+
+impl ShadowingLocal {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for ShadowingLocal {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for function in file_functions(&file.data) {
+            for local in function_locals(function) {
+                let Some(shadowed) = local.shadowed else {
+                    continue;
+                };
+                let span = local.name.span();
+                res.push(self.create_diag(
+                    (span.start(), span.end()),
+                    format!(
+                        "The {} '{}' shadows a {} of an enclosing scope",
+                        local.kind.describe(),
+                        local.name,
+                        shadowed.describe()
+                    ),
+                    file,
+                ));
+            }
+        }
+        res
+    }
+}
+
+impl ShadowingLocal {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = ShadowingLocal { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::linter::SolidFile;
+use crate::rules::scope::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::storage_layout::Definitions;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "shadowing-state";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct ShadowingState {
+    data: RuleEntry,
+}
+
+impl ShadowingState {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for ShadowingState {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let definitions = Definitions::new(files.iter().map(|file| &file.data));
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            let scope = ContractScope::new(&contract, &definitions);
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                for local in function_locals(&function) {
+                    let Some(member) = scope
+                        .lookup(&local.name.to_string())
+                        .find(|member| member.kind == DeclarationKind::StateVariable)
+                    else {
+                        continue;
+                    };
+                    let span = local.name.span();
+                    res.push(self.create_diag(
+                        (span.start(), span.end()),
+                        format!(
+                            "The {} '{}' shadows the state variable declared in '{}'",
+                            local.kind.describe(),
+                            local.name,
+                            member.contract.name
+                        ),
+                        file,
+                    ));
+                }
+            }
+        }
+        res
+    }
+}
+
+impl ShadowingState {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = ShadowingState { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
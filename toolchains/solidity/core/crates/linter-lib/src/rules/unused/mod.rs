@@ -0,0 +1,61 @@
+use crate::rules::types::{RuleEntry, RulesMap};
+use std::collections::HashMap;
+
+pub(crate) mod unused_declaration;
+pub(crate) mod unused_function;
+pub(crate) mod unused_import;
+pub(crate) mod unused_parameter;
+pub(crate) mod unused_state_variable;
+pub(crate) mod unused_variable;
+
+// List all rules
+use crate::rules::unused::unused_declaration::UnusedDeclaration;
+use crate::rules::unused::unused_function::UnusedFunction;
+use crate::rules::unused::unused_import::UnusedImport;
+use crate::rules::unused::unused_parameter::UnusedParameter;
+use crate::rules::unused::unused_state_variable::UnusedStateVariable;
+use crate::rules::unused::unused_variable::UnusedVariable;
+use crate::rules::RuleBuilder;
+
+/// Rules reporting code which can be removed, shown faded out by editors
+pub const UNUSED_CODE_RULES: [&str; 6] = [
+    unused_declaration::RULE_ID,
+    unused_function::RULE_ID,
+    unused_import::RULE_ID,
+    unused_parameter::RULE_ID,
+    unused_state_variable::RULE_ID,
+    unused_variable::RULE_ID,
+];
+
+pub fn create_default_rules() -> Vec<RuleEntry> {
+    vec![
+        UnusedDeclaration::create_default(),
+        UnusedFunction::create_default(),
+        UnusedImport::create_default(),
+        UnusedParameter::create_default(),
+        UnusedStateVariable::create_default(),
+        UnusedVariable::create_default(),
+    ]
+}
+
+pub fn create_rules() -> RulesMap {
+    let mut rules: HashMap<String, RuleBuilder> = HashMap::new();
+
+    rules.insert(
+        unused_declaration::RULE_ID.to_string(),
+        UnusedDeclaration::create,
+    );
+    rules.insert(unused_function::RULE_ID.to_string(), UnusedFunction::create);
+    rules.insert(unused_import::RULE_ID.to_string(), UnusedImport::create);
+    rules.insert(
+        unused_parameter::RULE_ID.to_string(),
+        UnusedParameter::create,
+    );
+    rules.insert(
+        unused_state_variable::RULE_ID.to_string(),
+        UnusedStateVariable::create,
+    );
+    rules.insert(unused_variable::RULE_ID.to_string(), UnusedVariable::create);
+
+    rules
+}
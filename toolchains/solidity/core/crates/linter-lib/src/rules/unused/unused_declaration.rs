@@ -0,0 +1,104 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "unused-declaration";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+/// Kind and name of the errors, events, structs and modifiers
+fn declaration(item: &Item) -> Option<(&str, &SolIdent)> {
+    match item {
+        Item::Error(error) => Some(("Error", &error.name)),
+        Item::Event(event) => Some(("Event", &event.name)),
+        Item::Struct(strukt) => Some(("Struct", &strukt.name)),
+        // Virtual modifiers are left to the contracts overriding them
+        Item::Function(function)
+            if function.kind.is_modifier() && !function.attributes.has_virtual() =>
+        {
+            function.name.as_ref().map(|name| ("Modifier", name))
+        }
+        _ => None,
+    }
+}
+
+pub struct UnusedDeclaration {
+    data: RuleEntry,
+}
+
+impl UnusedDeclaration {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for UnusedDeclaration {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        let references = files_references(files);
+        let contracts = retriever::retrieve_contract_nodes(&file.data);
+        // Interfaces declare errors and events for their implementations
+        let items = file.data.items.iter().chain(
+            contracts
+                .iter()
+                .filter(|contract| !contract.kind.is_interface())
+                .flat_map(|contract| contract.body.iter()),
+        );
+
+        for item in items {
+            let Some((kind, name)) = declaration(item) else {
+                continue;
+            };
+            if !references.contains(&name.to_string()) {
+                res.push(self.create_diag(
+                    (name.span().start(), name.span().end()),
+                    format!("{} '{}' is declared but never used", kind, name),
+                    file,
+                ));
+            }
+        }
+        res
+    }
+}
+
+impl UnusedDeclaration {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = UnusedDeclaration { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "unused-function";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+/// Whether a function can only be called from the files being linted: private functions, internal ones
+/// outside of libraries, and free functions. Virtual functions are left to the contracts overriding them.
+fn is_internal(function: &ItemFunction, contract: Option<&ItemContract>) -> bool {
+    if !function.kind.is_function() || function.attributes.has_virtual() {
+        return false;
+    }
+    match contract {
+        Some(contract) => {
+            function.attributes.has_private()
+                || (function.attributes.has_internal() && !contract.kind.is_library())
+        }
+        None => true,
+    }
+}
+
+pub struct UnusedFunction {
+    data: RuleEntry,
+}
+
+impl UnusedFunction {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for UnusedFunction {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        let references = files_references(files);
+        let contracts = retriever::retrieve_contract_nodes(&file.data);
+        let free_functions = file.data.items.iter().map(|item| (item, None));
+        let items = free_functions.chain(
+            contracts
+                .iter()
+                .flat_map(|contract| contract.body.iter().map(move |item| (item, Some(contract)))),
+        );
+
+        for (item, contract) in items {
+            let Item::Function(function) = item else {
+                continue;
+            };
+            let Some(name) = &function.name else {
+                continue;
+            };
+            if !is_internal(function, contract) {
+                continue;
+            }
+            let used = if function.attributes.has_private() {
+                contract.is_some_and(|contract| {
+                    contract_references(contract).contains(&name.to_string())
+                })
+            } else {
+                references.contains(&name.to_string())
+            };
+            if !used {
+                res.push(self.create_diag(
+                    (name.span().start(), name.span().end()),
+                    format!("Function '{}' is never called", name),
+                    file,
+                ));
+            }
+        }
+        res
+    }
+}
+
+impl UnusedFunction {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = UnusedFunction { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "unused-parameter";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct UnusedParameter {
+    data: RuleEntry,
+}
+
+impl UnusedParameter {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for UnusedParameter {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                if function.body.is_empty() {
+                    continue;
+                }
+                let references = function_references(&function);
+                for name in function
+                    .arguments
+                    .iter()
+                    .filter_map(|arg| arg.name.as_ref())
+                {
+                    if !references.contains(&name.to_string()) {
+                        res.push(self.create_diag(
+                            (name.span().start(), name.span().end()),
+                            format!(
+                                "Parameter '{}' is never used, leave it unnamed if the signature requires it",
+                                name
+                            ),
+                            file,
+                        ));
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl UnusedParameter {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = UnusedParameter { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
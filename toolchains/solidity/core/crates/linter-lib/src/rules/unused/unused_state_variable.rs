@@ -0,0 +1,91 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "unused-state-variable";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct UnusedStateVariable {
+    data: RuleEntry,
+}
+
+impl UnusedStateVariable {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for UnusedStateVariable {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        let references = files_references(files);
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            let contract_references = contract_references(&contract);
+            for item in &contract.body {
+                let Item::Variable(variable) = item else {
+                    continue;
+                };
+                // Public variables are read through their getter, private ones only by their contract
+                let used = if variable.attributes.has_public() {
+                    true
+                } else if variable.attributes.has_private() {
+                    contract_references.contains(&variable.name.to_string())
+                } else {
+                    references.contains(&variable.name.to_string())
+                };
+                if !used {
+                    res.push(self.create_diag(
+                        (variable.name.span().start(), variable.name.span().end()),
+                        format!("State variable '{}' is never used", variable.name),
+                        file,
+                    ));
+                }
+            }
+        }
+        res
+    }
+}
+
+impl UnusedStateVariable {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = UnusedStateVariable { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "unused-variable";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+/// Collects the local variables declared in a function body
+#[derive(Default)]
+struct LocalsVisitor {
+    locals: Vec<SolIdent>,
+}
+
+impl<'ast> Visit<'ast> for LocalsVisitor {
+    fn visit_stmt_var_decl(&mut self, stmt: &'ast StmtVarDecl) {
+        match &stmt.declaration {
+            VarDeclDecl::VarDecl(variable) => self.locals.extend(variable.name.clone()),
+            VarDeclDecl::Tuple(tuple) => {
+                for variable in tuple.vars.iter().flatten() {
+                    self.locals.extend(variable.name.clone());
+                }
+            }
+        }
+        visit::visit_stmt_var_decl(self, stmt);
+    }
+}
+
+pub struct UnusedVariable {
+    data: RuleEntry,
+}
+
+impl UnusedVariable {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for UnusedVariable {
+    fn diagnose(&self, file: &SolidFile, _files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                let FunctionBody::Block(block) = &function.body else {
+                    continue;
+                };
+                let mut visitor = LocalsVisitor::default();
+                visitor.visit_block(block);
+                let references = function_references(&function);
+                for local in visitor.locals {
+                    if !references.contains(&local.to_string()) {
+                        res.push(self.create_diag(
+                            (local.span().start(), local.span().end()),
+                            format!("Variable '{}' is never used", local),
+                            file,
+                        ));
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl UnusedVariable {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = UnusedVariable { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
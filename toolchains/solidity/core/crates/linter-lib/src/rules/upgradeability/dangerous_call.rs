@@ -0,0 +1,94 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::storage_layout::Definitions;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "upgradeable-dangerous-call";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::ERROR;
+
+pub struct DangerousCall {
+    data: RuleEntry,
+}
+
+impl DangerousCall {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        name: &SolIdent,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: format!(
+                "Avoid to use {} in an upgradeable contract, calling it on the implementation can destroy it and break every proxy using it",
+                name
+            ),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for DangerousCall {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let definitions = Definitions::new(files.iter().map(|file| &file.data));
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            if !is_upgradeable(&contract, &definitions) {
+                continue;
+            }
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                for (expr, _) in function_exprs(&function) {
+                    for call in expr_calls(&expr) {
+                        let name = match &*call.expr {
+                            // `suicide` is the name of `selfdestruct` before solidity 0.5
+                            Expr::Ident(ident) if ident == "selfdestruct" || ident == "suicide" => {
+                                ident
+                            }
+                            _ => match member_call(call) {
+                                Some((_, name)) if name == "delegatecall" => name,
+                                _ => continue,
+                            },
+                        };
+                        let location = (call.span().start(), call.span().end());
+                        res.push(self.create_diag(location, name, file));
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl DangerousCall {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = DangerousCall { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
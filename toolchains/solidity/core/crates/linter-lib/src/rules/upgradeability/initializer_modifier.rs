@@ -0,0 +1,109 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::storage_layout::Definitions;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "upgradeable-initializer-modifier";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::ERROR;
+
+const INITIALIZER_MODIFIERS: [&str; 3] = ["initializer", "reinitializer", "onlyInitializing"];
+
+/// Whether a function initializes the contract, following OpenZeppelin's naming:
+/// `initialize`, `reinitialize` and the `__Name_init` functions of the upgradeable bases
+fn is_initializer(name: &str) -> bool {
+    name.starts_with("initialize")
+        || name.starts_with("reinitialize")
+        || (name.starts_with("__")
+            && (name.ends_with("_init") || name.ends_with("_init_unchained")))
+}
+
+fn has_initializer_modifier(function: &ItemFunction) -> bool {
+    function.attributes.iter().any(|attribute| match attribute {
+        FunctionAttribute::Modifier(modifier) => INITIALIZER_MODIFIERS
+            .iter()
+            .any(|name| modifier.name.last() == name),
+        _ => false,
+    })
+}
+
+pub struct InitializerModifier {
+    data: RuleEntry,
+}
+
+impl InitializerModifier {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        function: &SolIdent,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: format!(
+                "Initializer '{}' can be called more than once, add the initializer, reinitializer or onlyInitializing modifier",
+                function
+            ),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for InitializerModifier {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let definitions = Definitions::new(files.iter().map(|file| &file.data));
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            if !is_upgradeable(&contract, &definitions) {
+                continue;
+            }
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                let Some(name) = &function.name else {
+                    continue;
+                };
+                if function.body.is_empty()
+                    || !is_initializer(&name.to_string())
+                    || has_initializer_modifier(&function)
+                {
+                    continue;
+                }
+                let location = (name.span().start(), name.span().end());
+                res.push(self.create_diag(location, name, file));
+            }
+        }
+        res
+    }
+}
+
+impl InitializerModifier {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = InitializerModifier { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::rules::types::{RuleEntry, RulesMap};
+use std::collections::HashMap;
+
+pub(crate) mod dangerous_call;
+pub(crate) mod initializer_modifier;
+pub(crate) mod state_initial_value;
+pub(crate) mod storage_layout;
+pub(crate) mod upgradeable_constructor;
+pub(crate) mod upgradeable_immutable;
+
+// List all rules
+use crate::rules::upgradeability::dangerous_call::DangerousCall;
+use crate::rules::upgradeability::initializer_modifier::InitializerModifier;
+use crate::rules::upgradeability::state_initial_value::StateInitialValue;
+use crate::rules::upgradeability::storage_layout::StorageLayout;
+use crate::rules::upgradeability::upgradeable_constructor::UpgradeableConstructor;
+use crate::rules::upgradeability::upgradeable_immutable::UpgradeableImmutable;
+use crate::rules::RuleBuilder;
+
+pub fn create_default_rules() -> Vec<RuleEntry> {
+    vec![
+        DangerousCall::create_default(),
+        InitializerModifier::create_default(),
+        StateInitialValue::create_default(),
+        StorageLayout::create_default(),
+        UpgradeableConstructor::create_default(),
+        UpgradeableImmutable::create_default(),
+    ]
+}
+
+pub fn create_rules() -> RulesMap {
+    let mut rules: HashMap<String, RuleBuilder> = HashMap::new();
+
+    rules.insert(dangerous_call::RULE_ID.to_string(), DangerousCall::create);
+    rules.insert(
+        initializer_modifier::RULE_ID.to_string(),
+        InitializerModifier::create,
+    );
+    rules.insert(
+        state_initial_value::RULE_ID.to_string(),
+        StateInitialValue::create,
+    );
+    rules.insert(storage_layout::RULE_ID.to_string(), StorageLayout::create);
+    rules.insert(
+        upgradeable_constructor::RULE_ID.to_string(),
+        UpgradeableConstructor::create,
+    );
+    rules.insert(
+        upgradeable_immutable::RULE_ID.to_string(),
+        UpgradeableImmutable::create,
+    );
+
+    rules
+}
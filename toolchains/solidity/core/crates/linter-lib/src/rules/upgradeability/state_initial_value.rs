@@ -0,0 +1,90 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::storage_layout::Definitions;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "upgradeable-state-initial-value";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::WARNING;
+
+pub struct StateInitialValue {
+    data: RuleEntry,
+}
+
+impl StateInitialValue {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        variable: &SolIdent,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: format!(
+                "State variable '{}' is initialized in the implementation storage only, set it in the initializer instead",
+                variable
+            ),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for StateInitialValue {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let definitions = Definitions::new(files.iter().map(|file| &file.data));
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            if !is_upgradeable(&contract, &definitions) {
+                continue;
+            }
+            for item in &contract.body {
+                let Item::Variable(variable) = item else {
+                    continue;
+                };
+                // Constants are part of the code, and immutables are reported by their own rule
+                if variable.initializer.is_none()
+                    || variable.attributes.has_constant()
+                    || variable.attributes.has_immutable()
+                {
+                    continue;
+                }
+                let location = (variable.name.span().start(), variable.name.span().end());
+                res.push(self.create_diag(location, &variable.name, file));
+            }
+        }
+        res
+    }
+}
+
+impl StateInitialValue {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = StateInitialValue { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use crate::errors::SolidHunterError;
+use crate::linter::SolidFile;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::storage_layout::{Definitions, StorageEntry};
+use osmium_libs_solidity_ast_extractor::*;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+// global
+pub const RULE_ID: &str = "upgradeable-storage-layout";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::ERROR;
+
+/// Storage layout of a contract as output by `solc --storage-layout` or `forge inspect <contract> storageLayout`
+#[derive(Deserialize, Debug)]
+pub(crate) struct BaselineLayout {
+    storage: Vec<BaselineEntry>,
+    #[serde(default)]
+    types: Option<HashMap<String, BaselineType>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct BaselineEntry {
+    label: String,
+    // solc outputs the slot as a string, as it can exceed 64 bits
+    slot: Value,
+    offset: usize,
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct BaselineType {
+    label: String,
+}
+
+impl BaselineLayout {
+    fn entries(&self) -> Vec<(String, String, usize, usize)> {
+        self.storage
+            .iter()
+            .map(|entry| {
+                let ty = self
+                    .types
+                    .as_ref()
+                    .and_then(|types| types.get(&entry.ty))
+                    .map(|ty| ty.label.clone())
+                    .unwrap_or_else(|| entry.ty.trim_start_matches("t_").to_string());
+                let slot = match &entry.slot {
+                    Value::String(slot) => slot.parse().unwrap_or(usize::MAX),
+                    slot => slot.as_u64().map_or(usize::MAX, |slot| slot as usize),
+                };
+                (entry.label.clone(), normalize_type(&ty), slot, entry.offset)
+            })
+            .collect()
+    }
+}
+
+/// Reads a baseline file, a JSON object mapping contract names to their previous storage layout
+pub(crate) fn load_baseline_layouts(
+    path: &str,
+) -> Result<HashMap<String, BaselineLayout>, SolidHunterError> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Writes a type the same way for solc labels and source code: without the `struct`, `enum` and `contract`
+/// keywords, the scope of the user defined types, `payable` and the aliases of `uint256` and `int256`
+fn normalize_type(ty: &str) -> String {
+    let ty = ty
+        .replace("struct ", "")
+        .replace("enum ", "")
+        .replace("contract ", "")
+        .replace(" payable", "");
+    let mut normalized = String::new();
+    let mut word = String::new();
+    for c in ty.chars().chain(std::iter::once(' ')) {
+        if c.is_alphanumeric() || c == '_' || c == '$' {
+            word.push(c);
+            continue;
+        }
+        if c != '.' {
+            normalized.push_str(match word.as_str() {
+                "uint" => "uint256",
+                "int" => "int256",
+                "byte" => "bytes1",
+                word => word,
+            });
+        }
+        word.clear();
+        if !c.is_whitespace() && c != '.' {
+            normalized.push(c);
+        }
+    }
+    normalized
+}
+
+pub struct StorageLayout {
+    baselines: HashMap<String, BaselineLayout>,
+    data: RuleEntry,
+}
+
+impl StorageLayout {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        message: String,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message,
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+
+    /// Compares each variable of the baseline with the one now stored at its position
+    fn incompatibilities(baseline: &BaselineLayout, layout: &[StorageEntry]) -> Vec<String> {
+        let mut res = Vec::new();
+        let find = |slot: usize, offset: usize| {
+            layout
+                .iter()
+                .find(|entry| entry.position.slot == slot && entry.position.offset == offset)
+        };
+
+        for (label, ty, slot, offset) in baseline.entries() {
+            let moved = layout.iter().find(|entry| entry.name == label);
+            match find(slot, offset) {
+                Some(entry) if normalize_type(&entry.ty.to_string()) != ty => res.push(format!(
+                    "slot {} offset {} changed from '{} {}' to '{} {}'",
+                    slot,
+                    offset,
+                    ty,
+                    label,
+                    normalize_type(&entry.ty.to_string()),
+                    entry.name
+                )),
+                // A variable renamed in place keeps its value
+                Some(entry) if entry.name == label || moved.is_none() => {}
+                _ => match moved {
+                    Some(entry) => res.push(format!(
+                        "'{}' moved from slot {} offset {} to slot {} offset {}",
+                        label, slot, offset, entry.position.slot, entry.position.offset
+                    )),
+                    None => res.push(format!(
+                        "'{}' at slot {} offset {} was removed",
+                        label, slot, offset
+                    )),
+                },
+            }
+        }
+        res
+    }
+}
+
+impl RuleType for StorageLayout {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        if self.baselines.is_empty() {
+            return res;
+        }
+        let definitions = Definitions::new(files.iter().map(|file| &file.data));
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            let Some(baseline) = self.baselines.get(&contract.name.to_string()) else {
+                continue;
+            };
+            let Some(layout) = definitions.contract_layout(&contract) else {
+                continue;
+            };
+            let location = (contract.name.span().start(), contract.name.span().end());
+            for incompatibility in Self::incompatibilities(baseline, &layout) {
+                res.push(self.create_diag(
+                    location,
+                    format!(
+                        "Storage layout of '{}' is incompatible with the baseline: {}",
+                        contract.name, incompatibility
+                    ),
+                    file,
+                ));
+            }
+        }
+        res
+    }
+}
+
+impl StorageLayout {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let mut baselines = HashMap::new();
+
+        if let Some(data) = &data.data {
+            match &data["baselineLayout"] {
+                Value::Null => {}
+                Value::String(path) => match load_baseline_layouts(path) {
+                    Ok(layouts) => baselines = layouts,
+                    Err(_) => eprintln!("{} rule : cannot load the baseline layout", RULE_ID),
+                },
+                _ => eprintln!("{} rule : bad config data", RULE_ID),
+            }
+        }
+        let rule = StorageLayout { baselines, data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: Some(serde_json::json!({
+                "baselineLayout": null,
+            })),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::linter::SolidFile;
+use crate::rules::analysis::*;
+use crate::rules::types::*;
+use crate::types::*;
+use osmium_libs_solidity_ast_extractor::storage_layout::Definitions;
+use osmium_libs_solidity_ast_extractor::*;
+
+// global
+pub const RULE_ID: &str = "upgradeable-constructor";
+
+// specific
+const DEFAULT_SEVERITY: Severity = Severity::ERROR;
+
+/// Whether a constructor does nothing but lock the implementation, as recommended by OpenZeppelin
+fn only_disables_initializers(function: &ItemFunction) -> bool {
+    let FunctionBody::Block(block) = &function.body else {
+        return true;
+    };
+    block.stmts.iter().all(|stmt| match stmt {
+        Stmt::Expr(StmtExpr {
+            expr: Expr::Call(call),
+            ..
+        }) => matches!(&*call.expr, Expr::Ident(ident) if ident == "_disableInitializers"),
+        _ => false,
+    })
+}
+
+pub struct UpgradeableConstructor {
+    data: RuleEntry,
+}
+
+impl UpgradeableConstructor {
+    fn create_diag(
+        &self,
+        location: (LineColumn, LineColumn),
+        contract: &SolIdent,
+        file: &SolidFile,
+    ) -> LintDiag {
+        LintDiag {
+            id: RULE_ID.to_string(),
+            range: Range {
+                start: Position {
+                    line: location.0.line,
+                    character: location.0.column,
+                },
+                end: Position {
+                    line: location.1.line,
+                    character: location.1.column,
+                },
+            },
+            message: format!(
+                "Contract '{}' is upgradeable, its constructor does not run for the proxies. Move its logic to an initializer",
+                contract
+            ),
+            severity: self.data.severity,
+            code: None,
+            source: None,
+            uri: file.path.clone(),
+        }
+    }
+}
+
+impl RuleType for UpgradeableConstructor {
+    fn diagnose(&self, file: &SolidFile, files: &[SolidFile]) -> Vec<LintDiag> {
+        let mut res = Vec::new();
+        let definitions = Definitions::new(files.iter().map(|file| &file.data));
+
+        for contract in retriever::retrieve_contract_nodes(&file.data) {
+            if !is_upgradeable(&contract, &definitions) {
+                continue;
+            }
+            for function in retriever::retrieve_functions_nodes(&contract) {
+                if function.kind.is_constructor() && !only_disables_initializers(&function) {
+                    let location = (function.span().start(), function.span().end());
+                    res.push(self.create_diag(location, &contract.name, file));
+                }
+            }
+        }
+        res
+    }
+}
+
+impl UpgradeableConstructor {
+    pub(crate) fn create(data: RuleEntry) -> Box<dyn RuleType> {
+        let rule = UpgradeableConstructor { data };
+        Box::new(rule)
+    }
+
+    pub(crate) fn create_default() -> RuleEntry {
+        RuleEntry {
+            id: RULE_ID.to_string(),
+            severity: DEFAULT_SEVERITY,
+            data: None,
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use osmium_libs_solidity_ast_extractor::*;
+use std::fmt;
+
+/// A compiler version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub(crate) struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Version {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parses a full or partial version such as `0.8.19`, `0.8`, `0.8.x` or `*`,
+    /// along with the number of components which were given
+    fn parse_partial(text: &str) -> Option<(Self, usize)> {
+        let mut components = Vec::new();
+        for component in text.split('.') {
+            if matches!(component, "x" | "X" | "*") {
+                break;
+            }
+            components.push(component.parse::<u32>().ok()?);
+        }
+        if components.len() > 3 {
+            return None;
+        }
+        let precision = components.len();
+        components.resize(3, 0);
+        Some((
+            Version::new(components[0], components[1], components[2]),
+            precision,
+        ))
+    }
+
+    /// The first version after all the ones sharing the given number of components with this one
+    fn bump(&self, precision: usize) -> Option<Self> {
+        match precision {
+            0 => None,
+            1 => Some(Version::new(self.major + 1, 0, 0)),
+            2 => Some(Version::new(self.major, self.minor + 1, 0)),
+            _ => Some(Version::new(self.major, self.minor, self.patch + 1)),
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Versions from a lower bound included to an upper bound excluded, unbounded when `None`
+type Interval = (Version, Option<Version>);
+
+/// The versions matched by a constraint such as `^0.8.0`, `>=0.7.0 <0.9.0` or `0.6.12 || ^0.8.0`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct VersionRange {
+    intervals: Vec<Interval>,
+}
+
+impl VersionRange {
+    pub fn any() -> Self {
+        VersionRange {
+            intervals: vec![(Version::default(), None)],
+        }
+    }
+
+    /// Parses a constraint as written in the source or as displayed by the parser, which separates
+    /// each token with spaces (`^ 0.8 . 0`)
+    pub fn parse(constraint: &str) -> Option<Self> {
+        let constraint = normalize_constraint(constraint);
+        let mut intervals = Vec::new();
+        for set in constraint.split("||") {
+            let words: Vec<&str> = set.split_whitespace().collect();
+            let mut range = VersionRange::any();
+            let mut idx = 0;
+            while idx < words.len() {
+                let interval = if words.get(idx + 1) == Some(&"-") {
+                    // Hyphen range, both ends included
+                    let (low, _) = Version::parse_partial(words[idx])?;
+                    let (high, precision) = Version::parse_partial(words.get(idx + 2)?)?;
+                    idx += 3;
+                    (low, high.bump(precision))
+                } else {
+                    idx += 1;
+                    comparator_interval(words[idx - 1])?
+                };
+                range = range.intersection(&VersionRange {
+                    intervals: vec![interval],
+                });
+            }
+            intervals.extend(range.intervals);
+        }
+        Some(VersionRange { intervals }.merged())
+    }
+
+    /// The lowest version of the range
+    pub fn min(&self) -> Option<Version> {
+        self.intervals.iter().map(|(low, _)| *low).min()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Whether the range allows a single version
+    pub fn is_exact(&self) -> bool {
+        matches!(self.intervals.as_slice(), [(low, Some(high))] if low.bump(3) == Some(*high))
+    }
+
+    pub fn intersection(&self, other: &VersionRange) -> VersionRange {
+        let mut intervals = Vec::new();
+        for (low, high) in &self.intervals {
+            for (other_low, other_high) in &other.intervals {
+                let low = *low.max(other_low);
+                let high = match (high, other_high) {
+                    (Some(high), Some(other_high)) => Some(*high.min(other_high)),
+                    (high, None) => *high,
+                    (None, other_high) => *other_high,
+                };
+                if high.is_none_or(|high| low < high) {
+                    intervals.push((low, high));
+                }
+            }
+        }
+        VersionRange { intervals }.merged()
+    }
+
+    /// Whether all the versions of this range belong to the other one
+    pub fn is_subset(&self, other: &VersionRange) -> bool {
+        self.intersection(other) == *self
+    }
+
+    fn merged(mut self) -> Self {
+        self.intervals.sort();
+        let mut intervals: Vec<Interval> = Vec::new();
+        for (low, high) in self.intervals {
+            match intervals.last_mut() {
+                Some((_, last_high)) if last_high.is_none_or(|last_high| low <= last_high) => {
+                    *last_high = match (*last_high, high) {
+                        (Some(last_high), Some(high)) => Some(last_high.max(high)),
+                        _ => None,
+                    };
+                }
+                _ => intervals.push((low, high)),
+            }
+        }
+        VersionRange { intervals }
+    }
+}
+
+/// Joins the operators and components of each version: `>= 0.8 . 0` becomes `>=0.8.0`
+pub(crate) fn normalize_constraint(constraint: &str) -> String {
+    let mut res = String::new();
+    let mut words = constraint.split_whitespace().peekable();
+    while let Some(word) = words.next() {
+        res.push_str(word);
+        let joined = word.ends_with('.')
+            || word.chars().all(|c| "^~<>=".contains(c))
+            || words.peek().is_some_and(|next| next.starts_with('.'));
+        if !joined && words.peek().is_some() {
+            res.push(' ');
+        }
+    }
+    res
+}
+
+fn comparator_interval(word: &str) -> Option<Interval> {
+    let split = word.find(|c: char| !"^~<>=".contains(c))?;
+    let (operator, version) = word.split_at(split);
+    let (version, precision) = Version::parse_partial(version)?;
+    let next = version.bump(precision);
+    let start = Version::default();
+    Some(match operator {
+        "" | "=" => (version, next),
+        "^" => {
+            // The first non-zero component given cannot change
+            let fixed = if version.major > 0 || precision == 1 {
+                1
+            } else if version.minor > 0 || precision == 2 {
+                2
+            } else {
+                3
+            };
+            (version, version.bump(fixed.min(precision)))
+        }
+        "~" => (version, version.bump(precision.min(2))),
+        ">=" => (version, None),
+        ">" => (next?, None),
+        "<" => (start, Some(version)),
+        "<=" => (start, next),
+        _ => return None,
+    })
+}
+
+/// A `pragma solidity` directive and the versions it allows, `None` when the constraint cannot be parsed
+pub(crate) struct VersionPragma<'a> {
+    pub directive: &'a PragmaDirective,
+    pub constraint: String,
+    pub range: Option<VersionRange>,
+}
+
+/// The `pragma solidity` directives of a file
+pub(crate) fn version_pragmas(file: &File) -> Vec<VersionPragma<'_>> {
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Pragma(
+                directive @ PragmaDirective {
+                    tokens: PragmaTokens::Version(_, version),
+                    ..
+                },
+            ) => {
+                let constraint = normalize_constraint(&version.to_string());
+                Some(VersionPragma {
+                    directive,
+                    range: VersionRange::parse(&constraint),
+                    constraint,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The versions a file can be compiled with, `None` without a valid `pragma solidity` directive
+pub(crate) fn file_version_range(file: &File) -> Option<VersionRange> {
+    let pragmas = version_pragmas(file);
+    if pragmas.is_empty() {
+        return None;
+    }
+    let mut range = VersionRange::any();
+    for pragma in pragmas {
+        range = range.intersection(&pragma.range?);
+    }
+    Some(range)
+}
+
+/// Whether the file only compiles with the given version or a later one.
+/// Files without version pragma are assumed to target a recent compiler.
+pub(crate) fn requires_version(file: &File, version: Version) -> bool {
+    file_version_range(file)
+        .and_then(|range| range.min())
+        .is_none_or(|min| min >= version)
+}
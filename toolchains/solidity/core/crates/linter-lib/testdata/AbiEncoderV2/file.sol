@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+pragma experimental ABIEncoderV2;
+
+contract AbiEncoderV2 {
+    struct Pair {
+        uint256 first;
+        uint256 second;
+    }
+
+    function sum(Pair calldata pair) external pure returns (uint256) {
+        return pair.first + pair.second;
+    }
+}
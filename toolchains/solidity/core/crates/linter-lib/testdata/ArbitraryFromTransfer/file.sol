@@ -0,0 +1,23 @@
+pragma solidity 0.8.0;
+
+interface IERC20 {
+    function transferFrom(address from, address to, uint256 amount) external returns (bool);
+}
+
+contract Pool {
+    IERC20 public token;
+
+    function deposit(uint256 amount) public {
+        token.transferFrom(msg.sender, address(this), amount);
+    }
+
+    function depositFor(address from, uint256 amount) public {
+        token.transferFrom(from, address(this), amount);
+    }
+
+    function pull(address asset, address from, uint256 amount) public {
+        IERC20(asset).transferFrom(from, msg.sender, amount);
+        SafeERC20.safeTransferFrom(token, from, address(this), amount);
+        SafeERC20.safeTransferFrom(token, msg.sender, address(this), amount);
+    }
+}
@@ -0,0 +1,15 @@
+pragma solidity 0.8.0;
+
+contract Killable {
+    address payable public owner;
+
+    function kill() public {
+        selfdestruct(owner);
+    }
+
+    function legacyKill() public {
+        if (msg.sender == owner) suicide(owner);
+    }
+
+    function destroy() public {}
+}
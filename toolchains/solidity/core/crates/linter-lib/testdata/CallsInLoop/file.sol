@@ -0,0 +1,21 @@
+pragma solidity 0.8.0;
+
+contract Batch {
+    function run(address[] memory targets, bytes memory data) public {
+        for (uint256 i = 0; i < targets.length; i++) {
+            targets[i].call(data);
+        }
+        uint256 j = 0;
+        while (j < targets.length) {
+            targets[j].delegatecall(data);
+            j++;
+        }
+        targets[0].call(data);
+    }
+
+    function pay(address[] memory targets) public {
+        for (uint256 i = 0; i < targets.length; i++) {
+            targets[i].call{value: 1}("");
+        }
+    }
+}
@@ -0,0 +1,30 @@
+pragma solidity 0.8.19;
+
+contract CodeComplexity {
+    function simple(uint256 a) external pure returns (uint256) {
+        return a;
+    }
+
+    function complex(uint256 a, uint256 b) external pure returns (uint256, bool) {
+        uint256 total;
+        for (uint256 i = 0; i < a; i++) {
+            if (i % 2 == 0 && i > b || i == 7) {
+                total += i;
+            } else if (i == 3) {
+                total -= 1;
+            } else {
+                while (total > 100) {
+                    total = total > 1000 ? total / 2 : total - 1;
+                }
+            }
+        }
+        return (total, true);
+    }
+}
+
+function free(uint256 a) pure returns (uint256) {
+    if (a > 1 && a < 10) {
+        return a > 5 ? 1 : 2;
+    }
+    return 1;
+}
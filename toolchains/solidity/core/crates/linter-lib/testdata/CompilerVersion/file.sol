@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: MIT
+pragma solidity >=0.7.0;
+pragma solidity ^0.8.4;
+pragma solidity 0.8.19;
+pragma solidity 0.7.6 || ^0.8.0;
+
+contract CompilerVersion {
+    uint256 public value;
+}
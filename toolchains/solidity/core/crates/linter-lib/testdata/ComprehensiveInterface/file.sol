@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+interface ICounter {
+    function count() external view returns (uint256);
+}
+
+contract Counter is ICounter {
+    uint256 private _count;
+
+    function count() external view override returns (uint256) {
+        return _count;
+    }
+
+    function increment() external {
+        _count += 1;
+    }
+
+    function reset() public {
+        _reset();
+    }
+
+    function _reset() internal {
+        _count = 0;
+    }
+}
+
+library Math {
+    function max(uint256 a, uint256 b) public pure returns (uint256) {
+        return a > b ? a : b;
+    }
+}
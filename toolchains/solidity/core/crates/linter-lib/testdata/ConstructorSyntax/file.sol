@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.4.24;
+
+contract Owned {
+    address public owner;
+
+    function Owned() public {
+        owner = msg.sender;
+    }
+}
+
+contract Token {
+    uint256 public supply;
+
+    constructor(uint256 initialSupply) public {
+        supply = initialSupply;
+    }
+
+    function token() public view returns (uint256) {
+        return supply;
+    }
+}
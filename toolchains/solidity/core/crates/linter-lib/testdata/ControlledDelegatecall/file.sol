@@ -0,0 +1,22 @@
+pragma solidity 0.8.0;
+
+contract Proxy {
+    address public implementation;
+
+    function forward(address target, bytes memory data) public {
+        target.delegatecall(data);
+    }
+
+    function forwardPayable(address target, bytes calldata data) external {
+        (bool ok, ) = payable(target).delegatecall(data);
+        require(ok);
+    }
+
+    function upgradeAndCall(bytes memory data) public {
+        implementation.delegatecall(data);
+    }
+
+    function _forward(address target, bytes memory data) internal {
+        target.delegatecall(data);
+    }
+}
@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import "./Token.sol";
+import "./../DuplicatedImports/Token.sol";
+import "Token.sol";
+import {Vault} from "./Token.sol";
+import {Vault} from "./Vault.sol";
+import {Vault as OtherVault} from "./Vault.sol";
+
+contract DuplicatedImports {}
@@ -0,0 +1,13 @@
+pragma solidity 0.8.0;
+
+contract Signatures {
+    string public prefix;
+
+    function hash(string memory a, bytes memory b, uint256 c) public view returns (bytes32) {
+        bytes32 collides = keccak256(abi.encodePacked(a, b));
+        bytes32 withState = keccak256(abi.encodePacked(prefix, c, a));
+        bytes32 safe = keccak256(abi.encode(a, b));
+        bytes32 single = keccak256(abi.encodePacked(a, c));
+        return keccak256(abi.encodePacked(string(b), bytes(a)));
+    }
+}
@@ -0,0 +1,23 @@
+pragma solidity 0.8.0;
+
+contract MultiToken {
+    event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+    event TransferBatch(address operator, address from, address to, uint256[] ids, uint256[] values);
+    event ApprovalForAll(address indexed account, address indexed operator, bool approved);
+
+    function safeTransferFrom(address from, address to, uint256 id, uint256 amount, bytes calldata data) external {}
+
+    function safeBatchTransferFrom(address from, address to, uint256[] calldata ids, uint256[] calldata amounts, bytes calldata data) external {}
+
+    function balanceOf(address account, uint256 id) external view returns (uint256) {}
+
+    function balanceOfBatch(address[] calldata accounts, uint256[] calldata ids) external view returns (uint256) {}
+
+    function setApprovalForAll(address operator, bool approved) external {}
+
+    function isApprovedForAll(address account, address operator) external view returns (bool) {}
+
+    function supportsInterface(bytes4 interfaceId) external view returns (bool) {
+        return interfaceId == 0xd9b67a26;
+    }
+}
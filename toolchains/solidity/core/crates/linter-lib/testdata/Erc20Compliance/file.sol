@@ -0,0 +1,44 @@
+pragma solidity 0.8.0;
+
+interface IERC20 {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+}
+
+contract Token {
+    mapping(address => uint256) public balanceOf;
+    mapping(address => mapping(address => uint256)) public allowance;
+    uint256 public totalSupply;
+    uint256 public decimals;
+
+    event Transfer(address from, address to, uint256 value);
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+
+    function transfer(address to, uint256 amount) external {}
+
+    function transferFrom(address from, address to, uint256 amount) external returns (bool) {}
+
+    function approve(address spender) external returns (bool) {}
+}
+
+contract Compliant is IERC20 {
+    uint8 public constant decimals = 18;
+    mapping(address => uint256) public balanceOf;
+    mapping(address => mapping(address => uint256)) public allowance;
+    uint256 public totalSupply;
+
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+
+    function transfer(address to, uint256 amount) external returns (bool) {}
+
+    function transferFrom(address from, address to, uint256 amount) external returns (bool) {}
+
+    function approve(address spender, uint256 amount) external returns (bool) {}
+}
+
+contract Incomplete is IERC20 {
+    function totalSupply() external view returns (uint256) {}
+}
+
+contract Vault {
+    function deposit(uint256 amount) external {}
+}
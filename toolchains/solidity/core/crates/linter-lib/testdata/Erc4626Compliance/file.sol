@@ -0,0 +1,35 @@
+pragma solidity 0.8.0;
+
+import "@openzeppelin/contracts/interfaces/IERC4626.sol";
+
+contract Vault is IERC4626 {
+    IERC20 public asset;
+
+    function totalAssets() external view returns (uint256) {}
+
+    function convertToShares(uint256 assets) external view returns (uint256) {}
+
+    function convertToAssets(uint256 shares) external view returns (uint256) {}
+
+    function maxDeposit(address receiver) external view returns (uint256) {}
+
+    function previewDeposit(uint256 assets) external view returns (uint256) {}
+
+    function deposit(uint256 assets, address receiver) external returns (uint256) {}
+
+    function maxMint(address receiver) external view returns (uint256) {}
+
+    function previewMint(uint256 shares) external view returns (uint256) {}
+
+    function mint(uint256 shares, address receiver) external returns (uint256) {}
+
+    function maxWithdraw(address owner) external view returns (uint256) {}
+
+    function previewWithdraw(uint256 assets) external view returns (uint256) {}
+
+    function withdraw(uint256 assets, address receiver) external returns (uint256) {}
+
+    function maxRedeem(address owner) external view returns (uint256) {}
+
+    function previewRedeem(uint256 shares) external view returns (uint256) {}
+}
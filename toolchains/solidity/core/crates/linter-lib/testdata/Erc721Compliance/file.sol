@@ -0,0 +1,53 @@
+pragma solidity 0.8.0;
+
+import "@openzeppelin/contracts/token/ERC721/IERC721.sol";
+
+contract Collection is IERC721 {
+    mapping(uint256 => address) public ownerOf;
+    mapping(address => uint256) public balanceOf;
+
+    event Transfer(address indexed from, address indexed to, uint256 tokenId);
+
+    function safeTransferFrom(address from, address to, uint256 tokenId, bytes calldata data) external {}
+
+    function safeTransferFrom(address from, address to, uint256 tokenId) external {}
+
+    function transferFrom(address from, address to, uint256 tokenId) external {}
+
+    function approve(address to, uint256 tokenId) external {}
+
+    function setApprovalForAll(address operator, bool approved) external {}
+
+    function getApproved(uint256 tokenId) external view returns (address) {}
+
+    function isApprovedForAll(address owner, address operator) external view returns (bool) {}
+
+    function tokenURI(uint256 tokenId) external view returns (bytes memory) {}
+
+    function supportsInterface(bytes4 interfaceId) external view returns (bool) {
+        return interfaceId == 0x01ffc9a7;
+    }
+}
+
+contract Compliant is IERC721 {
+    mapping(uint256 => address) public ownerOf;
+    mapping(address => uint256) public balanceOf;
+
+    function safeTransferFrom(address from, address to, uint256 tokenId, bytes calldata data) external {}
+
+    function safeTransferFrom(address from, address to, uint256 tokenId) external {}
+
+    function transferFrom(address from, address to, uint256 tokenId) external {}
+
+    function approve(address to, uint256 tokenId) external {}
+
+    function setApprovalForAll(address operator, bool approved) external {}
+
+    function getApproved(uint256 tokenId) external view returns (address) {}
+
+    function isApprovedForAll(address owner, address operator) external view returns (bool) {}
+
+    function supportsInterface(bytes4 interfaceId) external view returns (bool) {
+        return interfaceId == type(IERC721).interfaceId || interfaceId == 0x01ffc9a7;
+    }
+}
@@ -0,0 +1,17 @@
+pragma solidity 0.8.0;
+
+interface IERC20 {
+    function transfer(address to, uint256 amount) external returns (bool);
+}
+
+contract Payout {
+    function pay(address payable[] memory recipients, IERC20 token) public {
+        for (uint256 i = 0; i < recipients.length; i++) {
+            recipients[i].transfer(1);
+            recipients[i].send(1);
+            recipients[i].call{value: 1}("");
+            token.transfer(recipients[i], 1);
+        }
+        recipients[0].transfer(1);
+    }
+}
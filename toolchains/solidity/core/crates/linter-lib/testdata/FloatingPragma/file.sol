@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+pragma solidity >=0.8.4 <0.9.0;
+pragma solidity 0.8.19;
+
+contract FloatingPragma {
+    uint256 public value;
+}
@@ -0,0 +1,27 @@
+pragma solidity 0.8.0;
+
+contract Rewards {
+    uint256 public rate;
+    uint256 public total;
+    uint256 public constant MAX = 10;
+    mapping(address => uint256) public rewards;
+
+    function distribute(address[] memory users) public {
+        for (uint256 i = 0; i < users.length; ++i) {
+            rewards[users[i]] += rate * MAX;
+        }
+    }
+
+    function accumulate(uint256 count) public {
+        for (uint256 i = 0; i < count; ++i) {
+            total += rate;
+        }
+    }
+
+    function cached(address[] memory users) public {
+        uint256 rate = 2;
+        for (uint256 i = 0; i < users.length; ++i) {
+            rewards[users[i]] += rate;
+        }
+    }
+}
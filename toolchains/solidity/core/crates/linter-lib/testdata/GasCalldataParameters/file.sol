@@ -0,0 +1,13 @@
+pragma solidity 0.8.0;
+
+contract Batch {
+    function run(address[] memory targets, bytes memory data, uint256 value) external {}
+
+    function update(uint256[] memory values) external {
+        values[0] = 1;
+    }
+
+    function read(string calldata name) external {}
+
+    function internalRun(bytes memory data) public {}
+}
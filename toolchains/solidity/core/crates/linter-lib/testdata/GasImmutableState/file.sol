@@ -0,0 +1,25 @@
+pragma solidity 0.8.0;
+
+contract Token {
+    string public name = "Token";
+    uint256 public decimals = 18;
+    uint256 public constant MAX = 1000;
+    uint256 public limit = MAX * 2;
+    address public owner;
+    address public admin = msg.sender;
+    uint256 public supply;
+    uint256 public immutable created;
+
+    constructor() {
+        owner = msg.sender;
+        created = block.timestamp;
+    }
+
+    function mint(uint256 amount) public {
+        supply += amount;
+    }
+
+    function setAdmin(address newAdmin) public {
+        admin = newAdmin;
+    }
+}
@@ -0,0 +1,19 @@
+pragma solidity 0.8.0;
+
+contract Registry {
+    address[] public users;
+
+    function count() public view returns (uint256 total) {
+        for (uint256 i = 0; i < users.length; ++i) {
+            total += 1;
+        }
+        uint256 j = 0;
+        while (j < users.length && total > 0) {
+            j++;
+        }
+        uint256 length = users.length;
+        for (uint256 i = 0; i < length; ++i) {
+            total += 1;
+        }
+    }
+}
@@ -0,0 +1,12 @@
+pragma solidity 0.8.0;
+
+contract Counter {
+    uint256 public count;
+
+    function increment() public returns (uint256 previous) {
+        count++;
+        ++count;
+        previous = count--;
+        for (uint256 i = 0; i < 10; i++) {}
+    }
+}
@@ -0,0 +1,7 @@
+pragma solidity 0.8.0;
+
+contract Registry {
+    address[] public users;
+    uint256[] internal scores;
+    uint256 public count;
+}
@@ -0,0 +1,26 @@
+pragma solidity 0.8.0;
+
+contract Base {
+    uint128 public supply;
+}
+
+contract Vault is Base {
+    uint128 public fee;
+    uint256 public total;
+    bool public paused;
+    address public owner;
+    uint256 public constant MAX = 1;
+}
+
+contract Loose {
+    bool public paused;
+    uint256 public total;
+    address public owner;
+    mapping(address => uint256) public balances;
+}
+
+contract Packed {
+    address public owner;
+    bool public paused;
+    uint256 public total;
+}
@@ -0,0 +1,31 @@
+pragma solidity 0.8.0;
+
+struct Loose {
+    uint128 a;
+    uint256 b;
+    uint128 c;
+}
+
+contract Storage {
+    enum Status {
+        Active,
+        Closed
+    }
+
+    struct Packed {
+        uint128 a;
+        uint128 b;
+        uint256 c;
+    }
+
+    struct Position {
+        bool open;
+        uint256 amount;
+        address owner;
+        Status status;
+    }
+
+    struct Single {
+        uint256 value;
+    }
+}
@@ -0,0 +1,18 @@
+pragma solidity ^0.8.0;
+
+contract Loops {
+    function sum(uint256[] memory values) public pure returns (uint256 total) {
+        for (uint256 i = 0; i < values.length; ++i) {
+            total += values[i];
+        }
+        for (uint256 i = 0; i < values.length; i += 1) {
+            total += values[i];
+        }
+        for (uint256 i = 0; i <= values.length; ++i) {
+            total += 1;
+        }
+        for (uint256 i = 0; i < values.length; ++i) {
+            i += 2;
+        }
+    }
+}
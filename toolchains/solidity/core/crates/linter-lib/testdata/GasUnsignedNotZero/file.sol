@@ -0,0 +1,18 @@
+pragma solidity 0.8.0;
+
+contract Vault {
+    uint256 public total;
+    int256 public delta;
+    mapping(address => uint256) public balances;
+
+    function withdraw(uint256 amount) public {
+        require(amount > 0 && balances[msg.sender] > 0, "nothing");
+        if (0 < total) {
+            total -= amount;
+        }
+        if (delta > 0) {
+            delta = 0;
+        }
+        require(amount != 0);
+    }
+}
@@ -0,0 +1,6 @@
+// SPDX-License-Identifier: MIT OR GPL-3.0-only
+pragma solidity 0.8.19;
+
+contract LicenseIdentifier {
+    uint256 public value;
+}
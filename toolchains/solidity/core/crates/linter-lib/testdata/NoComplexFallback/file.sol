@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Simple {
+    event Received(address sender, uint256 amount);
+
+    receive() external payable {
+        emit Received(msg.sender, msg.value);
+    }
+}
+
+contract Complex {
+    mapping(address => uint256) public balances;
+    uint256 public total;
+    address public last;
+
+    fallback() external payable {
+        balances[msg.sender] += msg.value;
+        total += msg.value;
+        last = msg.sender;
+    }
+
+    receive() external payable {
+        balances[msg.sender] += msg.value;
+        total += msg.value;
+        last = msg.sender;
+    }
+}
@@ -0,0 +1,6 @@
+// SPDX-License-Identifier: MIT
+pragma solidity >=0.8.19;
+
+import "./Legacy.sol";
+
+contract Recent is Legacy {}
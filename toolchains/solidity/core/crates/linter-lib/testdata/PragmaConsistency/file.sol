@@ -0,0 +1,7 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import "Old.sol";
+import "./Recent.sol";
+
+contract PragmaConsistency is Old, Recent {}
@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import './Token.sol';
+
+contract Quotes {
+    string public constant NAME = "Quotes";
+    string public constant SYMBOL = 'QTS';
+    // 'quoted' in a comment
+    string public constant QUOTED = 'say "hello"';
+
+    /* 'quoted'
+       in a block comment */
+    function greet() external pure returns (string memory) {
+        return 'it\'s me';
+    }
+}
@@ -0,0 +1,61 @@
+pragma solidity 0.8.0;
+
+interface IERC20 {
+    function transfer(address to, uint256 amount) external returns (bool);
+}
+
+contract Vault {
+    mapping(address => uint256) public balances;
+    uint256 public total;
+    IERC20 public token;
+    address[] public users;
+
+    modifier lock() {
+        _;
+    }
+
+    function withdraw() public {
+        uint256 amount = balances[msg.sender];
+        (bool success, ) = msg.sender.call{value: amount}("");
+        require(success);
+        balances[msg.sender] = 0;
+    }
+
+    function withdrawSafe() public {
+        uint256 amount = balances[msg.sender];
+        balances[msg.sender] = 0;
+        (bool success, ) = msg.sender.call{value: amount}("");
+        require(success);
+    }
+
+    function withdrawLocked() public lock {
+        payable(msg.sender).transfer(balances[msg.sender]);
+        balances[msg.sender] = 0;
+    }
+
+    function claim(uint256 amount) public {
+        token.transfer(msg.sender, amount);
+        total -= amount;
+    }
+
+    function early(bool flag) public {
+        if (flag) {
+            payable(msg.sender).send(1);
+            return;
+        }
+        total = 0;
+    }
+
+    function loop(address[] memory targets) public {
+        for (uint256 i = 0; i < targets.length; i++) {
+            users.push(targets[i]);
+            IERC20(targets[i]).transfer(msg.sender, 1);
+        }
+    }
+
+    function local() public {
+        uint256 total = 1;
+        payable(msg.sender).transfer(1);
+        total = 2;
+    }
+}
@@ -0,0 +1,22 @@
+pragma solidity 0.4.24;
+
+contract ShadowingBuiltin {
+    uint256 now;
+    event assert(uint256 value);
+
+    function require(bool condition) internal {}
+
+    function hash(bytes memory data) public returns (bytes32 keccak256) {
+        uint256 block = 1;
+        address msg = address(0);
+        return 0;
+    }
+
+    modifier sha256() {
+        _;
+    }
+}
+
+function ecrecover(uint256 tx) pure returns (uint256) {
+    return tx;
+}
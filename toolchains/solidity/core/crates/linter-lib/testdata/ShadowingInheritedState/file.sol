@@ -0,0 +1,17 @@
+pragma solidity 0.5.0;
+
+contract Root {
+    uint256 public supply;
+}
+
+contract Base is Root {
+    address internal owner;
+    uint256 private secret;
+}
+
+contract ShadowingInheritedState is Base {
+    address internal owner;
+    uint256 private secret;
+    uint256 public supply;
+    uint256 public other;
+}
@@ -0,0 +1,19 @@
+pragma solidity 0.8.19;
+
+contract ShadowingLocal {
+    function compute(uint256 amount) external returns (uint256 result) {
+        uint256 amount = 1;
+        for (uint256 i = 0; i < 10; i++) {
+            uint256 result = i;
+            {
+                uint256 i = 2;
+            }
+        }
+        for (uint256 i = 0; i < 10; i++) {}
+        {
+            uint256 scoped = 1;
+        }
+        uint256 scoped = 2;
+        try this.compute(1) returns (uint256 result) {} catch Error(string memory amount) {}
+    }
+}
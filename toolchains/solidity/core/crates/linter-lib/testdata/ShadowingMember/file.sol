@@ -0,0 +1,27 @@
+pragma solidity 0.8.19;
+
+contract Base {
+    function balance() public view returns (uint256) {
+        return 0;
+    }
+
+    function hidden() private {}
+}
+
+contract ShadowingMember is Base {
+    event Transfer(address to, uint256 amount);
+
+    function deposit(uint256 amount) external returns (uint256 balance) {
+        return amount;
+    }
+
+    function notify(uint256 Transfer, uint256 hidden) external {}
+
+    function getValue(uint256 value) external returns (uint256) {
+        return value;
+    }
+
+    function value() public pure returns (uint256) {
+        return 1;
+    }
+}
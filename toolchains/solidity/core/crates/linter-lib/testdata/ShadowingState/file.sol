@@ -0,0 +1,27 @@
+pragma solidity 0.8.19;
+
+contract Base {
+    uint256 internal total;
+    uint256 private secret;
+}
+
+contract ShadowingState is Base {
+    address public owner;
+
+    function setOwner(address owner) external {
+        uint256 total = 1;
+        uint256 secret = total;
+    }
+
+    function count() external view returns (uint256 owner) {
+        return 0;
+    }
+
+    modifier only(address owner) {
+        _;
+    }
+
+    function ok(address newOwner) external {
+        owner = newOwner;
+    }
+}
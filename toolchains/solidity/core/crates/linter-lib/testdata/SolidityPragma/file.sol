@@ -0,0 +1,5 @@
+// SPDX-License-Identifier: MIT
+
+contract SolidityPragma {
+    uint256 public value;
+}
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+library Math {
+    function max(uint256 a, uint256 b) internal pure returns (uint256) {
+        return a > b ? a : b;
+    }
+}
+
+contract Base {
+    uint256 internal total;
+
+    function _total() internal view returns (uint256) {
+        return total;
+    }
+}
+
+contract Vault is Base {
+    uint256 public constant FEE = 3;
+    address public immutable owner;
+    mapping(address => uint256) public balances;
+    uint256[] private history;
+
+    event Deposited(address indexed account, uint256 amount);
+
+    modifier onlyOwner() {
+        require(msg.sender == owner, "not owner");
+        _;
+    }
+
+    constructor() {
+        owner = msg.sender;
+    }
+
+    function deposit() external payable {
+        balances[msg.sender] += msg.value;
+        emit Deposited(msg.sender, msg.value);
+    }
+
+    function balanceOf(address account) external returns (uint256) {
+        return balances[account];
+    }
+
+    function fee(uint256 amount) public view returns (uint256) {
+        return (amount * FEE) / 100;
+    }
+
+    function larger(uint256 a, uint256 b) external returns (uint256) {
+        return Math.max(a, b);
+    }
+
+    function current() external returns (uint256) {
+        return _total();
+    }
+
+    function record(uint256 amount) external view {
+        history.push(amount);
+    }
+
+    function sender() external pure returns (address) {
+        return msg.sender;
+    }
+
+    function reset() external onlyOwner {
+        Data storage data = _data();
+        data.count = 0;
+    }
+
+    function ownerOnly() external onlyOwner returns (uint256) {
+        return FEE;
+    }
+
+    function forward(address target) external {
+        (bool success, ) = target.call("");
+        require(success);
+    }
+
+    function withAssembly() external returns (uint256 size) {
+        assembly {
+            size := 1
+        }
+    }
+
+    function hashed(bytes memory data) external returns (bytes32) {
+        return keccak256(abi.encodePacked(data));
+    }
+
+    function bump() external {
+        total++;
+    }
+
+    struct Data {
+        uint256 count;
+    }
+
+    Data private stored;
+
+    function _data() internal view returns (Data storage) {
+        return stored;
+    }
+
+    uint256 private _a;
+    uint256 private _b;
+
+    function pair() internal pure returns (uint256, uint256) {
+        return (1, 2);
+    }
+
+    function assignPair() external {
+        (_a, _b) = pair();
+    }
+
+    function assignFirst() external {
+        (_a, ) = pair();
+    }
+
+    function assignTuple() external {
+        (_a, _b) = (_b + 1, 2);
+    }
+
+    function local(uint256 total) external returns (uint256) {
+        return total + 1;
+    }
+}
+
+interface IVault {
+    function deposit() external payable;
+}
@@ -0,0 +1,43 @@
+pragma solidity 0.8.0;
+
+contract Wallet {
+    address public owner;
+
+    function discarded(address payable to) public {
+        to.send(1);
+        to.call{value: 1}("");
+        to.delegatecall(abi.encodeWithSignature("f()"));
+    }
+
+    function unread(address to) public {
+        (bool ok, ) = to.call("");
+        bool sent = payable(to).send(1);
+    }
+
+    function unchecked(address to) public returns (bool) {
+        (bool success, bytes memory data) = to.staticcall("");
+        emit Called(data);
+        return true;
+    }
+
+    function checked(address payable to) public returns (bool) {
+        (bool ok, ) = to.call{value: 1}("");
+        require(ok, "call failed");
+        bool sent = to.send(1);
+        if (!sent) {
+            revert();
+        }
+        bool done;
+        (done, ) = to.delegatecall("");
+        assert(done);
+        require(to.send(1));
+        return to.send(1);
+    }
+
+    function reassigned(address to) public {
+        bool ok;
+        (ok, ) = to.call("");
+    }
+
+    event Called(bytes data);
+}
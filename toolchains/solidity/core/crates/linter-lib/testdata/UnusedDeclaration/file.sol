@@ -0,0 +1,38 @@
+pragma solidity 0.8.0;
+
+error Unauthorized();
+error Forgotten();
+
+interface IVault {
+    event Deposit(uint256 amount);
+}
+
+contract Vault {
+    struct Position {
+        uint256 amount;
+    }
+
+    struct Unused {
+        uint256 amount;
+    }
+
+    event Withdrawn(uint256 amount);
+    event Ignored(uint256 amount);
+
+    mapping(address => Position) positions;
+
+    modifier onlyOwner() {
+        _;
+    }
+
+    modifier neverApplied() {
+        _;
+    }
+
+    function withdraw(uint256 amount) public onlyOwner {
+        if (amount == 0) {
+            revert Unauthorized();
+        }
+        emit Withdrawn(amount);
+    }
+}
@@ -0,0 +1,35 @@
+pragma solidity 0.8.0;
+
+function freeUsed(uint256 a) pure returns (uint256) {
+    return a + 1;
+}
+
+function freeUnused(uint256 a) pure returns (uint256) {
+    return a;
+}
+
+library Helpers {
+    function api(uint256 a) internal pure returns (uint256) {
+        return a;
+    }
+
+    function _secret(uint256 a) private pure returns (uint256) {
+        return a;
+    }
+}
+
+contract Test {
+    function run(uint256 a) public pure returns (uint256) {
+        return _used(a) + freeUsed(a);
+    }
+
+    function _used(uint256 a) private pure returns (uint256) {
+        return a;
+    }
+
+    function _unused(uint256 a) internal pure returns (uint256) {
+        return a;
+    }
+
+    function _hook(uint256 a) internal virtual {}
+}
@@ -0,0 +1,14 @@
+pragma solidity 0.8.0;
+
+import {IERC20, SafeERC20 as Safe} from "./Token.sol";
+import {Ownable} from "./Ownable.sol";
+import "./Math.sol" as Math;
+import * as Utils from "./Utils.sol";
+
+contract Vault is Ownable {
+    using Safe for IERC20;
+
+    function max(uint256 a, uint256 b) public pure returns (uint256) {
+        return Math.max(a, b);
+    }
+}
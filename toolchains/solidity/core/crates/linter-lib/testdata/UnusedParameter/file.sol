@@ -0,0 +1,20 @@
+pragma solidity 0.8.0;
+
+contract Test {
+    uint256 public total;
+
+    modifier onlyAbove(uint256 minimum, uint256 unused) {
+        require(total > minimum);
+        _;
+    }
+
+    function add(uint256 amount, address recipient) public onlyAbove(amount, 0) {
+        total += 1;
+    }
+
+    function hook(uint256, bytes memory data) public {
+        total = data.length;
+    }
+
+    function declared(uint256 amount) public virtual;
+}
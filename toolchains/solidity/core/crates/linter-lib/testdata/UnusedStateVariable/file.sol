@@ -0,0 +1,20 @@
+pragma solidity 0.8.0;
+
+contract Base {
+    uint256 internal shared;
+    uint256 private hidden;
+    uint256 private used;
+    uint256 internal forgotten;
+    uint256 public exposed;
+    uint256 private constant LIMIT = 10;
+
+    function read() public view returns (uint256) {
+        return used + LIMIT;
+    }
+}
+
+contract Child is Base {
+    function write() public {
+        shared = 1;
+    }
+}
@@ -0,0 +1,15 @@
+pragma solidity 0.8.0;
+
+contract Test {
+    function compute(uint256 a) public pure returns (uint256) {
+        uint256 unused = a * 2;
+        uint256 doubled = a * 2;
+        (uint256 first, uint256 second) = (a, a + 1);
+        uint256 raw;
+        uint256 stmts;
+        assembly {
+            raw := add(a, 1)
+        }
+        return doubled + first + raw;
+    }
+}
@@ -0,0 +1,29 @@
+pragma solidity 0.8.0;
+
+import "@openzeppelin/contracts-upgradeable/proxy/utils/Initializable.sol";
+
+contract Vault is Initializable {
+    address public owner;
+
+    constructor() {
+        owner = msg.sender;
+    }
+}
+
+contract Locked is Initializable {
+    constructor() {
+        _disableInitializers();
+    }
+}
+
+contract Child is Vault {
+    constructor() {}
+}
+
+contract NotUpgradeable {
+    address public owner;
+
+    constructor() {
+        owner = msg.sender;
+    }
+}
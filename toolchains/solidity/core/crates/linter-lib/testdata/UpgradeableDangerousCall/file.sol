@@ -0,0 +1,26 @@
+pragma solidity 0.8.0;
+
+import "@openzeppelin/contracts-upgradeable/proxy/utils/Initializable.sol";
+
+contract Vault is Initializable {
+    function destroy() public {
+        selfdestruct(payable(msg.sender));
+    }
+
+    function execute(address target, bytes memory data) public {
+        (bool success, ) = target.delegatecall(data);
+        require(success);
+    }
+
+    function forward(address target, bytes memory data) public {
+        (bool success, ) = target.call(data);
+        require(success);
+    }
+}
+
+contract Proxy {
+    function execute(address target, bytes memory data) public {
+        (bool success, ) = target.delegatecall(data);
+        require(success);
+    }
+}
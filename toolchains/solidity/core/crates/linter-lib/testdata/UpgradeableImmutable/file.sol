@@ -0,0 +1,16 @@
+pragma solidity 0.8.0;
+
+import "@openzeppelin/contracts-upgradeable/proxy/utils/Initializable.sol";
+
+contract Vault is Initializable {
+    address public immutable token;
+    uint256 public constant MAX_FEE = 100;
+
+    constructor(address _token) {
+        token = _token;
+    }
+}
+
+contract Registry {
+    address public immutable owner = msg.sender;
+}
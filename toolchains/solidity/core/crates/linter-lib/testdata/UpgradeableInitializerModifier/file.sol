@@ -0,0 +1,26 @@
+pragma solidity 0.8.0;
+
+import "@openzeppelin/contracts-upgradeable/access/OwnableUpgradeable.sol";
+
+contract Vault is OwnableUpgradeable {
+    uint256 public fee;
+
+    function initialize(uint256 _fee) public {
+        __Ownable_init(msg.sender);
+        fee = _fee;
+    }
+
+    function reinitializeFee(uint256 _fee) public reinitializer(2) {
+        fee = _fee;
+    }
+
+    function __Vault_init(uint256 _fee) internal {
+        fee = _fee;
+    }
+
+    function __Vault_init_unchained() internal onlyInitializing {}
+}
+
+contract Registry {
+    function initialize() public {}
+}
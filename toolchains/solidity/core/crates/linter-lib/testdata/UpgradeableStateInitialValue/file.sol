@@ -0,0 +1,18 @@
+pragma solidity 0.8.0;
+
+import "@openzeppelin/contracts-upgradeable/proxy/utils/Initializable.sol";
+
+contract Vault is Initializable {
+    uint256 public constant MAX_FEE = 100;
+    uint256 public fee = 10;
+    address public owner;
+    bool public paused = false;
+
+    function initialize() public initializer {
+        owner = msg.sender;
+    }
+}
+
+contract Registry {
+    uint256 public fee = 10;
+}
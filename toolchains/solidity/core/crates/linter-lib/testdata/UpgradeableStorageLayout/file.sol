@@ -0,0 +1,30 @@
+pragma solidity 0.8.0;
+
+import "@openzeppelin/contracts-upgradeable/proxy/utils/Initializable.sol";
+
+contract Base {
+    uint256 public supply;
+}
+
+contract Vault is Initializable, Base {
+    struct Position {
+        uint256 amount;
+        address owner;
+    }
+
+    address public admin;
+    uint128 public fee;
+    bool public paused;
+    mapping(address => Position) public positions;
+    uint256 public created;
+}
+
+contract Registry {
+    address public admin;
+    uint256 public count;
+}
+
+contract Unchanged {
+    address payable public treasury;
+    uint256 public count;
+}
@@ -0,0 +1,16 @@
+pragma solidity 0.8.0;
+
+contract Lottery {
+    function draw(uint256 players) public view returns (uint256) {
+        uint256 seed = uint256(blockhash(block.number - 1));
+        return (seed + block.prevrandao) % players;
+    }
+
+    function legacyDraw(uint256 players) public view returns (uint256) {
+        return uint256(keccak256(abi.encode(block.difficulty, block.timestamp))) % players;
+    }
+
+    function number() public view returns (uint256) {
+        return block.number;
+    }
+}
@@ -49,7 +49,7 @@ fn test_directory(base_name: &str) {
     test_linter(&config, &source, &expected_findings);
 }
 
-fn test_linter(config: &str, source: &str, expected_findings: &Vec<Finding>) {
+fn test_linter(config: &str, source: &str, expected_findings: &[Finding]) {
     let mut linter: SolidLinter = SolidLinter::new();
     let _ = linter.initialize_rules(&String::from(config));
 
@@ -63,9 +63,9 @@ fn test_linter(config: &str, source: &str, expected_findings: &Vec<Finding>) {
     match result {
         Ok(file_diags) => {
             let mut found;
-            for (_, diag) in file_diags.diags.iter().enumerate() {
+            for diag in file_diags.diags.iter() {
                 found = false;
-                for (_, expected_finding) in expected_findings.iter().enumerate() {
+                for expected_finding in expected_findings.iter() {
                     if (diag.range.start == expected_finding.start)
                         && (diag.range.end == expected_finding.end)
                         && (diag.id == expected_finding.id)
@@ -79,9 +79,9 @@ fn test_linter(config: &str, source: &str, expected_findings: &Vec<Finding>) {
                     not_needed_findings.push(diag);
                 }
             }
-            for (_, expected_finding) in expected_findings.iter().enumerate() {
+            for expected_finding in expected_findings.iter() {
                 found = false;
-                for (_, found_finding) in found_findings.iter().enumerate() {
+                for found_finding in found_findings.iter() {
                     if (expected_finding.start == found_finding.start)
                         && (expected_finding.end == found_finding.end)
                         && (expected_finding.id == found_finding.id)
@@ -90,13 +90,13 @@ fn test_linter(config: &str, source: &str, expected_findings: &Vec<Finding>) {
                         break;
                     }
                 }
-                if found == false {
+                if !found {
                     not_found_findings.push(expected_finding);
                 }
             }
-            if not_needed_findings.len() > 0 {
+            if !not_needed_findings.is_empty() {
                 println!("Diagnostics not expected:");
-                for (_, finding) in not_needed_findings.iter().enumerate() {
+                for finding in not_needed_findings.iter() {
                     println!(
                         "{}:{}:{}:{}:{}",
                         finding.id,
@@ -108,9 +108,9 @@ fn test_linter(config: &str, source: &str, expected_findings: &Vec<Finding>) {
                 }
                 not_needed_found = true;
             }
-            if not_found_findings.len() > 0 {
+            if !not_found_findings.is_empty() {
                 println!("\nMissing diagnostics:");
-                for (_, finding) in not_found_findings.iter().enumerate() {
+                for finding in not_found_findings.iter() {
                     println!(
                         "{}:{}:{}:{}:{}",
                         finding.id,
@@ -122,7 +122,7 @@ fn test_linter(config: &str, source: &str, expected_findings: &Vec<Finding>) {
                 }
                 not_found = true;
             }
-            assert_eq!(not_needed_found == true || not_found == true, false, "There are some missing or not needed diagnostics:\n Not needed found: {}\n Not found: {}", not_needed_findings.len(), not_found_findings.len());
+            assert!(!(not_needed_found || not_found), "There are some missing or not needed diagnostics:\n Not needed found: {}\n Not found: {}", not_needed_findings.len(), not_found_findings.len());
         }
         Err(e) => {
             panic!("{}", e);
@@ -171,7 +171,61 @@ test_directories! {
     Ordering,
     PrivateVarsLeadingUnderscore,
     FoundryTestFunctions,
-    AvoidTxOrigin
+    AvoidTxOrigin,
+    Reentrancy,
+    UncheckedLowLevelCall,
+    AvoidSelfdestruct,
+    ControlledDelegatecall,
+    CallsInLoop,
+    EtherTransferInLoop,
+    WeakRandomness,
+    EncodePackedCollision,
+    ArbitraryFromTransfer,
+    GasCacheStorageInLoops,
+    GasLengthInLoops,
+    GasPrefixIncrement,
+    GasUncheckedIncrement,
+    GasUnsignedNotZero,
+    GasPublicArray,
+    GasImmutableState,
+    GasCalldataParameters,
+    GasStructPacking,
+    GasStateVariablePacking,
+    UpgradeableConstructor,
+    UpgradeableInitializerModifier,
+    UpgradeableDangerousCall,
+    UpgradeableStateInitialValue,
+    UpgradeableImmutable,
+    UpgradeableStorageLayout,
+    Erc20Compliance,
+    Erc721Compliance,
+    Erc1155Compliance,
+    Erc4626Compliance,
+    UnusedVariable,
+    UnusedParameter,
+    UnusedStateVariable,
+    UnusedImport,
+    UnusedFunction,
+    UnusedDeclaration,
+    ShadowingState,
+    ShadowingInheritedState,
+    ShadowingBuiltin,
+    ShadowingMember,
+    ShadowingLocal,
+    SolidityPragma,
+    FloatingPragma,
+    CompilerVersion,
+    PragmaConsistency,
+    LicenseIdentifier,
+    AbiEncoderV2,
+    CodeComplexity,
+    StateMutability,
+    Quotes,
+    ComprehensiveInterface,
+    ContractNameMatchesFile,
+    DuplicatedImports,
+    ConstructorSyntax,
+    NoComplexFallback
 }
 
 #[allow(non_snake_case)]
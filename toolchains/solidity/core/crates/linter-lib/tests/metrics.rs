@@ -0,0 +1,64 @@
+use solidhunter_lib::metrics::{parse_file_metrics, FunctionMetrics};
+use std::{fs, path::PathBuf};
+
+#[test]
+fn function_complexity_metrics() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata")
+        .join("CodeComplexity")
+        .join("file.sol");
+    let content = fs::read_to_string(&path).unwrap();
+    let metrics = parse_file_metrics(path.to_str().unwrap(), &content).unwrap();
+
+    assert_eq!(metrics.contracts.len(), 1);
+    assert_eq!(
+        metrics.contracts[0].functions,
+        vec![
+            FunctionMetrics {
+                name: "simple".to_string(),
+                line: 4,
+                cyclomatic_complexity: 1,
+                cognitive_complexity: 0,
+                nesting_depth: 0,
+                parameters: 1,
+                return_values: 1,
+            },
+            FunctionMetrics {
+                name: "complex".to_string(),
+                line: 8,
+                cyclomatic_complexity: 8,
+                cognitive_complexity: 14,
+                nesting_depth: 4,
+                parameters: 2,
+                return_values: 2,
+            },
+        ]
+    );
+    assert_eq!(
+        metrics.functions,
+        vec![FunctionMetrics {
+            name: "free".to_string(),
+            line: 25,
+            cyclomatic_complexity: 4,
+            cognitive_complexity: 4,
+            nesting_depth: 2,
+            parameters: 1,
+            return_values: 1,
+        }]
+    );
+    assert_eq!(
+        metrics.csv_rows()[2],
+        format!("{},,free,25,4,4,2,1,1", path.to_str().unwrap())
+    );
+}
+
+#[test]
+fn csv_rows_quote_fields() {
+    let content = "function free() {}";
+    let metrics = parse_file_metrics("contracts/a,\"b\".sol", content).unwrap();
+
+    assert_eq!(
+        metrics.csv_rows(),
+        vec!["\"contracts/a,\"\"b\"\".sol\",,free,1,1,0,0,0,0".to_string()]
+    );
+}
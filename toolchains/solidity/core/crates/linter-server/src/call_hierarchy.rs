@@ -0,0 +1,386 @@
+use crate::symbols::{
+    all_functions, ancestors, find_contract, find_file, free_functions, ContractRef, FunctionRef,
+};
+use crate::utils::{range_contains, range_from_line_columns};
+use osmium_libs_lsp_server_wrapper::lsp_types::{
+    CallHierarchyIncomingCall, CallHierarchyItem, CallHierarchyOutgoingCall, Position, Range,
+};
+use osmium_libs_solidity_ast_extractor::{
+    visit, ArgListImpl, Expr, ExprCall, ExprMember, Item, SolIdent, Spanned, Type,
+    VariableDeclaration, Visit,
+};
+use solidhunter_lib::linter::SolidFile;
+use std::collections::HashMap;
+
+enum Callee<'ast> {
+    Internal(String),
+    Super(String),
+    Member(&'ast Expr, String),
+}
+
+struct Call<'ast> {
+    callee: Callee<'ast>,
+    range: Range,
+}
+
+struct CallVisitor<'ast> {
+    calls: Vec<Call<'ast>>,
+    variables: HashMap<String, String>,
+}
+
+impl<'ast> CallVisitor<'ast> {
+    pub fn new() -> Self {
+        Self {
+            calls: Vec::new(),
+            variables: HashMap::new(),
+        }
+    }
+
+    fn push_call(&mut self, callee: Callee<'ast>, name: &SolIdent) {
+        let span = name.span();
+        self.calls.push(Call {
+            callee,
+            range: range_from_line_columns(span.start(), span.end()),
+        });
+    }
+
+    fn push_member_call(&mut self, receiver: &'ast Expr, name: &SolIdent) {
+        let callee = match receiver {
+            Expr::Ident(receiver) if receiver == "super" => Callee::Super(name.to_string()),
+            Expr::Ident(receiver) if receiver == "this" => Callee::Internal(name.to_string()),
+            receiver => Callee::Member(receiver, name.to_string()),
+        };
+        self.push_call(callee, name);
+    }
+}
+
+fn callee_expr(call: &ExprCall) -> &Expr {
+    let mut expr: &Expr = &call.expr;
+    while let Expr::CallOptions(options) = expr {
+        expr = &options.expr;
+    }
+    expr
+}
+
+impl<'ast> Visit<'ast> for CallVisitor<'ast> {
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        match callee_expr(call) {
+            Expr::Ident(name) => self.push_call(Callee::Internal(name.to_string()), name),
+            Expr::Member(member) => {
+                if let Expr::Ident(name) = member.member.as_ref() {
+                    self.push_member_call(&member.expr, name);
+                }
+            }
+            _ => {}
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    // `a.f()` is parsed as a member access whose member is the call `f()`
+    fn visit_expr_member(&mut self, member: &'ast ExprMember) {
+        if let Expr::Call(call) = member.member.as_ref() {
+            if let Expr::Ident(name) = callee_expr(call) {
+                self.push_member_call(&member.expr, name);
+                self.visit_expr(&member.expr);
+                match &call.args.list {
+                    ArgListImpl::Unnamed(args) => {
+                        for arg in args {
+                            self.visit_expr(arg);
+                        }
+                    }
+                    ArgListImpl::Named(args) => {
+                        for arg in &args.list {
+                            self.visit_expr(&arg.arg);
+                        }
+                    }
+                }
+                return;
+            }
+        }
+        visit::visit_expr_member(self, member);
+    }
+
+    fn visit_variable_declaration(&mut self, var: &'ast VariableDeclaration) {
+        if let (Some(name), Type::Custom(path)) = (&var.name, &var.ty) {
+            self.variables
+                .insert(name.to_string(), path.last().to_string());
+        }
+        visit::visit_variable_declaration(self, var);
+    }
+}
+
+struct Resolver<'a> {
+    files: &'a [SolidFile],
+    caller: FunctionRef<'a>,
+    variables: HashMap<String, String>,
+}
+
+impl<'a> Resolver<'a> {
+    fn caller_contract(&self) -> Option<ContractRef<'a>> {
+        self.caller.contract.map(|contract| ContractRef {
+            file: self.caller.file,
+            contract,
+        })
+    }
+
+    fn lookup(&self, contract: ContractRef<'a>, name: &str) -> Option<FunctionRef<'a>> {
+        contract.find_function(name).or_else(|| {
+            ancestors(self.files, contract)
+                .into_iter()
+                .find_map(|base| base.find_function(name))
+        })
+    }
+
+    fn lookup_free_function(&self, name: &str) -> Option<FunctionRef<'a>> {
+        free_functions(self.caller.file)
+            .chain(self.files.iter().flat_map(free_functions))
+            .find(|function| function.name() == name)
+    }
+
+    fn state_variable_type(&self, contract: ContractRef<'a>, name: &str) -> Option<String> {
+        let mut hierarchy = vec![contract];
+        hierarchy.extend(ancestors(self.files, contract));
+        hierarchy.iter().find_map(|contract| {
+            contract.contract.body.iter().find_map(|item| match item {
+                Item::Variable(var) if var.name == name => match &var.ty {
+                    Type::Custom(path) => Some(path.last().to_string()),
+                    _ => None,
+                },
+                _ => None,
+            })
+        })
+    }
+
+    fn receiver_type(&self, receiver: &Expr) -> Option<ContractRef<'a>> {
+        let name = match receiver {
+            Expr::Ident(ident) => {
+                let ident = ident.to_string();
+                self.variables
+                    .get(&ident)
+                    .cloned()
+                    .or_else(|| {
+                        self.caller_contract()
+                            .and_then(|contract| self.state_variable_type(contract, &ident))
+                    })
+                    .unwrap_or(ident)
+            }
+            Expr::Call(call) => match call.expr.as_ref() {
+                Expr::Ident(ident) => ident.to_string(),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        find_contract(self.files, &name)
+    }
+
+    fn resolve(&self, callee: &Callee) -> Option<FunctionRef<'a>> {
+        match callee {
+            Callee::Internal(name) => self
+                .caller_contract()
+                .and_then(|contract| self.lookup(contract, name))
+                .or_else(|| self.lookup_free_function(name)),
+            Callee::Super(name) => self.caller_contract().and_then(|contract| {
+                ancestors(self.files, contract)
+                    .into_iter()
+                    .find_map(|base| base.find_function(name))
+            }),
+            Callee::Member(receiver, name) => self
+                .receiver_type(receiver)
+                .and_then(|contract| self.lookup(contract, name)),
+        }
+    }
+}
+
+fn resolved_calls<'a>(
+    files: &'a [SolidFile],
+    caller: FunctionRef<'a>,
+) -> Vec<(FunctionRef<'a>, Range)> {
+    let mut visitor = CallVisitor::new();
+    visitor.visit_item_function(caller.function);
+    let resolver = Resolver {
+        files,
+        caller,
+        variables: visitor.variables,
+    };
+    visitor
+        .calls
+        .iter()
+        .filter_map(|call| {
+            resolver
+                .resolve(&call.callee)
+                .map(|function| (function, call.range))
+        })
+        .collect()
+}
+
+fn item_from_function(function: &FunctionRef) -> Option<CallHierarchyItem> {
+    Some(CallHierarchyItem {
+        name: function.name(),
+        kind: function.symbol_kind(),
+        tags: None,
+        detail: function.contract.map(|contract| contract.name.to_string()),
+        uri: function.uri()?,
+        range: function.range(),
+        selection_range: function.selection_range(),
+        data: None,
+    })
+}
+
+fn is_same_item(a: &CallHierarchyItem, b: &CallHierarchyItem) -> bool {
+    a.uri == b.uri && a.selection_range == b.selection_range
+}
+
+pub fn prepare_call_hierarchy(
+    files: &[SolidFile],
+    path: &str,
+    position: Position,
+) -> Vec<CallHierarchyItem> {
+    let file = match find_file(files, path) {
+        Some(file) => file,
+        None => return vec![],
+    };
+    let caller = match all_functions(file)
+        .into_iter()
+        .find(|function| range_contains(&function.range(), &position))
+    {
+        Some(caller) => caller,
+        None => return vec![],
+    };
+    let target = resolved_calls(files, caller)
+        .into_iter()
+        .find(|(_, range)| range_contains(range, &position))
+        .map(|(function, _)| function)
+        .unwrap_or(caller);
+    item_from_function(&target).into_iter().collect()
+}
+
+pub fn incoming_calls(
+    files: &[SolidFile],
+    item: &CallHierarchyItem,
+) -> Vec<CallHierarchyIncomingCall> {
+    let mut res: Vec<CallHierarchyIncomingCall> = Vec::new();
+
+    for file in files {
+        for caller in all_functions(file) {
+            let from_ranges: Vec<Range> = resolved_calls(files, caller)
+                .into_iter()
+                .filter(|(callee, _)| {
+                    item_from_function(callee)
+                        .map(|callee| is_same_item(&callee, item))
+                        .unwrap_or(false)
+                })
+                .map(|(_, range)| range)
+                .collect();
+            if from_ranges.is_empty() {
+                continue;
+            }
+            if let Some(from) = item_from_function(&caller) {
+                res.push(CallHierarchyIncomingCall { from, from_ranges });
+            }
+        }
+    }
+    res
+}
+
+pub fn outgoing_calls(
+    files: &[SolidFile],
+    item: &CallHierarchyItem,
+) -> Vec<CallHierarchyOutgoingCall> {
+    let caller = files.iter().find_map(|file| {
+        all_functions(file).into_iter().find(|function| {
+            item_from_function(function)
+                .map(|function| is_same_item(&function, item))
+                .unwrap_or(false)
+        })
+    });
+    let caller = match caller {
+        Some(caller) => caller,
+        None => return vec![],
+    };
+
+    let mut res: Vec<CallHierarchyOutgoingCall> = Vec::new();
+    for (callee, range) in resolved_calls(files, caller) {
+        let to = match item_from_function(&callee) {
+            Some(to) => to,
+            None => continue,
+        };
+        match res.iter_mut().find(|call| is_same_item(&call.to, &to)) {
+            Some(call) => call.from_ranges.push(range),
+            None => res.push(CallHierarchyOutgoingCall {
+                to,
+                from_ranges: vec![range],
+            }),
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::solid_file;
+
+    fn files() -> Vec<SolidFile> {
+        vec![
+            solid_file(
+                "/base.sol",
+                "contract Base {\n    function check() internal {}\n    function run() public virtual {\n        check();\n    }\n}\n",
+            ),
+            solid_file(
+                "/child.sol",
+                "contract Child is Base {\n    Base other;\n    function run() public override {\n        super.run();\n        other.run();\n        check();\n    }\n}\n",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_prepare_call_hierarchy_enclosing_function() {
+        let files = files();
+        let items = prepare_call_hierarchy(&files, "/child.sol", Position::new(4, 0));
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "run");
+        assert_eq!(items[0].detail, Some("Child".to_string()));
+    }
+
+    #[test]
+    fn test_prepare_call_hierarchy_on_call() {
+        let files = files();
+        let items = prepare_call_hierarchy(&files, "/child.sol", Position::new(5, 9));
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "check");
+        assert_eq!(items[0].detail, Some("Base".to_string()));
+    }
+
+    #[test]
+    fn test_prepare_call_hierarchy_selects_name() {
+        let files = files();
+        let items = prepare_call_hierarchy(&files, "/base.sol", Position::new(1, 20));
+        assert_eq!(
+            items[0].selection_range,
+            Range::new(Position::new(1, 13), Position::new(1, 18))
+        );
+        assert_eq!(items[0].range.start, Position::new(1, 4));
+    }
+
+    #[test]
+    fn test_outgoing_calls() {
+        let files = files();
+        let item = prepare_call_hierarchy(&files, "/child.sol", Position::new(4, 0)).remove(0);
+        let calls = outgoing_calls(&files, &item);
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].to.name, "run");
+        assert_eq!(calls[0].to.detail, Some("Base".to_string()));
+        assert_eq!(calls[0].from_ranges.len(), 2);
+        assert_eq!(calls[1].to.name, "check");
+    }
+
+    #[test]
+    fn test_incoming_calls() {
+        let files = files();
+        let item = prepare_call_hierarchy(&files, "/base.sol", Position::new(1, 20)).remove(0);
+        let calls = incoming_calls(&files, &item);
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].from.detail, Some("Base".to_string()));
+        assert_eq!(calls[1].from.detail, Some("Child".to_string()));
+    }
+}
@@ -0,0 +1,1309 @@
+use crate::symbols::{
+    all_functions, ancestors, contract_range, contracts, find_file, function_range, ContractRef,
+};
+use crate::utils::{filepath_from_uri, position_from_line_column, range_contains};
+use osmium_libs_lsp_server_wrapper::lsp_types::{
+    CodeAction, CodeActionContext, CodeActionKind, CodeActionOrCommand, Diagnostic, NumberOrString,
+    Position, Range, TextEdit, Url, WorkspaceEdit,
+};
+use osmium_libs_solidity_ast_extractor::{
+    extract::extract_ast_from_content, retriever, visit, ArgListImpl, BinOp, Block, ContractKind,
+    Expr, ExprBinary, ExprPostfix, ExprTernary, ExprUnary, File, FunctionAttribute, FunctionBody,
+    FunctionKind, ImportDirective, ImportPath, Item, ItemContract, ItemFunction, Lit, SolIdent,
+    SolPath, Spanned, Stmt, StmtDoWhile, StmtFor, StmtVarDecl, StmtWhile, UnOp, VarDeclDecl,
+    VariableDeclaration, Visit,
+};
+use solidhunter_lib::{imports::ImportResolver, linter::SolidFile};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CUSTOM_ERRORS_RULE: &str = "custom-errors";
+const NO_GLOBAL_IMPORT_RULE: &str = "no-global-import";
+const DEFAULT_ERROR_NAME: &str = "RequirementFailed";
+
+/// Computes the code actions of a document. `text` is the latest content of the document in the editor:
+/// the parsed document may be a debounce behind it, and the edits must apply to the text the client has.
+pub fn code_actions(
+    files: &[SolidFile],
+    resolver: &ImportResolver,
+    uri: &Url,
+    text: Option<&str>,
+    range: Range,
+    context: &CodeActionContext,
+) -> Vec<CodeActionOrCommand> {
+    let Some(parsed) = find_file(files, &filepath_from_uri(uri)) else {
+        return vec![];
+    };
+    let latest;
+    let file = match text {
+        Some(text) if text != parsed.content => {
+            let Ok(data) = extract_ast_from_content(text) else {
+                return vec![];
+            };
+            latest = SolidFile {
+                data,
+                path: parsed.path.clone(),
+                content: text.to_string(),
+            };
+            &latest
+        }
+        _ => parsed,
+    };
+    let document = Document { file, uri };
+
+    let mut actions = require_to_custom_error(&document, range, &context.diagnostics);
+    actions.extend(extract_local(&document, range));
+    actions.extend(natspec_skeleton(&document, range));
+    actions.extend(named_imports(
+        files,
+        resolver,
+        &document,
+        range,
+        &context.diagnostics,
+    ));
+    actions.extend(implement_missing_functions(files, &document, range));
+
+    if let Some(only) = &context.only {
+        actions.retain(|action| {
+            action
+                .kind
+                .as_ref()
+                .is_some_and(|kind| only.iter().any(|only| is_sub_kind(kind, only)))
+        });
+    }
+    actions
+        .into_iter()
+        .map(CodeActionOrCommand::CodeAction)
+        .collect()
+}
+
+/// A document for which code actions are computed
+struct Document<'a> {
+    file: &'a SolidFile,
+    uri: &'a Url,
+}
+
+impl<'a> Document<'a> {
+    fn text(&self, range: Range) -> &'a str {
+        let content = &self.file.content;
+        &content[offset(content, range.start)..offset(content, range.end)]
+    }
+
+    fn indentation(&self, line: u32) -> String {
+        self.file
+            .content
+            .lines()
+            .nth(line as usize)
+            .map(|line| line.chars().take_while(|c| c.is_whitespace()).collect())
+            .unwrap_or_default()
+    }
+
+    fn action(
+        &self,
+        title: String,
+        kind: CodeActionKind,
+        edits: Vec<TextEdit>,
+        diagnostics: Vec<Diagnostic>,
+    ) -> CodeAction {
+        let paired = !diagnostics.is_empty();
+        CodeAction {
+            title,
+            kind: Some(if paired {
+                CodeActionKind::QUICKFIX
+            } else {
+                kind
+            }),
+            diagnostics: paired.then_some(diagnostics),
+            edit: Some(WorkspaceEdit {
+                changes: Some(HashMap::from([(self.uri.clone(), edits)])),
+                ..WorkspaceEdit::default()
+            }),
+            is_preferred: paired.then_some(true),
+            ..CodeAction::default()
+        }
+    }
+}
+
+fn offset(content: &str, position: Position) -> usize {
+    let mut offset = 0;
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        if index == position.line as usize {
+            return offset
+                + line
+                    .char_indices()
+                    .nth(position.character as usize)
+                    .map_or(line.len(), |(index, _)| index);
+        }
+        offset += line.len();
+    }
+    content.len()
+}
+
+fn node_range<T: Spanned>(node: &T) -> Range {
+    let span = node.span();
+    Range {
+        start: position_from_line_column(span.start()),
+        end: position_from_line_column(span.end()),
+    }
+}
+
+fn overlaps(a: &Range, b: &Range) -> bool {
+    a.start <= b.end && b.start <= a.end
+}
+
+fn is_sub_kind(kind: &CodeActionKind, parent: &CodeActionKind) -> bool {
+    let (kind, parent) = (kind.as_str(), parent.as_str());
+    kind == parent || kind.starts_with(&format!("{}.", parent))
+}
+
+/// Diagnostics of a rule reported on a range, which the action fixes
+fn paired_diagnostics(diagnostics: &[Diagnostic], rule: &str, range: &Range) -> Vec<Diagnostic> {
+    diagnostics
+        .iter()
+        .filter(|diag| {
+            diag.code == Some(NumberOrString::String(rule.to_string()))
+                && overlaps(&diag.range, range)
+        })
+        .cloned()
+        .collect()
+}
+
+fn require_to_custom_error(
+    document: &Document,
+    range: Range,
+    diagnostics: &[Diagnostic],
+) -> Vec<CodeAction> {
+    let mut res = Vec::new();
+
+    for contract in contracts(document.file) {
+        let contract = contract.contract;
+        for stmt in retriever::retrieve_stmts_nodes(contract) {
+            let Stmt::Expr(stmt) = &stmt else {
+                continue;
+            };
+            let Expr::Call(call) = &stmt.expr else {
+                continue;
+            };
+            if !matches!(&*call.expr, Expr::Ident(ident) if *ident == "require") {
+                continue;
+            }
+            let call_range = node_range(call);
+            if !overlaps(&call_range, &range) {
+                continue;
+            }
+            let ArgListImpl::Unnamed(args) = &call.args.list else {
+                continue;
+            };
+            let Some(cond) = args.first() else {
+                continue;
+            };
+            let name = match args.iter().nth(1) {
+                Some(Expr::Lit(Lit::Str(message))) => error_name(&message.value()),
+                _ => DEFAULT_ERROR_NAME.to_string(),
+            };
+
+            let mut edits = vec![TextEdit {
+                range: call_range,
+                new_text: format!("if ({}) revert {}()", negate(document, cond), name),
+            }];
+            if !declares_error(document.file, contract, &name) {
+                edits.insert(0, error_declaration(document, contract, &name));
+            }
+            res.push(document.action(
+                format!("Replace require with custom error {}", name),
+                CodeActionKind::REFACTOR_REWRITE,
+                edits,
+                paired_diagnostics(diagnostics, CUSTOM_ERRORS_RULE, &call_range),
+            ));
+        }
+    }
+    res
+}
+
+/// Builds an error name from a require message, such as `NotOwner` for "Ownable: not owner"
+fn error_name(message: &str) -> String {
+    let message = message.rsplit(':').next().unwrap_or(message);
+    let name: String = message
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect();
+    match name.chars().next() {
+        None => DEFAULT_ERROR_NAME.to_string(),
+        Some(first) if first.is_ascii_digit() => format!("Error{}", name),
+        Some(_) => name,
+    }
+}
+
+fn negate(document: &Document, cond: &Expr) -> String {
+    match cond {
+        Expr::Unary(ExprUnary {
+            op: UnOp::Not(_),
+            expr,
+        }) => document.text(node_range(&**expr)).to_string(),
+        Expr::Ident(_)
+        | Expr::Call(_)
+        | Expr::Member(_)
+        | Expr::Index(_)
+        | Expr::Lit(_)
+        | Expr::Tuple(_) => format!("!{}", document.text(node_range(cond))),
+        _ => format!("!({})", document.text(node_range(cond))),
+    }
+}
+
+fn declares_error(file: &SolidFile, contract: &ItemContract, name: &str) -> bool {
+    let declared = |items: &[Item]| {
+        items
+            .iter()
+            .any(|item| matches!(item, Item::Error(error) if error.name == name))
+    };
+    declared(&contract.body) || declared(&file.data.items)
+}
+
+/// Inserts an error declaration as the first member of a contract
+fn error_declaration(document: &Document, contract: &ItemContract, name: &str) -> TextEdit {
+    let open = position_from_line_column(contract.brace_token.span.open().end());
+    let close = position_from_line_column(contract.brace_token.span.close().start());
+    let contract_indentation = document.indentation(open.line);
+    let rest = document.text(Range {
+        start: open,
+        end: Position {
+            line: open.line,
+            character: u32::MAX,
+        },
+    });
+
+    if !rest.trim().is_empty() || close.line == open.line {
+        return TextEdit {
+            range: Range {
+                start: open,
+                end: open,
+            },
+            new_text: format!(" error {}();", name),
+        };
+    }
+    let next_line = open.line + 1;
+    let member_indentation = if next_line < close.line {
+        document.indentation(next_line)
+    } else {
+        format!("{}    ", contract_indentation)
+    };
+    let position = Position {
+        line: next_line,
+        character: 0,
+    };
+    TextEdit {
+        range: Range {
+            start: position,
+            end: position,
+        },
+        new_text: format!("{}error {}();\n", member_indentation, name),
+    }
+}
+
+/// Finds the expression spanning a range, and the statement before which it can be evaluated
+struct ExpressionFinder<'ast> {
+    range: Range,
+    statement: Option<Range>,
+    found: Option<(&'ast Expr, Range)>,
+}
+
+impl<'ast> ExpressionFinder<'ast> {
+    /// Visits the children of an expression which is written to, or evaluated conditionally
+    fn visit_children_only(&mut self, expr: &'ast Expr) {
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_without_statement(&mut self, visit: impl FnOnce(&mut Self)) {
+        let statement = self.statement.take();
+        visit(self);
+        self.statement = statement;
+    }
+}
+
+impl<'ast> Visit<'ast> for ExpressionFinder<'ast> {
+    fn visit_block(&mut self, block: &'ast Block) {
+        for stmt in &block.stmts {
+            let statement = self.statement.replace(node_range(stmt));
+            self.visit_stmt(stmt);
+            self.statement = statement;
+        }
+    }
+
+    fn visit_stmt_for(&mut self, stmt: &'ast StmtFor) {
+        self.visit_without_statement(|finder| visit::visit_stmt_for(finder, stmt));
+    }
+
+    fn visit_stmt_while(&mut self, stmt: &'ast StmtWhile) {
+        self.visit_without_statement(|finder| visit::visit_stmt_while(finder, stmt));
+    }
+
+    fn visit_stmt_dowhile(&mut self, stmt: &'ast StmtDoWhile) {
+        self.visit_without_statement(|finder| visit::visit_stmt_dowhile(finder, stmt));
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if self.found.is_none() && node_range(expr) == self.range {
+            if let Some(statement) = self.statement {
+                self.found = Some((expr, statement));
+            }
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_expr_binary(&mut self, expr: &'ast ExprBinary) {
+        if is_assignment(&expr.op) {
+            self.visit_children_only(&expr.left);
+            self.visit_expr(&expr.right);
+        } else if matches!(expr.op, BinOp::And(_) | BinOp::Or(_)) {
+            self.visit_expr(&expr.left);
+            self.visit_without_statement(|finder| finder.visit_expr(&expr.right));
+        } else {
+            visit::visit_expr_binary(self, expr);
+        }
+    }
+
+    fn visit_expr_ternary(&mut self, expr: &'ast ExprTernary) {
+        self.visit_expr(&expr.cond);
+        self.visit_without_statement(|finder| {
+            finder.visit_expr(&expr.if_true);
+            finder.visit_expr(&expr.if_false);
+        });
+    }
+
+    fn visit_expr_unary(&mut self, expr: &'ast ExprUnary) {
+        match expr.op {
+            UnOp::Increment(..) | UnOp::Decrement(..) => self.visit_children_only(&expr.expr),
+            _ => visit::visit_expr_unary(self, expr),
+        }
+    }
+
+    fn visit_expr_postfix(&mut self, expr: &'ast ExprPostfix) {
+        self.visit_children_only(&expr.expr);
+    }
+}
+
+fn is_assignment(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::Assign(_)
+            | BinOp::AddAssign(_)
+            | BinOp::SubAssign(_)
+            | BinOp::MulAssign(_)
+            | BinOp::DivAssign(_)
+            | BinOp::RemAssign(_)
+            | BinOp::BitAndAssign(_)
+            | BinOp::BitOrAssign(_)
+            | BinOp::BitXorAssign(_)
+            | BinOp::SarAssign(..)
+            | BinOp::ShlAssign(_)
+            | BinOp::ShrAssign(_)
+    )
+}
+
+/// Collects the variables declared in a function body
+#[derive(Default)]
+struct LocalsCollector<'ast> {
+    locals: Vec<&'ast VariableDeclaration>,
+}
+
+impl<'ast> Visit<'ast> for LocalsCollector<'ast> {
+    fn visit_stmt_var_decl(&mut self, stmt: &'ast StmtVarDecl) {
+        if let VarDeclDecl::VarDecl(decl) = &stmt.declaration {
+            self.locals.push(decl);
+        }
+        visit::visit_stmt_var_decl(self, stmt);
+    }
+}
+
+/// Types of the variables and functions visible from a function
+struct Scope {
+    variables: HashMap<String, String>,
+    functions: HashMap<String, String>,
+    structs: HashSet<String>,
+}
+
+impl Scope {
+    fn new(document: &Document, contract: Option<&ItemContract>, function: &ItemFunction) -> Self {
+        let text = |range: Range| normalize(document.text(range));
+        let mut scope = Scope {
+            variables: HashMap::new(),
+            functions: HashMap::new(),
+            structs: retriever::retrieve_structs_file_nodes(&document.file.data)
+                .iter()
+                .map(|strukt| strukt.name.to_string())
+                .collect(),
+        };
+
+        for item in contract.map_or(&[][..], |contract| &contract.body[..]) {
+            match item {
+                Item::Variable(var) => {
+                    scope
+                        .variables
+                        .insert(var.name.to_string(), text(node_range(&var.ty)));
+                }
+                Item::Function(function) => {
+                    let returns = function.returns.as_ref().map(|returns| &returns.returns);
+                    if let (Some(name), Some(returns)) = (&function.name, returns) {
+                        if returns.len() == 1 {
+                            let ty = &returns.first().unwrap().ty;
+                            scope
+                                .functions
+                                .insert(name.to_string(), text(node_range(ty)));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut locals = LocalsCollector::default();
+        if let FunctionBody::Block(block) = &function.body {
+            locals.visit_block(block);
+        }
+        let returns = function.returns.iter().flat_map(|returns| &returns.returns);
+        for var in function
+            .arguments
+            .iter()
+            .chain(returns)
+            .chain(locals.locals)
+        {
+            if let Some(name) = &var.name {
+                scope
+                    .variables
+                    .insert(name.to_string(), text(node_range(&var.ty)));
+            }
+        }
+        scope
+    }
+
+    /// Adds the data location a local variable of a type requires
+    fn local_type(&self, ty: &str) -> Option<String> {
+        if ty.starts_with("mapping") {
+            None
+        } else if ty == "string" || ty == "bytes" || ty.ends_with(']') || self.structs.contains(ty)
+        {
+            Some(format!("{} memory", ty))
+        } else {
+            Some(ty.to_string())
+        }
+    }
+
+    fn infer(&self, document: &Document, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Lit(Lit::Bool(_)) => Some("bool".to_string()),
+            Expr::Lit(Lit::Number(_)) | Expr::LitDenominated(_) => Some("uint256".to_string()),
+            Expr::Lit(Lit::Str(_)) | Expr::Lit(Lit::Unicode(_)) => {
+                Some("string memory".to_string())
+            }
+            Expr::Lit(Lit::Hex(_)) => Some("bytes memory".to_string()),
+            Expr::Ident(ident) => self.local_type(self.variables.get(&ident.to_string())?),
+            Expr::Tuple(tuple) if tuple.elems.len() == 1 => {
+                self.infer(document, tuple.elems.first()?)
+            }
+            Expr::Unary(unary) => match unary.op {
+                UnOp::Not(_) => Some("bool".to_string()),
+                _ => self.infer(document, &unary.expr),
+            },
+            Expr::Binary(binary) => match binary.op {
+                BinOp::Lt(_)
+                | BinOp::Gt(_)
+                | BinOp::Le(_)
+                | BinOp::Ge(_)
+                | BinOp::Eq(_)
+                | BinOp::Neq(_)
+                | BinOp::And(_)
+                | BinOp::Or(_) => Some("bool".to_string()),
+                BinOp::Shl(_) | BinOp::Shr(_) | BinOp::Sar(..) | BinOp::Pow(..) => {
+                    self.infer(document, &binary.left)
+                }
+                ref op if is_assignment(op) => None,
+                // Literals take the type of the other operand
+                _ if matches!(*binary.left, Expr::Lit(_)) => self
+                    .infer(document, &binary.right)
+                    .or_else(|| self.infer(document, &binary.left)),
+                _ => self
+                    .infer(document, &binary.left)
+                    .or_else(|| self.infer(document, &binary.right)),
+            },
+            Expr::Ternary(ternary) => self
+                .infer(document, &ternary.if_true)
+                .or_else(|| self.infer(document, &ternary.if_false)),
+            Expr::Member(member) => {
+                let (name, is_call) = match &*member.member {
+                    Expr::Ident(ident) => (ident.to_string(), false),
+                    Expr::Call(call) => match &*call.expr {
+                        Expr::Ident(ident) => (ident.to_string(), true),
+                        _ => return None,
+                    },
+                    _ => return None,
+                };
+                let base = match &*member.expr {
+                    Expr::Ident(ident) => ident.to_string(),
+                    _ => String::new(),
+                };
+                match (base.as_str(), name.as_str(), is_call) {
+                    ("msg", "sender", false) | ("tx", "origin", false) => {
+                        Some("address".to_string())
+                    }
+                    ("block", "coinbase", false) => Some("address payable".to_string()),
+                    ("msg", "sig", false) => Some("bytes4".to_string()),
+                    ("msg", "data", false) => Some("bytes memory".to_string()),
+                    ("msg", "value", false)
+                    | ("tx", "gasprice", false)
+                    | ("block", _, false)
+                    | (_, "length", false)
+                    | (_, "balance", false) => Some("uint256".to_string()),
+                    ("abi", name, true) if name.starts_with("encode") => {
+                        Some("bytes memory".to_string())
+                    }
+                    _ => None,
+                }
+            }
+            Expr::Call(call) => match &*call.expr {
+                Expr::Ident(ident) => match ident.to_string().as_str() {
+                    "keccak256" | "sha256" | "blockhash" => Some("bytes32".to_string()),
+                    "ripemd160" => Some("bytes20".to_string()),
+                    "ecrecover" => Some("address".to_string()),
+                    "gasleft" | "addmod" | "mulmod" => Some("uint256".to_string()),
+                    name => self.local_type(self.functions.get(name)?),
+                },
+                Expr::Type(ty) => Some(normalize(document.text(node_range(ty)))),
+                _ => None,
+            },
+            Expr::Payable(_) => Some("address payable".to_string()),
+            _ => None,
+        }
+    }
+
+    fn unused_name(&self, base: &str) -> String {
+        let mut name = base.to_string();
+        let mut index = 1;
+        while self.variables.contains_key(&name) || self.functions.contains_key(&name) {
+            name = format!("{}{}", base, index);
+            index += 1;
+        }
+        name
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn extract_local(document: &Document, range: Range) -> Vec<CodeAction> {
+    if range.start == range.end {
+        return vec![];
+    }
+    let Some(function) = all_functions(document.file)
+        .into_iter()
+        .find(|function| range_contains(&function_range(function.function), &range.start))
+    else {
+        return vec![];
+    };
+    let FunctionBody::Block(block) = &function.function.body else {
+        return vec![];
+    };
+
+    let mut finder = ExpressionFinder {
+        range,
+        statement: None,
+        found: None,
+    };
+    finder.visit_block(block);
+    let Some((expr, statement)) = finder.found else {
+        return vec![];
+    };
+    // Extracting an identifier or a whole statement does not simplify anything
+    if matches!(expr, Expr::Ident(_)) || statement.start == range.start {
+        return vec![];
+    }
+
+    let scope = Scope::new(document, function.contract, function.function);
+    let Some(ty) = scope.infer(document, expr) else {
+        return vec![];
+    };
+    let name = scope.unused_name("value");
+    let declaration = format!(
+        "{} {} = {};\n{}",
+        ty,
+        name,
+        document.text(range),
+        document.indentation(statement.start.line)
+    );
+    vec![document.action(
+        "Extract expression into a local variable".to_string(),
+        CodeActionKind::REFACTOR_EXTRACT,
+        vec![
+            TextEdit {
+                range: Range {
+                    start: statement.start,
+                    end: statement.start,
+                },
+                new_text: declaration,
+            },
+            TextEdit {
+                range,
+                new_text: name,
+            },
+        ],
+        vec![],
+    )]
+}
+
+/// Whether the declaration starting on a line is preceded by a NatSpec comment
+fn has_natspec(document: &Document, line: u32) -> bool {
+    document
+        .file
+        .content
+        .lines()
+        .take(line as usize)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .is_some_and(|line| {
+            let line = line.trim();
+            line.starts_with("///") || line.ends_with("*/")
+        })
+}
+
+fn natspec_skeleton(document: &Document, range: Range) -> Vec<CodeAction> {
+    let function = all_functions(document.file)
+        .into_iter()
+        .find(|function| range_contains(&function_range(function.function), &range.start));
+
+    let (name, start, tags) = match function {
+        Some(function) => {
+            let function = function.function;
+            let mut tags = vec!["@notice".to_string()];
+            for arg in function.arguments.iter() {
+                if let Some(name) = &arg.name {
+                    tags.push(format!("@param {}", name));
+                }
+            }
+            for ret in function.returns.iter().flat_map(|returns| &returns.returns) {
+                tags.push(match &ret.name {
+                    Some(name) => format!("@return {}", name),
+                    None => "@return".to_string(),
+                });
+            }
+            let name = match &function.name {
+                Some(name) => name.to_string(),
+                None => function.kind.as_str().to_string(),
+            };
+            (name, function_range(function).start, tags)
+        }
+        None => {
+            let Some(contract) = contracts(document.file)
+                .find(|contract| range_contains(&contract.range(), &range.start))
+            else {
+                return vec![];
+            };
+            let name = contract.name();
+            let tags = vec![format!("@title {}", name), "@notice".to_string()];
+            (name, contract.range().start, tags)
+        }
+    };
+    if has_natspec(document, start.line) {
+        return vec![];
+    }
+
+    let indentation = document.indentation(start.line);
+    let position = Position {
+        line: start.line,
+        character: 0,
+    };
+    let new_text = tags
+        .iter()
+        .map(|tag| format!("{}/// {}\n", indentation, tag))
+        .collect();
+    vec![document.action(
+        format!("Add NatSpec documentation to {}", name),
+        CodeActionKind::REFACTOR_REWRITE,
+        vec![TextEdit {
+            range: Range {
+                start: position,
+                end: position,
+            },
+            new_text,
+        }],
+        vec![],
+    )]
+}
+
+/// Collects the names a file refers to, outside of its import directives
+#[derive(Default)]
+struct UsedNames {
+    names: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for UsedNames {
+    fn visit_ident(&mut self, ident: &'ast SolIdent) {
+        self.names.insert(ident.to_string());
+    }
+
+    fn visit_path(&mut self, path: &'ast SolPath) {
+        self.names.insert(path.first().to_string());
+    }
+
+    fn visit_import_directive(&mut self, _: &'ast ImportDirective) {}
+
+    fn visit_item_contract(&mut self, contract: &'ast ItemContract) {
+        if let Some(inheritance) = &contract.inheritance {
+            for base in inheritance.inheritance.iter() {
+                self.visit_path(&base.name);
+            }
+        }
+        visit::visit_item_contract(self, contract);
+    }
+
+    fn visit_item_function(&mut self, function: &'ast ItemFunction) {
+        for attr in function.attributes.iter() {
+            if let FunctionAttribute::Modifier(modifier) = attr {
+                self.visit_path(&modifier.name);
+            }
+        }
+        visit::visit_item_function(self, function);
+    }
+}
+
+/// Names made visible by importing a file globally, including the ones it imports globally itself
+fn exported_names(
+    files: &[SolidFile],
+    resolver: &ImportResolver,
+    from: &Path,
+    import: &str,
+    visited: &mut Vec<PathBuf>,
+) -> Vec<String> {
+    let Some(path) = resolver.resolve(from, import) else {
+        return vec![];
+    };
+    if visited.contains(&path) {
+        return vec![];
+    }
+    visited.push(path.clone());
+
+    let parsed: File;
+    let ast = match find_file(files, &path.to_string_lossy()) {
+        Some(file) => &file.data,
+        None => {
+            let Some(ast) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| extract_ast_from_content(&content).ok())
+            else {
+                return vec![];
+            };
+            parsed = ast;
+            &parsed
+        }
+    };
+
+    let mut names = Vec::new();
+    for item in &ast.items {
+        let Item::Import(import) = item else {
+            names.extend(item.name().map(|name| name.to_string()));
+            continue;
+        };
+        match &import.path {
+            ImportPath::Plain(plain) => match &plain.alias {
+                Some(alias) => names.push(alias.alias.to_string()),
+                None => names.extend(exported_names(
+                    files,
+                    resolver,
+                    &path,
+                    &plain.path.value(),
+                    visited,
+                )),
+            },
+            ImportPath::Aliases(aliases) => {
+                for (name, alias) in aliases.imports.iter() {
+                    names.push(
+                        alias
+                            .as_ref()
+                            .map_or(name, |alias| &alias.alias)
+                            .to_string(),
+                    );
+                }
+            }
+            ImportPath::Glob(glob) => {
+                names.extend(glob.alias.as_ref().map(|alias| alias.alias.to_string()))
+            }
+        }
+    }
+    names
+}
+
+fn named_imports(
+    files: &[SolidFile],
+    resolver: &ImportResolver,
+    document: &Document,
+    range: Range,
+    diagnostics: &[Diagnostic],
+) -> Vec<CodeAction> {
+    let mut used = UsedNames::default();
+    let mut res = Vec::new();
+
+    for item in &document.file.data.items {
+        let Item::Import(import) = item else {
+            continue;
+        };
+        let ImportPath::Plain(plain) = &import.path else {
+            continue;
+        };
+        let plain_range = node_range(plain);
+        if plain.alias.is_some() || !overlaps(&plain_range, &range) {
+            continue;
+        }
+        if used.names.is_empty() {
+            used.visit_file(&document.file.data);
+        }
+
+        let mut visited = Vec::new();
+        let mut names: Vec<String> = Vec::new();
+        for name in exported_names(
+            files,
+            resolver,
+            Path::new(&document.file.path),
+            &plain.path.value(),
+            &mut visited,
+        ) {
+            if used.names.contains(&name) && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        if names.is_empty() {
+            continue;
+        }
+
+        let path = document.text(node_range(&plain.path));
+        res.push(document.action(
+            "Import only the used symbols".to_string(),
+            CodeActionKind::REFACTOR_REWRITE,
+            vec![TextEdit {
+                range: plain_range,
+                new_text: format!("{{{}}} from {}", names.join(", "), path),
+            }],
+            paired_diagnostics(diagnostics, NO_GLOBAL_IMPORT_RULE, &plain_range),
+        ));
+    }
+    res
+}
+
+/// Identifies a function by its name and parameter types
+fn signature(content: &str, function: &ItemFunction) -> Option<String> {
+    let types: Vec<String> = function
+        .arguments
+        .iter()
+        .map(|arg| {
+            let range = node_range(&arg.ty);
+            normalize(&content[offset(content, range.start)..offset(content, range.end)])
+        })
+        .collect();
+    Some(format!("{}({})", function.name.as_ref()?, types.join(",")))
+}
+
+/// Turns the declaration of an unimplemented function into the header of its implementation
+fn stub_header(content: &str, function: &ItemFunction) -> Option<String> {
+    let FunctionBody::Empty(semi) = &function.body else {
+        return None;
+    };
+    let start = position_from_line_column(function.kind.span().start());
+    let end = position_from_line_column(semi.span().start());
+    let declaration = &content[offset(content, start)..offset(content, end)];
+
+    let mut tokens: Vec<&str> = declaration
+        .split_whitespace()
+        .filter(|token| *token != "virtual")
+        .collect();
+    if !tokens.iter().any(|token| token.starts_with("override")) {
+        let position = tokens
+            .iter()
+            .position(|token| *token == "returns" || token.starts_with("returns("))
+            .unwrap_or(tokens.len());
+        tokens.insert(position, "override");
+    }
+    Some(tokens.join(" "))
+}
+
+fn implement_missing_functions(
+    files: &[SolidFile],
+    document: &Document,
+    range: Range,
+) -> Vec<CodeAction> {
+    let Some(contract) = contracts(document.file).find(|contract| {
+        matches!(
+            contract.contract.kind,
+            ContractKind::Contract(_) | ContractKind::AbstractContract(..)
+        ) && range_contains(&contract_range(contract.contract), &range.start)
+    }) else {
+        return vec![];
+    };
+    let ancestors = ancestors(files, contract);
+    let hierarchy: Vec<&ContractRef> = std::iter::once(&contract).chain(&ancestors).collect();
+
+    let mut implemented: HashSet<String> = HashSet::new();
+    for base in &hierarchy {
+        for function in base.functions() {
+            if let FunctionBody::Block(_) = function.function.body {
+                implemented.extend(signature(&base.file.content, function.function));
+            }
+        }
+    }
+
+    let mut stubs = Vec::new();
+    for base in &ancestors {
+        for function in base.functions() {
+            if !matches!(function.function.kind, FunctionKind::Function(_)) {
+                continue;
+            }
+            let Some(signature) = signature(&base.file.content, function.function) else {
+                continue;
+            };
+            if !implemented.insert(signature) {
+                continue;
+            }
+            stubs.extend(stub_header(&base.file.content, function.function));
+        }
+    }
+    if stubs.is_empty() {
+        return vec![];
+    }
+
+    let item = contract.contract;
+    let open = position_from_line_column(item.brace_token.span.open().end());
+    let close = position_from_line_column(item.brace_token.span.close().start());
+    let contract_indentation = document.indentation(open.line);
+    let member_indentation = format!("{}    ", contract_indentation);
+    let stubs: Vec<String> = stubs
+        .iter()
+        .map(|header| format!("{}{} {{}}\n", member_indentation, header))
+        .collect();
+    let before_close = document.text(Range {
+        start: Position {
+            line: close.line,
+            character: 0,
+        },
+        end: close,
+    });
+
+    let (position, new_text) = if close.line != open.line && before_close.trim().is_empty() {
+        let separator = if item.body.is_empty() { "" } else { "\n" };
+        (
+            Position {
+                line: close.line,
+                character: 0,
+            },
+            format!("{}{}", separator, stubs.join("\n")),
+        )
+    } else {
+        (
+            close,
+            format!("\n{}{}", stubs.join("\n"), contract_indentation),
+        )
+    };
+    vec![document.action(
+        format!(
+            "Implement {} missing function{}",
+            stubs.len(),
+            if stubs.len() > 1 { "s" } else { "" }
+        ),
+        CodeActionKind::REFACTOR_REWRITE,
+        vec![TextEdit {
+            range: Range {
+                start: position,
+                end: position,
+            },
+            new_text,
+        }],
+        vec![],
+    )]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{project, solid_file};
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> Range {
+        Range {
+            start: Position {
+                line: start.0,
+                character: start.1,
+            },
+            end: Position {
+                line: end.0,
+                character: end.1,
+            },
+        }
+    }
+
+    fn actions(files: &[SolidFile], range: Range, diagnostics: Vec<Diagnostic>) -> Vec<CodeAction> {
+        let uri = Url::from_file_path(&files[0].path).unwrap();
+        let resolver = ImportResolver::from_root(Path::new(&files[0].path).parent().unwrap());
+        let context = CodeActionContext {
+            diagnostics,
+            only: None,
+            trigger_kind: None,
+        };
+        code_actions(files, &resolver, &uri, None, range, &context)
+            .into_iter()
+            .filter_map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) => Some(action),
+                CodeActionOrCommand::Command(_) => None,
+            })
+            .collect()
+    }
+
+    /// Applies the edits of an action to the content of the file it was computed for
+    fn apply(file: &SolidFile, action: &CodeAction) -> String {
+        let changes = action.edit.as_ref().unwrap().changes.as_ref().unwrap();
+        let mut edits = changes.values().next().unwrap().clone();
+        edits.sort_by_key(|edit| edit.range.start);
+        let mut content = file.content.clone();
+        for edit in edits.iter().rev() {
+            let start = offset(&file.content, edit.range.start);
+            let end = offset(&file.content, edit.range.end);
+            content.replace_range(start..end, &edit.new_text);
+        }
+        content
+    }
+
+    fn find<'a>(actions: &'a [CodeAction], title: &str) -> &'a CodeAction {
+        actions
+            .iter()
+            .find(|action| action.title.starts_with(title))
+            .unwrap_or_else(|| panic!("no action starting with {:?}", title))
+    }
+
+    #[test]
+    fn test_require_to_custom_error() {
+        let root = project("code-actions-require");
+        let file = solid_file(
+            root.join("src/Test.sol"),
+            "contract Test {\n    function f(uint a) public {\n        require(a > 0 && a < 10, \"Test: invalid amount\");\n    }\n}\n",
+        );
+        let diagnostic = Diagnostic {
+            range: range((2, 8), (2, 56)),
+            code: Some(NumberOrString::String(CUSTOM_ERRORS_RULE.to_string())),
+            ..Diagnostic::default()
+        };
+        let files = [file];
+        let actions = actions(&files, range((2, 10), (2, 10)), vec![diagnostic]);
+
+        let action = find(&actions, "Replace require with custom error InvalidAmount");
+        assert_eq!(action.kind, Some(CodeActionKind::QUICKFIX));
+        assert_eq!(action.diagnostics.as_ref().unwrap().len(), 1);
+        assert_eq!(
+            apply(&files[0], action),
+            "contract Test {\n    error InvalidAmount();\n    function f(uint a) public {\n        if (!(a > 0 && a < 10)) revert InvalidAmount();\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_require_reuses_declared_error() {
+        let root = project("code-actions-require-declared");
+        let file = solid_file(
+            root.join("src/Test.sol"),
+            "contract Test {\n    error NotOwner();\n    function f() public {\n        require(!paused(), \"not owner\");\n    }\n}\n",
+        );
+        let files = [file];
+        let actions = actions(&files, range((3, 8), (3, 8)), vec![]);
+
+        let action = find(&actions, "Replace require with custom error NotOwner");
+        assert_eq!(action.kind, Some(CodeActionKind::REFACTOR_REWRITE));
+        assert_eq!(
+            apply(&files[0], action),
+            "contract Test {\n    error NotOwner();\n    function f() public {\n        if (paused()) revert NotOwner();\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_edits_apply_to_latest_text() {
+        let root = project("code-actions-latest-text");
+        let file = solid_file(
+            root.join("src/Test.sol"),
+            "contract Test {\n    function f() public {\n        require(!paused(), \"Test: paused\");\n    }\n}\n",
+        );
+        let latest = solid_file(
+            root.join("src/Test.sol"),
+            "// edited\ncontract Test {\n    function f() public {\n        require(!paused(), \"Test: paused\");\n    }\n}\n",
+        );
+        let uri = Url::from_file_path(&file.path).unwrap();
+        let resolver = ImportResolver::from_root(&root);
+        let context = CodeActionContext {
+            diagnostics: vec![],
+            only: None,
+            trigger_kind: None,
+        };
+        let files = [file];
+        let actions: Vec<CodeAction> = code_actions(
+            &files,
+            &resolver,
+            &uri,
+            Some(&latest.content),
+            range((3, 8), (3, 8)),
+            &context,
+        )
+        .into_iter()
+        .filter_map(|action| match action {
+            CodeActionOrCommand::CodeAction(action) => Some(action),
+            CodeActionOrCommand::Command(_) => None,
+        })
+        .collect();
+
+        let action = find(&actions, "Replace require with custom error Paused");
+        assert_eq!(
+            apply(&latest, action),
+            "// edited\ncontract Test {\n    error Paused();\n    function f() public {\n        if (paused()) revert Paused();\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_error_name() {
+        assert_eq!(
+            error_name("Ownable: caller is not the owner"),
+            "CallerIsNotTheOwner"
+        );
+        assert_eq!(error_name("insufficient-balance"), "InsufficientBalance");
+        assert_eq!(error_name("404"), "Error404");
+        assert_eq!(error_name(""), DEFAULT_ERROR_NAME);
+    }
+
+    #[test]
+    fn test_extract_local() {
+        let root = project("code-actions-extract");
+        let file = solid_file(
+            root.join("src/Test.sol"),
+            "contract Test {\n    uint256 total;\n    function f(uint256 a) public {\n        total = total + a * 2;\n    }\n}\n",
+        );
+        let files = [file];
+        let actions = actions(&files, range((3, 24), (3, 29)), vec![]);
+
+        let action = find(&actions, "Extract expression into a local variable");
+        assert_eq!(action.kind, Some(CodeActionKind::REFACTOR_EXTRACT));
+        assert_eq!(
+            apply(&files[0], action),
+            "contract Test {\n    uint256 total;\n    function f(uint256 a) public {\n        uint256 value = a * 2;\n        total = total + value;\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_extract_local_skips_loop_conditions_and_unknown_types() {
+        let root = project("code-actions-extract-skip");
+        let file = solid_file(
+            root.join("src/Test.sol"),
+            "contract Test {\n    function f(uint256 a) public {\n        for (uint256 i; i < a + 1; ++i) {}\n        g(h(a));\n    }\n}\n",
+        );
+        let files = [file];
+        let in_loop = actions(&files, range((2, 28), (2, 33)), vec![]);
+        assert!(in_loop
+            .iter()
+            .all(|action| !action.title.starts_with("Extract")));
+        let unknown = actions(&files, range((3, 10), (3, 14)), vec![]);
+        assert!(unknown
+            .iter()
+            .all(|action| !action.title.starts_with("Extract")));
+    }
+
+    #[test]
+    fn test_natspec_skeleton() {
+        let root = project("code-actions-natspec");
+        let file = solid_file(
+            root.join("src/Test.sol"),
+            "/// @title Test\ncontract Test {\n    function f(uint256 a, address to) public returns (bool ok) {}\n}\n",
+        );
+        let files = [file];
+        let actions = actions(&files, range((2, 14), (2, 14)), vec![]);
+
+        let action = find(&actions, "Add NatSpec documentation to f");
+        assert_eq!(
+            apply(&files[0], action),
+            "/// @title Test\ncontract Test {\n    /// @notice\n    /// @param a\n    /// @param to\n    /// @return ok\n    function f(uint256 a, address to) public returns (bool ok) {}\n}\n"
+        );
+        assert!(actions
+            .iter()
+            .all(|action| action.title != "Add NatSpec documentation to Test"));
+    }
+
+    #[test]
+    fn test_named_import() {
+        let root = project("code-actions-named-import");
+        fs::write(
+            root.join("src/Token.sol"),
+            "import \"./Base.sol\";\ninterface IToken {}\ncontract Token is Base {}\nerror Unused();\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/Base.sol"), "contract Base {}\n").unwrap();
+        let file = solid_file(
+            root.join("src/Test.sol"),
+            "import \"./Token.sol\";\ncontract Test is IToken {\n    Base base;\n}\n",
+        );
+        let diagnostic = Diagnostic {
+            range: range((0, 7), (0, 19)),
+            code: Some(NumberOrString::String(NO_GLOBAL_IMPORT_RULE.to_string())),
+            ..Diagnostic::default()
+        };
+        let files = [file];
+        let actions = actions(&files, range((0, 10), (0, 10)), vec![diagnostic]);
+
+        let action = find(&actions, "Import only the used symbols");
+        assert_eq!(action.kind, Some(CodeActionKind::QUICKFIX));
+        assert_eq!(
+            apply(&files[0], action),
+            "import {Base, IToken} from \"./Token.sol\";\ncontract Test is IToken {\n    Base base;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_implement_missing_functions() {
+        let root = project("code-actions-implement");
+        let interface = solid_file(
+            root.join("src/IToken.sol"),
+            "interface IToken {\n    function transfer(address to, uint256 amount)\n        external\n        returns (bool);\n    function total() external view returns (uint256);\n}\n",
+        );
+        let file = solid_file(
+            root.join("src/Test.sol"),
+            "contract Test is IToken {\n    function total() external view override returns (uint256) {\n        return 0;\n    }\n}\n",
+        );
+        let files = [file, interface];
+        let actions = actions(&files, range((0, 3), (0, 3)), vec![]);
+
+        let action = find(&actions, "Implement 1 missing function");
+        assert_eq!(
+            apply(&files[0], action),
+            "contract Test is IToken {\n    function total() external view override returns (uint256) {\n        return 0;\n    }\n\n    function transfer(address to, uint256 amount) external override returns (bool) {}\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_only_filters_kinds() {
+        let root = project("code-actions-only");
+        let file = solid_file(
+            root.join("src/Test.sol"),
+            "contract Test {\n    function f(uint a) public {\n        require(a > 0);\n    }\n}\n",
+        );
+        let uri = Url::from_file_path(&file.path).unwrap();
+        let context = CodeActionContext {
+            diagnostics: vec![],
+            only: Some(vec![CodeActionKind::REFACTOR_EXTRACT]),
+            trigger_kind: None,
+        };
+        let actions = code_actions(
+            &[file],
+            &ImportResolver::from_root(&root),
+            &uri,
+            None,
+            range((2, 10), (2, 10)),
+            &context,
+        );
+        assert!(actions.is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::utils::range_from_lint_range;
+use osmium_libs_lsp_server_wrapper::lsp_types::{
+    Diagnostic, DiagnosticSeverity, DocumentLink, NumberOrString, Url,
+};
+use solidhunter_lib::{imports::ImportResolver, linter::SolidFile};
+
+pub const IMPORT_NOT_FOUND_CODE: &str = "import-not-found";
+
+pub fn document_links(resolver: &ImportResolver, file: &SolidFile) -> Vec<DocumentLink> {
+    resolver
+        .resolve_file_imports(file)
+        .into_iter()
+        .filter_map(|import| {
+            let target = Url::from_file_path(import.resolved?).ok()?;
+            Some(DocumentLink {
+                range: range_from_lint_range(&import.range),
+                target: Some(target),
+                tooltip: Some(import.path),
+                data: None,
+            })
+        })
+        .collect()
+}
+
+pub fn import_diagnostics(resolver: &ImportResolver, file: &SolidFile) -> Vec<Diagnostic> {
+    resolver
+        .resolve_file_imports(file)
+        .into_iter()
+        .filter(|import| import.resolved.is_none())
+        .map(|import| {
+            let mut message = format!("Import not found: \"{}\"", import.path);
+            if let Some(remapping) = resolver.suggest_remapping(&import.path) {
+                message.push_str(&format!(
+                    ". Adding the remapping \"{}\" to remappings.txt would resolve it",
+                    remapping
+                ));
+            }
+            Diagnostic {
+                range: range_from_lint_range(&import.range),
+                severity: Some(DiagnosticSeverity::ERROR),
+                code: Some(NumberOrString::String(IMPORT_NOT_FOUND_CODE.to_string())),
+                code_description: None,
+                source: Some("osmium-solidity-linter".to_string()),
+                message,
+                related_information: None,
+                tags: None,
+                data: None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{self, solid_file};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn project(name: &str) -> PathBuf {
+        let root = test_utils::project(&format!("document-link-{}", name));
+        fs::create_dir_all(root.join("lib/openzeppelin-contracts/contracts/token")).unwrap();
+        fs::write(root.join("src/Other.sol"), "").unwrap();
+        fs::write(
+            root.join("lib/openzeppelin-contracts/contracts/token/ERC20.sol"),
+            "",
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn test_document_links() {
+        let root = project("links");
+        let resolver = ImportResolver::from_root(&root);
+        let file = solid_file(
+            root.join("src/Test.sol"),
+            "import \"./Other.sol\";\nimport \"./Missing.sol\";\n",
+        );
+
+        let links = document_links(&resolver, &file);
+        assert_eq!(links.len(), 1);
+        assert_eq!(
+            links[0].target,
+            Url::from_file_path(root.join("src/Other.sol")).ok()
+        );
+        assert_eq!(links[0].range.start.line, 0);
+        assert_eq!(links[0].range.start.character, 7);
+    }
+
+    #[test]
+    fn test_import_diagnostics_with_remapping_suggestion() {
+        let root = project("diagnostics");
+        let resolver = ImportResolver::from_root(&root);
+        let file = solid_file(
+            root.join("src/Test.sol"),
+            "import \"@openzeppelin/contracts/token/ERC20.sol\";\n",
+        );
+
+        let diags = import_diagnostics(&resolver, &file);
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0]
+            .message
+            .contains("@openzeppelin/=lib/openzeppelin-contracts/"));
+
+        fs::write(
+            root.join("remappings.txt"),
+            "@openzeppelin/=lib/openzeppelin-contracts/\n",
+        )
+        .unwrap();
+        let resolver = ImportResolver::from_root(&root);
+        assert!(import_diagnostics(&resolver, &file).is_empty());
+        assert_eq!(document_links(&resolver, &file).len(), 1);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::symbols::{contract_range, function_range};
+use crate::utils::range_from_line_columns;
+use osmium_libs_lsp_server_wrapper::lsp_types::{FoldingRange, FoldingRangeKind, Position, Range};
+use osmium_libs_solidity_ast_extractor::{
+    visit, FunctionBody, Item, ItemContract, ItemEnum, ItemFunction, ItemStruct, Spanned,
+    StmtAssembly, Visit,
+};
+use solidhunter_lib::linter::SolidFile;
+
+struct FoldingVisitor {
+    ranges: Vec<FoldingRange>,
+}
+
+impl FoldingVisitor {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    fn push(&mut self, range: Range, kind: Option<FoldingRangeKind>) {
+        push_folding_range(&mut self.ranges, range, kind);
+    }
+}
+
+impl<'ast> Visit<'ast> for FoldingVisitor {
+    fn visit_item_contract(&mut self, contract: &'ast ItemContract) {
+        self.push(contract_range(contract), None);
+        visit::visit_item_contract(self, contract);
+    }
+
+    fn visit_item_function(&mut self, function: &'ast ItemFunction) {
+        if let FunctionBody::Block(_) = function.body {
+            self.push(function_range(function), None);
+        }
+        visit::visit_item_function(self, function);
+    }
+
+    fn visit_item_struct(&mut self, strukt: &'ast ItemStruct) {
+        let range = range_from_line_columns(
+            strukt.struct_token.span().start(),
+            strukt.brace_token.span.join().end(),
+        );
+        self.push(range, None);
+        visit::visit_item_struct(self, strukt);
+    }
+
+    fn visit_item_enum(&mut self, enumm: &'ast ItemEnum) {
+        let range = range_from_line_columns(
+            enumm.enum_token.span().start(),
+            enumm.brace_token.span.join().end(),
+        );
+        self.push(range, None);
+        visit::visit_item_enum(self, enumm);
+    }
+
+    fn visit_stmt_asm(&mut self, asm: &'ast StmtAssembly) {
+        let span = asm.span();
+        self.push(range_from_line_columns(span.start(), span.end()), None);
+        visit::visit_stmt_asm(self, asm);
+    }
+}
+
+fn push_folding_range(
+    ranges: &mut Vec<FoldingRange>,
+    range: Range,
+    kind: Option<FoldingRangeKind>,
+) {
+    if range.end.line <= range.start.line {
+        return;
+    }
+    ranges.push(FoldingRange {
+        start_line: range.start.line,
+        start_character: Some(range.start.character),
+        end_line: range.end.line,
+        end_character: Some(range.end.character),
+        kind,
+        collapsed_text: None,
+    });
+}
+
+fn import_ranges(file: &SolidFile) -> Vec<FoldingRange> {
+    let mut res: Vec<FoldingRange> = Vec::new();
+    let mut group: Option<Range> = None;
+
+    for item in &file.data.items {
+        match item {
+            Item::Import(import) => {
+                let span = import.span();
+                let range = range_from_line_columns(span.start(), span.end());
+                group = match group {
+                    Some(group) => Some(Range {
+                        start: group.start,
+                        end: range.end,
+                    }),
+                    None => Some(range),
+                };
+            }
+            _ => {
+                if let Some(group) = group.take() {
+                    push_folding_range(&mut res, group, Some(FoldingRangeKind::Imports));
+                }
+            }
+        }
+    }
+    if let Some(group) = group {
+        push_folding_range(&mut res, group, Some(FoldingRangeKind::Imports));
+    }
+    res
+}
+
+/// Comments are not part of the AST, so they are retrieved from the raw content
+fn comment_ranges(content: &str) -> Vec<FoldingRange> {
+    let mut res: Vec<FoldingRange> = Vec::new();
+    let mut block_start: Option<(u32, u32)> = None;
+    let mut line_comments: Option<(u32, u32)> = None;
+
+    for (line_idx, line) in (0..).zip(content.lines()) {
+        let chars: Vec<char> = line.chars().collect();
+        let mut quote: Option<char> = None;
+        let mut has_line_comment = false;
+        let mut idx = 0;
+
+        while idx < chars.len() {
+            let current = chars[idx];
+            let next = chars.get(idx + 1).copied();
+
+            if let Some((start_line, start_character)) = block_start {
+                if current == '*' && next == Some('/') {
+                    let range = Range {
+                        start: Position::new(start_line, start_character),
+                        end: Position::new(line_idx, idx as u32 + 2),
+                    };
+                    push_folding_range(&mut res, range, Some(FoldingRangeKind::Comment));
+                    block_start = None;
+                    idx += 1;
+                }
+            } else if let Some(delimiter) = quote {
+                if current == '\\' {
+                    idx += 1;
+                } else if current == delimiter {
+                    quote = None;
+                }
+            } else if current == '"' || current == '\'' {
+                quote = Some(current);
+            } else if current == '/' && next == Some('*') {
+                block_start = Some((line_idx, idx as u32));
+                idx += 1;
+            } else if current == '/' && next == Some('/') {
+                has_line_comment = chars[..idx].iter().all(|c| c.is_whitespace());
+                break;
+            }
+            idx += 1;
+        }
+
+        line_comments = match (line_comments, has_line_comment) {
+            (Some((start, _)), true) => Some((start, line_idx)),
+            (None, true) => Some((line_idx, line_idx)),
+            (Some((start, end)), false) => {
+                push_line_comments(&mut res, start, end);
+                None
+            }
+            (None, false) => None,
+        };
+    }
+    if let Some((start, end)) = line_comments {
+        push_line_comments(&mut res, start, end);
+    }
+    res
+}
+
+fn push_line_comments(ranges: &mut Vec<FoldingRange>, start: u32, end: u32) {
+    if end > start {
+        ranges.push(FoldingRange {
+            start_line: start,
+            start_character: None,
+            end_line: end,
+            end_character: None,
+            kind: Some(FoldingRangeKind::Comment),
+            collapsed_text: None,
+        });
+    }
+}
+
+pub fn folding_ranges(file: &SolidFile) -> Vec<FoldingRange> {
+    let mut visitor = FoldingVisitor::new();
+    visitor.visit_file(&file.data);
+
+    let mut res = visitor.ranges;
+    res.append(&mut import_ranges(file));
+    res.append(&mut comment_ranges(&file.content));
+    res.sort_by_key(|range| (range.start_line, range.end_line));
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::solid_file;
+
+    #[test]
+    fn test_folding_ranges() {
+        let file = solid_file(
+            "/test.sol",
+            "import \"a.sol\";\nimport \"b.sol\";\n/**\n * @title Test\n */\ncontract Test {\n    struct S {\n        uint a;\n    }\n    function f() public {\n        assembly {\n            let x := 1\n        }\n    }\n}\n",
+        );
+        let ranges: Vec<(u32, u32, Option<FoldingRangeKind>)> = folding_ranges(&file)
+            .into_iter()
+            .map(|range| (range.start_line, range.end_line, range.kind))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                (0, 1, Some(FoldingRangeKind::Imports)),
+                (2, 4, Some(FoldingRangeKind::Comment)),
+                (5, 14, None),
+                (6, 8, None),
+                (9, 13, None),
+                (10, 12, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_comments_folding() {
+        let ranges = comment_ranges("// a\n// b\nuint x = 1; // c\n// d\n");
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].start_line, 0);
+        assert_eq!(ranges[0].end_line, 1);
+    }
+}
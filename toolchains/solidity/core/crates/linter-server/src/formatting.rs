@@ -0,0 +1,169 @@
+use osmium_libs_lsp_server_wrapper::lsp_types::{FormattingOptions, Position, Range, TextEdit};
+use solidity_formatter::{config::FormatterConfig, errors::FormatError, LineEdit};
+use std::path::Path;
+
+/// Reads the formatter configuration of the workspace, or follows the editor settings without one
+pub fn formatter_config(root: Option<&Path>, options: &FormattingOptions) -> FormatterConfig {
+    match root.map(|root| root.join(".solidhunter.json")) {
+        Some(path) if path.exists() => FormatterConfig::from_rules_file(&path),
+        _ => FormatterConfig {
+            tab_width: options.tab_size as usize,
+            ..FormatterConfig::default()
+        },
+    }
+}
+
+pub fn format_document(
+    content: &str,
+    config: &FormatterConfig,
+) -> Result<Vec<TextEdit>, FormatError> {
+    let formatted = solidity_formatter::format(content, config)?;
+    Ok(text_edits(
+        content,
+        solidity_formatter::edits(content, &formatted),
+    ))
+}
+
+pub fn format_range(
+    content: &str,
+    range: Range,
+    config: &FormatterConfig,
+) -> Result<Vec<TextEdit>, FormatError> {
+    let edits = solidity_formatter::format_range(
+        content,
+        range.start.line as usize,
+        range.end.line as usize,
+        config,
+    )?;
+    Ok(text_edits(content, edits))
+}
+
+/// Formats the block closed by a typed `}`, or the line ended by a typed `;`
+pub fn format_on_type(
+    content: &str,
+    position: Position,
+    ch: &str,
+    config: &FormatterConfig,
+) -> Result<Vec<TextEdit>, FormatError> {
+    let start_line = match ch {
+        "}" => block_start_line(content, position).unwrap_or(position.line),
+        _ => position.line,
+    };
+    let range = Range {
+        start: Position {
+            line: start_line,
+            character: 0,
+        },
+        end: position,
+    };
+    format_range(content, range, config)
+}
+
+/// Line of the `{` matching the `}` before a position
+fn block_start_line(content: &str, position: Position) -> Option<u32> {
+    let lines: Vec<&str> = content.split('\n').collect();
+    let mut depth = 0;
+    for line in (0..=position.line as usize).rev() {
+        let text = lines.get(line)?;
+        let chars: Vec<char> = if line == position.line as usize {
+            text.chars().take(position.character as usize).collect()
+        } else {
+            text.chars().collect()
+        };
+        for c in chars.into_iter().rev() {
+            match c {
+                '}' => depth += 1,
+                '{' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(line as u32);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// Converts line edits, clamping the ones which reach the end of the document to its last line
+fn text_edits(content: &str, edits: Vec<LineEdit>) -> Vec<TextEdit> {
+    let lines: Vec<&str> = content.split('\n').collect();
+    let position = |line: usize| {
+        if line < lines.len() {
+            Position {
+                line: line as u32,
+                character: 0,
+            }
+        } else {
+            Position {
+                line: lines.len() as u32 - 1,
+                character: lines[lines.len() - 1].chars().count() as u32,
+            }
+        }
+    };
+    edits
+        .into_iter()
+        .map(|edit| TextEdit {
+            range: Range {
+                start: position(edit.start_line),
+                end: position(edit.end_line),
+            },
+            new_text: edit.new_text,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "contract A {\nfunction f() public {\nuint a=1;\n}\n}";
+
+    fn apply(content: &str, edits: &[TextEdit]) -> String {
+        let lines: Vec<&str> = content.split('\n').collect();
+        let offset = |position: Position| {
+            lines[..position.line as usize]
+                .iter()
+                .map(|line| line.len() + 1)
+                .sum::<usize>()
+                + position.character as usize
+        };
+        let mut res = content.to_string();
+        for edit in edits.iter().rev() {
+            res.replace_range(
+                offset(edit.range.start)..offset(edit.range.end),
+                &edit.new_text,
+            );
+        }
+        res
+    }
+
+    #[test]
+    fn formats_the_whole_document() {
+        let edits = format_document(CONTENT, &FormatterConfig::default()).unwrap();
+        assert_eq!(
+            apply(CONTENT, &edits),
+            "contract A {\n    function f() public {\n        uint a = 1;\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn formats_the_typed_block_only() {
+        let content = "contract A {\n    function g() public {\nuint b=2;\n    }\n\n    function f() public {\nuint a=1;\n    }\n}\n";
+        let position = Position {
+            line: 7,
+            character: 5,
+        };
+        let edits = format_on_type(content, position, "}", &FormatterConfig::default()).unwrap();
+        assert_eq!(
+            apply(content, &edits),
+            "contract A {\n    function g() public {\nuint b=2;\n    }\n\n    function f() public {\n        uint a = 1;\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn reports_invalid_code() {
+        assert!(format_document("contract A {", &FormatterConfig::default()).is_err());
+    }
+}
@@ -0,0 +1,589 @@
+use crate::document_link;
+use crate::progress::ProgressReporter;
+use crate::pull_diagnostics::{CachedDiagnostics, DiagnosticsCache};
+use crate::symbols::find_file;
+use crate::utils::{filepath_from_uri, range_from_lint_range};
+use osmium_libs_lsp_server_wrapper::lsp_types::{
+    Diagnostic, DiagnosticSeverity, DiagnosticTag, NumberOrString, Url,
+};
+use solidhunter_lib::{
+    imports::ImportResolver,
+    linter::{SolidFile, SolidLinter},
+    rules::unused::UNUSED_CODE_RULES,
+    types::{LintDiag, Severity},
+};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Delay without edits after which a changed document is linted
+pub const CHANGE_DEBOUNCE: Duration = Duration::from_millis(300);
+
+type Query = Box<dyn FnOnce(&[SolidFile]) + Send>;
+type Publisher = Box<dyn Fn(Url, Vec<Diagnostic>) + Send>;
+
+enum Task {
+    /// Lints a document once the delay elapsed, unless a newer version arrives before
+    Lint {
+        uri: Url,
+        content: String,
+        delay: Duration,
+    },
+    /// Lints a document right away, if its diagnostics are not cached yet
+    Pull {
+        path: String,
+        reply: Sender<Option<CachedDiagnostics>>,
+    },
+    /// Runs a closure on the parsed documents
+    Query(Query),
+    /// Queues files which are not opened, to be linted to index their symbols
+    Index {
+        files: Vec<PathBuf>,
+        progress: Option<ProgressReporter>,
+    },
+    /// Reloads the linter configuration, then lints the parsed documents again
+    Reload { progress: Option<ProgressReporter> },
+    /// Drops the pending lint and the cached diagnostics of a closed document
+    Close { path: String },
+}
+
+struct PendingLint {
+    uri: Url,
+    content: String,
+    deadline: Instant,
+}
+
+/// State shared between the language server and the lint worker
+#[derive(Clone, Default)]
+pub struct SharedState {
+    pub diagnostics: Arc<Mutex<DiagnosticsCache>>,
+    pub import_resolver: Arc<RwLock<ImportResolver>>,
+}
+
+/// Handle to the thread owning the linter and the parsed documents.
+/// The AST cannot be sent across threads, so every access to the documents goes through this worker.
+pub struct LintWorker {
+    sender: Sender<Task>,
+    handle: JoinHandle<()>,
+}
+
+impl LintWorker {
+    pub fn spawn(
+        root: Option<PathBuf>,
+        state: SharedState,
+        publish: impl Fn(Url, Vec<Diagnostic>) + Send + 'static,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            Worker {
+                linter: create_linter(root.as_deref()),
+                root,
+                state,
+                publish: Box::new(publish),
+                pending: HashMap::new(),
+                indexing: VecDeque::new(),
+                index_progress: None,
+            }
+            .run(receiver);
+        });
+        Self { sender, handle }
+    }
+
+    /// Schedules a lint of the document, replacing any pending lint of a previous version
+    pub fn lint(&self, uri: Url, content: String, delay: Duration) {
+        let _ = self.sender.send(Task::Lint {
+            uri,
+            content,
+            delay,
+        });
+    }
+
+    /// Returns the diagnostics of a document, linting it first if needed
+    pub fn pull(&self, path: &str) -> Option<CachedDiagnostics> {
+        let (reply, receiver) = mpsc::channel();
+        self.sender
+            .send(Task::Pull {
+                path: path.to_string(),
+                reply,
+            })
+            .ok()?;
+        receiver.recv().ok()?
+    }
+
+    pub fn query<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&[SolidFile]) -> T + Send + 'static,
+    ) -> Option<T> {
+        let (reply, receiver) = mpsc::channel();
+        let query: Query = Box::new(move |files| {
+            let _ = reply.send(f(files));
+        });
+        self.sender.send(Task::Query(query)).ok()?;
+        receiver.recv().ok()
+    }
+
+    pub fn index(&self, files: Vec<PathBuf>, progress: Option<ProgressReporter>) {
+        let _ = self.sender.send(Task::Index { files, progress });
+    }
+
+    pub fn reload(&self, progress: Option<ProgressReporter>) {
+        let _ = self.sender.send(Task::Reload { progress });
+    }
+
+    pub fn close(&self, path: &str) {
+        let _ = self.sender.send(Task::Close {
+            path: path.to_string(),
+        });
+    }
+
+    /// Stops the worker once the tasks already sent are processed
+    pub fn stop(self) {
+        drop(self.sender);
+        let _ = self.handle.join();
+    }
+}
+
+/// Creates a linter using the `.solidhunter.json` configuration of the project if there is one,
+/// or the default rules otherwise
+fn create_linter(root: Option<&Path>) -> SolidLinter {
+    let root = match root {
+        Some(root) => root,
+        None => return SolidLinter::new_fileless(),
+    };
+    let config = root.join(".solidhunter.json");
+    let mut linter = SolidLinter::new();
+    linter.set_root(root);
+    if !config.is_file() || linter.initialize_rules(&config.to_string_lossy()).is_err() {
+        linter.initialize_default_rules();
+    }
+    let root = root.to_string_lossy().to_string();
+    if let Err(e) = linter.initialize_excluded_files(None, &vec![root]) {
+        eprintln!("Cannot load excluded files: {}", e);
+    }
+    linter
+}
+
+struct Worker {
+    linter: SolidLinter,
+    root: Option<PathBuf>,
+    state: SharedState,
+    publish: Publisher,
+    pending: HashMap<String, PendingLint>,
+    /// Files left to index, one being linted whenever no other task is waiting
+    indexing: VecDeque<PathBuf>,
+    index_progress: Option<ProgressReporter>,
+}
+
+impl Worker {
+    fn run(mut self, receiver: Receiver<Task>) {
+        loop {
+            self.lint_due(&receiver);
+            // Requests and edits go before the indexing, which may take long on large projects
+            if !self.indexing.is_empty() {
+                match receiver.try_recv() {
+                    Ok(task) => self.handle(task),
+                    Err(TryRecvError::Empty) => self.index_next(),
+                    Err(TryRecvError::Disconnected) => {
+                        while !self.indexing.is_empty() {
+                            self.index_next();
+                        }
+                        break;
+                    }
+                }
+                continue;
+            }
+            let next_deadline = self.pending.values().map(|pending| pending.deadline).min();
+            let task = match next_deadline {
+                Some(deadline) => {
+                    match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                    {
+                        Ok(task) => Some(task),
+                        Err(RecvTimeoutError::Timeout) => None,
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+                None => match receiver.recv() {
+                    Ok(task) => Some(task),
+                    Err(_) => break,
+                },
+            };
+            if let Some(task) = task {
+                self.handle(task);
+            }
+        }
+    }
+
+    fn handle(&mut self, task: Task) {
+        match task {
+            Task::Lint {
+                uri,
+                content,
+                delay,
+            } => {
+                let path = filepath_from_uri(&uri);
+                self.pending.insert(
+                    path,
+                    PendingLint {
+                        uri,
+                        content,
+                        deadline: Instant::now() + delay,
+                    },
+                );
+            }
+            Task::Pull { path, reply } => {
+                let cached = match self.pending.remove(&path) {
+                    Some(pending) => self.lint(&path, Some(&pending.content)),
+                    None => {
+                        let cached = self.state.diagnostics.lock().unwrap().get(&path).cloned();
+                        cached.or_else(|| self.lint(&path, None))
+                    }
+                };
+                let _ = reply.send(cached);
+            }
+            Task::Query(query) => query(self.linter.get_documents()),
+            Task::Index { files, progress } => {
+                self.indexing.extend(files);
+                if let Some(previous) = std::mem::replace(&mut self.index_progress, progress) {
+                    previous.end();
+                }
+                if self.indexing.is_empty() {
+                    self.end_indexing();
+                }
+            }
+            Task::Reload { progress } => self.reload(progress),
+            Task::Close { path } => {
+                self.pending.remove(&path);
+                self.state.diagnostics.lock().unwrap().remove(&path);
+            }
+        }
+    }
+
+    /// Lints the next file to index, unless it is opened as its content may differ on the disk
+    fn index_next(&mut self) {
+        let Some(file) = self.indexing.pop_front() else {
+            return;
+        };
+        let path = file.to_string_lossy().to_string();
+        let parsed = self
+            .linter
+            .get_documents()
+            .iter()
+            .any(|document| document.path == path);
+        if !parsed && !self.pending.contains_key(&path) {
+            if let (Some(cached), Ok(uri)) = (self.lint(&path, None), Url::from_file_path(&file)) {
+                (self.publish)(uri, cached.items);
+            }
+        }
+        if let Some(progress) = self.index_progress.as_mut() {
+            progress.advance();
+        }
+        if self.indexing.is_empty() {
+            self.end_indexing();
+        }
+    }
+
+    fn end_indexing(&mut self) {
+        if let Some(progress) = self.index_progress.take() {
+            progress.end();
+        }
+    }
+
+    fn reload(&mut self, mut progress: Option<ProgressReporter>) {
+        let documents: Vec<(String, String)> = self
+            .linter
+            .get_documents()
+            .iter()
+            .map(|document| (document.path.clone(), document.content.clone()))
+            .collect();
+        self.linter = create_linter(self.root.as_deref());
+        for (path, content) in documents {
+            if let (Some(cached), Some(uri)) = (
+                self.lint(&path, Some(&content)),
+                Url::from_file_path(&path).ok(),
+            ) {
+                (self.publish)(uri, cached.items);
+            }
+            if let Some(progress) = progress.as_mut() {
+                progress.advance();
+            }
+        }
+        if let Some(progress) = progress {
+            progress.end();
+        }
+    }
+
+    /// Lints the documents whose delay elapsed.
+    /// Results are dropped when a newer version of the document arrived during the run,
+    /// or when the document was closed.
+    fn lint_due(&mut self, receiver: &Receiver<Task>) {
+        let now = Instant::now();
+        let due: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| pending.deadline <= now)
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        for path in due {
+            let pending = match self.pending.remove(&path) {
+                Some(pending) => pending,
+                None => continue,
+            };
+            let cached = self.lint(&path, Some(&pending.content));
+
+            while let Ok(task) = receiver.try_recv() {
+                self.handle(task);
+            }
+            if self.pending.contains_key(&path) {
+                continue;
+            }
+            let Some(cached) = cached else {
+                continue;
+            };
+            // The diagnostics of a document closed during the run are no longer cached
+            if self.state.diagnostics.lock().unwrap().get(&path).is_some() {
+                (self.publish)(pending.uri, cached.items);
+            }
+        }
+    }
+
+    /// Lints a document, reading it from the disk when its content is not given,
+    /// and caches the resulting diagnostics
+    fn lint(&mut self, filepath: &str, content: Option<&str>) -> Option<CachedDiagnostics> {
+        let diags_res = match content {
+            Some(content) => self.linter.parse_content(filepath, content),
+            None => self.linter.parse_file(filepath.to_string()),
+        };
+
+        match diags_res {
+            Ok(diags) => {
+                let mut items: Vec<Diagnostic> = diags
+                    .diags
+                    .iter()
+                    .map(|d| diagnostic_from_lintdiag(d.clone()))
+                    .collect();
+                if let Some(file) = find_file(self.linter.get_documents(), filepath) {
+                    items.append(&mut document_link::import_diagnostics(
+                        &self.state.import_resolver.read().unwrap(),
+                        file,
+                    ));
+                }
+                let mut cache = self.state.diagnostics.lock().unwrap();
+                Some(
+                    cache
+                        .insert(filepath, &diags.source_file_content, items)
+                        .clone(),
+                )
+            }
+            Err(e) => {
+                eprintln!("Cannot lint {}: {}", filepath, e);
+                None
+            }
+        }
+    }
+}
+
+fn diagnostic_from_lintdiag(diag: LintDiag) -> Diagnostic {
+    let severity = match diag.severity {
+        Severity::ERROR => DiagnosticSeverity::ERROR,
+        Severity::WARNING => DiagnosticSeverity::WARNING,
+        Severity::INFO => DiagnosticSeverity::INFORMATION,
+        Severity::HINT => DiagnosticSeverity::HINT,
+    };
+    let tags = UNUSED_CODE_RULES
+        .contains(&diag.id.as_str())
+        .then(|| vec![DiagnosticTag::UNNECESSARY]);
+    Diagnostic {
+        range: range_from_lint_range(&diag.range),
+        severity: Some(severity),
+        code: Some(NumberOrString::String(diag.id)),
+        code_description: None,
+        source: Some("osmium-solidity-linter".to_string()),
+        message: diag.message,
+        related_information: None,
+        tags,
+        data: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spawn_worker() -> (LintWorker, Receiver<(Url, Vec<Diagnostic>)>) {
+        let (sender, receiver) = mpsc::channel();
+        let worker = LintWorker::spawn(None, SharedState::default(), move |uri, diags| {
+            let _ = sender.send((uri, diags));
+        });
+        (worker, receiver)
+    }
+
+    #[test]
+    fn test_outdated_versions_are_not_linted() {
+        let (worker, published) = spawn_worker();
+        let uri = Url::parse("file:///test.sol").unwrap();
+        for idx in 0..5 {
+            worker.lint(
+                uri.clone(),
+                format!("contract Test{} {{}}", idx),
+                CHANGE_DEBOUNCE,
+            );
+        }
+
+        let (published_uri, _) = published.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(published_uri, uri);
+        let names = worker
+            .query(|files| {
+                files
+                    .iter()
+                    .map(|file| file.content.clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap();
+        assert_eq!(names, vec!["contract Test4 {}".to_string()]);
+
+        worker.stop();
+        assert!(published.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_index_skips_opened_documents() {
+        let root = std::env::temp_dir().join("osmium-lint-worker-index");
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("A.sol"), "contract A {}").unwrap();
+        std::fs::write(root.join("B.sol"), "contract B {}").unwrap();
+
+        let (worker, published) = spawn_worker();
+        let opened = Url::from_file_path(root.join("B.sol")).unwrap();
+        worker.lint(opened, "contract Opened {}".to_string(), Duration::ZERO);
+        worker.index(vec![root.join("A.sol"), root.join("B.sol")], None);
+        for _ in 0..2 {
+            published.recv_timeout(Duration::from_secs(5)).unwrap();
+        }
+
+        let mut contents = worker
+            .query(|files| {
+                files
+                    .iter()
+                    .map(|file| file.content.clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap();
+        contents.sort();
+        assert_eq!(contents, vec!["contract A {}", "contract Opened {}"]);
+
+        worker.stop();
+        assert_eq!(published.try_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_queries_are_not_blocked_by_indexing() {
+        let root = std::env::temp_dir().join("osmium-lint-worker-index-queries");
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        let files: Vec<PathBuf> = (0..50)
+            .map(|idx| {
+                let file = root.join(format!("C{}.sol", idx));
+                std::fs::write(&file, format!("contract C{} {{}}", idx)).unwrap();
+                file
+            })
+            .collect();
+
+        // The indexing is paused on each published file until the gate opens or closes
+        let (published_sender, published) = mpsc::channel();
+        let (gate, gate_receiver) = mpsc::channel::<()>();
+        let worker = LintWorker::spawn(None, SharedState::default(), move |uri, _| {
+            let _ = published_sender.send(uri);
+            let _ = gate_receiver.recv();
+        });
+        worker.index(files, None);
+        published.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        let (reply, indexed) = mpsc::channel();
+        let query: Query = Box::new(move |files| {
+            let _ = reply.send(files.len());
+        });
+        worker.sender.send(Task::Query(query)).unwrap();
+        gate.send(()).unwrap();
+        assert_eq!(indexed.recv_timeout(Duration::from_secs(5)).unwrap(), 1);
+
+        drop(gate);
+        worker.stop();
+        assert_eq!(published.try_iter().count(), 49);
+    }
+
+    #[test]
+    fn test_close_drops_pending_lint_and_diagnostics() {
+        let state = SharedState::default();
+        let worker = LintWorker::spawn(None, state.clone(), |_, _| {});
+        let uri = Url::parse("file:///test.sol").unwrap();
+        worker.lint(uri.clone(), "contract A {}".to_string(), Duration::ZERO);
+        assert!(worker.pull("/test.sol").is_some());
+        worker.lint(uri, "contract B {}".to_string(), Duration::from_secs(60));
+        worker.close("/test.sol");
+
+        // Without a pending lint nor cached diagnostics, the missing file is linted from the disk
+        assert!(worker.pull("/test.sol").is_none());
+        assert!(state.diagnostics.lock().unwrap().get("/test.sol").is_none());
+        worker.stop();
+    }
+
+    #[test]
+    fn test_pull_skips_debounce() {
+        let (worker, published) = spawn_worker();
+        let uri = Url::parse("file:///test.sol").unwrap();
+        worker.lint(uri, "contract Test {}".to_string(), Duration::from_secs(60));
+
+        assert!(worker.pull("/test.sol").is_some());
+        worker.stop();
+        assert!(published.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_pull_reflects_debounced_edit() {
+        let (worker, _published) = spawn_worker();
+        let uri = Url::parse("file:///test.sol").unwrap();
+        worker.lint(uri.clone(), "contract A {}".to_string(), Duration::ZERO);
+        let before = worker.pull("/test.sol").unwrap();
+
+        worker.lint(uri, "contract B {}".to_string(), CHANGE_DEBOUNCE);
+        let after = worker.pull("/test.sol").unwrap();
+
+        let mut cache = DiagnosticsCache::new();
+        let expected = cache.insert("/test.sol", "contract B {}", vec![]);
+        assert_ne!(after.result_id, before.result_id);
+        assert_eq!(after.result_id, expected.result_id);
+        worker.stop();
+    }
+
+    #[test]
+    fn test_unused_code_is_tagged_unnecessary() {
+        let diag = |id: &str| LintDiag {
+            range: solidhunter_lib::types::Range {
+                start: solidhunter_lib::types::Position {
+                    line: 1,
+                    character: 0,
+                },
+                end: solidhunter_lib::types::Position {
+                    line: 1,
+                    character: 4,
+                },
+            },
+            severity: Severity::WARNING,
+            code: None,
+            source: None,
+            message: String::new(),
+            id: id.to_string(),
+            uri: "test.sol".to_string(),
+        };
+
+        let unused = diagnostic_from_lintdiag(diag("unused-variable"));
+        assert_eq!(unused.tags, Some(vec![DiagnosticTag::UNNECESSARY]));
+        let other = diagnostic_from_lintdiag(diag("reentrancy"));
+        assert_eq!(other.tags, None);
+    }
+}
@@ -1,23 +1,159 @@
+use clap::Parser;
 use osmium_libs_lsp_server_wrapper::{
-    lsp_types::*, Client, LanguageServer, LspStdioServer, Result,
+    lsp_types::*, socket_addr, Client, LanguageServer, LspStdioServer, Result, Transport,
 };
-use solidhunter_lib::{linter::SolidLinter, types::LintDiag};
-use std::{cell::RefCell, rc::Rc};
+use solidhunter_lib::{imports::ImportResolver, linter::SolidFile};
+use solidity_formatter::{config::FormatterConfig, errors::FormatError};
+use std::{
+    collections::HashMap,
+    net::TcpListener,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    thread,
+    time::Duration,
+};
+
+mod call_hierarchy;
+mod code_actions;
+mod document_link;
+mod folding_range;
+mod formatting;
+mod lint_worker;
+mod progress;
+mod pull_diagnostics;
+mod selection_range;
+mod status;
+mod symbols;
+#[cfg(test)]
+mod test_utils;
+mod type_hierarchy;
+mod utils;
+use lint_worker::{LintWorker, SharedState, CHANGE_DEBOUNCE};
+use progress::ProgressReporter;
+use pull_diagnostics::{CachedDiagnostics, DiagnosticsCache};
+use status::{StatusNotification, StatusParams};
+use symbols::find_file;
+use utils::filepath_from_uri;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Osmium Solidity linter language server", long_about = None)]
+#[group(multiple = false)]
+struct Args {
+    #[arg(long = "stdio", help = "Communicate over stdin and stdout (default)")]
+    stdio: bool,
+
+    #[arg(
+        long = "port",
+        help = "Listen on a TCP port or address, serving every client which connects to it"
+    )]
+    port: Option<String>,
+
+    #[arg(
+        long = "socket",
+        help = "Connect to a TCP port or address on which the client listens"
+    )]
+    socket: Option<String>,
+
+    #[arg(
+        long = "pipe",
+        help = "Connect to a named pipe or Unix domain socket on which the client listens"
+    )]
+    pipe: Option<PathBuf>,
+}
 
 struct Backend {
-    connection: Rc<RefCell<Client>>,
-    linter: RefCell<Option<SolidLinter>>,
+    connection: Client,
+    worker: Mutex<Option<LintWorker>>,
+    state: SharedState,
+    root: RwLock<Option<PathBuf>>,
+    pull_diagnostics: Arc<AtomicBool>,
+    work_done_progress: AtomicBool,
+    /// Whether the client accepts the dynamic registration of the type hierarchy
+    type_hierarchy_registration: AtomicBool,
+    /// Latest text of the open documents, as the worker only gets it once the debounce expires
+    texts: Mutex<HashMap<Url, String>>,
 }
 
 impl LanguageServer for Backend {
-    fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+    fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
         eprintln!("starting example main loop");
+        let root_uri = params
+            .workspace_folders
+            .and_then(|folders| folders.first().map(|folder| folder.uri.clone()))
+            .or(params.root_uri);
+        if let Some(root) = root_uri.and_then(|uri| uri.to_file_path().ok()) {
+            *self.state.import_resolver.write().unwrap() = ImportResolver::from_root(&root);
+            *self.root.write().unwrap() = Some(root);
+        }
+        self.work_done_progress.store(
+            params
+                .capabilities
+                .window
+                .as_ref()
+                .and_then(|window| window.work_done_progress)
+                .unwrap_or(false),
+            Ordering::Relaxed,
+        );
+        self.type_hierarchy_registration.store(
+            params
+                .capabilities
+                .text_document
+                .as_ref()
+                .and_then(|text_document| text_document.type_hierarchy.as_ref())
+                .and_then(|type_hierarchy| type_hierarchy.dynamic_registration)
+                .unwrap_or(false),
+            Ordering::Relaxed,
+        );
+        // Diagnostics are only pushed to clients which cannot pull them
+        self.pull_diagnostics.store(
+            params
+                .capabilities
+                .text_document
+                .and_then(|text_document| text_document.diagnostic)
+                .is_some(),
+            Ordering::Relaxed,
+        );
         Ok(InitializeResult {
             server_info: None,
             capabilities: ServerCapabilities {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
                     TextDocumentSyncKind::FULL,
                 )),
+                call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
+                folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+                selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Options(
+                    CodeActionOptions {
+                        code_action_kinds: Some(vec![
+                            CodeActionKind::QUICKFIX,
+                            CodeActionKind::REFACTOR_EXTRACT,
+                            CodeActionKind::REFACTOR_REWRITE,
+                        ]),
+                        resolve_provider: Some(false),
+                        work_done_progress_options: WorkDoneProgressOptions::default(),
+                    },
+                )),
+                document_formatting_provider: Some(OneOf::Left(true)),
+                document_range_formatting_provider: Some(OneOf::Left(true)),
+                document_on_type_formatting_provider: Some(DocumentOnTypeFormattingOptions {
+                    first_trigger_character: "}".to_string(),
+                    more_trigger_character: Some(vec![";".to_string()]),
+                }),
+                document_link_provider: Some(DocumentLinkOptions {
+                    resolve_provider: Some(false),
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                }),
+                diagnostic_provider: Some(DiagnosticServerCapabilities::Options(
+                    DiagnosticOptions {
+                        identifier: Some("osmium-solidity-linter".to_string()),
+                        inter_file_dependencies: true,
+                        workspace_diagnostics: true,
+                        work_done_progress_options: WorkDoneProgressOptions::default(),
+                    },
+                )),
                 ..ServerCapabilities::default()
             },
         })
@@ -25,117 +161,442 @@ impl LanguageServer for Backend {
 
     fn initialized(&self, _: InitializedParams) {
         self.connection
-            .borrow_mut()
             .log_message(MessageType::INFO, "Server initialized!");
 
-        self.linter
-            .borrow_mut()
-            .replace(SolidLinter::new_fileless());
+        let connection = self.connection.clone();
+        let pull_diagnostics = self.pull_diagnostics.clone();
+        let state = self.state.clone();
+        let root = self.root.read().unwrap().clone();
+        let worker = LintWorker::spawn(root.clone(), self.state.clone(), move |uri, diags| {
+            // Diagnostics are only pushed to clients which cannot pull them
+            if !pull_diagnostics.load(Ordering::Relaxed) {
+                connection.publish_diagnostics(uri, diags, None);
+            }
+            send_status(&connection, &state);
+        });
+        if let Some(root) = root {
+            let files = pull_diagnostics::workspace_files(&root);
+            worker.index(
+                files.clone(),
+                self.create_progress("Indexing Solidity files", files.len()),
+            );
+        }
+        self.worker.lock().unwrap().replace(worker);
+
+        // lsp-types does not expose the type hierarchy server capability, so it can only be registered
+        // dynamically, with the clients supporting it
+        if self.type_hierarchy_registration.load(Ordering::Relaxed) {
+            let _ = self.connection.register_capability(vec![Registration {
+                id: "osmium-type-hierarchy".to_string(),
+                method: "textDocument/prepareTypeHierarchy".to_string(),
+                register_options: Some(serde_json::json!({
+                    "documentSelector": [{ "language": "solidity" }]
+                })),
+            }]);
+        }
 
         self.connection
-            .borrow_mut()
             .log_message(MessageType::INFO, "Linter initialized!");
     }
 
     fn shutdown(&self) -> Result<()> {
         self.connection
-            .borrow_mut()
             .log_message(MessageType::INFO, "Server shutdown!");
+        if let Some(worker) = self.worker.lock().unwrap().take() {
+            worker.stop();
+        }
         Ok(())
     }
 
     fn did_open(&self, params: DidOpenTextDocumentParams) {
-        self.connection.borrow_mut().log_message(
+        self.connection.log_message(
             MessageType::INFO,
             format!("file opened!: {:}", params.text_document.uri),
         );
 
-        self.lint(params.text_document.uri, params.text_document.text);
+        self.texts.lock().unwrap().insert(
+            params.text_document.uri.clone(),
+            params.text_document.text.clone(),
+        );
+        self.lint(
+            params.text_document.uri,
+            params.text_document.text,
+            Duration::ZERO,
+        );
     }
 
     fn did_change(&self, params: DidChangeTextDocumentParams) {
-        self.connection.borrow_mut().log_message(
+        self.connection.log_message(
             MessageType::INFO,
             format!("file changed!: {:}", params.text_document.uri),
         );
 
+        self.texts.lock().unwrap().insert(
+            params.text_document.uri.clone(),
+            params.content_changes[0].text.clone(),
+        );
         self.lint(
             params.text_document.uri,
             params.content_changes[0].text.clone(),
+            CHANGE_DEBOUNCE,
         );
     }
+
+    fn did_close(&self, params: DidCloseTextDocumentParams) {
+        self.texts.lock().unwrap().remove(&params.text_document.uri);
+        if let Some(worker) = self.worker.lock().unwrap().as_ref() {
+            worker.close(&filepath_from_uri(&params.text_document.uri));
+        }
+    }
+
+    fn did_change_configuration(&self, _: DidChangeConfigurationParams) {
+        self.refresh_diagnostics();
+    }
+
+    fn did_change_watched_files(&self, _: DidChangeWatchedFilesParams) {
+        self.refresh_diagnostics();
+    }
+
+    fn diagnostic(
+        &self,
+        params: DocumentDiagnosticParams,
+    ) -> Result<DocumentDiagnosticReportResult> {
+        let filepath = filepath_from_uri(&params.text_document.uri);
+        let cached = self.pull(&filepath).unwrap_or(CachedDiagnostics {
+            result_id: String::new(),
+            items: vec![],
+        });
+        send_status(&self.connection, &self.state);
+        Ok(DocumentDiagnosticReportResult::Report(
+            pull_diagnostics::document_report(&cached, params.previous_result_id.as_deref()),
+        ))
+    }
+
+    fn workspace_diagnostic(
+        &self,
+        params: WorkspaceDiagnosticParams,
+    ) -> Result<WorkspaceDiagnosticReportResult> {
+        let previous_result_ids: HashMap<String, String> = params
+            .previous_result_ids
+            .into_iter()
+            .map(|previous| (filepath_from_uri(&previous.uri), previous.value))
+            .collect();
+        let files = match self.root.read().unwrap().as_ref() {
+            Some(root) => pull_diagnostics::workspace_files(root),
+            None => vec![],
+        };
+
+        let title = "Linting Solidity files";
+        let mut progress = match params.work_done_progress_params.work_done_token {
+            Some(token) => Some(ProgressReporter::begin(
+                &self.connection,
+                token,
+                title,
+                files.len(),
+            )),
+            None => self.create_progress(title, files.len()),
+        };
+
+        let mut items: Vec<WorkspaceDocumentDiagnosticReport> = Vec::new();
+        for file in files {
+            if let Some(progress) = progress.as_mut() {
+                progress.advance();
+            }
+            let filepath = file.to_string_lossy().to_string();
+            let uri = match Url::from_file_path(&file) {
+                Ok(uri) => uri,
+                Err(_) => continue,
+            };
+            if let Some(cached) = self.pull(&filepath) {
+                items.push(pull_diagnostics::workspace_report(
+                    uri,
+                    &cached,
+                    previous_result_ids.get(&filepath).map(|id| id.as_str()),
+                ));
+            }
+        }
+        if let Some(progress) = progress {
+            progress.end();
+        }
+        send_status(&self.connection, &self.state);
+        Ok(WorkspaceDiagnosticReportResult::Report(
+            WorkspaceDiagnosticReport { items },
+        ))
+    }
+
+    fn prepare_call_hierarchy(
+        &self,
+        params: CallHierarchyPrepareParams,
+    ) -> Result<Option<Vec<CallHierarchyItem>>> {
+        let position = params.text_document_position_params;
+        let filepath = filepath_from_uri(&position.text_document.uri);
+        let items = self.with_documents(move |files| {
+            call_hierarchy::prepare_call_hierarchy(files, &filepath, position.position)
+        });
+        Ok(Some(items))
+    }
+
+    fn incoming_calls(
+        &self,
+        params: CallHierarchyIncomingCallsParams,
+    ) -> Result<Option<Vec<CallHierarchyIncomingCall>>> {
+        let calls =
+            self.with_documents(move |files| call_hierarchy::incoming_calls(files, &params.item));
+        Ok(Some(calls))
+    }
+
+    fn outgoing_calls(
+        &self,
+        params: CallHierarchyOutgoingCallsParams,
+    ) -> Result<Option<Vec<CallHierarchyOutgoingCall>>> {
+        let calls =
+            self.with_documents(move |files| call_hierarchy::outgoing_calls(files, &params.item));
+        Ok(Some(calls))
+    }
+
+    fn document_link(&self, params: DocumentLinkParams) -> Result<Option<Vec<DocumentLink>>> {
+        let filepath = filepath_from_uri(&params.text_document.uri);
+        let resolver = self.state.import_resolver.clone();
+        let links = self.with_documents(move |files| {
+            find_file(files, &filepath)
+                .map(|file| document_link::document_links(&resolver.read().unwrap(), file))
+        });
+        Ok(links)
+    }
+
+    fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let resolver = self.state.import_resolver.clone();
+        let text = self
+            .texts
+            .lock()
+            .unwrap()
+            .get(&params.text_document.uri)
+            .cloned();
+        let actions = self.with_documents(move |files| {
+            code_actions::code_actions(
+                files,
+                &resolver.read().unwrap(),
+                &params.text_document.uri,
+                text.as_deref(),
+                params.range,
+                &params.context,
+            )
+        });
+        Ok(Some(actions))
+    }
+
+    fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        Ok(self.format(
+            &params.text_document.uri,
+            &params.options,
+            formatting::format_document,
+        ))
+    }
+
+    fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        Ok(self.format(
+            &params.text_document.uri,
+            &params.options,
+            |text, config| formatting::format_range(text, params.range, config),
+        ))
+    }
+
+    fn on_type_formatting(
+        &self,
+        params: DocumentOnTypeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        let position = params.text_document_position;
+        Ok(self.format(
+            &position.text_document.uri,
+            &params.options,
+            |text, config| formatting::format_on_type(text, position.position, &params.ch, config),
+        ))
+    }
+
+    fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
+        let filepath = filepath_from_uri(&params.text_document.uri);
+        let ranges = self.with_documents(move |files| {
+            find_file(files, &filepath).map(folding_range::folding_ranges)
+        });
+        Ok(ranges)
+    }
+
+    fn selection_range(&self, params: SelectionRangeParams) -> Result<Option<Vec<SelectionRange>>> {
+        let filepath = filepath_from_uri(&params.text_document.uri);
+        let ranges = self.with_documents(move |files| {
+            find_file(files, &filepath)
+                .map(|file| selection_range::selection_ranges(file, &params.positions))
+        });
+        Ok(ranges)
+    }
+
+    fn prepare_type_hierarchy(
+        &self,
+        params: TypeHierarchyPrepareParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        let position = params.text_document_position_params;
+        let filepath = filepath_from_uri(&position.text_document.uri);
+        let items = self.with_documents(move |files| {
+            type_hierarchy::prepare_type_hierarchy(files, &filepath, position.position)
+        });
+        Ok(Some(items))
+    }
+
+    fn supertypes(
+        &self,
+        params: TypeHierarchySupertypesParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        let items =
+            self.with_documents(move |files| type_hierarchy::supertypes(files, &params.item));
+        Ok(Some(items))
+    }
+
+    fn subtypes(
+        &self,
+        params: TypeHierarchySubtypesParams,
+    ) -> Result<Option<Vec<TypeHierarchyItem>>> {
+        let items = self.with_documents(move |files| type_hierarchy::subtypes(files, &params.item));
+        Ok(Some(items))
+    }
 }
 
 impl Backend {
-    pub fn new(connection: Rc<RefCell<Client>>) -> Self {
+    pub fn new(connection: Client) -> Self {
         Self {
             connection,
-            linter: RefCell::new(None),
+            worker: Mutex::new(None),
+            state: SharedState {
+                diagnostics: Arc::new(Mutex::new(DiagnosticsCache::new())),
+                import_resolver: Arc::new(RwLock::new(ImportResolver::default())),
+            },
+            root: RwLock::new(None),
+            pull_diagnostics: Arc::new(AtomicBool::new(false)),
+            work_done_progress: AtomicBool::new(false),
+            type_hierarchy_registration: AtomicBool::new(false),
+            texts: Mutex::new(HashMap::new()),
         }
     }
 
-    pub fn lint(&self, uri: Url, text: String) {
-        let filepath = filepath_from_uri(&uri);
-        let mut linter = self.linter.borrow_mut();
-        let linter = match linter.as_mut() {
-            Some(l) => l,
-            None => {
-                eprintln!("Linter cannot be ran due to previous errors");
-                return;
+    fn with_documents<T: Default + Send + 'static>(
+        &self,
+        f: impl FnOnce(&[SolidFile]) -> T + Send + 'static,
+    ) -> T {
+        match self.worker.lock().unwrap().as_ref() {
+            Some(worker) => worker.query(f).unwrap_or_default(),
+            None => T::default(),
+        }
+    }
+
+    /// Schedules a lint of the document on the worker, which publishes the diagnostics
+    pub fn lint(&self, uri: Url, text: String, delay: Duration) {
+        match self.worker.lock().unwrap().as_ref() {
+            Some(worker) => worker.lint(uri, text, delay),
+            None => eprintln!("Linter cannot be ran due to previous errors"),
+        }
+    }
+
+    /// Returns the diagnostics of a document through the worker, which first lints the edits
+    /// still waiting for their debounce, so that the result is never older than the latest text
+    fn pull(&self, filepath: &str) -> Option<CachedDiagnostics> {
+        self.worker.lock().unwrap().as_ref()?.pull(filepath)
+    }
+
+    /// Formats the latest text of a document, leaving it as is when it cannot be formatted
+    fn format(
+        &self,
+        uri: &Url,
+        options: &FormattingOptions,
+        format: impl FnOnce(&str, &FormatterConfig) -> std::result::Result<Vec<TextEdit>, FormatError>,
+    ) -> Option<Vec<TextEdit>> {
+        let text = self.texts.lock().unwrap().get(uri).cloned()?;
+        let root = self.root.read().unwrap().clone();
+        let config = formatting::formatter_config(root.as_deref(), options);
+        match format(&text, &config) {
+            Ok(edits) => Some(edits),
+            Err(err) => {
+                self.connection
+                    .log_message(MessageType::WARNING, format!("{}: {}", uri, err));
+                None
             }
-        };
-        let diags_res = linter.parse_content(&filepath, &text);
-
-        if let Ok(diags) = diags_res {
-            let diags = diags
-                .diags
-                .iter()
-                .map(|d| diagnostic_from_lintdiag(d.clone()))
-                .collect();
-            eprintln!("diags: {:#?}", diags);
-            self.connection
-                .borrow_mut()
-                .publish_diagnostics(uri.clone(), diags, None);
-        } else if let Err(e) = diags_res {
-            self.connection
-                .borrow_mut()
-                .log_message(MessageType::ERROR, e.to_string());
         }
     }
-}
 
-pub fn filepath_from_uri(uri: &Url) -> String {
-    let path = uri.path();
-    path.to_string()
-}
+    /// Creates a progress if the client supports it
+    fn create_progress(&self, title: &str, total: usize) -> Option<ProgressReporter> {
+        if !self.work_done_progress.load(Ordering::Relaxed) {
+            return None;
+        }
+        ProgressReporter::create(&self.connection, title, total)
+    }
 
-fn diagnostic_from_lintdiag(diag: LintDiag) -> Diagnostic {
-    Diagnostic {
-        range: Range {
-            start: Position {
-                line: diag.range.start.line as u32 - 1,
-                character: diag.range.start.character as u32,
-            },
-            end: Position {
-                line: diag.range.end.line as u32 - 1,
-                character: diag.range.end.character as u32,
-            },
-        },
-        severity: Some(DiagnosticSeverity::WARNING),
-        code: None,
-        code_description: None,
-        source: Some("osmium-solidity-linter".to_string()),
-        message: diag.message,
-        related_information: None,
-        tags: None,
-        data: None,
+    /// Reloads the configuration, drops cached diagnostics and asks the client to pull them again
+    fn refresh_diagnostics(&self) {
+        if let Some(root) = self.root.read().unwrap().as_ref() {
+            *self.state.import_resolver.write().unwrap() = ImportResolver::from_root(root);
+        }
+        self.state.diagnostics.lock().unwrap().invalidate();
+        if let Some(worker) = self.worker.lock().unwrap().as_ref() {
+            let documents = worker.query(|files| files.len()).unwrap_or(0);
+            worker.reload(self.create_progress("Loading configuration", documents));
+        }
+        if self.pull_diagnostics.load(Ordering::Relaxed) {
+            let _ = self.connection.workspace_diagnostic_refresh();
+        }
     }
 }
 
-fn main() -> std::result::Result<(), usize> {
-    let server = LspStdioServer::new();
+fn send_status(connection: &Client, state: &SharedState) {
+    let status = StatusParams::from_cache(&state.diagnostics.lock().unwrap());
+    connection.send_notification::<StatusNotification>(status);
+}
+
+fn serve(server: Arc<LspStdioServer>) -> std::result::Result<(), usize> {
     LspStdioServer::serve(server, Backend::new).map_err(|err| {
         eprintln!("Error: {:?}", err);
         1
     })
 }
+
+/// Serves each client connecting to the address from its own thread
+fn listen(addr: &str) -> std::io::Result<()> {
+    let listener = TcpListener::bind(socket_addr(addr)?)?;
+    eprintln!("Listening on {}", listener.local_addr()?);
+    for stream in listener.incoming() {
+        let stream = stream?;
+        thread::spawn(move || match LspStdioServer::from_tcp_stream(stream) {
+            Ok(server) => {
+                let _ = serve(server);
+            }
+            Err(e) => eprintln!("Cannot serve client: {}", e),
+        });
+    }
+    Ok(())
+}
+
+fn main() -> std::result::Result<(), usize> {
+    let args = Args::parse();
+
+    if let Some(port) = &args.port {
+        return listen(port).map_err(|err| {
+            eprintln!("Error: {}", err);
+            1
+        });
+    }
+
+    let server = match (&args.socket, &args.pipe) {
+        (Some(addr), _) => socket_addr(addr)
+            .and_then(|addr| LspStdioServer::with_transport(Transport::Connect(&addr.to_string()))),
+        (_, Some(path)) => LspStdioServer::with_transport(Transport::Pipe(path)),
+        _ => Ok(LspStdioServer::new()),
+    };
+    match server {
+        Ok(server) => serve(server),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            Err(1)
+        }
+    }
+}
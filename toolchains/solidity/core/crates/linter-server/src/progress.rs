@@ -0,0 +1,76 @@
+use osmium_libs_lsp_server_wrapper::{
+    lsp_types::{
+        NumberOrString, ProgressToken, WorkDoneProgress, WorkDoneProgressBegin,
+        WorkDoneProgressEnd, WorkDoneProgressReport,
+    },
+    Client,
+};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static NEXT_TOKEN: AtomicU32 = AtomicU32::new(0);
+
+/// Work done progress of a task over a known number of files
+pub struct ProgressReporter {
+    client: Client,
+    token: ProgressToken,
+    total: usize,
+    done: usize,
+}
+
+impl ProgressReporter {
+    /// Asks the client to create a progress and begins it.
+    /// Must not be called from the lint worker, as the main loop may be waiting for it.
+    pub fn create(client: &Client, title: &str, total: usize) -> Option<Self> {
+        let token = NumberOrString::String(format!(
+            "osmium-solidity-linter/{}",
+            NEXT_TOKEN.fetch_add(1, Ordering::Relaxed)
+        ));
+        if let Err(e) = client.work_done_progress_create(token.clone()) {
+            eprintln!("Cannot create progress: {}", e);
+            return None;
+        }
+        Some(Self::begin(client, token, title, total))
+    }
+
+    /// Begins a progress using a token given by the client
+    pub fn begin(client: &Client, token: ProgressToken, title: &str, total: usize) -> Self {
+        client.send_progress(
+            token.clone(),
+            WorkDoneProgress::Begin(WorkDoneProgressBegin {
+                title: title.to_string(),
+                cancellable: Some(false),
+                message: Some(format!("0/{}", total)),
+                percentage: Some(0),
+            }),
+        );
+        Self {
+            client: client.clone(),
+            token,
+            total,
+            done: 0,
+        }
+    }
+
+    /// Reports that one more file was processed
+    pub fn advance(&mut self) {
+        self.done += 1;
+        let percentage = (self.done * 100).checked_div(self.total).unwrap_or(100);
+        self.client.send_progress(
+            self.token.clone(),
+            WorkDoneProgress::Report(WorkDoneProgressReport {
+                cancellable: Some(false),
+                message: Some(format!("{}/{}", self.done, self.total)),
+                percentage: Some(percentage.min(100) as u32),
+            }),
+        );
+    }
+
+    pub fn end(self) {
+        self.client.send_progress(
+            self.token,
+            WorkDoneProgress::End(WorkDoneProgressEnd {
+                message: Some(format!("{} files", self.done)),
+            }),
+        );
+    }
+}
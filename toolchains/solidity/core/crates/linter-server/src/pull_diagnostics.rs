@@ -0,0 +1,196 @@
+use glob::glob;
+use osmium_libs_lsp_server_wrapper::lsp_types::{
+    Diagnostic, DocumentDiagnosticReport, FullDocumentDiagnosticReport,
+    RelatedFullDocumentDiagnosticReport, RelatedUnchangedDocumentDiagnosticReport,
+    UnchangedDocumentDiagnosticReport, Url, WorkspaceDocumentDiagnosticReport,
+    WorkspaceFullDocumentDiagnosticReport, WorkspaceUnchangedDocumentDiagnosticReport,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Folders containing dependencies, which are not reported in workspace diagnostics
+const IGNORED_FOLDERS: [&str; 2] = ["node_modules", "lib"];
+
+#[derive(Debug, Clone)]
+pub struct CachedDiagnostics {
+    pub result_id: String,
+    pub items: Vec<Diagnostic>,
+}
+
+/// Last diagnostics computed for each document, identified by a result id
+/// which only changes when the document content or the configuration changes
+#[derive(Debug, Default)]
+pub struct DiagnosticsCache {
+    generation: u64,
+    documents: HashMap<String, CachedDiagnostics>,
+}
+
+impl DiagnosticsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(
+        &mut self,
+        path: &str,
+        content: &str,
+        items: Vec<Diagnostic>,
+    ) -> &CachedDiagnostics {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        let result_id = format!("{}-{:x}", self.generation, hasher.finish());
+        self.documents
+            .insert(path.to_string(), CachedDiagnostics { result_id, items });
+        &self.documents[path]
+    }
+
+    pub fn get(&self, path: &str) -> Option<&CachedDiagnostics> {
+        self.documents.get(path)
+    }
+
+    pub fn remove(&mut self, path: &str) {
+        self.documents.remove(path);
+    }
+
+    /// Iterates over the cached diagnostics of every document
+    pub fn diagnostics(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.documents
+            .values()
+            .flat_map(|cached| cached.items.iter())
+    }
+
+    /// Drops every cached result, so that the next pull recomputes all diagnostics
+    pub fn invalidate(&mut self) {
+        self.generation += 1;
+        self.documents.clear();
+    }
+}
+
+pub fn document_report(
+    cached: &CachedDiagnostics,
+    previous_result_id: Option<&str>,
+) -> DocumentDiagnosticReport {
+    if previous_result_id == Some(cached.result_id.as_str()) {
+        DocumentDiagnosticReport::Unchanged(RelatedUnchangedDocumentDiagnosticReport {
+            related_documents: None,
+            unchanged_document_diagnostic_report: UnchangedDocumentDiagnosticReport {
+                result_id: cached.result_id.clone(),
+            },
+        })
+    } else {
+        DocumentDiagnosticReport::Full(RelatedFullDocumentDiagnosticReport {
+            related_documents: None,
+            full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                result_id: Some(cached.result_id.clone()),
+                items: cached.items.clone(),
+            },
+        })
+    }
+}
+
+pub fn workspace_report(
+    uri: Url,
+    cached: &CachedDiagnostics,
+    previous_result_id: Option<&str>,
+) -> WorkspaceDocumentDiagnosticReport {
+    match document_report(cached, previous_result_id) {
+        DocumentDiagnosticReport::Full(report) => {
+            WorkspaceDocumentDiagnosticReport::Full(WorkspaceFullDocumentDiagnosticReport {
+                uri,
+                version: None,
+                full_document_diagnostic_report: report.full_document_diagnostic_report,
+            })
+        }
+        DocumentDiagnosticReport::Unchanged(report) => {
+            WorkspaceDocumentDiagnosticReport::Unchanged(
+                WorkspaceUnchangedDocumentDiagnosticReport {
+                    uri,
+                    version: None,
+                    unchanged_document_diagnostic_report: report
+                        .unchanged_document_diagnostic_report,
+                },
+            )
+        }
+    }
+}
+
+/// Lists the solidity files of the workspace, dependencies excluded
+pub fn workspace_files(root: &Path) -> Vec<PathBuf> {
+    let pattern = root.join("**").join("*.sol");
+    let entries = match glob(&pattern.to_string_lossy()) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    entries
+        .flatten()
+        .filter(|path| {
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            !relative.components().any(|component| {
+                IGNORED_FOLDERS.contains(&component.as_os_str().to_string_lossy().as_ref())
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unchanged_report() {
+        let mut cache = DiagnosticsCache::new();
+        let result_id = cache
+            .insert("/test.sol", "contract Test {}", vec![])
+            .result_id
+            .clone();
+        let cached = cache.get("/test.sol").unwrap();
+
+        assert!(matches!(
+            document_report(cached, None),
+            DocumentDiagnosticReport::Full(_)
+        ));
+        assert!(matches!(
+            document_report(cached, Some(&result_id)),
+            DocumentDiagnosticReport::Unchanged(_)
+        ));
+
+        let new_result_id = cache
+            .insert("/test.sol", "contract Test2 {}", vec![])
+            .result_id
+            .clone();
+        assert_ne!(result_id, new_result_id);
+    }
+
+    #[test]
+    fn test_invalidate_changes_result_id() {
+        let mut cache = DiagnosticsCache::new();
+        let result_id = cache
+            .insert("/test.sol", "contract Test {}", vec![])
+            .result_id
+            .clone();
+        cache.invalidate();
+        assert!(cache.get("/test.sol").is_none());
+
+        let new_result_id = cache
+            .insert("/test.sol", "contract Test {}", vec![])
+            .result_id
+            .clone();
+        assert_ne!(result_id, new_result_id);
+    }
+
+    #[test]
+    fn test_workspace_files_skip_dependencies() {
+        let root = std::env::temp_dir().join("osmium-pull-diagnostics-workspace");
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("lib/forge-std/src")).unwrap();
+        std::fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        std::fs::write(root.join("src/Test.sol"), "").unwrap();
+        std::fs::write(root.join("lib/forge-std/src/Test.sol"), "").unwrap();
+        std::fs::write(root.join("node_modules/pkg/Test.sol"), "").unwrap();
+
+        assert_eq!(workspace_files(&root), vec![root.join("src/Test.sol")]);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::symbols::{contract_range, function_range};
+use crate::utils::{range_contains, range_from_line_columns};
+use osmium_libs_lsp_server_wrapper::lsp_types::{Position, Range, SelectionRange};
+use osmium_libs_solidity_ast_extractor::{
+    visit, Block, Expr, ItemContract, ItemFunction, Spanned, Stmt, VariableDefinition, Visit,
+};
+use solidhunter_lib::linter::SolidFile;
+
+struct SelectionVisitor {
+    position: Position,
+    ranges: Vec<Range>,
+}
+
+impl SelectionVisitor {
+    pub fn new(position: Position) -> Self {
+        Self {
+            position,
+            ranges: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, range: Range) {
+        if range_contains(&range, &self.position) && !self.ranges.contains(&range) {
+            self.ranges.push(range);
+        }
+    }
+
+    fn push_span<T: Spanned>(&mut self, node: &T) {
+        let span = node.span();
+        self.push(range_from_line_columns(span.start(), span.end()));
+    }
+}
+
+impl<'ast> Visit<'ast> for SelectionVisitor {
+    fn visit_item_contract(&mut self, contract: &'ast ItemContract) {
+        self.push(contract_range(contract));
+        visit::visit_item_contract(self, contract);
+    }
+
+    fn visit_item_function(&mut self, function: &'ast ItemFunction) {
+        self.push(function_range(function));
+        visit::visit_item_function(self, function);
+    }
+
+    fn visit_variable_definition(&mut self, var: &'ast VariableDefinition) {
+        self.push_span(var);
+        visit::visit_variable_definition(self, var);
+    }
+
+    fn visit_block(&mut self, block: &'ast Block) {
+        self.push_span(block);
+        visit::visit_block(self, block);
+    }
+
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        self.push_span(stmt);
+        visit::visit_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        self.push_span(expr);
+        visit::visit_expr(self, expr);
+    }
+}
+
+fn selection_range(file: &SolidFile, position: Position) -> SelectionRange {
+    let mut visitor = SelectionVisitor::new(position);
+    visitor.visit_file(&file.data);
+
+    // Sort from the outermost range to the innermost one
+    let mut ranges = visitor.ranges;
+    ranges.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+
+    let mut res: Option<SelectionRange> = None;
+    for range in ranges {
+        res = Some(SelectionRange {
+            range,
+            parent: res.map(Box::new),
+        });
+    }
+    res.unwrap_or(SelectionRange {
+        range: Range {
+            start: position,
+            end: position,
+        },
+        parent: None,
+    })
+}
+
+pub fn selection_ranges(file: &SolidFile, positions: &[Position]) -> Vec<SelectionRange> {
+    positions
+        .iter()
+        .map(|position| selection_range(file, *position))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use osmium_libs_solidity_ast_extractor::extract::extract_ast_from_content;
+
+    #[test]
+    fn test_selection_ranges() {
+        let content =
+            "contract Test {\n    function f() public {\n        uint a = 1 + 2;\n    }\n}\n";
+        let file = SolidFile {
+            data: extract_ast_from_content(content).unwrap(),
+            path: "/test.sol".to_string(),
+            content: content.to_string(),
+        };
+        let res = selection_ranges(&file, &[Position::new(2, 17)]);
+        assert_eq!(res.len(), 1);
+
+        let mut ranges: Vec<Range> = Vec::new();
+        let mut current = Some(&res[0]);
+        while let Some(selection) = current {
+            ranges.push(selection.range);
+            current = selection.parent.as_deref();
+        }
+        assert_eq!(
+            ranges.first(),
+            Some(&Range::new(Position::new(2, 17), Position::new(2, 18)))
+        );
+        assert_eq!(
+            ranges.last(),
+            Some(&Range::new(Position::new(0, 0), Position::new(4, 1)))
+        );
+        for window in ranges.windows(2) {
+            assert!(range_contains(&window[1], &window[0].start));
+            assert!(range_contains(&window[1], &window[0].end));
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::pull_diagnostics::DiagnosticsCache;
+use osmium_libs_lsp_server_wrapper::lsp_types::{notification::Notification, DiagnosticSeverity};
+use serde::{Deserialize, Serialize};
+
+/// Custom notification sent after each lint with the diagnostics count of the workspace,
+/// so that the client can display it in its status bar
+pub enum StatusNotification {}
+
+impl Notification for StatusNotification {
+    type Params = StatusParams;
+    const METHOD: &'static str = "osmium/status";
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusParams {
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+    pub hints: usize,
+}
+
+impl StatusParams {
+    pub fn from_cache(cache: &DiagnosticsCache) -> Self {
+        let mut status = StatusParams::default();
+        for diag in cache.diagnostics() {
+            match diag.severity {
+                Some(DiagnosticSeverity::ERROR) => status.errors += 1,
+                Some(DiagnosticSeverity::INFORMATION) => status.infos += 1,
+                Some(DiagnosticSeverity::HINT) => status.hints += 1,
+                _ => status.warnings += 1,
+            }
+        }
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use osmium_libs_lsp_server_wrapper::lsp_types::{Diagnostic, Range};
+
+    fn diagnostic(severity: DiagnosticSeverity) -> Diagnostic {
+        Diagnostic {
+            range: Range::default(),
+            severity: Some(severity),
+            message: String::new(),
+            ..Diagnostic::default()
+        }
+    }
+
+    #[test]
+    fn test_status_counts_severities() {
+        let mut cache = DiagnosticsCache::new();
+        cache.insert(
+            "/a.sol",
+            "",
+            vec![
+                diagnostic(DiagnosticSeverity::ERROR),
+                diagnostic(DiagnosticSeverity::WARNING),
+            ],
+        );
+        cache.insert(
+            "/b.sol",
+            "",
+            vec![
+                diagnostic(DiagnosticSeverity::ERROR),
+                diagnostic(DiagnosticSeverity::HINT),
+            ],
+        );
+
+        assert_eq!(
+            StatusParams::from_cache(&cache),
+            StatusParams {
+                errors: 2,
+                warnings: 1,
+                infos: 0,
+                hints: 1,
+            }
+        );
+    }
+}
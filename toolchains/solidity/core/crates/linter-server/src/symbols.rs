@@ -0,0 +1,182 @@
+use crate::utils::{range_from_line_columns, uri_from_filepath};
+use osmium_libs_lsp_server_wrapper::lsp_types::{Range, SymbolKind, Url};
+use osmium_libs_solidity_ast_extractor::{
+    ContractKind, FunctionBody, FunctionKind, Item, ItemContract, ItemFunction, Spanned,
+};
+use solidhunter_lib::linter::SolidFile;
+
+#[derive(Clone, Copy)]
+pub struct ContractRef<'a> {
+    pub file: &'a SolidFile,
+    pub contract: &'a ItemContract,
+}
+
+#[derive(Clone, Copy)]
+pub struct FunctionRef<'a> {
+    pub file: &'a SolidFile,
+    pub contract: Option<&'a ItemContract>,
+    pub function: &'a ItemFunction,
+}
+
+impl<'a> ContractRef<'a> {
+    pub fn name(&self) -> String {
+        self.contract.name.to_string()
+    }
+
+    pub fn uri(&self) -> Option<Url> {
+        uri_from_filepath(&self.file.path)
+    }
+
+    pub fn range(&self) -> Range {
+        contract_range(self.contract)
+    }
+
+    pub fn selection_range(&self) -> Range {
+        let span = self.contract.name.span();
+        range_from_line_columns(span.start(), span.end())
+    }
+
+    pub fn symbol_kind(&self) -> SymbolKind {
+        match self.contract.kind {
+            ContractKind::Interface(_) => SymbolKind::INTERFACE,
+            ContractKind::Library(_) => SymbolKind::MODULE,
+            _ => SymbolKind::CLASS,
+        }
+    }
+
+    pub fn base_names(&self) -> Vec<String> {
+        match &self.contract.inheritance {
+            Some(inheritance) => inheritance
+                .inheritance
+                .iter()
+                .map(|base| base.name.last().to_string())
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    pub fn functions(&self) -> impl Iterator<Item = FunctionRef<'a>> {
+        let file = self.file;
+        let contract = self.contract;
+        contract.body.iter().filter_map(move |item| match item {
+            Item::Function(function) => Some(FunctionRef {
+                file,
+                contract: Some(contract),
+                function,
+            }),
+            _ => None,
+        })
+    }
+
+    pub fn find_function(&self, name: &str) -> Option<FunctionRef<'a>> {
+        self.functions().find(|function| function.name() == name)
+    }
+}
+
+impl<'a> FunctionRef<'a> {
+    pub fn name(&self) -> String {
+        match &self.function.name {
+            Some(name) => name.to_string(),
+            None => self.function.kind.to_string(),
+        }
+    }
+
+    pub fn uri(&self) -> Option<Url> {
+        uri_from_filepath(&self.file.path)
+    }
+
+    pub fn range(&self) -> Range {
+        function_range(self.function)
+    }
+
+    pub fn selection_range(&self) -> Range {
+        let span = match &self.function.name {
+            Some(name) => name.span(),
+            None => self.function.kind.span(),
+        };
+        range_from_line_columns(span.start(), span.end())
+    }
+
+    pub fn symbol_kind(&self) -> SymbolKind {
+        if matches!(self.function.kind, FunctionKind::Constructor(_)) {
+            SymbolKind::CONSTRUCTOR
+        } else if self.contract.is_some() {
+            SymbolKind::METHOD
+        } else {
+            SymbolKind::FUNCTION
+        }
+    }
+}
+
+pub fn contract_range(contract: &ItemContract) -> Range {
+    range_from_line_columns(
+        contract.kind.span().start(),
+        contract.brace_token.span.join().end(),
+    )
+}
+
+pub fn function_range(function: &ItemFunction) -> Range {
+    let end = match &function.body {
+        FunctionBody::Block(block) => block.span().end(),
+        FunctionBody::Empty(semi) => semi.span().end(),
+    };
+    range_from_line_columns(function.kind.span().start(), end)
+}
+
+pub fn contracts(file: &SolidFile) -> impl Iterator<Item = ContractRef<'_>> {
+    file.data.items.iter().filter_map(move |item| match item {
+        Item::Contract(contract) => Some(ContractRef { file, contract }),
+        _ => None,
+    })
+}
+
+pub fn free_functions(file: &SolidFile) -> impl Iterator<Item = FunctionRef<'_>> {
+    file.data.items.iter().filter_map(move |item| match item {
+        Item::Function(function) => Some(FunctionRef {
+            file,
+            contract: None,
+            function,
+        }),
+        _ => None,
+    })
+}
+
+pub fn all_functions(file: &SolidFile) -> Vec<FunctionRef<'_>> {
+    let mut functions: Vec<FunctionRef> = free_functions(file).collect();
+    for contract in contracts(file) {
+        functions.extend(contract.functions());
+    }
+    functions
+}
+
+pub fn find_file<'a>(files: &'a [SolidFile], path: &str) -> Option<&'a SolidFile> {
+    files.iter().find(|file| file.path == path)
+}
+
+pub fn find_contract<'a>(files: &'a [SolidFile], name: &str) -> Option<ContractRef<'a>> {
+    files
+        .iter()
+        .flat_map(contracts)
+        .find(|contract| contract.name() == name)
+}
+
+/// Returns the ancestors of a contract, from the most derived to the most basic one.
+pub fn ancestors<'a>(files: &'a [SolidFile], contract: ContractRef<'a>) -> Vec<ContractRef<'a>> {
+    let mut visited: Vec<String> = vec![contract.name()];
+    let mut res: Vec<ContractRef<'a>> = Vec::new();
+    let mut stack: Vec<ContractRef<'a>> = vec![contract];
+
+    while let Some(current) = stack.pop() {
+        for base in current.base_names().iter().rev() {
+            if visited.contains(base) {
+                continue;
+            }
+            visited.push(base.clone());
+            if let Some(base) = find_contract(files, base) {
+                res.push(base);
+                stack.insert(0, base);
+            }
+        }
+    }
+    res
+}
@@ -0,0 +1,21 @@
+use osmium_libs_solidity_ast_extractor::extract::extract_ast_from_content;
+use solidhunter_lib::linter::SolidFile;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Parses a document as if it was opened at the given path
+pub fn solid_file(path: impl AsRef<Path>, content: &str) -> SolidFile {
+    SolidFile {
+        data: extract_ast_from_content(content).unwrap(),
+        path: path.as_ref().to_string_lossy().to_string(),
+        content: content.to_string(),
+    }
+}
+
+/// Creates an empty project with a `src` folder in the temporary directory
+pub fn project(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("osmium-{}", name));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("src")).unwrap();
+    root
+}
@@ -0,0 +1,141 @@
+use crate::symbols::{contracts, find_contract, find_file, ContractRef};
+use crate::utils::{filepath_from_uri, range_contains, range_from_line_columns};
+use osmium_libs_lsp_server_wrapper::lsp_types::{Position, TypeHierarchyItem};
+use osmium_libs_solidity_ast_extractor::Spanned;
+use solidhunter_lib::linter::SolidFile;
+
+fn item_from_contract(contract: &ContractRef) -> Option<TypeHierarchyItem> {
+    Some(TypeHierarchyItem {
+        name: contract.name(),
+        kind: contract.symbol_kind(),
+        tags: None,
+        detail: Some(contract.contract.kind.to_string()),
+        uri: contract.uri()?,
+        range: contract.range(),
+        selection_range: contract.selection_range(),
+        data: None,
+    })
+}
+
+fn contract_from_item<'a>(
+    files: &'a [SolidFile],
+    item: &TypeHierarchyItem,
+) -> Option<ContractRef<'a>> {
+    let file = find_file(files, &filepath_from_uri(&item.uri))?;
+    contracts(file)
+        .find(|contract| contract.selection_range() == item.selection_range)
+        .or_else(|| find_contract(files, &item.name))
+}
+
+pub fn prepare_type_hierarchy(
+    files: &[SolidFile],
+    path: &str,
+    position: Position,
+) -> Vec<TypeHierarchyItem> {
+    let file = match find_file(files, path) {
+        Some(file) => file,
+        None => return vec![],
+    };
+    let contract =
+        match contracts(file).find(|contract| range_contains(&contract.range(), &position)) {
+            Some(contract) => contract,
+            None => return vec![],
+        };
+
+    // The cursor may be placed on one of the inherited contracts
+    let base = contract
+        .contract
+        .inheritance
+        .as_ref()
+        .and_then(|inheritance| {
+            inheritance.inheritance.iter().find_map(|base| {
+                let span = base.name.span();
+                if range_contains(
+                    &range_from_line_columns(span.start(), span.end()),
+                    &position,
+                ) {
+                    find_contract(files, &base.name.last().to_string())
+                } else {
+                    None
+                }
+            })
+        });
+
+    item_from_contract(&base.unwrap_or(contract))
+        .into_iter()
+        .collect()
+}
+
+pub fn supertypes(files: &[SolidFile], item: &TypeHierarchyItem) -> Vec<TypeHierarchyItem> {
+    let contract = match contract_from_item(files, item) {
+        Some(contract) => contract,
+        None => return vec![],
+    };
+    contract
+        .base_names()
+        .iter()
+        .filter_map(|base| find_contract(files, base))
+        .filter_map(|base| item_from_contract(&base))
+        .collect()
+}
+
+pub fn subtypes(files: &[SolidFile], item: &TypeHierarchyItem) -> Vec<TypeHierarchyItem> {
+    files
+        .iter()
+        .flat_map(contracts)
+        .filter(|contract| contract.base_names().contains(&item.name))
+        .filter_map(|contract| item_from_contract(&contract))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::solid_file;
+
+    fn files() -> Vec<SolidFile> {
+        vec![
+            solid_file("/a.sol", "interface IA {}\ncontract A is IA {}\n"),
+            solid_file("/b.sol", "contract B is A {}\ncontract C is A, IA {}\n"),
+        ]
+    }
+
+    #[test]
+    fn test_prepare_type_hierarchy() {
+        let files = files();
+        let items = prepare_type_hierarchy(&files, "/a.sol", Position::new(1, 3));
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "A");
+    }
+
+    #[test]
+    fn test_prepare_type_hierarchy_on_base() {
+        let files = files();
+        let items = prepare_type_hierarchy(&files, "/b.sol", Position::new(0, 14));
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "A");
+        assert_eq!(items[0].uri.path(), "/a.sol");
+    }
+
+    #[test]
+    fn test_supertypes() {
+        let files = files();
+        let item = prepare_type_hierarchy(&files, "/b.sol", Position::new(1, 3)).remove(0);
+        let names: Vec<String> = supertypes(&files, &item)
+            .into_iter()
+            .map(|item| item.name)
+            .collect();
+        assert_eq!(names, vec!["A", "IA"]);
+    }
+
+    #[test]
+    fn test_subtypes() {
+        let files = files();
+        let item = prepare_type_hierarchy(&files, "/a.sol", Position::new(1, 3)).remove(0);
+        let names: Vec<String> = subtypes(&files, &item)
+            .into_iter()
+            .map(|item| item.name)
+            .collect();
+        assert_eq!(names, vec!["B", "C"]);
+    }
+}
@@ -0,0 +1,44 @@
+use osmium_libs_lsp_server_wrapper::lsp_types::{Position, Range, Url};
+use osmium_libs_solidity_ast_extractor::LineColumn;
+
+pub fn filepath_from_uri(uri: &Url) -> String {
+    match uri.to_file_path() {
+        Ok(path) => path.to_string_lossy().to_string(),
+        Err(_) => uri.path().to_string(),
+    }
+}
+
+pub fn uri_from_filepath(path: &str) -> Option<Url> {
+    Url::from_file_path(path).ok()
+}
+
+pub fn position_from_line_column(location: LineColumn) -> Position {
+    Position {
+        line: location.line as u32 - 1,
+        character: location.column as u32,
+    }
+}
+
+pub fn range_from_line_columns(start: LineColumn, end: LineColumn) -> Range {
+    Range {
+        start: position_from_line_column(start),
+        end: position_from_line_column(end),
+    }
+}
+
+pub fn range_contains(range: &Range, position: &Position) -> bool {
+    range.start <= *position && *position <= range.end
+}
+
+pub fn range_from_lint_range(range: &solidhunter_lib::types::Range) -> Range {
+    Range {
+        start: Position {
+            line: range.start.line as u32 - 1,
+            character: range.start.character as u32,
+        },
+        end: Position {
+            line: range.end.line as u32 - 1,
+            character: range.end.character as u32,
+        },
+    }
+}
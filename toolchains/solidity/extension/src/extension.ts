@@ -3,9 +3,8 @@
  * Licensed under the MIT License. See License.txt in the project root for license information.
  * ------------------------------------------------------------------------------------------ */
 
-import { glob } from 'glob';
 import * as path from 'path';
-import { workspace, ExtensionContext } from 'vscode';
+import { workspace, window, ExtensionContext, StatusBarAlignment } from 'vscode';
 
 import {
 	LanguageClient,
@@ -16,7 +15,14 @@ import {
 
 let client: LanguageClient;
 
-export async function activate(context: ExtensionContext) {
+interface StatusParams {
+	errors: number;
+	warnings: number;
+	infos: number;
+	hints: number;
+}
+
+export function activate(context: ExtensionContext) {
 	// The server is implemented in node
 	const serverBinary = context.asAbsolutePath(
 		path.join('dist', 'linter-server')
@@ -37,8 +43,11 @@ export async function activate(context: ExtensionContext) {
 		// Register the server for plain text documents
 		documentSelector: [{ scheme: 'file', language: 'solidity' }],
 		synchronize: {
-			// Notify the server about file changes to '.clientrc files contained in the workspace
-			fileEvents: workspace.createFileSystemWatcher('**/.solidhunter.json')
+			// Notify the server about changes to the linter configuration and the import remappings
+			fileEvents: [
+				workspace.createFileSystemWatcher('**/.solidhunter.json'),
+				workspace.createFileSystemWatcher('**/remappings.txt')
+			]
 		}
 	};
 
@@ -50,18 +59,19 @@ export async function activate(context: ExtensionContext) {
 		clientOptions
 	);
 
-	// Start the client. This will also launch the server
-	client.start();
+	// Show the diagnostics count of the workspace in the status bar
+	const statusBarItem = window.createStatusBarItem(StatusBarAlignment.Left);
+	statusBarItem.command = 'workbench.actions.view.problems';
+	context.subscriptions.push(statusBarItem);
 
-	const folders = workspace.workspaceFolders;
-	if (folders) {
-		const folder = folders[0];
-		const files = await workspace.findFiles('**/*.sol', `${folder.uri.fsPath}/**`);
-		files.forEach(file => {
-			workspace.openTextDocument(file);
+	// Start the client. This will also launch the server
+	client.start().then(() => {
+		client.onNotification('osmium/status', (status: StatusParams) => {
+			statusBarItem.text = `$(error) ${status.errors} $(warning) ${status.warnings} $(info) ${status.infos + status.hints}`;
+			statusBarItem.tooltip = 'Osmium Solidity diagnostics';
+			statusBarItem.show();
 		});
-	}
-
+	});
 }
 
 export function deactivate(): Thenable<void> | undefined {